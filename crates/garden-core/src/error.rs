@@ -3,6 +3,7 @@
 use thiserror::Error;
 
 use crate::models::{BlockId, ChannelId};
+use crate::services::MediaError;
 
 /// Errors that can occur in domain operations.
 #[derive(Debug, Error)]
@@ -23,9 +24,20 @@ pub enum DomainError {
     #[error("invalid input: {0}")]
     InvalidInput(String),
 
+    /// Another channel already has this title.
+    ///
+    /// Only raised when the store enforces unique channel titles, which is
+    /// opt-in.
+    #[error("a channel titled '{0}' already exists")]
+    DuplicateTitle(String),
+
     /// Repository error.
     #[error("repository error: {0}")]
     Repository(#[from] RepoError),
+
+    /// Media file error.
+    #[error("media error: {0}")]
+    Media(#[from] MediaError),
 }
 
 /// Errors that can occur in repository operations.
@@ -39,6 +51,15 @@ pub enum RepoError {
     #[error("duplicate record")]
     Duplicate,
 
+    /// Another channel already has this title, in a store that enforces
+    /// unique channel titles.
+    #[error("duplicate channel title")]
+    DuplicateTitle,
+
+    /// Block content is over the store's size limit.
+    #[error("block content is {size} bytes, over the {max}-byte limit")]
+    ContentTooLarge { size: usize, max: usize },
+
     /// Database error.
     #[error("database error: {0}")]
     Database(String),
@@ -10,6 +10,10 @@
 //!
 //! The domain has no dependencies on storage, networking, or UI.
 //! Adapters (in other crates) implement the ports to provide concrete functionality.
+//!
+//! The default `tracing` feature wraps service methods in `#[instrument]`
+//! spans. Disabling it removes that per-call overhead; events are still
+//! logged.
 
 pub mod error;
 pub mod models;
@@ -17,6 +21,12 @@ pub mod ports;
 pub mod services;
 pub mod validation;
 
+/// Version of the data model shared with the frontend.
+///
+/// Bump whenever a model exported to TypeScript changes shape, so a client
+/// built against different generated types can detect the mismatch.
+pub const GARDEN_SCHEMA_VERSION: u32 = 2;
+
 /// Re-export commonly used types for convenience.
 pub mod prelude {
     pub use crate::error::{DomainError, DomainResult, RepoError, RepoResult};
@@ -37,15 +47,60 @@ mod type_export_tests {
         crate::models::Channel::export_all().expect("Failed to export Channel");
         crate::models::NewChannel::export_all().expect("Failed to export NewChannel");
         crate::models::ChannelUpdate::export_all().expect("Failed to export ChannelUpdate");
+        crate::models::ChannelPreview::export_all().expect("Failed to export ChannelPreview");
 
         crate::models::BlockId::export_all().expect("Failed to export BlockId");
         crate::models::BlockContent::export_all().expect("Failed to export BlockContent");
+        crate::models::GalleryItem::export_all().expect("Failed to export GalleryItem");
         crate::models::Block::export_all().expect("Failed to export Block");
         crate::models::NewBlock::export_all().expect("Failed to export NewBlock");
         crate::models::BlockUpdate::export_all().expect("Failed to export BlockUpdate");
+        crate::models::ContentKind::export_all().expect("Failed to export ContentKind");
+        crate::models::MetadataStats::export_all().expect("Failed to export MetadataStats");
 
         crate::models::Connection::export_all().expect("Failed to export Connection");
         crate::models::NewConnection::export_all().expect("Failed to export NewConnection");
+        crate::models::BlockNeighbors::export_all().expect("Failed to export BlockNeighbors");
+        crate::models::BlockPositionInfo::export_all().expect("Failed to export BlockPositionInfo");
+        crate::models::BlockContext::export_all().expect("Failed to export BlockContext");
+        crate::models::ChannelOrder::export_all().expect("Failed to export ChannelOrder");
+        crate::models::BlockSortKey::export_all().expect("Failed to export BlockSortKey");
+        crate::models::OrderSnapshot::export_all().expect("Failed to export OrderSnapshot");
+        crate::models::PositionIssue::export_all().expect("Failed to export PositionIssue");
+        crate::models::ChannelSetMode::export_all().expect("Failed to export ChannelSetMode");
+        crate::models::Placement::export_all().expect("Failed to export Placement");
+        crate::models::SharingStats::export_all().expect("Failed to export SharingStats");
+
+        crate::models::ActivityKind::export_all().expect("Failed to export ActivityKind");
+        crate::models::ActivityEntry::export_all().expect("Failed to export ActivityEntry");
+
+        crate::models::SearchMode::export_all().expect("Failed to export SearchMode");
+        crate::models::SearchHit::export_all().expect("Failed to export SearchHit");
+
+        crate::models::GardenExport::export_all().expect("Failed to export GardenExport");
+        crate::models::ChannelExport::export_all().expect("Failed to export ChannelExport");
+        crate::models::ImportStrategy::export_all().expect("Failed to export ImportStrategy");
+        crate::models::InvalidBlock::export_all().expect("Failed to export InvalidBlock");
+        crate::models::ImportPlan::export_all().expect("Failed to export ImportPlan");
+        crate::models::UrlImportLine::export_all().expect("Failed to export UrlImportLine");
+        crate::models::UrlImportReport::export_all().expect("Failed to export UrlImportReport");
+
+        crate::models::IntegrityReport::export_all().expect("Failed to export IntegrityReport");
+        crate::models::ForeignKeyViolation::export_all()
+            .expect("Failed to export ForeignKeyViolation");
+        crate::models::DuplicateConnection::export_all()
+            .expect("Failed to export DuplicateConnection");
+        crate::models::PositionCollision::export_all().expect("Failed to export PositionCollision");
+
+        crate::models::StorageUsage::export_all().expect("Failed to export StorageUsage");
+        crate::models::MediaUsage::export_all().expect("Failed to export MediaUsage");
+        crate::models::LinkStatus::export_all().expect("Failed to export LinkStatus");
+        crate::models::MediaType::export_all().expect("Failed to export MediaType");
+        crate::models::SupportedType::export_all().expect("Failed to export SupportedType");
+
+        crate::models::FieldUpdate::<String>::export_all().expect("Failed to export FieldUpdate");
+        crate::models::Page::<()>::export_all().expect("Failed to export Page");
+        crate::models::ApiVersion::export_all().expect("Failed to export ApiVersion");
 
         println!("TypeScript types exported successfully!");
     }
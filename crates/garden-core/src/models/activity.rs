@@ -0,0 +1,147 @@
+//! Activity models - a log of changes to channels, blocks, and connections.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{BlockId, ChannelId};
+
+/// What kind of change an activity entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    ChannelCreated,
+    ChannelUpdated,
+    ChannelDeleted,
+    BlockCreated,
+    BlockUpdated,
+    BlockDeleted,
+    /// A block was connected to a channel.
+    BlockConnected,
+    /// A block was disconnected from a channel.
+    BlockDisconnected,
+    /// A block moved within a channel.
+    BlockReordered,
+}
+
+impl ActivityKind {
+    /// The kind's tag, as used in serialized entries and the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ChannelCreated => "channel_created",
+            Self::ChannelUpdated => "channel_updated",
+            Self::ChannelDeleted => "channel_deleted",
+            Self::BlockCreated => "block_created",
+            Self::BlockUpdated => "block_updated",
+            Self::BlockDeleted => "block_deleted",
+            Self::BlockConnected => "block_connected",
+            Self::BlockDisconnected => "block_disconnected",
+            Self::BlockReordered => "block_reordered",
+        }
+    }
+
+    /// Look up a kind by its tag (see [`Self::as_str`]).
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        Some(match tag {
+            "channel_created" => Self::ChannelCreated,
+            "channel_updated" => Self::ChannelUpdated,
+            "channel_deleted" => Self::ChannelDeleted,
+            "block_created" => Self::BlockCreated,
+            "block_updated" => Self::BlockUpdated,
+            "block_deleted" => Self::BlockDeleted,
+            "block_connected" => Self::BlockConnected,
+            "block_disconnected" => Self::BlockDisconnected,
+            "block_reordered" => Self::BlockReordered,
+            _ => return None,
+        })
+    }
+}
+
+/// One change in the activity log.
+///
+/// Entries keep plain IDs rather than references, so they outlive the
+/// channels and blocks they describe.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ActivityEntry {
+    /// Unique identifier for the entry.
+    pub id: String,
+    /// What happened.
+    pub kind: ActivityKind,
+    /// The channel or block the change is about. For connection changes,
+    /// this is the block.
+    pub entity_id: String,
+    /// The channel involved, if any.
+    pub channel_id: Option<ChannelId>,
+    /// The block involved, if any.
+    pub block_id: Option<BlockId>,
+    /// When the change happened.
+    #[ts(type = "string")]
+    pub at: DateTime<Utc>,
+}
+
+impl ActivityEntry {
+    fn new(
+        kind: ActivityKind,
+        entity_id: String,
+        channel_id: Option<ChannelId>,
+        block_id: Option<BlockId>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            entity_id,
+            channel_id,
+            block_id,
+            at: Utc::now(),
+        }
+    }
+
+    /// Record a change to a channel, happening now.
+    pub fn channel(kind: ActivityKind, channel_id: &ChannelId) -> Self {
+        Self::new(kind, channel_id.0.clone(), Some(channel_id.clone()), None)
+    }
+
+    /// Record a change to a block, happening now.
+    pub fn block(kind: ActivityKind, block_id: &BlockId) -> Self {
+        Self::new(kind, block_id.0.clone(), None, Some(block_id.clone()))
+    }
+
+    /// Record a change to a block's place in a channel, happening now.
+    pub fn connection(kind: ActivityKind, channel_id: &ChannelId, block_id: &BlockId) -> Self {
+        Self::new(
+            kind,
+            block_id.0.clone(),
+            Some(channel_id.clone()),
+            Some(block_id.clone()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_tags_round_trip() {
+        for kind in [
+            ActivityKind::ChannelCreated,
+            ActivityKind::ChannelUpdated,
+            ActivityKind::ChannelDeleted,
+            ActivityKind::BlockCreated,
+            ActivityKind::BlockUpdated,
+            ActivityKind::BlockDeleted,
+            ActivityKind::BlockConnected,
+            ActivityKind::BlockDisconnected,
+            ActivityKind::BlockReordered,
+        ] {
+            assert_eq!(ActivityKind::from_tag(kind.as_str()), Some(kind));
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                serde_json::json!(kind.as_str())
+            );
+        }
+        assert_eq!(ActivityKind::from_tag("unknown"), None);
+    }
+}
@@ -2,17 +2,25 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use ts_rs::TS;
 
+use super::{IdStrategy, MimeType};
+
 /// Unique identifier for a block.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct BlockId(pub String);
 
 impl BlockId {
-    /// Create a new block ID.
+    /// Create a new block ID using [`IdStrategy::DEFAULT`].
     pub fn new() -> Self {
-        Self(uuid::Uuid::new_v4().to_string())
+        Self::with_strategy(IdStrategy::DEFAULT)
+    }
+
+    /// Create a new block ID using the given strategy.
+    pub fn with_strategy(strategy: IdStrategy) -> Self {
+        Self(strategy.generate())
     }
 
     /// Create a block ID from an existing string.
@@ -33,9 +41,52 @@ impl std::fmt::Display for BlockId {
     }
 }
 
+/// A single media file within a gallery block.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GalleryItem {
+    /// Relative path within media directory: "images/{uuid}.{ext}"
+    pub file_path: String,
+    /// MIME type: "image/jpeg", "video/mp4", etc.
+    #[ts(type = "string")]
+    pub mime_type: MimeType,
+    /// Width in pixels.
+    pub width: Option<u32>,
+    /// Height in pixels.
+    pub height: Option<u32>,
+    /// Alt text for accessibility.
+    pub alt_text: Option<String>,
+    /// SHA-256 of the stored file, hex-encoded, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub content_hash: Option<String>,
+}
+
+impl GalleryItem {
+    /// Create a gallery item with no dimensions or alt text.
+    pub fn new(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
+        Self {
+            file_path: file_path.into(),
+            mime_type: mime_type.into(),
+            width: None,
+            height: None,
+            alt_text: None,
+            content_hash: None,
+        }
+    }
+}
+
+/// Width divided by height, or `None` unless both are known and non-zero.
+pub fn aspect_ratio(width: Option<u32>, height: Option<u32>) -> Option<f32> {
+    match (width, height) {
+        (Some(w), Some(h)) if w > 0 && h > 0 => Some(w as f32 / h as f32),
+        _ => None,
+    }
+}
+
 /// The content of a block.
 ///
-/// Supports Text, Link, Image, Video, and Audio types.
+/// Supports Text, Link, Image, Video, Audio, Gallery, and RemoteImage types.
 /// Future types: Code.
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -67,10 +118,19 @@ pub enum BlockContent {
         width: Option<u32>,
         /// Image height in pixels.
         height: Option<u32>,
+        /// Width divided by height, computed once at import.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        aspect_ratio: Option<f32>,
         /// MIME type: "image/jpeg", "image/png", etc.
-        mime_type: String,
+        #[ts(type = "string")]
+        mime_type: MimeType,
         /// Alt text for accessibility.
         alt_text: Option<String>,
+        /// SHA-256 of the stored file, hex-encoded, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        content_hash: Option<String>,
     },
     /// A video stored locally.
     Video {
@@ -82,12 +142,21 @@ pub enum BlockContent {
         width: Option<u32>,
         /// Video height in pixels.
         height: Option<u32>,
+        /// Width divided by height, computed once at import.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        aspect_ratio: Option<f32>,
         /// Duration in seconds.
         duration: Option<f32>,
         /// MIME type: "video/mp4", "video/webm", etc.
-        mime_type: String,
+        #[ts(type = "string")]
+        mime_type: MimeType,
         /// Alt text for accessibility.
         alt_text: Option<String>,
+        /// SHA-256 of the stored file, hex-encoded, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        content_hash: Option<String>,
     },
     /// An audio file stored locally.
     Audio {
@@ -98,14 +167,66 @@ pub enum BlockContent {
         /// Duration in seconds.
         duration: Option<f32>,
         /// MIME type: "audio/mpeg", "audio/ogg", etc.
-        mime_type: String,
+        #[ts(type = "string")]
+        mime_type: MimeType,
         /// Title from ID3 tags or filename.
         title: Option<String>,
         /// Artist from ID3 tags.
         artist: Option<String>,
+        /// SHA-256 of the stored file, hex-encoded, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        content_hash: Option<String>,
+    },
+    /// An ordered group of images or videos stored locally.
+    Gallery {
+        /// The gallery's media files, in display order.
+        items: Vec<GalleryItem>,
+    },
+    /// An image hot-linked from a remote URL and never downloaded.
+    RemoteImage {
+        /// The image URL.
+        url: String,
+        /// Image width in pixels, if known.
+        width: Option<u32>,
+        /// Image height in pixels, if known.
+        height: Option<u32>,
+        /// Alt text for accessibility.
+        alt_text: Option<String>,
     },
 }
 
+/// The kind of a block's content, without its data.
+///
+/// Matches the `type` tag of [`BlockContent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    Text,
+    Link,
+    Image,
+    Video,
+    Audio,
+    Gallery,
+    RemoteImage,
+}
+
+impl ContentKind {
+    /// The kind's tag, as used in serialized content and the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Link => "link",
+            Self::Image => "image",
+            Self::Video => "video",
+            Self::Audio => "audio",
+            Self::Gallery => "gallery",
+            Self::RemoteImage => "remote_image",
+        }
+    }
+}
+
 impl BlockContent {
     /// Create text content.
     pub fn text(body: impl Into<String>) -> Self {
@@ -138,21 +259,23 @@ impl BlockContent {
     }
 
     /// Create image content.
-    pub fn image(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn image(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::Image {
             file_path: file_path.into(),
             original_url: None,
             width: None,
             height: None,
+            aspect_ratio: None,
             mime_type: mime_type.into(),
             alt_text: None,
+            content_hash: None,
         }
     }
 
     /// Create image content with full metadata.
     pub fn image_with_meta(
         file_path: impl Into<String>,
-        mime_type: impl Into<String>,
+        mime_type: impl Into<MimeType>,
         original_url: Option<String>,
         width: Option<u32>,
         height: Option<u32>,
@@ -163,28 +286,32 @@ impl BlockContent {
             original_url,
             width,
             height,
+            aspect_ratio: aspect_ratio(width, height),
             mime_type: mime_type.into(),
             alt_text,
+            content_hash: None,
         }
     }
 
     /// Create video content.
-    pub fn video(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn video(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::Video {
             file_path: file_path.into(),
             original_url: None,
             width: None,
             height: None,
+            aspect_ratio: None,
             duration: None,
             mime_type: mime_type.into(),
             alt_text: None,
+            content_hash: None,
         }
     }
 
     /// Create video content with full metadata.
     pub fn video_with_meta(
         file_path: impl Into<String>,
-        mime_type: impl Into<String>,
+        mime_type: impl Into<MimeType>,
         original_url: Option<String>,
         width: Option<u32>,
         height: Option<u32>,
@@ -196,14 +323,16 @@ impl BlockContent {
             original_url,
             width,
             height,
+            aspect_ratio: aspect_ratio(width, height),
             duration,
             mime_type: mime_type.into(),
             alt_text,
+            content_hash: None,
         }
     }
 
     /// Create audio content.
-    pub fn audio(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn audio(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::Audio {
             file_path: file_path.into(),
             original_url: None,
@@ -211,13 +340,14 @@ impl BlockContent {
             mime_type: mime_type.into(),
             title: None,
             artist: None,
+            content_hash: None,
         }
     }
 
     /// Create audio content with full metadata.
     pub fn audio_with_meta(
         file_path: impl Into<String>,
-        mime_type: impl Into<String>,
+        mime_type: impl Into<MimeType>,
         original_url: Option<String>,
         duration: Option<f32>,
         title: Option<String>,
@@ -230,6 +360,22 @@ impl BlockContent {
             mime_type: mime_type.into(),
             title,
             artist,
+            content_hash: None,
+        }
+    }
+
+    /// Create gallery content.
+    pub fn gallery(items: Vec<GalleryItem>) -> Self {
+        Self::Gallery { items }
+    }
+
+    /// Create remote image content that references `url` without storing it.
+    pub fn remote_image(url: impl Into<String>) -> Self {
+        Self::RemoteImage {
+            url: url.into(),
+            width: None,
+            height: None,
+            alt_text: None,
         }
     }
 
@@ -251,13 +397,23 @@ impl BlockContent {
                 }
             }
             Self::Link { title, url, .. } => title.as_deref().unwrap_or(url),
-            Self::Image { alt_text, file_path, .. } => {
-                alt_text.as_deref().unwrap_or(file_path)
-            }
-            Self::Video { alt_text, file_path, .. } => {
-                alt_text.as_deref().unwrap_or(file_path)
-            }
-            Self::Audio { title, artist, file_path, .. } => {
+            Self::Image {
+                alt_text,
+                file_path,
+                ..
+            } => alt_text.as_deref().unwrap_or(file_path),
+            Self::Video {
+                alt_text,
+                file_path,
+                ..
+            } => alt_text.as_deref().unwrap_or(file_path),
+            Self::RemoteImage { alt_text, url, .. } => alt_text.as_deref().unwrap_or(url),
+            Self::Audio {
+                title,
+                artist,
+                file_path,
+                ..
+            } => {
                 // Prefer title, then "artist - title", then file_path
                 if let Some(t) = title {
                     t
@@ -267,33 +423,156 @@ impl BlockContent {
                     file_path
                 }
             }
+            // Prefer the first item's alt text, then its file_path
+            Self::Gallery { items } => items
+                .first()
+                .map_or("Gallery", |i| i.alt_text.as_deref().unwrap_or(&i.file_path)),
         }
     }
 
-    /// Returns true if this content is a media type (Image, Video, or Audio).
+    /// Returns true if this content is a media type (Image, Video, Audio,
+    /// Gallery, or RemoteImage).
     pub fn is_media(&self) -> bool {
-        matches!(self, Self::Image { .. } | Self::Video { .. } | Self::Audio { .. })
+        matches!(
+            self,
+            Self::Image { .. }
+                | Self::Video { .. }
+                | Self::Audio { .. }
+                | Self::Gallery { .. }
+                | Self::RemoteImage { .. }
+        )
     }
 
-    /// Get the file path if this is a media type.
+    /// Get the file path if this is a locally stored media type.
+    ///
+    /// For galleries, this is the first item's path (the cover). Remote
+    /// images have no local file, so this is `None` for them.
     pub fn file_path(&self) -> Option<&str> {
         match self {
             Self::Image { file_path, .. }
             | Self::Video { file_path, .. }
             | Self::Audio { file_path, .. } => Some(file_path),
+            Self::Gallery { items } => items.first().map(|i| i.file_path.as_str()),
             _ => None,
         }
     }
 
+    /// Get the playback length in seconds, for video and audio that have
+    /// one.
+    pub fn duration(&self) -> Option<f32> {
+        match self {
+            Self::Video { duration, .. } | Self::Audio { duration, .. } => *duration,
+            _ => None,
+        }
+    }
+
+    /// Returns true if this content stores the given media file.
+    ///
+    /// Unlike [`file_path`](Self::file_path), this checks every gallery item.
+    pub fn references_file(&self, path: &str) -> bool {
+        match self {
+            Self::Gallery { items } => items.iter().any(|i| i.file_path == path),
+            _ => self.file_path() == Some(path),
+        }
+    }
+
+    /// Get every locally stored file with its recorded hash, if any.
+    ///
+    /// Galleries list each item in order; other media has at most one file.
+    pub fn media_files(&self) -> Vec<(&str, Option<&str>)> {
+        match self {
+            Self::Image {
+                file_path,
+                content_hash,
+                ..
+            }
+            | Self::Video {
+                file_path,
+                content_hash,
+                ..
+            }
+            | Self::Audio {
+                file_path,
+                content_hash,
+                ..
+            } => vec![(file_path.as_str(), content_hash.as_deref())],
+            Self::Gallery { items } => items
+                .iter()
+                .map(|i| (i.file_path.as_str(), i.content_hash.as_deref()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// A hex SHA-256 identifying this content, for spotting duplicates.
+    ///
+    /// Media whose files all have a recorded hash is identified by those
+    /// hashes, since each import of the same file is stored under a new
+    /// path; captions and tags read from the file don't count. Anything
+    /// else is identified by its full serialized content.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.kind().as_str());
+        let file_hashes: Option<Vec<&str>> = self
+            .media_files()
+            .into_iter()
+            .map(|(_, hash)| hash)
+            .collect();
+        match file_hashes {
+            Some(hashes) if !hashes.is_empty() => {
+                for hash in hashes {
+                    hasher.update(b"\0");
+                    hasher.update(hash);
+                }
+            }
+            _ => {
+                hasher.update(b"\0");
+                hasher.update(serde_json::to_vec(self).unwrap_or_default());
+            }
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
     /// Get the MIME type if this is a media type.
+    ///
+    /// For galleries, this is the first item's MIME type.
     pub fn mime_type(&self) -> Option<&str> {
         match self {
             Self::Image { mime_type, .. }
             | Self::Video { mime_type, .. }
-            | Self::Audio { mime_type, .. } => Some(mime_type),
+            | Self::Audio { mime_type, .. } => Some(mime_type.as_str()),
+            Self::Gallery { items } => items.first().map(|i| i.mime_type.as_str()),
             _ => None,
         }
     }
+
+    /// Count the words a person wrote or archived in this content.
+    ///
+    /// Covers text bodies and link titles and descriptions; media blocks
+    /// count as zero. Words are runs of non-whitespace.
+    pub fn word_count(&self) -> usize {
+        let count = |text: &str| text.split_whitespace().count();
+        match self {
+            Self::Text { body } => count(body),
+            Self::Link {
+                title, description, ..
+            } => title.as_deref().map_or(0, count) + description.as_deref().map_or(0, count),
+            _ => 0,
+        }
+    }
+
+    /// The kind of this content.
+    pub fn kind(&self) -> ContentKind {
+        match self {
+            Self::Text { .. } => ContentKind::Text,
+            Self::Link { .. } => ContentKind::Link,
+            Self::Image { .. } => ContentKind::Image,
+            Self::Video { .. } => ContentKind::Video,
+            Self::Audio { .. } => ContentKind::Audio,
+            Self::Gallery { .. } => ContentKind::Gallery,
+            Self::RemoteImage { .. } => ContentKind::RemoteImage,
+        }
+    }
 }
 
 /// A block is a piece of content that can be connected to multiple channels.
@@ -322,6 +601,10 @@ pub struct Block {
     pub original_date: Option<String>,
     /// User's personal notes about this block.
     pub notes: Option<String>,
+    /// Hidden from default listings, search, and channel views, but kept
+    /// along with its connections. Not a deletion.
+    #[serde(default)]
+    pub archived: bool,
 }
 
 impl Block {
@@ -338,6 +621,7 @@ impl Block {
             creator: None,
             original_date: None,
             notes: None,
+            archived: false,
         }
     }
 
@@ -352,17 +636,17 @@ impl Block {
     }
 
     /// Create a new image block.
-    pub fn image(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn image(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::new(BlockContent::image(file_path, mime_type))
     }
 
     /// Create a new video block.
-    pub fn video(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn video(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::new(BlockContent::video(file_path, mime_type))
     }
 
     /// Create a new audio block.
-    pub fn audio(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn audio(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::new(BlockContent::audio(file_path, mime_type))
     }
 
@@ -423,17 +707,17 @@ impl NewBlock {
     }
 
     /// Create a new image block.
-    pub fn image(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn image(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::new(BlockContent::image(file_path, mime_type))
     }
 
     /// Create a new video block.
-    pub fn video(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn video(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::new(BlockContent::video(file_path, mime_type))
     }
 
     /// Create a new audio block.
-    pub fn audio(file_path: impl Into<String>, mime_type: impl Into<String>) -> Self {
+    pub fn audio(file_path: impl Into<String>, mime_type: impl Into<MimeType>) -> Self {
         Self::new(BlockContent::audio(file_path, mime_type))
     }
 
@@ -479,6 +763,42 @@ pub struct BlockUpdate {
     pub notes: Option<super::FieldUpdate<String>>,
 }
 
+impl BlockUpdate {
+    /// An update that only sets (`Some`) or clears (`None`) the notes.
+    pub fn set_notes(notes: Option<String>) -> Self {
+        Self {
+            notes: Some(notes.into()),
+            ..Default::default()
+        }
+    }
+
+    /// An update that only sets (`Some`) or clears (`None`) the source URL.
+    pub fn set_source_url(source_url: Option<String>) -> Self {
+        Self {
+            source_url: Some(source_url.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// How many blocks are missing each archive metadata field.
+///
+/// A field counts as missing when it is unset or empty.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MetadataStats {
+    /// Number of blocks counted.
+    pub total: usize,
+    /// Blocks without a source URL.
+    pub missing_source_url: usize,
+    /// Blocks without a creator.
+    pub missing_creator: usize,
+    /// Blocks without an original date.
+    pub missing_original_date: usize,
+    /// Blocks without notes.
+    pub missing_notes: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -513,6 +833,30 @@ mod tests {
         assert_eq!(block.display_title(), "https://example.com");
     }
 
+    #[test]
+    fn word_count_by_variant() {
+        assert_eq!(
+            BlockContent::text("  one two\nthree\t four ").word_count(),
+            4
+        );
+        assert_eq!(BlockContent::text("").word_count(), 0);
+        assert_eq!(
+            BlockContent::link_with_meta(
+                "https://example.com/a-long-url",
+                Some("Example Site".to_string()),
+                Some("A site for examples".to_string()),
+                Some("ignored alt text".to_string()),
+            )
+            .word_count(),
+            6
+        );
+        assert_eq!(BlockContent::link("https://example.com").word_count(), 0);
+        assert_eq!(
+            BlockContent::image("images/a.jpg", "image/jpeg").word_count(),
+            0
+        );
+    }
+
     #[test]
     fn image_block_creation() {
         let block = Block::image("images/abc123.jpg", "image/jpeg");
@@ -561,7 +905,13 @@ mod tests {
         );
         let block = Block::new(content);
         assert_eq!(block.display_title(), "Tutorial video");
-        if let BlockContent::Video { width, height, duration, .. } = &block.content {
+        if let BlockContent::Video {
+            width,
+            height,
+            duration,
+            ..
+        } = &block.content
+        {
             assert_eq!(*width, Some(1920));
             assert_eq!(*height, Some(1080));
             assert_eq!(*duration, Some(120.5));
@@ -612,6 +962,46 @@ mod tests {
         assert_eq!(block.display_title(), "audio/song123.mp3");
     }
 
+    #[test]
+    fn gallery_block_is_media() {
+        let block = Block::new(BlockContent::gallery(vec![
+            GalleryItem::new("images/one.jpg", "image/jpeg"),
+            GalleryItem::new("images/two.png", "image/png"),
+        ]));
+        assert!(block.is_media());
+        assert_eq!(block.content.file_path(), Some("images/one.jpg"));
+        assert_eq!(block.content.mime_type(), Some("image/jpeg"));
+    }
+
+    #[test]
+    fn gallery_block_display_title_uses_first_alt_text() {
+        let mut first = GalleryItem::new("images/one.jpg", "image/jpeg");
+        first.alt_text = Some("Harbour at dawn".to_string());
+        let block = Block::new(BlockContent::gallery(vec![
+            first,
+            GalleryItem::new("images/two.png", "image/png"),
+        ]));
+        assert_eq!(block.display_title(), "Harbour at dawn");
+    }
+
+    #[test]
+    fn gallery_block_display_title_falls_back_to_file_path() {
+        let block = Block::new(BlockContent::gallery(vec![
+            GalleryItem::new("images/one.jpg", "image/jpeg"),
+            GalleryItem::new("images/two.png", "image/png"),
+        ]));
+        assert_eq!(block.display_title(), "images/one.jpg");
+    }
+
+    #[test]
+    fn remote_image_is_media_without_a_file() {
+        let block = Block::new(BlockContent::remote_image("https://example.com/cat.png"));
+        assert!(block.is_media());
+        assert!(block.content.file_path().is_none());
+        assert!(block.content.mime_type().is_none());
+        assert_eq!(block.display_title(), "https://example.com/cat.png");
+    }
+
     #[test]
     fn text_block_is_not_media() {
         let block = Block::text("Hello");
@@ -620,6 +1010,33 @@ mod tests {
         assert!(block.content.mime_type().is_none());
     }
 
+    #[test]
+    fn fingerprint_matches_same_file_under_new_path() {
+        let with_hash = |path: &str, hash: &str| {
+            let mut content = BlockContent::image(path, "image/png");
+            if let BlockContent::Image { content_hash, .. } = &mut content {
+                *content_hash = Some(hash.to_string());
+            }
+            content
+        };
+        assert_eq!(
+            with_hash("images/a.png", "abc").fingerprint(),
+            with_hash("images/b.png", "abc").fingerprint()
+        );
+        assert_ne!(
+            with_hash("images/a.png", "abc").fingerprint(),
+            with_hash("images/a.png", "def").fingerprint()
+        );
+        assert_eq!(
+            BlockContent::text("Same").fingerprint(),
+            BlockContent::text("Same").fingerprint()
+        );
+        assert_ne!(
+            BlockContent::text("Same").fingerprint(),
+            BlockContent::text("Other").fingerprint()
+        );
+    }
+
     #[test]
     fn link_block_is_not_media() {
         let block = Block::link("https://example.com");
@@ -4,15 +4,22 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use super::{Block, IdStrategy};
+
 /// Unique identifier for a channel.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct ChannelId(pub String);
 
 impl ChannelId {
-    /// Create a new channel ID.
+    /// Create a new channel ID using [`IdStrategy::DEFAULT`].
     pub fn new() -> Self {
-        Self(uuid::Uuid::new_v4().to_string())
+        Self::with_strategy(IdStrategy::DEFAULT)
+    }
+
+    /// Create a new channel ID using the given strategy.
+    pub fn with_strategy(strategy: IdStrategy) -> Self {
+        Self(strategy.generate())
     }
 
     /// Create a channel ID from an existing string.
@@ -43,12 +50,24 @@ pub struct Channel {
     pub title: String,
     /// Optional description.
     pub description: Option<String>,
+    /// Optional longer Markdown "about" text; `description` stays the short
+    /// summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub body: Option<String>,
     /// When the channel was created.
     #[ts(type = "string")]
     pub created_at: DateTime<Utc>,
     /// When the channel was last updated.
     #[ts(type = "string")]
     pub updated_at: DateTime<Utc>,
+    /// When the channel was last opened, if ever.
+    ///
+    /// Tracked separately from `updated_at` so viewing a channel does not
+    /// count as editing it.
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    pub accessed_at: Option<DateTime<Utc>>,
 }
 
 impl Channel {
@@ -59,8 +78,10 @@ impl Channel {
             id: ChannelId::new(),
             title: title.into(),
             description: None,
+            body: None,
             created_at: now,
             updated_at: now,
+            accessed_at: None,
         }
     }
 
@@ -78,6 +99,9 @@ impl Channel {
 pub struct NewChannel {
     pub title: String,
     pub description: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub body: Option<String>,
 }
 
 /// Data for updating a channel.
@@ -90,6 +114,21 @@ pub struct ChannelUpdate {
     /// Description update (Keep/Clear/Set).
     #[serde(default)]
     pub description: super::FieldUpdate<String>,
+    /// Body update (Keep/Clear/Set).
+    #[serde(default)]
+    pub body: super::FieldUpdate<String>,
+}
+
+/// A channel with its first few blocks, for rendering a channel card.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ChannelPreview {
+    /// The channel.
+    pub channel: Channel,
+    /// Total number of blocks in the channel.
+    pub block_count: usize,
+    /// The first blocks in the channel, in position order.
+    pub preview_blocks: Vec<Block>,
 }
 
 #[cfg(test)]
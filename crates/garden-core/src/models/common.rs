@@ -38,9 +38,48 @@ impl<T> FieldUpdate<T> {
     }
 }
 
-/// A paginated response.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+impl<T> From<Option<T>> for FieldUpdate<T> {
+    /// Map `None` to `Clear` and `Some(value)` to `Set(value)`.
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::Set(value),
+            None => Self::Clear,
+        }
+    }
+}
+
+/// The backend's model and crate versions, for client compatibility checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[ts(export)]
+pub struct ApiVersion {
+    /// [`GARDEN_SCHEMA_VERSION`](crate::GARDEN_SCHEMA_VERSION) of this build.
+    pub schema_version: u32,
+    /// Version of the `garden-core` crate, e.g. `"0.1.0"`.
+    pub crate_version: String,
+}
+
+impl ApiVersion {
+    /// The versions of this build.
+    pub fn current() -> Self {
+        Self {
+            schema_version: crate::GARDEN_SCHEMA_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// A paginated response.
+///
+/// Serializes with the navigation fields (`has_next`, `has_prev`,
+/// `page_number`, `total_pages`) alongside the items, so the frontend does
+/// not have to derive them itself. They are computed from the other fields,
+/// and ignored when deserializing.
+#[derive(Debug, Clone, Deserialize, TS)]
+#[ts(
+    export,
+    as = "SerializedPage<'static, T>",
+    bound = "T: ts_rs::TS + 'static"
+)]
 pub struct Page<T> {
     /// The items in this page.
     pub items: Vec<T>,
@@ -75,11 +114,7 @@ impl<T> Page<T> {
 
     /// Get the current page number (0-indexed).
     pub fn page_number(&self) -> usize {
-        if self.limit == 0 {
-            0
-        } else {
-            self.offset / self.limit
-        }
+        self.offset.checked_div(self.limit).unwrap_or(0)
     }
 
     /// Get the total number of pages.
@@ -92,6 +127,48 @@ impl<T> Page<T> {
     }
 }
 
+impl<T: Serialize> Serialize for Page<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedPage {
+            items: &self.items,
+            total: self.total,
+            offset: self.offset,
+            limit: self.limit,
+            has_next: self.has_next(),
+            has_prev: self.has_prev(),
+            page_number: self.page_number(),
+            total_pages: self.total_pages(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// A paginated response.
+///
+/// The navigation fields (`has_next`, `has_prev`, `page_number`,
+/// `total_pages`) are computed by the backend and serialized alongside the
+/// items, so the frontend does not have to derive them itself.
+#[derive(Serialize, TS)]
+#[ts(rename = "Page")]
+pub(crate) struct SerializedPage<'a, T> {
+    /// The items in this page.
+    items: &'a [T],
+    /// Total number of items across all pages.
+    total: usize,
+    /// Offset of the first item in this page.
+    offset: usize,
+    /// Maximum number of items per page.
+    limit: usize,
+    /// Whether there are items after this page.
+    has_next: bool,
+    /// Whether there are items before this page.
+    has_prev: bool,
+    /// The current page number (0-indexed).
+    page_number: usize,
+    /// The total number of pages.
+    total_pages: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,13 +203,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn field_update_from_option() {
+        assert_eq!(FieldUpdate::<String>::from(None), FieldUpdate::Clear);
+        assert_eq!(
+            FieldUpdate::from(Some("new".to_string())),
+            FieldUpdate::Set("new".to_string())
+        );
+    }
+
     #[test]
     fn page_has_next() {
         let page: Page<i32> = Page::new(vec![1, 2, 3], 10, 0, 3);
         assert!(page.has_next());
+        assert!(!page.has_prev());
 
         let last_page: Page<i32> = Page::new(vec![10], 10, 9, 3);
         assert!(!last_page.has_next());
+        assert!(last_page.has_prev());
     }
 
     #[test]
@@ -141,4 +229,40 @@ mod tests {
         assert_eq!(page.page_number(), 2);
         assert_eq!(page.total_pages(), 4);
     }
+
+    #[test]
+    fn page_serializes_navigation_fields() {
+        let page: Page<i32> = Page::new(vec![4, 5, 6], 10, 3, 3);
+        let json = serde_json::to_value(&page).unwrap();
+
+        assert_eq!(json["has_next"], true);
+        assert_eq!(json["has_prev"], true);
+        assert_eq!(json["page_number"], 1);
+        assert_eq!(json["total_pages"], 4);
+
+        let back: Page<i32> = serde_json::from_value(json).unwrap();
+        assert_eq!(back.items, page.items);
+        let bare: Page<i32> =
+            serde_json::from_str(r#"{"items":[1],"total":1,"offset":0,"limit":3}"#).unwrap();
+        assert!(!bare.has_next());
+    }
+
+    #[test]
+    fn page_typescript_includes_navigation_fields() {
+        let decl = Page::<()>::decl();
+        assert!(decl.starts_with("type Page<T> = {"));
+        assert!(decl.contains("has_next: boolean"));
+        assert!(decl.contains("total_pages: number"));
+    }
+
+    #[test]
+    fn api_version_reports_schema_version() {
+        let version = ApiVersion::current();
+        assert_eq!(version.schema_version, crate::GARDEN_SCHEMA_VERSION);
+        assert_eq!(version.crate_version, env!("CARGO_PKG_VERSION"));
+
+        let export = crate::models::GardenExport::new(Vec::new(), Vec::new(), Vec::new());
+        let json = serde_json::to_value(&export).unwrap();
+        assert_eq!(json["schema_version"], crate::GARDEN_SCHEMA_VERSION);
+    }
 }
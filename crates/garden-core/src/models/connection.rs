@@ -1,10 +1,12 @@
 //! Connection model - links blocks to channels.
 
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use super::{BlockId, ChannelId};
+use super::{Block, BlockId, Channel, ChannelId};
 
 /// A connection links a block to a channel.
 ///
@@ -46,6 +48,240 @@ pub struct NewConnection {
     pub position: Option<i32>,
 }
 
+/// How to order the channels a block belongs to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelOrder {
+    /// Most recently connected first.
+    #[default]
+    ByConnectedAtDesc,
+    /// Alphabetical by title, ignoring ASCII case.
+    ByTitleAsc,
+}
+
+/// What to sort a channel's blocks by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockSortKey {
+    /// When the block was created.
+    CreatedAt,
+    /// The block's display title, ignoring case.
+    Title,
+    /// When the block was connected to the channel.
+    ConnectedAt,
+}
+
+/// A channel's block order at one moment, kept so a sort can be undone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct OrderSnapshot {
+    /// The channel the order belongs to.
+    pub channel_id: ChannelId,
+    /// Each block with its position, in channel order.
+    pub positions: Vec<(BlockId, i32)>,
+    /// When the snapshot was taken.
+    #[ts(type = "string")]
+    pub taken_at: DateTime<Utc>,
+}
+
+/// How to combine channels when listing the blocks they contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelSetMode {
+    /// Blocks in any of the channels.
+    Union,
+    /// Blocks in every one of the channels.
+    Intersection,
+}
+
+/// Where to put a moved block relative to its anchor block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    /// Directly before the anchor.
+    Before,
+    /// Directly after the anchor.
+    After,
+}
+
+/// A problem with the positions of a channel's blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionIssue {
+    /// A block has a negative position.
+    Negative,
+    /// Two or more blocks share a position.
+    Duplicate,
+    /// Counting up from 0, the positions step by more than the position
+    /// gap somewhere.
+    Gap,
+}
+
+/// Find the issues with a channel's block positions, in declaration order
+/// and each reported once.
+///
+/// `gap` is the spacing appends leave between positions, so a channel
+/// appended with a gap of 100 has no [`PositionIssue::Gap`].
+pub fn position_issues(positions: &[i32], gap: i32) -> Vec<PositionIssue> {
+    let mut sorted = positions.to_vec();
+    sorted.sort_unstable();
+    let distinct = {
+        let mut distinct = sorted.clone();
+        distinct.dedup();
+        distinct
+    };
+
+    let mut issues = Vec::new();
+    if sorted.first().is_some_and(|p| *p < 0) {
+        issues.push(PositionIssue::Negative);
+    }
+    if distinct.len() < sorted.len() {
+        issues.push(PositionIssue::Duplicate);
+    }
+    // The first position may be at most gap - 1, as if -1 came before it
+    let mut previous = -1i64;
+    let gapped = distinct.iter().filter(|p| **p >= 0).any(|p| {
+        let step = i64::from(*p) - previous;
+        previous = i64::from(*p);
+        step > i64::from(gap)
+    });
+    if gapped {
+        issues.push(PositionIssue::Gap);
+    }
+    issues
+}
+
+/// The position of the `index`th block when a channel is renumbered `gap`
+/// apart from zero.
+///
+/// Returns `None` if the position would pass `i32::MAX`.
+pub fn gapped_position(index: usize, gap: i32) -> Option<i32> {
+    i32::try_from(index).ok()?.checked_mul(gap)
+}
+
+/// Move `block_id` next to `anchor` in a channel's block order.
+///
+/// Returns `None` if either block is missing from `order` or they are the
+/// same block.
+pub fn move_relative(
+    order: &[BlockId],
+    block_id: &BlockId,
+    anchor: &BlockId,
+    placement: Placement,
+) -> Option<Vec<BlockId>> {
+    if block_id == anchor || !order.contains(block_id) {
+        return None;
+    }
+    let mut moved: Vec<BlockId> = order.iter().filter(|id| *id != block_id).cloned().collect();
+    let anchor_index = moved.iter().position(|id| id == anchor)?;
+    let index = match placement {
+        Placement::Before => anchor_index,
+        Placement::After => anchor_index + 1,
+    };
+    moved.insert(index, block_id.clone());
+    Some(moved)
+}
+
+/// Extend `order` to cover every block in a channel's `current` order.
+///
+/// Blocks missing from `order` follow it in their current relative order;
+/// repeated IDs are ignored. Returns `None` if `order` names a block that
+/// is not in `current`.
+pub fn complete_order(current: &[BlockId], order: &[BlockId]) -> Option<Vec<BlockId>> {
+    let known: HashSet<&BlockId> = current.iter().collect();
+    let mut listed: HashSet<&BlockId> = HashSet::with_capacity(order.len());
+    let mut complete: Vec<BlockId> = Vec::with_capacity(current.len());
+    for id in order {
+        if !known.contains(id) {
+            return None;
+        }
+        if listed.insert(id) {
+            complete.push(id.clone());
+        }
+    }
+    complete.extend(current.iter().filter(|id| !listed.contains(id)).cloned());
+    Some(complete)
+}
+
+/// Move a group of blocks to `target_index` as one contiguous run.
+///
+/// The group keeps its current relative order and `target_index` counts
+/// positions among the remaining blocks, clamped to the end. Repeated IDs
+/// are ignored. Returns `None` if any block is missing from `order`.
+pub fn move_group(
+    order: &[BlockId],
+    block_ids: &[BlockId],
+    target_index: usize,
+) -> Option<Vec<BlockId>> {
+    let grouped: HashSet<&BlockId> = block_ids.iter().collect();
+    let known: HashSet<&BlockId> = order.iter().collect();
+    if grouped.iter().any(|id| !known.contains(id)) {
+        return None;
+    }
+    let (group, mut rest): (Vec<BlockId>, Vec<BlockId>) =
+        order.iter().cloned().partition(|id| grouped.contains(id));
+    let index = target_index.min(rest.len());
+    rest.splice(index..index, group);
+    Some(rest)
+}
+
+/// An unused position strictly between two neighbouring positions.
+///
+/// Picks the midpoint so repeated inserts keep splitting the remaining gap.
+/// Returns `None` if the neighbours are adjacent and the channel would
+/// need renumbering.
+pub fn position_between(before: i32, after: i32) -> Option<i32> {
+    let (low, high) = (before.min(after) as i64, before.max(after) as i64);
+    (high - low > 1).then(|| (low + (high - low) / 2) as i32)
+}
+
+/// The blocks on either side of a block within a channel.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BlockNeighbors {
+    /// The block before, or `None` if this is the first block.
+    pub previous: Option<Block>,
+    /// The block after, or `None` if this is the last block.
+    pub next: Option<Block>,
+}
+
+/// Where a block sits within a channel, e.g. for a "3 of 42" indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BlockPositionInfo {
+    /// The block's zero-based index in position order.
+    pub index: usize,
+    /// How many blocks the channel holds.
+    pub total: usize,
+}
+
+/// A block together with the channels it is in.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct BlockContext {
+    /// The block itself.
+    pub block: Block,
+    /// The channels containing the block, ordered by title.
+    pub channels: Vec<Channel>,
+}
+
+/// How many of a channel's blocks are unique to it versus shared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SharingStats {
+    /// Number of blocks in the channel.
+    pub total: usize,
+    /// Blocks connected to this channel and no other.
+    pub exclusive: usize,
+    /// Blocks also connected to at least one other channel.
+    pub shared: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,4 +296,67 @@ mod tests {
         assert_eq!(conn.channel_id, channel_id);
         assert_eq!(conn.position, 0);
     }
+
+    #[test]
+    fn move_relative_places_next_to_anchor() {
+        let ids: Vec<BlockId> = (0..3).map(|_| BlockId::new()).collect();
+        let [a, b, c] = [&ids[0], &ids[1], &ids[2]];
+
+        let before = move_relative(&ids, c, a, Placement::Before).unwrap();
+        assert_eq!(before, vec![c.clone(), a.clone(), b.clone()]);
+
+        let after = move_relative(&ids, a, c, Placement::After).unwrap();
+        assert_eq!(after, vec![b.clone(), c.clone(), a.clone()]);
+
+        assert!(move_relative(&ids, a, a, Placement::After).is_none());
+        assert!(move_relative(&ids, a, &BlockId::new(), Placement::After).is_none());
+    }
+
+    #[test]
+    fn complete_order_appends_unlisted_blocks() {
+        let ids = vec![BlockId::new(), BlockId::new(), BlockId::new()];
+        let (a, b, c) = (&ids[0], &ids[1], &ids[2]);
+
+        let order = complete_order(&ids, &[c.clone(), a.clone(), c.clone()]).unwrap();
+        assert_eq!(order, vec![c.clone(), a.clone(), b.clone()]);
+
+        assert!(complete_order(&ids, &[BlockId::new()]).is_none());
+    }
+
+    #[test]
+    fn position_issues_finds_each_kind_once() {
+        assert!(position_issues(&[], 1).is_empty());
+        assert!(position_issues(&[2, 0, 1], 1).is_empty());
+        assert_eq!(
+            position_issues(&[-1, 0, 1, 1], 1),
+            vec![PositionIssue::Negative, PositionIssue::Duplicate]
+        );
+        assert_eq!(position_issues(&[0, 2, 5], 1), vec![PositionIssue::Gap]);
+        assert_eq!(position_issues(&[1], 1), vec![PositionIssue::Gap]);
+    }
+
+    #[test]
+    fn position_issues_allows_the_position_gap() {
+        assert!(position_issues(&[0, 100, 200], 100).is_empty());
+        assert!(position_issues(&[0, 1, 50, 100], 100).is_empty());
+        assert_eq!(
+            position_issues(&[0, 100, 201], 100),
+            vec![PositionIssue::Gap]
+        );
+        assert_eq!(position_issues(&[100], 100), vec![PositionIssue::Gap]);
+    }
+
+    #[test]
+    fn move_group_keeps_group_order_and_clamps_index() {
+        let ids: Vec<BlockId> = (0..4).map(|_| BlockId::new()).collect();
+        let (a, b, c, d) = (&ids[0], &ids[1], &ids[2], &ids[3]);
+
+        let order = move_group(&ids, &[c.clone(), a.clone()], 1).unwrap();
+        assert_eq!(order, vec![b.clone(), a.clone(), c.clone(), d.clone()]);
+
+        let order = move_group(&ids, std::slice::from_ref(b), 99).unwrap();
+        assert_eq!(order, vec![a.clone(), c.clone(), d.clone(), b.clone()]);
+
+        assert!(move_group(&ids, &[BlockId::new()], 0).is_none());
+    }
 }
@@ -0,0 +1,171 @@
+//! Export models - whole-garden archives and import planning.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{Block, BlockId, Channel, Connection};
+
+/// A snapshot of an entire garden.
+///
+/// Used for backups and for moving a garden between instances. IDs and
+/// timestamps are kept as-is so that re-importing an export is idempotent.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct GardenExport {
+    /// Format version of this export.
+    pub version: u32,
+    /// [`GARDEN_SCHEMA_VERSION`](crate::GARDEN_SCHEMA_VERSION) of the build
+    /// that wrote the export; 0 if it predates the field.
+    #[serde(default)]
+    pub schema_version: u32,
+    /// When the export was taken.
+    #[ts(type = "string")]
+    pub exported_at: DateTime<Utc>,
+    /// All channels.
+    pub channels: Vec<Channel>,
+    /// All blocks.
+    pub blocks: Vec<Block>,
+    /// All connections between blocks and channels.
+    pub connections: Vec<Connection>,
+}
+
+impl GardenExport {
+    /// The export format version written by this build.
+    pub const VERSION: u32 = 1;
+
+    /// Create an export at the current format version.
+    pub fn new(channels: Vec<Channel>, blocks: Vec<Block>, connections: Vec<Connection>) -> Self {
+        Self {
+            version: Self::VERSION,
+            schema_version: crate::GARDEN_SCHEMA_VERSION,
+            exported_at: Utc::now(),
+            channels,
+            blocks,
+            connections,
+        }
+    }
+}
+
+/// A snapshot of a single channel and its contents.
+///
+/// The unit for sharing or backing up one collection. Blocks are in channel
+/// order, and `connections` lists the matching positions in the same order.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ChannelExport {
+    /// Format version of this export.
+    pub version: u32,
+    /// When the export was taken.
+    #[ts(type = "string")]
+    pub exported_at: DateTime<Utc>,
+    /// The exported channel.
+    pub channel: Channel,
+    /// The channel's blocks, in order.
+    pub blocks: Vec<Block>,
+    /// The channel's connections, in the same order as `blocks`.
+    pub connections: Vec<Connection>,
+}
+
+impl ChannelExport {
+    /// The export format version written by this build.
+    pub const VERSION: u32 = 1;
+
+    /// Create an export at the current format version.
+    pub fn new(channel: Channel, blocks: Vec<Block>, connections: Vec<Connection>) -> Self {
+        Self {
+            version: Self::VERSION,
+            exported_at: Utc::now(),
+            channel,
+            blocks,
+            connections,
+        }
+    }
+}
+
+/// How to treat IDs when importing a [`ChannelExport`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStrategy {
+    /// Give the channel and every block fresh IDs.
+    ///
+    /// Always succeeds against an existing garden, even one the export was
+    /// taken from, at the cost of duplicating any blocks already present.
+    #[default]
+    RemapIds,
+    /// Keep the IDs from the export.
+    ///
+    /// Fails if the channel already exists. Blocks that already exist are
+    /// connected as-is rather than overwritten.
+    PreserveIds,
+}
+
+/// A block in an export that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct InvalidBlock {
+    /// The offending block.
+    pub block_id: BlockId,
+    /// Why the block was rejected.
+    pub reason: String,
+}
+
+/// Summary of what importing a [`GardenExport`] does.
+///
+/// Returned by both a dry run and a real import, so the frontend can show
+/// the same report before and after.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ImportPlan {
+    /// Channels that will be created.
+    pub new_channels: usize,
+    /// Blocks that will be created.
+    pub new_blocks: usize,
+    /// Connections that will be created.
+    pub new_connections: usize,
+    /// Channels whose ID already exists; the existing channel is kept.
+    pub channel_collisions: usize,
+    /// Blocks whose ID already exists; the existing block is kept.
+    pub block_collisions: usize,
+    /// Connections skipped because they already exist, are duplicated in the
+    /// export, or reference a missing or invalid block or channel.
+    pub skipped_connections: usize,
+    /// Blocks that failed validation and will not be imported.
+    pub invalid_blocks: Vec<InvalidBlock>,
+}
+
+impl ImportPlan {
+    /// Returns true if the import would write nothing.
+    pub fn is_empty(&self) -> bool {
+        self.new_channels == 0 && self.new_blocks == 0 && self.new_connections == 0
+    }
+}
+
+/// The outcome of one line of a URL list import.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UrlImportLine {
+    /// Line number in the file, starting at 1.
+    pub line: usize,
+    /// The URL as written, trimmed.
+    pub url: String,
+    /// The link block created for this line, if it was imported.
+    pub block_id: Option<BlockId>,
+    /// Why the line was skipped, if it was.
+    pub error: Option<String>,
+}
+
+/// Summary of importing a newline-delimited list of URLs into a channel.
+///
+/// Blank lines and `#` comments are not reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct UrlImportReport {
+    /// Number of link blocks created and connected.
+    pub imported: usize,
+    /// Number of lines skipped.
+    pub skipped: usize,
+    /// Every URL line, in file order.
+    pub lines: Vec<UrlImportLine>,
+}
@@ -0,0 +1,67 @@
+//! Facet models - grouped counts for archive browsing.
+
+use std::collections::HashMap;
+
+use url::Url;
+
+/// Extract the domain of a URL for grouping, without a leading `www.`.
+///
+/// Returns `None` if the URL does not parse or has no host.
+pub fn source_domain(url: &str) -> Option<String> {
+    let parsed = Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    Some(match host.strip_prefix("www.") {
+        Some(rest) => rest.to_string(),
+        None => host,
+    })
+}
+
+/// Group URLs by domain and count them.
+///
+/// Sorted by count (highest first), then by domain. URLs without a
+/// domain are skipped.
+pub fn count_source_domains<'a>(urls: impl IntoIterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for domain in urls.into_iter().filter_map(source_domain) {
+        *counts.entry(domain).or_default() += 1;
+    }
+
+    let mut facets: Vec<_> = counts.into_iter().collect();
+    facets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_domain_strips_www_and_lowercases() {
+        assert_eq!(
+            source_domain("https://WWW.NYTimes.com/2024/article"),
+            Some("nytimes.com".to_string())
+        );
+        assert_eq!(
+            source_domain("https://m.youtube.com/watch?v=1"),
+            Some("m.youtube.com".to_string())
+        );
+        assert_eq!(source_domain("not a url"), None);
+    }
+
+    #[test]
+    fn count_source_domains_groups_and_sorts() {
+        let facets = count_source_domains([
+            "https://youtube.com/a",
+            "https://www.nytimes.com/a",
+            "https://nytimes.com/b",
+            "garbage",
+        ]);
+        assert_eq!(
+            facets,
+            vec![
+                ("nytimes.com".to_string(), 2),
+                ("youtube.com".to_string(), 1)
+            ]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! ID generation for channels and blocks.
+
+/// How new channel and block IDs are generated.
+///
+/// Both strategies produce hyphenated UUID strings, so stored IDs of either
+/// kind live side by side and [`super::BlockId::from_string`] and
+/// [`super::ChannelId::from_string`] accept any of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdStrategy {
+    /// Random UUIDv4. Sorts in no particular order.
+    V4,
+    /// Time-ordered UUIDv7. IDs sort by creation time, which keeps inserts
+    /// near the end of primary-key indexes on large tables.
+    V7,
+}
+
+impl IdStrategy {
+    /// The strategy used by `BlockId::new` and `ChannelId::new`: UUIDv7 with
+    /// the `uuid-v7` feature, UUIDv4 otherwise.
+    pub const DEFAULT: Self = if cfg!(feature = "uuid-v7") {
+        Self::V7
+    } else {
+        Self::V4
+    };
+
+    /// Generate a new ID string.
+    ///
+    /// UUIDv7 IDs from one process increase strictly, even within the same
+    /// millisecond.
+    pub fn generate(self) -> String {
+        match self {
+            Self::V4 => uuid::Uuid::new_v4(),
+            Self::V7 => uuid::Uuid::now_v7(),
+        }
+        .to_string()
+    }
+}
+
+impl Default for IdStrategy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v7_ids_increase_within_a_batch() {
+        let ids: Vec<String> = (0..1000).map(|_| IdStrategy::V7.generate()).collect();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        for id in &ids {
+            assert_eq!(uuid::Uuid::parse_str(id).unwrap().get_version_num(), 7);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Integrity models - problems found by a database integrity check.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{BlockId, ChannelId};
+
+/// A row whose foreign key points at a missing parent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ForeignKeyViolation {
+    /// The table containing the dangling reference.
+    pub table: String,
+    /// Row ID of the offending row, if the table has one.
+    #[ts(type = "number | null")]
+    pub rowid: Option<i64>,
+    /// The table the reference should point into.
+    pub parent: String,
+}
+
+/// A block connected to the same channel more than once.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct DuplicateConnection {
+    /// The connected block.
+    pub block_id: BlockId,
+    /// The channel it is connected to.
+    pub channel_id: ChannelId,
+    /// Number of connection rows for the pair.
+    pub count: usize,
+}
+
+/// Several blocks sharing one position within a channel.
+///
+/// Reads tolerate this by breaking ties on block ID, but it usually means a
+/// reorder or import went wrong.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PositionCollision {
+    /// The channel with the collision.
+    pub channel_id: ChannelId,
+    /// The shared position.
+    pub position: i32,
+    /// Number of blocks at the position.
+    pub count: usize,
+}
+
+/// Problems found by a database integrity check.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IntegrityReport {
+    /// Messages from `PRAGMA integrity_check`, empty when it reports `ok`.
+    pub integrity_errors: Vec<String>,
+    /// Rows reported by `PRAGMA foreign_key_check`.
+    pub foreign_key_violations: Vec<ForeignKeyViolation>,
+    /// Block/channel pairs with more than one connection row.
+    ///
+    /// The primary key prevents this, so any hit means the table was
+    /// rebuilt or edited without it.
+    pub duplicate_connections: Vec<DuplicateConnection>,
+    /// Positions used by more than one block in a channel.
+    pub position_collisions: Vec<PositionCollision>,
+}
+
+impl IntegrityReport {
+    /// Returns true if no problems were found.
+    pub fn is_healthy(&self) -> bool {
+        self.integrity_errors.is_empty()
+            && self.foreign_key_violations.is_empty()
+            && self.duplicate_connections.is_empty()
+            && self.position_collisions.is_empty()
+    }
+}
@@ -0,0 +1,19 @@
+//! Link models - whether link blocks' URLs still resolve.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// The outcome of checking a link block's URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(tag = "status", content = "detail", rename_all = "snake_case")]
+pub enum LinkStatus {
+    /// The URL answered with a success status.
+    Ok,
+    /// The URL redirects elsewhere; holds the absolute target URL.
+    Redirected(String),
+    /// The URL answered 404 Not Found or 410 Gone.
+    NotFound,
+    /// Any other error status, or no response at all.
+    Error(String),
+}
@@ -0,0 +1,152 @@
+//! Markdown rendering - channels as publishable documents.
+
+use super::{Block, BlockContent, Channel};
+
+/// Render a channel and its blocks as Markdown.
+///
+/// The channel title becomes a heading, followed by its description and one
+/// list item per block, in the order given. Media paths are joined onto
+/// `media_base` so they resolve next to the rendered file; an empty base
+/// leaves them relative to the media directory.
+pub fn channel_markdown(channel: &Channel, blocks: &[Block], media_base: &str) -> String {
+    let mut out = format!("# {}\n", single_line(&channel.title));
+    if let Some(description) = channel.description.as_deref().map(str::trim) {
+        if !description.is_empty() {
+            out.push('\n');
+            out.push_str(description);
+            out.push('\n');
+        }
+    }
+
+    if !blocks.is_empty() {
+        out.push('\n');
+    }
+    for block in blocks {
+        out.push_str("- ");
+        out.push_str(&indent_continuation(&block_markdown(
+            &block.content,
+            media_base,
+        )));
+        out.push('\n');
+    }
+    out
+}
+
+/// Render a single block's content as inline Markdown.
+fn block_markdown(content: &BlockContent, media_base: &str) -> String {
+    match content {
+        BlockContent::Text { body } => body.trim().to_string(),
+        BlockContent::Link { url, title, .. } => {
+            let label = title.as_deref().filter(|t| !t.trim().is_empty());
+            format!("[{}]({})", link_text(label.unwrap_or(url)), url)
+        }
+        BlockContent::Image {
+            file_path,
+            alt_text,
+            ..
+        } => image(alt_text.as_deref(), &media_path(media_base, file_path)),
+        BlockContent::Video {
+            file_path,
+            alt_text,
+            ..
+        } => media_link(alt_text.as_deref(), &media_path(media_base, file_path)),
+        BlockContent::Audio {
+            file_path, title, ..
+        } => media_link(title.as_deref(), &media_path(media_base, file_path)),
+        BlockContent::Gallery { items } => items
+            .iter()
+            .map(|item| {
+                let path = media_path(media_base, &item.file_path);
+                if item.mime_type.as_str().starts_with("image/") {
+                    image(item.alt_text.as_deref(), &path)
+                } else {
+                    media_link(item.alt_text.as_deref(), &path)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        BlockContent::RemoteImage { url, alt_text, .. } => image(alt_text.as_deref(), url),
+    }
+}
+
+/// An image embed.
+fn image(alt_text: Option<&str>, path: &str) -> String {
+    format!("![{}]({})", link_text(alt_text.unwrap_or_default()), path)
+}
+
+/// A plain link to a media file, labelled with `label` or the file name.
+fn media_link(label: Option<&str>, path: &str) -> String {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let label = label.filter(|l| !l.trim().is_empty()).unwrap_or(file_name);
+    format!("[{}]({})", link_text(label), path)
+}
+
+/// Join a stored media path onto the output base.
+fn media_path(media_base: &str, file_path: &str) -> String {
+    let base = media_base.trim_end_matches('/');
+    if base.is_empty() {
+        file_path.to_string()
+    } else {
+        format!("{}/{}", base, file_path)
+    }
+}
+
+/// Escape text for use inside `[...]`.
+fn link_text(text: &str) -> String {
+    single_line(text)
+        .replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+}
+
+/// Collapse whitespace, including newlines, to single spaces.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Indent every line after the first so it stays inside its list item.
+fn indent_continuation(text: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| match (i, line.is_empty()) {
+            (0, _) | (_, true) => line.to_string(),
+            _ => format!("  {}", line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GalleryItem;
+
+    #[test]
+    fn media_paths_are_joined_onto_base() {
+        let channel = Channel::new("Photos");
+        let blocks = vec![
+            Block::new(BlockContent::image("images/a.jpg", "image/jpeg")),
+            Block::new(BlockContent::Gallery {
+                items: vec![
+                    GalleryItem::new("images/b.png", "image/png"),
+                    GalleryItem::new("videos/c.mp4", "video/mp4"),
+                ],
+            }),
+        ];
+
+        let markdown = channel_markdown(&channel, &blocks, "assets/");
+
+        assert!(markdown.contains("- ![](assets/images/a.jpg)\n"));
+        assert!(markdown.contains("- ![](assets/images/b.png)\n  [c.mp4](assets/videos/c.mp4)\n"));
+    }
+
+    #[test]
+    fn multiline_text_stays_in_its_list_item() {
+        let channel = Channel::new("Notes");
+        let blocks = vec![Block::new(BlockContent::text("first\nsecond"))];
+
+        let markdown = channel_markdown(&channel, &blocks, "");
+
+        assert_eq!(markdown, "# Notes\n\n- first\n  second\n");
+    }
+}
@@ -0,0 +1,234 @@
+//! MIME type model - normalized media type strings.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// MIME types Garden recognizes as media, with the extension stored files
+/// of each type get. The one list behind [`MimeType::extension`] and
+/// [`SupportedType::all`].
+const KNOWN_TYPES: &[(&str, &str)] = &[
+    // Images
+    ("image/jpeg", "jpg"),
+    ("image/png", "png"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+    ("image/svg+xml", "svg"),
+    // Videos
+    ("video/mp4", "mp4"),
+    ("video/webm", "webm"),
+    ("video/quicktime", "mov"),
+    ("video/x-msvideo", "avi"),
+    // Audio
+    ("audio/mpeg", "mp3"),
+    ("audio/ogg", "ogg"),
+    ("audio/wav", "wav"),
+    ("audio/webm", "webm"),
+    ("audio/flac", "flac"),
+];
+
+/// Media type classification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaType {
+    Image,
+    Video,
+    Audio,
+}
+
+impl MediaType {
+    /// Get the subdirectory name for this media type.
+    pub fn subdir(&self) -> &'static str {
+        match self {
+            MediaType::Image => "images",
+            MediaType::Video => "videos",
+            MediaType::Audio => "audio",
+        }
+    }
+
+    /// Get the top-level MIME category for this media type (e.g. "image").
+    pub fn category(&self) -> &'static str {
+        match self {
+            MediaType::Image => "image",
+            MediaType::Video => "video",
+            MediaType::Audio => "audio",
+        }
+    }
+
+    /// Detect media type from MIME type string.
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        MimeType::new(mime).media_type()
+    }
+}
+
+/// A normalized MIME type, e.g. `image/jpeg`.
+///
+/// Construction trims whitespace, drops parameters (`; charset=...`), and
+/// lowercases, so `IMAGE/JPEG; q=1` and `image/jpeg` compare equal. It
+/// serializes as the plain string, so stored JSON and the TypeScript types
+/// are unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct MimeType(String);
+
+impl MimeType {
+    /// The fallback type for unknown binary content.
+    pub const OCTET_STREAM: &'static str = "application/octet-stream";
+
+    /// Create a normalized MIME type.
+    pub fn new(mime: &str) -> Self {
+        let essence = mime.split(';').next().unwrap_or(mime).trim();
+        Self(essence.to_ascii_lowercase())
+    }
+
+    /// Get the MIME type as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if the MIME type is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the media type this MIME type belongs to, if any.
+    pub fn media_type(&self) -> Option<MediaType> {
+        match self.0.split('/').next() {
+            Some("image") => Some(MediaType::Image),
+            Some("video") => Some(MediaType::Video),
+            Some("audio") => Some(MediaType::Audio),
+            _ => None,
+        }
+    }
+
+    /// Get the preferred file extension for known media types.
+    pub fn extension(&self) -> Option<&'static str> {
+        KNOWN_TYPES
+            .iter()
+            .find(|(mime, _)| *mime == self.0)
+            .map(|(_, extension)| *extension)
+    }
+}
+
+/// A media format Garden recognizes, for building file picker filters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SupportedType {
+    /// The MIME type, e.g. `image/jpeg`.
+    #[ts(type = "string")]
+    pub mime: MimeType,
+    /// The extension stored files of this type get, without the dot.
+    pub extension: String,
+    /// Which kind of media the type is.
+    pub media_type: MediaType,
+}
+
+impl SupportedType {
+    /// Every recognized media format, images first, then videos, then
+    /// audio.
+    pub fn all() -> Vec<Self> {
+        KNOWN_TYPES
+            .iter()
+            .filter_map(|(mime, extension)| {
+                let mime = MimeType::new(mime);
+                Some(Self {
+                    media_type: mime.media_type()?,
+                    mime,
+                    extension: extension.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl From<&str> for MimeType {
+    fn from(mime: &str) -> Self {
+        Self::new(mime)
+    }
+}
+
+impl From<String> for MimeType {
+    fn from(mime: String) -> Self {
+        Self::new(&mime)
+    }
+}
+
+impl From<MimeType> for String {
+    fn from(mime: MimeType) -> Self {
+        mime.0
+    }
+}
+
+impl AsRef<str> for MimeType {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for MimeType {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for MimeType {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl std::fmt::Display for MimeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_type_normalizes_casing() {
+        assert_eq!(MimeType::new("IMAGE/JPEG"), "image/jpeg");
+        assert_eq!(MimeType::new("Image/Jpeg"), MimeType::new("image/jpeg"));
+    }
+
+    #[test]
+    fn mime_type_drops_parameters_and_whitespace() {
+        assert_eq!(MimeType::new(" text/html; charset=UTF-8 "), "text/html");
+    }
+
+    #[test]
+    fn mime_type_extension() {
+        assert_eq!(MimeType::new("image/jpeg").extension(), Some("jpg"));
+        assert_eq!(MimeType::new("VIDEO/MP4").extension(), Some("mp4"));
+        assert_eq!(MimeType::new("audio/mpeg").extension(), Some("mp3"));
+        assert_eq!(MimeType::new("unknown/type").extension(), None);
+    }
+
+    #[test]
+    fn mime_type_media_type() {
+        assert_eq!(
+            MimeType::new("image/png").media_type(),
+            Some(MediaType::Image)
+        );
+        assert_eq!(
+            MimeType::new("video/webm").media_type(),
+            Some(MediaType::Video)
+        );
+        assert_eq!(
+            MimeType::new("audio/ogg").media_type(),
+            Some(MediaType::Audio)
+        );
+        assert_eq!(MimeType::new("text/plain").media_type(), None);
+    }
+
+    #[test]
+    fn mime_type_serializes_as_plain_string() {
+        let mime = MimeType::new("image/png");
+        assert_eq!(serde_json::to_string(&mime).unwrap(), "\"image/png\"");
+
+        let parsed: MimeType = serde_json::from_str("\"IMAGE/PNG\"").unwrap();
+        assert_eq!(parsed, mime);
+    }
+}
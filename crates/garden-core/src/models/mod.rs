@@ -1,11 +1,31 @@
 //! Domain models for Garden.
 
+mod activity;
 mod block;
 mod channel;
 mod common;
 mod connection;
+mod export;
+mod facet;
+mod id;
+mod integrity;
+mod link;
+mod markdown;
+mod mime;
+mod search;
+mod storage;
 
+pub use activity::*;
 pub use block::*;
 pub use channel::*;
 pub use common::*;
 pub use connection::*;
+pub use export::*;
+pub use facet::*;
+pub use id::*;
+pub use integrity::*;
+pub use link::*;
+pub use markdown::*;
+pub use mime::*;
+pub use search::*;
+pub use storage::*;
@@ -0,0 +1,209 @@
+//! Search models - full-text search over blocks.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::{Block, BlockContent};
+
+/// How search results should be returned.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SearchMode {
+    /// Return matching blocks only.
+    #[default]
+    Plain,
+    /// Return matching blocks with a snippet around the match.
+    ///
+    /// Matched terms in the snippet are wrapped in `open` and `close`
+    /// (e.g. `<mark>` and `</mark>`), which gives the frontend the
+    /// highlight ranges without re-running the match itself.
+    Snippet {
+        /// Delimiter inserted before each matched term.
+        open: String,
+        /// Delimiter inserted after each matched term.
+        close: String,
+    },
+}
+
+impl SearchMode {
+    /// Snippet mode using `<mark>` tags as delimiters.
+    pub fn snippet() -> Self {
+        Self::Snippet {
+            open: "<mark>".to_string(),
+            close: "</mark>".to_string(),
+        }
+    }
+}
+
+/// A single search result.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SearchHit {
+    /// The matching block.
+    pub block: Block,
+    /// Snippet with highlighted terms (only in `SearchMode::Snippet`).
+    pub snippet: Option<String>,
+}
+
+/// Number of characters of context kept on each side of a naive snippet match.
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+impl Block {
+    /// Get all searchable text for this block, joined by spaces.
+    ///
+    /// This mirrors what the SQLite full-text index stores, so in-memory
+    /// search matches the same fields.
+    pub fn searchable_text(&self) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        match &self.content {
+            BlockContent::Text { body } => parts.push(body),
+            BlockContent::Link {
+                url,
+                title,
+                description,
+                alt_text,
+            } => {
+                parts.extend(title.as_deref());
+                parts.push(url);
+                parts.extend(description.as_deref());
+                parts.extend(alt_text.as_deref());
+            }
+            BlockContent::Image { alt_text, .. } | BlockContent::Video { alt_text, .. } => {
+                parts.extend(alt_text.as_deref());
+            }
+            BlockContent::RemoteImage { url, alt_text, .. } => {
+                parts.push(url);
+                parts.extend(alt_text.as_deref());
+            }
+            BlockContent::Audio { title, artist, .. } => {
+                parts.extend(title.as_deref());
+                parts.extend(artist.as_deref());
+            }
+            // Not in the SQLite index yet, which only covers top-level fields
+            BlockContent::Gallery { .. } => {}
+        }
+        parts.extend(self.notes.as_deref());
+        parts.join(" ")
+    }
+}
+
+/// Split a user query into lowercase search terms.
+pub fn search_terms(query: &str) -> Vec<String> {
+    query.split_whitespace().map(str::to_lowercase).collect()
+}
+
+/// Build a naive snippet around the first occurrence of any term.
+///
+/// Used by backends without native snippet support (e.g. the in-memory
+/// repositories). Matching is case-insensitive; every occurrence of every
+/// term inside the snippet window is wrapped in `open`/`close`. Returns
+/// `None` if no term occurs in `text`.
+pub fn naive_snippet(text: &str, terms: &[String], open: &str, close: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths for some scripts; fall back to the
+    // original text without highlighting rather than slicing at bad offsets.
+    if lower.len() != text.len() {
+        return terms
+            .iter()
+            .any(|t| lower.contains(t.as_str()))
+            .then(|| text.to_string());
+    }
+
+    let first = terms
+        .iter()
+        .filter(|t| !t.is_empty())
+        .filter_map(|t| lower.find(t.as_str()))
+        .min()?;
+
+    let start = floor_char_boundary(text, first.saturating_sub(SNIPPET_CONTEXT_CHARS));
+    let end = ceil_char_boundary(text, (first + SNIPPET_CONTEXT_CHARS).min(text.len()));
+    let window = &text[start..end];
+    let window_lower = &lower[start..end];
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    let mut i = 0;
+    while i < window.len() {
+        let matched = terms
+            .iter()
+            .filter(|t| !t.is_empty())
+            .find(|t| window_lower[i..].starts_with(t.as_str()));
+        match matched {
+            Some(term) => {
+                out.push_str(open);
+                out.push_str(&window[i..i + term.len()]);
+                out.push_str(close);
+                i += term.len();
+            }
+            None => {
+                let ch = window[i..].chars().next().unwrap_or_default();
+                out.push(ch);
+                i += ch.len_utf8();
+            }
+        }
+    }
+    if end < text.len() {
+        out.push('…');
+    }
+    Some(out)
+}
+
+fn floor_char_boundary(s: &str, mut i: usize) -> usize {
+    while i > 0 && !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+fn ceil_char_boundary(s: &str, mut i: usize) -> usize {
+    while i < s.len() && !s.is_char_boundary(i) {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn naive_snippet_wraps_matches() {
+        let terms = search_terms("Garden");
+        let snippet = naive_snippet("A walled garden of links", &terms, "[", "]").unwrap();
+        assert_eq!(snippet, "A walled [garden] of links");
+    }
+
+    #[test]
+    fn naive_snippet_truncates_long_text() {
+        let text = format!("{} needle {}", "x".repeat(100), "y".repeat(100));
+        let snippet = naive_snippet(&text, &search_terms("needle"), "<b>", "</b>").unwrap();
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.contains("<b>needle</b>"));
+    }
+
+    #[test]
+    fn naive_snippet_no_match() {
+        assert!(naive_snippet("nothing here", &search_terms("absent"), "[", "]").is_none());
+    }
+
+    #[test]
+    fn searchable_text_includes_link_fields_and_notes() {
+        let mut block = Block::new(BlockContent::link_with_meta(
+            "https://example.com",
+            Some("Example".to_string()),
+            Some("A description".to_string()),
+            None,
+        ));
+        block.notes = Some("my notes".to_string());
+
+        let text = block.searchable_text();
+        assert!(text.contains("Example"));
+        assert!(text.contains("https://example.com"));
+        assert!(text.contains("A description"));
+        assert!(text.contains("my notes"));
+    }
+}
@@ -0,0 +1,49 @@
+//! Storage models - disk space used by media files.
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use super::MediaType;
+
+/// Bytes and files used by one kind of media.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct MediaUsage {
+    /// Total size of the files, in bytes.
+    #[ts(type = "number")]
+    pub bytes: u64,
+    /// Number of files.
+    pub file_count: usize,
+}
+
+/// Disk space used by the media directory, in total and per media type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct StorageUsage {
+    /// Total size of all media files, in bytes.
+    #[ts(type = "number")]
+    pub total_bytes: u64,
+    /// Number of media files.
+    pub file_count: usize,
+    /// Files under `images/`.
+    pub images: MediaUsage,
+    /// Files under `videos/`.
+    pub videos: MediaUsage,
+    /// Files under `audio/`.
+    pub audio: MediaUsage,
+}
+
+impl StorageUsage {
+    /// Count a file of `bytes` towards `media_type` and the total.
+    pub fn add(&mut self, media_type: MediaType, bytes: u64) {
+        let usage = match media_type {
+            MediaType::Image => &mut self.images,
+            MediaType::Video => &mut self.videos,
+            MediaType::Audio => &mut self.audio,
+        };
+        usage.bytes += bytes;
+        usage.file_count += 1;
+        self.total_bytes += bytes;
+        self.file_count += 1;
+    }
+}
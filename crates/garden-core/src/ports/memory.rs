@@ -18,30 +18,40 @@
 //! let service = fixture.service();
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 
 use crate::error::{RepoError, RepoResult};
-use crate::models::{Block, BlockId, Channel, ChannelId, Connection, Page};
-use crate::ports::{BlockRepository, ChannelRepository, ConnectionRepository};
+use crate::models::{
+    complete_order, count_source_domains, gapped_position, move_group, move_relative,
+    naive_snippet, position_issues, search_terms, ActivityEntry, Block, BlockId, Channel,
+    ChannelId, ChannelOrder, ChannelSetMode, Connection, MetadataStats, Page, Placement,
+    PositionIssue, SearchHit, SearchMode, SharingStats,
+};
+use crate::ports::{ActivityRepository, BlockRepository, ChannelRepository, ConnectionRepository};
 
 // Type aliases for shared storage
 type SharedChannelStore = Arc<RwLock<HashMap<ChannelId, Channel>>>;
 type SharedBlockStore = Arc<RwLock<HashMap<BlockId, Block>>>;
 type SharedConnectionStore = Arc<RwLock<Vec<Connection>>>;
+type IdempotencyStore<Id> = Arc<RwLock<HashMap<String, (Id, DateTime<Utc>)>>>;
+type TagStore = Arc<RwLock<HashMap<BlockId, BTreeSet<String>>>>;
 
 /// In-memory channel repository.
 #[derive(Debug, Clone)]
 pub struct InMemoryChannelRepository {
     channels: SharedChannelStore,
+    idempotency_keys: IdempotencyStore<ChannelId>,
 }
 
 impl Default for InMemoryChannelRepository {
     fn default() -> Self {
         Self {
             channels: Arc::new(RwLock::new(HashMap::new())),
+            idempotency_keys: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 }
@@ -54,7 +64,10 @@ impl InMemoryChannelRepository {
 
     /// Create with shared storage (used by TestFixture).
     pub(crate) fn with_shared_store(channels: SharedChannelStore) -> Self {
-        Self { channels }
+        Self {
+            channels,
+            idempotency_keys: Arc::new(RwLock::new(HashMap::new())),
+        }
     }
 }
 
@@ -72,6 +85,34 @@ impl ChannelRepository for InMemoryChannelRepository {
         Ok(())
     }
 
+    async fn create_idempotent(
+        &self,
+        channel: &Channel,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Channel> {
+        let mut keys = self
+            .idempotency_keys
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let mut channels = self
+            .channels
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        keys.retain(|_, (_, created_at)| *created_at >= not_before);
+        if let Some(existing) = keys.get(key).and_then(|(id, _)| channels.get(id)) {
+            return Ok(existing.clone());
+        }
+
+        if channels.contains_key(&channel.id) {
+            return Err(RepoError::Duplicate);
+        }
+        channels.insert(channel.id.clone(), channel.clone());
+        keys.insert(key.to_string(), (channel.id.clone(), Utc::now()));
+        Ok(channel.clone())
+    }
+
     async fn get(&self, id: &ChannelId) -> RepoResult<Option<Channel>> {
         let channels = self
             .channels
@@ -88,8 +129,12 @@ impl ChannelRepository for InMemoryChannelRepository {
         let total = channels.len();
 
         let mut items: Vec<_> = channels.values().cloned().collect();
-        // Sort by created_at descending for consistent ordering
-        items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        // Match SQLite: newest first, ties by ID so pages never overlap
+        items.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| b.id.0.cmp(&a.id.0))
+        });
 
         let items: Vec<_> = items.into_iter().skip(offset).take(limit).collect();
 
@@ -126,18 +171,60 @@ impl ChannelRepository for InMemoryChannelRepository {
             .map_err(|_| RepoError::Database("lock poisoned".into()))?;
         Ok(channels.len())
     }
+
+    async fn touch_accessed(&self, id: &ChannelId) -> RepoResult<()> {
+        let mut channels = self
+            .channels
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let channel = channels.get_mut(id).ok_or(RepoError::NotFound)?;
+        channel.accessed_at = Some(Utc::now());
+        Ok(())
+    }
+
+    async fn list_recently_accessed(&self, limit: usize) -> RepoResult<Vec<Channel>> {
+        let channels = self
+            .channels
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut items: Vec<_> = channels
+            .values()
+            .filter(|c| c.accessed_at.is_some())
+            .cloned()
+            .collect();
+        items.sort_by_key(|c| std::cmp::Reverse(c.accessed_at));
+        items.truncate(limit);
+        Ok(items)
+    }
+
+    async fn list_recently_updated(&self, limit: usize) -> RepoResult<Vec<Channel>> {
+        let channels = self
+            .channels
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut items: Vec<_> = channels.values().cloned().collect();
+        items.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+        items.truncate(limit);
+        Ok(items)
+    }
 }
 
 /// In-memory block repository.
 #[derive(Debug, Clone)]
 pub struct InMemoryBlockRepository {
     blocks: SharedBlockStore,
+    idempotency_keys: IdempotencyStore<BlockId>,
+    tags: TagStore,
 }
 
 impl Default for InMemoryBlockRepository {
     fn default() -> Self {
         Self {
             blocks: Arc::new(RwLock::new(HashMap::new())),
+            idempotency_keys: Arc::new(RwLock::new(HashMap::new())),
+            tags: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 }
@@ -150,7 +237,11 @@ impl InMemoryBlockRepository {
 
     /// Create with shared storage (used by TestFixture).
     pub(crate) fn with_shared_store(blocks: SharedBlockStore) -> Self {
-        Self { blocks }
+        Self {
+            blocks,
+            idempotency_keys: Arc::new(RwLock::new(HashMap::new())),
+            tags: Arc::new(RwLock::new(HashMap::new())),
+        }
     }
 }
 
@@ -168,7 +259,38 @@ impl BlockRepository for InMemoryBlockRepository {
         Ok(())
     }
 
+    async fn create_idempotent(
+        &self,
+        block: &Block,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Block> {
+        let mut keys = self
+            .idempotency_keys
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let mut blocks = self
+            .blocks
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        keys.retain(|_, (_, created_at)| *created_at >= not_before);
+        if let Some(existing) = keys.get(key).and_then(|(id, _)| blocks.get(id)) {
+            return Ok(existing.clone());
+        }
+
+        if blocks.contains_key(&block.id) {
+            return Err(RepoError::Duplicate);
+        }
+        blocks.insert(block.id.clone(), block.clone());
+        keys.insert(key.to_string(), (block.id.clone(), Utc::now()));
+        Ok(block.clone())
+    }
+
     async fn create_batch(&self, blocks_to_create: &[Block]) -> RepoResult<()> {
+        if blocks_to_create.is_empty() {
+            return Ok(());
+        }
         let mut blocks = self
             .blocks
             .write()
@@ -204,6 +326,23 @@ impl BlockRepository for InMemoryBlockRepository {
         Ok(())
     }
 
+    async fn update_batch(&self, blocks_to_update: &[Block]) -> RepoResult<()> {
+        let mut blocks = self
+            .blocks
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        if blocks_to_update
+            .iter()
+            .any(|block| !blocks.contains_key(&block.id))
+        {
+            return Err(RepoError::NotFound);
+        }
+        for block in blocks_to_update {
+            blocks.insert(block.id.clone(), block.clone());
+        }
+        Ok(())
+    }
+
     async fn delete(&self, id: &BlockId) -> RepoResult<()> {
         let mut blocks = self
             .blocks
@@ -212,8 +351,263 @@ impl BlockRepository for InMemoryBlockRepository {
         if blocks.remove(id).is_none() {
             return Err(RepoError::NotFound);
         }
+        // Tags go with the block, like the SQLite cascade
+        self.tags
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?
+            .remove(id);
         Ok(())
     }
+
+    async fn source_domains(&self) -> RepoResult<Vec<(String, usize)>> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        Ok(count_source_domains(
+            blocks.values().filter_map(|b| b.source_url.as_deref()),
+        ))
+    }
+
+    async fn count_file_references(&self, file_path: &str) -> RepoResult<usize> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        Ok(blocks
+            .values()
+            .filter(|b| b.content.references_file(file_path))
+            .count())
+    }
+
+    async fn find_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut matches: Vec<_> = blocks
+            .values()
+            .filter(|b| include_archived || !b.archived)
+            .filter(|b| b.created_at >= start && b.created_at <= end)
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.0.cmp(&b.id.0)));
+        let total = matches.len();
+        let items = matches.into_iter().skip(offset).take(limit).collect();
+
+        Ok(Page::new(items, total, offset, limit))
+    }
+
+    async fn counts_by_day(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<(NaiveDate, usize)>> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut counts: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for day in blocks.values().map(|b| b.created_at.date_naive()) {
+            if day >= start && day <= end {
+                *counts.entry(day).or_default() += 1;
+            }
+        }
+        Ok(counts.into_iter().collect())
+    }
+
+    async fn find_by_duration(
+        &self,
+        min: Option<f32>,
+        max: Option<f32>,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Block>> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut matches: Vec<_> = blocks
+            .values()
+            .filter(|b| !b.archived)
+            .filter(|b| {
+                b.content.duration().is_some_and(|d| {
+                    min.is_none_or(|min| d >= min) && max.is_none_or(|max| d <= max)
+                })
+            })
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.0.cmp(&b.id.0)));
+        let total = matches.len();
+        let items = matches.into_iter().skip(offset).take(limit).collect();
+
+        Ok(Page::new(items, total, offset, limit))
+    }
+
+    async fn total_word_count(&self) -> RepoResult<usize> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        Ok(blocks.values().map(|b| b.content.word_count()).sum())
+    }
+
+    async fn metadata_completeness(&self) -> RepoResult<MetadataStats> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let missing =
+            |field: &Option<String>| usize::from(field.as_deref().is_none_or(str::is_empty));
+        Ok(blocks
+            .values()
+            .fold(MetadataStats::default(), |stats, b| MetadataStats {
+                total: stats.total + 1,
+                missing_source_url: stats.missing_source_url + missing(&b.source_url),
+                missing_creator: stats.missing_creator + missing(&b.creator),
+                missing_original_date: stats.missing_original_date + missing(&b.original_date),
+                missing_notes: stats.missing_notes + missing(&b.notes),
+            }))
+    }
+
+    async fn for_each(
+        &self,
+        batch_size: usize,
+        f: &mut (dyn FnMut(Block) + Send),
+    ) -> RepoResult<usize> {
+        let batch_size = batch_size.max(1);
+        let mut after: Option<BlockId> = None;
+        let mut visited = 0;
+
+        loop {
+            // Hold the lock for one batch only, like a page query would
+            let batch: Vec<Block> = {
+                let blocks = self
+                    .blocks
+                    .read()
+                    .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+                let mut batch: Vec<&Block> = blocks
+                    .values()
+                    .filter(|b| after.as_ref().is_none_or(|a| b.id.0 > a.0))
+                    .collect();
+                batch.sort_by(|a, b| a.id.0.cmp(&b.id.0));
+                batch.into_iter().take(batch_size).cloned().collect()
+            };
+
+            let full = batch.len() == batch_size;
+            after = batch.last().map(|b| b.id.clone());
+            visited += batch.len();
+            batch.into_iter().for_each(&mut *f);
+            if !full {
+                return Ok(visited);
+            }
+        }
+    }
+
+    async fn get_tags(&self, block_id: &BlockId) -> RepoResult<Vec<String>> {
+        let tags = self
+            .tags
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        Ok(tags
+            .get(block_id)
+            .map(|t| t.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn add_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let mut tags = self
+            .tags
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        if block_ids.iter().any(|id| !blocks.contains_key(id)) {
+            return Err(RepoError::NotFound);
+        }
+
+        let added = block_ids
+            .iter()
+            .filter(|id| {
+                tags.entry((*id).clone())
+                    .or_default()
+                    .insert(tag.to_string())
+            })
+            .count();
+        Ok(added)
+    }
+
+    async fn remove_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize> {
+        let mut tags = self
+            .tags
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let removed = block_ids
+            .iter()
+            .filter(|id| tags.get_mut(*id).is_some_and(|t| t.remove(tag)))
+            .count();
+        Ok(removed)
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        mode: &SearchMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<SearchHit>> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let terms = search_terms(query);
+
+        let mut matches: Vec<_> = blocks
+            .values()
+            .filter(|b| include_archived || !b.archived)
+            .filter(|b| {
+                let text = b.searchable_text().to_lowercase();
+                !terms.is_empty() && terms.iter().all(|t| text.contains(t.as_str()))
+            })
+            .cloned()
+            .collect();
+        // No relevance ranking in memory; newest first for consistent ordering
+        matches.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        let total = matches.len();
+
+        let items = matches
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|block| {
+                let snippet = match mode {
+                    SearchMode::Plain => None,
+                    SearchMode::Snippet { open, close } => {
+                        naive_snippet(&block.searchable_text(), &terms, open, close)
+                    }
+                };
+                SearchHit { block, snippet }
+            })
+            .collect();
+
+        Ok(Page::new(items, total, offset, limit))
+    }
 }
 
 /// In-memory connection repository.
@@ -229,6 +623,8 @@ pub struct InMemoryConnectionRepository {
     blocks: SharedBlockStore,
     /// Reference to channels for get_channels_for_block.
     channels: SharedChannelStore,
+    /// Whether `get_connection` misses existing connections.
+    stale_lookups: bool,
 }
 
 impl Default for InMemoryConnectionRepository {
@@ -237,6 +633,7 @@ impl Default for InMemoryConnectionRepository {
             connections: Arc::new(RwLock::new(Vec::new())),
             blocks: Arc::new(RwLock::new(HashMap::new())),
             channels: Arc::new(RwLock::new(HashMap::new())),
+            stale_lookups: false,
         }
     }
 }
@@ -263,6 +660,7 @@ impl InMemoryConnectionRepository {
             connections: Arc::new(RwLock::new(Vec::new())),
             blocks: Arc::new(RwLock::new(blocks)),
             channels: Arc::new(RwLock::new(channels)),
+            stale_lookups: false,
         }
     }
 
@@ -276,9 +674,19 @@ impl InMemoryConnectionRepository {
             connections,
             blocks,
             channels,
+            stale_lookups: false,
         }
     }
 
+    /// Make `get_connection` miss existing connections (for testing).
+    ///
+    /// Simulates a concurrent connect landing after a caller's check, so
+    /// the write that follows is the first to see it.
+    pub fn with_stale_lookups(mut self) -> Self {
+        self.stale_lookups = true;
+        self
+    }
+
     /// Update the blocks reference (for testing).
     #[deprecated(
         since = "0.1.0",
@@ -306,6 +714,84 @@ impl InMemoryConnectionRepository {
         *c = channels;
         Ok(())
     }
+
+    /// Fail with `NotFound` unless both ends of a connection exist.
+    ///
+    /// Mirrors the foreign keys on the SQLite `connections` table, so tests
+    /// catch service code that connects missing blocks or channels.
+    fn check_endpoints_exist(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let channels = self
+            .channels
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        if !blocks.contains_key(block_id) || !channels.contains_key(channel_id) {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+
+    /// Renumber a channel's connections `gap` apart from zero, in the order
+    /// `arrange` makes from the current one; `None` from `arrange` fails
+    /// with `NotFound`.
+    fn apply_order<F>(&self, channel_id: &ChannelId, gap: i32, arrange: F) -> RepoResult<()>
+    where
+        F: FnOnce(&[BlockId]) -> Option<Vec<BlockId>>,
+    {
+        let mut connections = self
+            .connections
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut in_channel: Vec<&Connection> = connections
+            .iter()
+            .filter(|c| &c.channel_id == channel_id)
+            .collect();
+        in_channel.sort_by(|a, b| (a.position, &a.block_id.0).cmp(&(b.position, &b.block_id.0)));
+        let current: Vec<BlockId> = in_channel.iter().map(|c| c.block_id.clone()).collect();
+
+        let order = arrange(&current).ok_or(RepoError::NotFound)?;
+        let positions: HashMap<&BlockId, i32> = order
+            .iter()
+            .enumerate()
+            .map(|(index, id)| gapped_position(index, gap).map(|position| (id, position)))
+            .collect::<Option<_>>()
+            .ok_or_else(|| RepoError::Database("positions would pass i32::MAX".into()))?;
+        for conn in connections
+            .iter_mut()
+            .filter(|c| &c.channel_id == channel_id)
+        {
+            if let Some(&position) = positions.get(&conn.block_id) {
+                conn.position = position;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The channels holding a block, ordered by title.
+fn channels_of_block(
+    block_id: &BlockId,
+    connections: &[Connection],
+    channels: &HashMap<ChannelId, Channel>,
+) -> Vec<Channel> {
+    let mut in_channels: Vec<Channel> = connections
+        .iter()
+        .filter(|c| &c.block_id == block_id)
+        .filter_map(|c| channels.get(&c.channel_id).cloned())
+        .collect();
+    // Match SQLite, including its ASCII-only NOCASE collation
+    in_channels.sort_by(|a, b| {
+        a.title
+            .to_ascii_lowercase()
+            .cmp(&b.title.to_ascii_lowercase())
+            .then_with(|| a.id.0.cmp(&b.id.0))
+    });
+    in_channels
 }
 
 #[async_trait]
@@ -321,6 +807,8 @@ impl ConnectionRepository for InMemoryConnectionRepository {
             .write()
             .map_err(|_| RepoError::Database("lock poisoned".into()))?;
 
+        self.check_endpoints_exist(block_id, channel_id)?;
+
         // Check for duplicate
         if connections
             .iter()
@@ -337,14 +825,45 @@ impl ConnectionRepository for InMemoryConnectionRepository {
         Ok(())
     }
 
+    async fn upsert(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+        position: i32,
+    ) -> RepoResult<()> {
+        let mut connections = self
+            .connections
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        self.check_endpoints_exist(block_id, channel_id)?;
+
+        match connections
+            .iter_mut()
+            .find(|c| &c.block_id == block_id && &c.channel_id == channel_id)
+        {
+            Some(existing) => existing.position = position,
+            None => connections.push(Connection::new(
+                block_id.clone(),
+                channel_id.clone(),
+                position,
+            )),
+        }
+        Ok(())
+    }
+
     async fn connect_batch(&self, conns: &[(BlockId, ChannelId, i32)]) -> RepoResult<()> {
+        if conns.is_empty() {
+            return Ok(());
+        }
         let mut connections = self
             .connections
             .write()
             .map_err(|_| RepoError::Database("lock poisoned".into()))?;
 
-        // Check for duplicates first
+        // Check endpoints and duplicates first
         for (block_id, channel_id, _) in conns {
+            self.check_endpoints_exist(block_id, channel_id)?;
             if connections
                 .iter()
                 .any(|c| &c.block_id == block_id && &c.channel_id == channel_id)
@@ -363,34 +882,166 @@ impl ConnectionRepository for InMemoryConnectionRepository {
         Ok(())
     }
 
-    async fn disconnect(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+    async fn create_connected(
+        &self,
+        new_channels: &[Channel],
+        new_blocks: &[Block],
+        conns: &[(BlockId, ChannelId, i32)],
+    ) -> RepoResult<()> {
         let mut connections = self
             .connections
             .write()
             .map_err(|_| RepoError::Database("lock poisoned".into()))?;
-        let initial_len = connections.len();
-        connections.retain(|c| !(&c.block_id == block_id && &c.channel_id == channel_id));
-
-        if connections.len() == initial_len {
-            return Err(RepoError::NotFound);
-        }
-        Ok(())
-    }
-
-    async fn get_blocks_in_channel(&self, channel_id: &ChannelId) -> RepoResult<Vec<(Block, i32)>> {
-        let connections = self
-            .connections
-            .read()
-            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
-        let blocks = self
+        let mut blocks = self
             .blocks
-            .read()
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let mut channels = self
+            .channels
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        // Check everything first, so a failure writes nothing
+        let mut new_channel_ids = HashSet::new();
+        for channel in new_channels {
+            if channels.contains_key(&channel.id) || !new_channel_ids.insert(&channel.id) {
+                return Err(RepoError::Duplicate);
+            }
+        }
+        let mut new_ids = HashSet::new();
+        for block in new_blocks {
+            if blocks.contains_key(&block.id) || !new_ids.insert(&block.id) {
+                return Err(RepoError::Duplicate);
+            }
+        }
+        let mut pairs = HashSet::new();
+        for (block_id, channel_id, _) in conns {
+            let block_exists = blocks.contains_key(block_id) || new_ids.contains(block_id);
+            let channel_exists =
+                channels.contains_key(channel_id) || new_channel_ids.contains(channel_id);
+            if !block_exists || !channel_exists {
+                return Err(RepoError::NotFound);
+            }
+            if !pairs.insert((block_id, channel_id))
+                || connections
+                    .iter()
+                    .any(|c| &c.block_id == block_id && &c.channel_id == channel_id)
+            {
+                return Err(RepoError::Duplicate);
+            }
+        }
+
+        for channel in new_channels {
+            channels.insert(channel.id.clone(), channel.clone());
+        }
+        for block in new_blocks {
+            blocks.insert(block.id.clone(), block.clone());
+        }
+        for (block_id, channel_id, position) in conns {
+            connections.push(Connection::new(
+                block_id.clone(),
+                channel_id.clone(),
+                *position,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn prepend(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+        let mut connections = self
+            .connections
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        self.check_endpoints_exist(block_id, channel_id)?;
+        if connections
+            .iter()
+            .any(|c| &c.block_id == block_id && &c.channel_id == channel_id)
+        {
+            return Err(RepoError::Duplicate);
+        }
+
+        for conn in connections
+            .iter_mut()
+            .filter(|c| &c.channel_id == channel_id)
+        {
+            conn.position += 1;
+        }
+        connections.push(Connection::new(block_id.clone(), channel_id.clone(), 0));
+        Ok(())
+    }
+
+    async fn merge_blocks(&self, keep: &BlockId, merge: &[BlockId]) -> RepoResult<Vec<ChannelId>> {
+        let mut connections = self
+            .connections
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let mut blocks = self
+            .blocks
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        if std::iter::once(keep)
+            .chain(merge)
+            .any(|id| !blocks.contains_key(id))
+        {
+            return Err(RepoError::NotFound);
+        }
+
+        let mut held: HashSet<ChannelId> = connections
+            .iter()
+            .filter(|c| &c.block_id == keep)
+            .map(|c| c.channel_id.clone())
+            .collect();
+        let mut joined = Vec::new();
+        for id in merge {
+            for conn in connections.iter_mut().filter(|c| &c.block_id == id) {
+                if held.insert(conn.channel_id.clone()) {
+                    conn.block_id = keep.clone();
+                    joined.push(conn.channel_id.clone());
+                }
+            }
+            // Connections left behind were duplicates; they go with the block
+            connections.retain(|c| &c.block_id != id);
+            blocks.remove(id);
+        }
+        Ok(joined)
+    }
+
+    async fn disconnect(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+        let mut connections = self
+            .connections
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let initial_len = connections.len();
+        connections.retain(|c| !(&c.block_id == block_id && &c.channel_id == channel_id));
+
+        if connections.len() == initial_len {
+            return Err(RepoError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn get_blocks_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, i32)>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
             .map_err(|_| RepoError::Database("lock poisoned".into()))?;
 
         let mut result: Vec<_> = connections
             .iter()
             .filter(|c| &c.channel_id == channel_id)
-            .filter_map(|c| blocks.get(&c.block_id).map(|b| (b.clone(), c.position)))
+            .filter_map(|c| blocks.get(&c.block_id).map(|b| (b, c.position)))
+            .filter(|(b, _)| include_archived || !b.archived)
+            .map(|(b, position)| (b.clone(), position))
             .collect();
 
         // Sort by position
@@ -398,7 +1049,314 @@ impl ConnectionRepository for InMemoryConnectionRepository {
         Ok(result)
     }
 
-    async fn get_channels_for_block(&self, block_id: &BlockId) -> RepoResult<Vec<Channel>> {
+    async fn connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<Vec<(Block, Connection)>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut result: Vec<_> = connections
+            .iter()
+            .filter(|c| &c.channel_id == channel_id)
+            .filter_map(|c| blocks.get(&c.block_id).map(|b| (b.clone(), c.clone())))
+            .collect();
+        result.sort_by(|(_, a), (_, b)| {
+            (a.position, &a.block_id.0).cmp(&(b.position, &b.block_id.0))
+        });
+        Ok(result)
+    }
+
+    async fn recent_connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, Connection)>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut result: Vec<_> = connections
+            .iter()
+            .filter(|c| &c.channel_id == channel_id)
+            .filter_map(|c| blocks.get(&c.block_id).map(|b| (b, c)))
+            .filter(|(b, _)| include_archived || !b.archived)
+            .map(|(b, c)| (b.clone(), c.clone()))
+            .collect();
+        result.sort_by(|(_, a), (_, b)| {
+            b.connected_at
+                .cmp(&a.connected_at)
+                .then_with(|| a.block_id.0.cmp(&b.block_id.0))
+        });
+        result.truncate(limit);
+        Ok(result)
+    }
+
+    async fn get_blocks_in_channel_paged(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, i32)>> {
+        let all = self
+            .get_blocks_in_channel(channel_id, include_archived)
+            .await?;
+        let total = all.len();
+        let items = all.into_iter().skip(offset).take(limit).collect();
+        Ok(Page::new(items, total, offset, limit))
+    }
+
+    async fn neighbors(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<(Option<Block>, Option<Block>)> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut in_channel: Vec<_> = connections
+            .iter()
+            .filter(|c| &c.channel_id == channel_id)
+            .collect();
+        in_channel.sort_by(|a, b| (a.position, &a.block_id.0).cmp(&(b.position, &b.block_id.0)));
+
+        let index = in_channel
+            .iter()
+            .position(|c| &c.block_id == block_id)
+            .ok_or(RepoError::NotFound)?;
+        let block_at = |i: usize| {
+            in_channel
+                .get(i)
+                .and_then(|c| blocks.get(&c.block_id))
+                .cloned()
+        };
+
+        Ok((index.checked_sub(1).and_then(block_at), block_at(index + 1)))
+    }
+
+    async fn block_index_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<Option<(usize, usize)>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut in_channel: Vec<_> = connections
+            .iter()
+            .filter(|c| &c.channel_id == channel_id)
+            .collect();
+        in_channel.sort_by(|a, b| (a.position, &a.block_id.0).cmp(&(b.position, &b.block_id.0)));
+
+        Ok(in_channel
+            .iter()
+            .position(|c| &c.block_id == block_id)
+            .map(|index| (index, in_channel.len())))
+    }
+
+    async fn get_channels_for_block(
+        &self,
+        block_id: &BlockId,
+        order: ChannelOrder,
+    ) -> RepoResult<Vec<Channel>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let channels = self
+            .channels
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut result: Vec<_> = connections
+            .iter()
+            .filter(|c| &c.block_id == block_id)
+            .filter_map(|c| Some((c.connected_at, channels.get(&c.channel_id)?.clone())))
+            .collect();
+        // Match SQLite, including its ASCII-only NOCASE collation
+        match order {
+            ChannelOrder::ByConnectedAtDesc => {
+                result.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.id.0.cmp(&b.1.id.0)))
+            }
+            ChannelOrder::ByTitleAsc => result.sort_by(|a, b| {
+                a.1.title
+                    .to_ascii_lowercase()
+                    .cmp(&b.1.title.to_ascii_lowercase())
+                    .then_with(|| a.1.id.0.cmp(&b.1.id.0))
+            }),
+        }
+
+        Ok(result.into_iter().map(|(_, channel)| channel).collect())
+    }
+
+    async fn blocks_in_channels(
+        &self,
+        channel_ids: &[ChannelId],
+        mode: ChannelSetMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let wanted: HashSet<&ChannelId> = channel_ids.iter().collect();
+        let required = match mode {
+            ChannelSetMode::Union => 1,
+            ChannelSetMode::Intersection => wanted.len(),
+        };
+
+        let mut memberships: HashMap<&BlockId, usize> = HashMap::new();
+        for c in connections
+            .iter()
+            .filter(|c| wanted.contains(&c.channel_id))
+        {
+            *memberships.entry(&c.block_id).or_insert(0) += 1;
+        }
+        let mut matched: Vec<&Block> = memberships
+            .into_iter()
+            .filter(|(_, count)| !wanted.is_empty() && *count >= required)
+            .filter_map(|(id, _)| blocks.get(id))
+            .filter(|b| include_archived || !b.archived)
+            .collect();
+        // Match SQLite: newest first, ties by block ID
+        matched.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| a.id.0.cmp(&b.id.0))
+        });
+
+        let total = matched.len();
+        let items = matched
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect();
+        Ok(Page::new(items, total, offset, limit))
+    }
+
+    async fn blocks_not_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let connected: HashSet<&BlockId> = connections
+            .iter()
+            .filter(|c| &c.channel_id == channel_id)
+            .map(|c| &c.block_id)
+            .collect();
+        let mut available: Vec<&Block> = blocks
+            .values()
+            .filter(|b| (include_archived || !b.archived) && !connected.contains(&b.id))
+            .collect();
+        // Match SQLite: newest first, ties by block ID
+        available.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| a.id.0.cmp(&b.id.0))
+        });
+
+        let total = available.len();
+        let items = available
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect();
+        Ok(Page::new(items, total, offset, limit))
+    }
+
+    async fn blocks_with_channels(
+        &self,
+        block_ids: Option<&[BlockId]>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, Vec<Channel>)>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let channels = self
+            .channels
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let wanted: Option<HashSet<&BlockId>> = block_ids.map(|ids| ids.iter().collect());
+        let mut all: Vec<&Block> = blocks
+            .values()
+            .filter(|b| include_archived || !b.archived)
+            .filter(|b| wanted.as_ref().is_none_or(|w| w.contains(&b.id)))
+            .collect();
+        // Match SQLite: newest first, ties by block ID
+        all.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| a.id.0.cmp(&b.id.0))
+        });
+
+        let total = all.len();
+        let items = all
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|block| {
+                let in_channels = channels_of_block(&block.id, &connections, &channels);
+                (block.clone(), in_channels)
+            })
+            .collect();
+        Ok(Page::new(items, total, offset, limit))
+    }
+
+    async fn get_channels_for_block_paged(
+        &self,
+        block_id: &BlockId,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>> {
         let connections = self
             .connections
             .read()
@@ -408,13 +1366,70 @@ impl ConnectionRepository for InMemoryConnectionRepository {
             .read()
             .map_err(|_| RepoError::Database("lock poisoned".into()))?;
 
-        let result: Vec<_> = connections
+        let mut for_block: Vec<_> = connections
             .iter()
             .filter(|c| &c.block_id == block_id)
+            .collect();
+        // Match SQLite: most recently connected first, ties by channel ID
+        for_block.sort_by(|a, b| {
+            b.connected_at
+                .cmp(&a.connected_at)
+                .then_with(|| a.channel_id.0.cmp(&b.channel_id.0))
+        });
+
+        let total = for_block.len();
+        let items = for_block
+            .into_iter()
+            .skip(offset)
+            .take(limit)
             .filter_map(|c| channels.get(&c.channel_id).cloned())
             .collect();
 
-        Ok(result)
+        Ok(Page::new(items, total, offset, limit))
+    }
+
+    async fn channels_with_content_type(
+        &self,
+        content_type: &str,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let channels = self
+            .channels
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let matching: HashSet<&ChannelId> = connections
+            .iter()
+            .filter(|c| {
+                blocks
+                    .get(&c.block_id)
+                    .is_some_and(|b| b.content.kind().as_str() == content_type)
+            })
+            .map(|c| &c.channel_id)
+            .collect();
+        let mut items: Vec<Channel> = matching
+            .into_iter()
+            .filter_map(|id| channels.get(id).cloned())
+            .collect();
+        // Match SQLite: newest channel first, ties by channel ID
+        items.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then_with(|| a.id.0.cmp(&b.id.0))
+        });
+
+        let total = items.len();
+        let items = items.into_iter().skip(offset).take(limit).collect();
+        Ok(Page::new(items, total, offset, limit))
     }
 
     async fn get_connection(
@@ -422,6 +1437,9 @@ impl ConnectionRepository for InMemoryConnectionRepository {
         block_id: &BlockId,
         channel_id: &ChannelId,
     ) -> RepoResult<Option<Connection>> {
+        if self.stale_lookups {
+            return Ok(None);
+        }
         let connections = self
             .connections
             .read()
@@ -452,7 +1470,66 @@ impl ConnectionRepository for InMemoryConnectionRepository {
         Ok(())
     }
 
-    async fn next_position(&self, channel_id: &ChannelId) -> RepoResult<i32> {
+    async fn swap_positions(
+        &self,
+        channel_id: &ChannelId,
+        block_a: &BlockId,
+        block_b: &BlockId,
+    ) -> RepoResult<()> {
+        let mut connections = self
+            .connections
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let index_of = |block_id: &BlockId| {
+            connections
+                .iter()
+                .position(|c| &c.block_id == block_id && &c.channel_id == channel_id)
+                .ok_or(RepoError::NotFound)
+        };
+        let (a, b) = (index_of(block_a)?, index_of(block_b)?);
+
+        let position = connections[a].position;
+        connections[a].position = connections[b].position;
+        connections[b].position = position;
+        Ok(())
+    }
+
+    async fn move_relative(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+        anchor: &BlockId,
+        placement: Placement,
+        gap: i32,
+    ) -> RepoResult<()> {
+        self.apply_order(channel_id, gap, |current| {
+            move_relative(current, block_id, anchor, placement)
+        })
+    }
+
+    async fn set_order(
+        &self,
+        channel_id: &ChannelId,
+        order: &[BlockId],
+        gap: i32,
+    ) -> RepoResult<()> {
+        self.apply_order(channel_id, gap, |current| complete_order(current, order))
+    }
+
+    async fn move_group(
+        &self,
+        channel_id: &ChannelId,
+        block_ids: &[BlockId],
+        target_index: usize,
+        gap: i32,
+    ) -> RepoResult<()> {
+        self.apply_order(channel_id, gap, |current| {
+            move_group(current, block_ids, target_index)
+        })
+    }
+
+    async fn next_position(&self, channel_id: &ChannelId, gap: i32) -> RepoResult<Option<i32>> {
         let connections = self
             .connections
             .read()
@@ -462,10 +1539,197 @@ impl ConnectionRepository for InMemoryConnectionRepository {
             .iter()
             .filter(|c| &c.channel_id == channel_id)
             .map(|c| c.position)
-            .max()
-            .unwrap_or(-1);
+            .max();
+
+        Ok(match max_pos {
+            Some(max) => max.checked_add(gap),
+            None => Some(0),
+        })
+    }
+
+    async fn sharing_stats(&self, channel_id: &ChannelId) -> RepoResult<SharingStats> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut stats = SharingStats::default();
+        for c in connections.iter().filter(|c| &c.channel_id == channel_id) {
+            let channels = connections
+                .iter()
+                .filter(|other| other.block_id == c.block_id)
+                .count();
+            stats.total += 1;
+            if channels == 1 {
+                stats.exclusive += 1;
+            } else {
+                stats.shared += 1;
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn content_type_breakdown_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<HashMap<String, usize>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut counts = HashMap::new();
+        for c in connections.iter().filter(|c| &c.channel_id == channel_id) {
+            if let Some(block) = blocks.get(&c.block_id) {
+                *counts
+                    .entry(block.content.kind().as_str().to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    async fn counts_by_channel(&self) -> RepoResult<HashMap<ChannelId, usize>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut counts = HashMap::new();
+        for c in connections.iter() {
+            *counts.entry(c.channel_id.clone()).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    async fn channels_with_position_collisions(&self) -> RepoResult<Vec<ChannelId>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut seen = HashSet::new();
+        let mut channels: Vec<ChannelId> = connections
+            .iter()
+            .filter(|c| !seen.insert((&c.channel_id, c.position)))
+            .map(|c| c.channel_id.clone())
+            .collect();
+        channels.sort_by(|a, b| a.0.cmp(&b.0));
+        channels.dedup();
+        Ok(channels)
+    }
+
+    async fn find_position_anomalies(
+        &self,
+        gap: i32,
+    ) -> RepoResult<Vec<(ChannelId, PositionIssue)>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut by_channel: HashMap<&ChannelId, Vec<i32>> = HashMap::new();
+        for c in connections.iter() {
+            by_channel
+                .entry(&c.channel_id)
+                .or_default()
+                .push(c.position);
+        }
+        let mut by_channel: Vec<_> = by_channel.into_iter().collect();
+        by_channel.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+
+        Ok(by_channel
+            .into_iter()
+            .flat_map(|(channel_id, positions)| {
+                position_issues(&positions, gap)
+                    .into_iter()
+                    .map(move |issue| (channel_id.clone(), issue))
+            })
+            .collect())
+    }
+
+    async fn most_connected_blocks(
+        &self,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, usize)>> {
+        let connections = self
+            .connections
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        let blocks = self
+            .blocks
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        let mut counts: HashMap<&BlockId, usize> = HashMap::new();
+        for c in connections.iter() {
+            *counts.entry(&c.block_id).or_insert(0) += 1;
+        }
+        let mut ranked: Vec<(&Block, usize)> = counts
+            .into_iter()
+            .filter_map(|(id, count)| blocks.get(id).map(|b| (b, count)))
+            .filter(|(b, _)| include_archived || !b.archived)
+            .collect();
+        // Match SQLite: most connected, then newest, then by block ID
+        ranked.sort_by(|(a, a_count), (b, b_count)| {
+            b_count
+                .cmp(a_count)
+                .then_with(|| b.created_at.cmp(&a.created_at))
+                .then_with(|| a.id.0.cmp(&b.id.0))
+        });
+
+        Ok(ranked
+            .into_iter()
+            .take(limit)
+            .map(|(block, count)| (block.clone(), count))
+            .collect())
+    }
+}
+
+/// In-memory activity log.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryActivityRepository {
+    entries: Arc<RwLock<Vec<ActivityEntry>>>,
+}
+
+impl InMemoryActivityRepository {
+    /// Create a new empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
 
-        Ok(max_pos + 1)
+#[async_trait]
+impl ActivityRepository for InMemoryActivityRepository {
+    async fn record(&self, entry: &ActivityEntry) -> RepoResult<()> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+        entries.push(entry.clone());
+        Ok(())
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> RepoResult<Page<ActivityEntry>> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|_| RepoError::Database("lock poisoned".into()))?;
+
+        // Entries are appended in order, so the newest is last
+        let items: Vec<_> = entries
+            .iter()
+            .rev()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect();
+        Ok(Page::new(items, entries.len(), offset, limit))
     }
 }
 
@@ -499,7 +1763,7 @@ impl ConnectionRepository for InMemoryConnectionRepository {
 ///     service.connect_block(&block.id, &channel.id, None).await.unwrap();
 ///
 ///     // Lookups work correctly
-///     let blocks = service.get_blocks_in_channel(&channel.id).await.unwrap();
+///     let blocks = service.get_blocks_in_channel(&channel.id, false).await.unwrap();
 ///     assert_eq!(blocks.len(), 1);
 /// }
 /// ```
@@ -594,6 +1858,29 @@ mod tests {
         assert!(repo.get(&channel.id).await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn channel_repo_list_breaks_timestamp_ties_by_id() {
+        let repo = InMemoryChannelRepository::new();
+        let created_at = Utc::now();
+        let mut ids = Vec::new();
+        for i in 0..7 {
+            let mut channel = Channel::new(format!("Channel {}", i));
+            channel.created_at = created_at;
+            repo.create(&channel).await.unwrap();
+            ids.push(channel.id);
+        }
+        ids.sort_by(|a, b| b.0.cmp(&a.0));
+
+        for _ in 0..3 {
+            let mut paged = Vec::new();
+            for offset in (0..7).step_by(3) {
+                let page = repo.list(3, offset).await.unwrap();
+                paged.extend(page.items.into_iter().map(|c| c.id));
+            }
+            assert_eq!(paged, ids);
+        }
+    }
+
     #[tokio::test]
     async fn block_repo_batch_create() {
         let repo = InMemoryBlockRepository::new();
@@ -606,6 +1893,22 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn block_repo_for_each_visits_every_block_once() {
+        let repo = InMemoryBlockRepository::new();
+        let blocks: Vec<Block> = (0..25).map(|i| Block::text(format!("{}", i))).collect();
+        repo.create_batch(&blocks).await.unwrap();
+
+        let mut seen = HashSet::new();
+        let visited = repo
+            .for_each(10, &mut |block| assert!(seen.insert(block.id)))
+            .await
+            .unwrap();
+
+        assert_eq!(visited, 25);
+        assert_eq!(seen.len(), 25);
+    }
+
     #[tokio::test]
     async fn connection_repo_operations() {
         // Use TestFixture for properly synchronized repositories
@@ -632,11 +1935,17 @@ mod tests {
         assert!(conn.is_some());
 
         // Get blocks in channel - this works because repos share data
-        let blocks_in_channel = conn_repo.get_blocks_in_channel(&channel.id).await.unwrap();
+        let blocks_in_channel = conn_repo
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
         assert_eq!(blocks_in_channel.len(), 1);
 
         // Get channels for block
-        let channels_for_block = conn_repo.get_channels_for_block(&block.id).await.unwrap();
+        let channels_for_block = conn_repo
+            .get_channels_for_block(&block.id, ChannelOrder::default())
+            .await
+            .unwrap();
         assert_eq!(channels_for_block.len(), 1);
 
         // Disconnect
@@ -648,6 +1957,38 @@ mod tests {
             .is_none());
     }
 
+    #[tokio::test]
+    async fn connect_missing_block_or_channel_fails() {
+        let fixture = TestFixture::new();
+        let channel_repo = fixture.channel_repo();
+        let block_repo = fixture.block_repo();
+        let conn_repo = fixture.connection_repo();
+
+        let block = Block::text("Test block");
+        let channel = Channel::new("Test channel");
+        block_repo.create(&block).await.unwrap();
+        channel_repo.create(&channel).await.unwrap();
+
+        let missing_block = conn_repo.connect(&BlockId::new(), &channel.id, 0).await;
+        assert!(matches!(missing_block, Err(RepoError::NotFound)));
+
+        let missing_channel = conn_repo.connect(&block.id, &ChannelId::new(), 0).await;
+        assert!(matches!(missing_channel, Err(RepoError::NotFound)));
+
+        let batch = conn_repo
+            .connect_batch(&[
+                (block.id.clone(), channel.id.clone(), 0),
+                (BlockId::new(), channel.id.clone(), 1),
+            ])
+            .await;
+        assert!(matches!(batch, Err(RepoError::NotFound)));
+        assert!(conn_repo
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_fixture_service_integration() {
         use crate::models::{NewBlock, NewChannel};
@@ -660,6 +2001,7 @@ mod tests {
             .create_channel(NewChannel {
                 title: "Test Channel".to_string(),
                 description: None,
+                body: None,
             })
             .await
             .unwrap();
@@ -676,12 +2018,18 @@ mod tests {
             .unwrap();
 
         // Verify the connection works - get_blocks_in_channel should find the block
-        let blocks = service.get_blocks_in_channel(&channel.id).await.unwrap();
+        let blocks = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
         assert_eq!(blocks.len(), 1);
         assert_eq!(blocks[0].id, block.id);
 
         // Verify the reverse lookup works too
-        let channels = service.get_channels_for_block(&block.id).await.unwrap();
+        let channels = service
+            .get_channels_for_block(&block.id, ChannelOrder::default())
+            .await
+            .unwrap();
         assert_eq!(channels.len(), 1);
         assert_eq!(channels[0].id, channel.id);
     }
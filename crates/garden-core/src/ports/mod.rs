@@ -5,6 +5,7 @@
 
 mod memory;
 mod repository;
+mod shared;
 
 pub use memory::*;
 pub use repository::*;
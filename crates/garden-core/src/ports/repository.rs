@@ -3,10 +3,16 @@
 //! These traits define the storage interface that adapters must implement.
 //! The domain services depend only on these traits, not on concrete implementations.
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 
 use crate::error::RepoResult;
-use crate::models::{Block, BlockId, Channel, ChannelId, Connection, Page};
+use crate::models::{
+    ActivityEntry, Block, BlockId, Channel, ChannelId, ChannelOrder, ChannelSetMode, Connection,
+    MetadataStats, Page, Placement, PositionIssue, SearchHit, SearchMode, SharingStats,
+};
 
 /// Repository for channel operations.
 #[async_trait]
@@ -14,6 +20,18 @@ pub trait ChannelRepository: Send + Sync {
     /// Create a new channel.
     async fn create(&self, channel: &Channel) -> RepoResult<()>;
 
+    /// Create a channel under an idempotency key.
+    ///
+    /// If `key` was recorded at or after `not_before` and its channel still
+    /// exists, that channel is returned and nothing is written. Otherwise
+    /// `channel` is created, the key is recorded for it, and it is returned.
+    async fn create_idempotent(
+        &self,
+        channel: &Channel,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Channel>;
+
     /// Get a channel by ID.
     async fn get(&self, id: &ChannelId) -> RepoResult<Option<Channel>>;
 
@@ -28,6 +46,17 @@ pub trait ChannelRepository: Send + Sync {
 
     /// Count total channels.
     async fn count(&self) -> RepoResult<usize>;
+
+    /// Record that a channel was opened, setting `accessed_at` to now.
+    ///
+    /// Only `accessed_at` changes; `updated_at` is left alone.
+    async fn touch_accessed(&self, id: &ChannelId) -> RepoResult<()>;
+
+    /// List channels that have been opened, most recently accessed first.
+    async fn list_recently_accessed(&self, limit: usize) -> RepoResult<Vec<Channel>>;
+
+    /// List up to `limit` channels, most recently updated first.
+    async fn list_recently_updated(&self, limit: usize) -> RepoResult<Vec<Channel>>;
 }
 
 /// Repository for block operations.
@@ -36,6 +65,16 @@ pub trait BlockRepository: Send + Sync {
     /// Create a new block.
     async fn create(&self, block: &Block) -> RepoResult<()>;
 
+    /// Create a block under an idempotency key.
+    ///
+    /// Behaves like [`ChannelRepository::create_idempotent`].
+    async fn create_idempotent(
+        &self,
+        block: &Block,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Block>;
+
     /// Create multiple blocks at once.
     async fn create_batch(&self, blocks: &[Block]) -> RepoResult<()>;
 
@@ -45,8 +84,110 @@ pub trait BlockRepository: Send + Sync {
     /// Update an existing block.
     async fn update(&self, block: &Block) -> RepoResult<()>;
 
+    /// Update several existing blocks at once.
+    ///
+    /// All or nothing: if any block doesn't exist, none are updated and
+    /// `NotFound` is returned.
+    async fn update_batch(&self, blocks: &[Block]) -> RepoResult<()>;
+
     /// Delete a block by ID.
     async fn delete(&self, id: &BlockId) -> RepoResult<()>;
+
+    /// Count blocks per source domain, taken from each block's `source_url`.
+    ///
+    /// Sorted by count (highest first), then by domain. Blocks without a
+    /// parseable source URL are not counted.
+    async fn source_domains(&self) -> RepoResult<Vec<(String, usize)>>;
+
+    /// Count blocks whose content references a media file.
+    ///
+    /// Gallery blocks count once if any of their items use the file.
+    async fn count_file_references(&self, file_path: &str) -> RepoResult<usize>;
+
+    /// List blocks created between `start` and `end`, inclusive.
+    ///
+    /// Sorted newest first. Archived blocks are skipped unless
+    /// `include_archived` is set.
+    async fn find_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>>;
+
+    /// Count blocks created on each UTC day from `start` to `end`, inclusive.
+    ///
+    /// Sorted by day. Days without blocks are left out rather than counted
+    /// as zero. Archived blocks are counted.
+    async fn counts_by_day(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<(NaiveDate, usize)>>;
+
+    /// List video and audio blocks whose duration, in seconds, lies within
+    /// `min..=max`. A missing bound is open.
+    ///
+    /// Blocks without a known duration never match. Sorted newest first;
+    /// archived blocks are skipped.
+    async fn find_by_duration(
+        &self,
+        min: Option<f32>,
+        max: Option<f32>,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Block>>;
+
+    /// Sum [`BlockContent::word_count`](crate::models::BlockContent::word_count)
+    /// over every block.
+    async fn total_word_count(&self) -> RepoResult<usize>;
+
+    /// Count the blocks missing each archive metadata field.
+    async fn metadata_completeness(&self) -> RepoResult<MetadataStats>;
+
+    /// Call `f` with every block, reading `batch_size` blocks at a time in
+    /// ID order.
+    ///
+    /// Pages by the last ID seen rather than by offset, so only one batch is
+    /// held in memory at a time. Returns the number of blocks visited.
+    async fn for_each(
+        &self,
+        batch_size: usize,
+        f: &mut (dyn FnMut(Block) + Send),
+    ) -> RepoResult<usize>;
+
+    /// Get a block's tags, sorted alphabetically.
+    async fn get_tags(&self, block_id: &BlockId) -> RepoResult<Vec<String>>;
+
+    /// Add a tag to several blocks in one transaction.
+    ///
+    /// Blocks that already have the tag are skipped. Returns the number of
+    /// blocks that gained the tag. Fails with `NotFound`, writing nothing,
+    /// if any block does not exist.
+    async fn add_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize>;
+
+    /// Remove a tag from several blocks in one transaction.
+    ///
+    /// Blocks without the tag are skipped. Returns the number of blocks that
+    /// lost the tag.
+    async fn remove_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize>;
+
+    /// Search blocks by text.
+    ///
+    /// Every whitespace-separated term in `query` must match. In
+    /// `SearchMode::Snippet`, each hit carries a snippet with matched terms
+    /// wrapped in the requested delimiters. Archived blocks are skipped
+    /// unless `include_archived` is set.
+    async fn search(
+        &self,
+        query: &str,
+        mode: &SearchMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<SearchHit>>;
 }
 
 /// Repository for connection operations (block ↔ channel relationships).
@@ -60,19 +201,184 @@ pub trait ConnectionRepository: Send + Sync {
         position: i32,
     ) -> RepoResult<()>;
 
+    /// Connect a block to a channel at the given position, or move it there
+    /// if it is already connected.
+    ///
+    /// An existing connection keeps its `connected_at`.
+    async fn upsert(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+        position: i32,
+    ) -> RepoResult<()>;
+
     /// Connect multiple blocks to channels at once.
     /// Each tuple is (block_id, channel_id, position).
     async fn connect_batch(&self, connections: &[(BlockId, ChannelId, i32)]) -> RepoResult<()>;
 
+    /// Create new channels and blocks, then make the given connections, all
+    /// in one transaction.
+    ///
+    /// Connections may refer to the new channels and blocks as well as to
+    /// existing ones. Fails with `Duplicate` if a channel or block already
+    /// exists, `DuplicateTitle` if the store enforces unique channel titles
+    /// and a title is taken, or `NotFound` if a connection's block or
+    /// channel doesn't exist; in every case nothing is written.
+    async fn create_connected(
+        &self,
+        channels: &[Channel],
+        blocks: &[Block],
+        connections: &[(BlockId, ChannelId, i32)],
+    ) -> RepoResult<()>;
+
+    /// Connect a block at position 0 of a channel, shifting every existing
+    /// position up by one, in one transaction.
+    ///
+    /// Fails with `Duplicate`, changing nothing, if the block is already in
+    /// the channel.
+    async fn prepend(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()>;
+
+    /// Move every connection of the `merge` blocks onto `keep`, then delete
+    /// the `merge` blocks, all in one step.
+    ///
+    /// Where a channel already holds `keep`, or holds several merged blocks,
+    /// only the first connection survives: `keep`'s own, else that of the
+    /// earliest block in `merge`. Returns the channels `keep` newly joined.
+    /// Fails with `NotFound`, changing nothing, if any block is missing.
+    /// `keep` must not appear in `merge`.
+    async fn merge_blocks(&self, keep: &BlockId, merge: &[BlockId]) -> RepoResult<Vec<ChannelId>>;
+
     /// Disconnect a block from a channel.
     async fn disconnect(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()>;
 
-    /// Get all blocks in a channel, ordered by position.
+    /// Get all blocks in a channel, ordered by position, leaving out
+    /// archived blocks unless `include_archived` is set.
     /// Returns tuples of (Block, position).
-    async fn get_blocks_in_channel(&self, channel_id: &ChannelId) -> RepoResult<Vec<(Block, i32)>>;
+    async fn get_blocks_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, i32)>>;
 
-    /// Get all channels that a block is connected to.
-    async fn get_channels_for_block(&self, block_id: &BlockId) -> RepoResult<Vec<Channel>>;
+    /// Get a page of blocks in a channel, ordered by position.
+    /// The page total is the number of blocks in the channel, not counting
+    /// archived blocks unless `include_archived` is set.
+    async fn get_blocks_in_channel_paged(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, i32)>>;
+
+    /// Get every block in a channel with its connection, ordered by
+    /// position, ties broken by block ID.
+    async fn connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<Vec<(Block, Connection)>>;
+
+    /// Get the most recently connected blocks in a channel, newest first.
+    ///
+    /// Ordered by `connected_at` rather than position, so a block inserted
+    /// at the top of a channel still counts as just added. Archived blocks
+    /// are left out unless `include_archived` is set.
+    async fn recent_connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, Connection)>>;
+
+    /// Get a page of the blocks in any (`Union`) or all (`Intersection`)
+    /// of `channel_ids`, newest first. Duplicate IDs are ignored; an empty
+    /// list matches nothing. Archived blocks are left out unless
+    /// `include_archived` is set.
+    async fn blocks_in_channels(
+        &self,
+        channel_ids: &[ChannelId],
+        mode: ChannelSetMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>>;
+
+    /// Get a page of the blocks not in a channel, newest first, e.g. to
+    /// pick blocks to add to it. Archived blocks are left out unless
+    /// `include_archived` is set.
+    async fn blocks_not_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>>;
+
+    /// Get a page of all blocks, newest first, each with the channels it
+    /// is in, ordered by title.
+    ///
+    /// Blocks in no channel are included with an empty list. When
+    /// `block_ids` is given only those blocks are paged; unknown IDs are
+    /// skipped. Archived blocks are left out unless `include_archived` is
+    /// set.
+    async fn blocks_with_channels(
+        &self,
+        block_ids: Option<&[BlockId]>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, Vec<Channel>)>>;
+
+    /// Get the blocks immediately before and after a block in a channel.
+    ///
+    /// Blocks are ordered by position, with ties broken by block ID. Returns
+    /// `(previous, next)`, either of which is `None` at the ends of the
+    /// channel, or `NotFound` if the block is not in the channel.
+    async fn neighbors(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<(Option<Block>, Option<Block>)>;
+
+    /// Get a block's zero-based index in a channel, ordered like
+    /// [`Self::neighbors`], with the channel's block count.
+    ///
+    /// Returns `(index, total)`, or `None` if the block is not in the
+    /// channel.
+    async fn block_index_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<Option<(usize, usize)>>;
+
+    /// Get all channels that a block is connected to, in the given order.
+    ///
+    /// The result is unbounded, so a block in hundreds of channels returns
+    /// all of them at once. Prefer [`Self::get_channels_for_block_paged`]
+    /// anywhere the count is not known to be small.
+    async fn get_channels_for_block(
+        &self,
+        block_id: &BlockId,
+        order: ChannelOrder,
+    ) -> RepoResult<Vec<Channel>>;
+
+    /// Get a page of the channels a block is connected to, most recently
+    /// connected first. The page total is the number of such channels.
+    async fn get_channels_for_block_paged(
+        &self,
+        block_id: &BlockId,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>>;
+
+    /// Get a page of the channels holding at least one block whose content
+    /// type is `content_type` (e.g. `"video"`), newest channel first.
+    async fn channels_with_content_type(
+        &self,
+        content_type: &str,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>>;
 
     /// Get a specific connection.
     async fn get_connection(
@@ -89,6 +395,116 @@ pub trait ConnectionRepository: Send + Sync {
         new_position: i32,
     ) -> RepoResult<()>;
 
-    /// Get the next available position in a channel.
-    async fn next_position(&self, channel_id: &ChannelId) -> RepoResult<i32>;
+    /// Exchange the positions of two blocks in the same channel in one
+    /// transaction.
+    ///
+    /// Fails with `NotFound`, changing nothing, if either block is not in
+    /// the channel.
+    async fn swap_positions(
+        &self,
+        channel_id: &ChannelId,
+        block_a: &BlockId,
+        block_b: &BlockId,
+    ) -> RepoResult<()>;
+
+    /// Move a block directly before or after another block in the same
+    /// channel, renumbering the channel's positions `gap` apart in one
+    /// transaction.
+    ///
+    /// Fails with `NotFound` if either block is not in the channel.
+    async fn move_relative(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+        anchor: &BlockId,
+        placement: Placement,
+        gap: i32,
+    ) -> RepoResult<()>;
+
+    /// Renumber a channel's blocks to positions `0, gap, 2 * gap, ...` in
+    /// the given order, in one transaction.
+    ///
+    /// Blocks in the channel but missing from `order` keep their relative
+    /// order after the listed ones. Fails with `NotFound` if `order` names a
+    /// block that is not in the channel.
+    async fn set_order(
+        &self,
+        channel_id: &ChannelId,
+        order: &[BlockId],
+        gap: i32,
+    ) -> RepoResult<()>;
+
+    /// Move a group of blocks to `target_index` as one contiguous run,
+    /// renumbering the channel's positions `gap` apart in one transaction.
+    ///
+    /// The group keeps its current relative order, and `target_index` counts
+    /// positions among the channel's other blocks. Fails with `NotFound` if
+    /// any block is not in the channel.
+    async fn move_group(
+        &self,
+        channel_id: &ChannelId,
+        block_ids: &[BlockId],
+        target_index: usize,
+        gap: i32,
+    ) -> RepoResult<()>;
+
+    /// Get the position `gap` past the last block in a channel, for
+    /// appending. An empty channel starts at 0.
+    ///
+    /// Returns `None` if that position would pass `i32::MAX`.
+    async fn next_position(&self, channel_id: &ChannelId, gap: i32) -> RepoResult<Option<i32>>;
+
+    /// Count a channel's blocks that appear in no other channel versus
+    /// those shared with at least one other channel.
+    async fn sharing_stats(&self, channel_id: &ChannelId) -> RepoResult<SharingStats>;
+
+    /// Count a channel's blocks by content type (e.g. `"image"`, `"link"`).
+    ///
+    /// Types with no blocks in the channel are not in the map.
+    async fn content_type_breakdown_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<HashMap<String, usize>>;
+
+    /// Count connected blocks for every channel in a single query.
+    ///
+    /// Channels with no blocks are absent from the map; callers should
+    /// treat a missing entry as 0.
+    async fn counts_by_channel(&self) -> RepoResult<HashMap<ChannelId, usize>>;
+
+    /// List the channels where two or more blocks share a position.
+    async fn channels_with_position_collisions(&self) -> RepoResult<Vec<ChannelId>>;
+
+    /// Find channels whose positions are negative, shared, or have gaps
+    /// wider than `gap` (see [`position_issues`](crate::models::position_issues)).
+    ///
+    /// A channel with several kinds of issue appears once per kind. Ordered
+    /// by channel ID, then issue.
+    async fn find_position_anomalies(
+        &self,
+        gap: i32,
+    ) -> RepoResult<Vec<(ChannelId, PositionIssue)>>;
+
+    /// Get up to `limit` blocks that are in the most channels, with their
+    /// channel counts, most connected first.
+    ///
+    /// Ties are broken newest block first, then by block ID. Blocks in no
+    /// channel are never returned, nor archived blocks unless
+    /// `include_archived` is set.
+    async fn most_connected_blocks(
+        &self,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, usize)>>;
+}
+
+/// Append-only log of changes, read back as an activity feed.
+#[async_trait]
+pub trait ActivityRepository: Send + Sync {
+    /// Append an entry to the log.
+    async fn record(&self, entry: &ActivityEntry) -> RepoResult<()>;
+
+    /// Get a page of entries, newest first. The page total is the number of
+    /// entries in the log.
+    async fn list(&self, limit: usize, offset: usize) -> RepoResult<Page<ActivityEntry>>;
 }
@@ -0,0 +1,452 @@
+//! Repository implementations for shared pointers.
+//!
+//! Lets `Arc<dyn ChannelRepository>` and friends stand in wherever a
+//! repository is expected, so the storage backend can be chosen at runtime.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::error::RepoResult;
+use crate::models::{
+    Block, BlockId, Channel, ChannelId, ChannelOrder, ChannelSetMode, Connection, MetadataStats,
+    Page, Placement, PositionIssue, SearchHit, SearchMode, SharingStats,
+};
+use crate::ports::{BlockRepository, ChannelRepository, ConnectionRepository};
+
+#[async_trait]
+impl<T: ChannelRepository + ?Sized> ChannelRepository for Arc<T> {
+    async fn create(&self, channel: &Channel) -> RepoResult<()> {
+        (**self).create(channel).await
+    }
+
+    async fn create_idempotent(
+        &self,
+        channel: &Channel,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Channel> {
+        (**self).create_idempotent(channel, key, not_before).await
+    }
+
+    async fn get(&self, id: &ChannelId) -> RepoResult<Option<Channel>> {
+        (**self).get(id).await
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> RepoResult<Page<Channel>> {
+        (**self).list(limit, offset).await
+    }
+
+    async fn update(&self, channel: &Channel) -> RepoResult<()> {
+        (**self).update(channel).await
+    }
+
+    async fn delete(&self, id: &ChannelId) -> RepoResult<()> {
+        (**self).delete(id).await
+    }
+
+    async fn count(&self) -> RepoResult<usize> {
+        (**self).count().await
+    }
+
+    async fn touch_accessed(&self, id: &ChannelId) -> RepoResult<()> {
+        (**self).touch_accessed(id).await
+    }
+
+    async fn list_recently_accessed(&self, limit: usize) -> RepoResult<Vec<Channel>> {
+        (**self).list_recently_accessed(limit).await
+    }
+
+    async fn list_recently_updated(&self, limit: usize) -> RepoResult<Vec<Channel>> {
+        (**self).list_recently_updated(limit).await
+    }
+}
+
+#[async_trait]
+impl<T: BlockRepository + ?Sized> BlockRepository for Arc<T> {
+    async fn create(&self, block: &Block) -> RepoResult<()> {
+        (**self).create(block).await
+    }
+
+    async fn create_idempotent(
+        &self,
+        block: &Block,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Block> {
+        (**self).create_idempotent(block, key, not_before).await
+    }
+
+    async fn create_batch(&self, blocks: &[Block]) -> RepoResult<()> {
+        (**self).create_batch(blocks).await
+    }
+
+    async fn get(&self, id: &BlockId) -> RepoResult<Option<Block>> {
+        (**self).get(id).await
+    }
+
+    async fn update(&self, block: &Block) -> RepoResult<()> {
+        (**self).update(block).await
+    }
+
+    async fn update_batch(&self, blocks: &[Block]) -> RepoResult<()> {
+        (**self).update_batch(blocks).await
+    }
+
+    async fn delete(&self, id: &BlockId) -> RepoResult<()> {
+        (**self).delete(id).await
+    }
+
+    async fn source_domains(&self) -> RepoResult<Vec<(String, usize)>> {
+        (**self).source_domains().await
+    }
+
+    async fn count_file_references(&self, file_path: &str) -> RepoResult<usize> {
+        (**self).count_file_references(file_path).await
+    }
+
+    async fn find_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        (**self)
+            .find_created_between(start, end, limit, offset, include_archived)
+            .await
+    }
+
+    async fn counts_by_day(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<(NaiveDate, usize)>> {
+        (**self).counts_by_day(start, end).await
+    }
+
+    async fn find_by_duration(
+        &self,
+        min: Option<f32>,
+        max: Option<f32>,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Block>> {
+        (**self).find_by_duration(min, max, limit, offset).await
+    }
+
+    async fn total_word_count(&self) -> RepoResult<usize> {
+        (**self).total_word_count().await
+    }
+
+    async fn metadata_completeness(&self) -> RepoResult<MetadataStats> {
+        (**self).metadata_completeness().await
+    }
+
+    async fn for_each(
+        &self,
+        batch_size: usize,
+        f: &mut (dyn FnMut(Block) + Send),
+    ) -> RepoResult<usize> {
+        (**self).for_each(batch_size, f).await
+    }
+
+    async fn get_tags(&self, block_id: &BlockId) -> RepoResult<Vec<String>> {
+        (**self).get_tags(block_id).await
+    }
+
+    async fn add_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize> {
+        (**self).add_tag(block_ids, tag).await
+    }
+
+    async fn remove_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize> {
+        (**self).remove_tag(block_ids, tag).await
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        mode: &SearchMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<SearchHit>> {
+        (**self)
+            .search(query, mode, limit, offset, include_archived)
+            .await
+    }
+}
+
+#[async_trait]
+impl<T: ConnectionRepository + ?Sized> ConnectionRepository for Arc<T> {
+    async fn connect(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+        position: i32,
+    ) -> RepoResult<()> {
+        (**self).connect(block_id, channel_id, position).await
+    }
+
+    async fn upsert(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+        position: i32,
+    ) -> RepoResult<()> {
+        (**self).upsert(block_id, channel_id, position).await
+    }
+
+    async fn connect_batch(&self, connections: &[(BlockId, ChannelId, i32)]) -> RepoResult<()> {
+        (**self).connect_batch(connections).await
+    }
+
+    async fn create_connected(
+        &self,
+        channels: &[Channel],
+        blocks: &[Block],
+        connections: &[(BlockId, ChannelId, i32)],
+    ) -> RepoResult<()> {
+        (**self)
+            .create_connected(channels, blocks, connections)
+            .await
+    }
+
+    async fn prepend(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+        (**self).prepend(block_id, channel_id).await
+    }
+
+    async fn merge_blocks(&self, keep: &BlockId, merge: &[BlockId]) -> RepoResult<Vec<ChannelId>> {
+        (**self).merge_blocks(keep, merge).await
+    }
+
+    async fn disconnect(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+        (**self).disconnect(block_id, channel_id).await
+    }
+
+    async fn get_blocks_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, i32)>> {
+        (**self)
+            .get_blocks_in_channel(channel_id, include_archived)
+            .await
+    }
+
+    async fn get_blocks_in_channel_paged(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, i32)>> {
+        (**self)
+            .get_blocks_in_channel_paged(channel_id, limit, offset, include_archived)
+            .await
+    }
+
+    async fn connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<Vec<(Block, Connection)>> {
+        (**self).connections_in_channel(channel_id).await
+    }
+
+    async fn recent_connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, Connection)>> {
+        (**self)
+            .recent_connections_in_channel(channel_id, limit, include_archived)
+            .await
+    }
+
+    async fn blocks_in_channels(
+        &self,
+        channel_ids: &[ChannelId],
+        mode: ChannelSetMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        (**self)
+            .blocks_in_channels(channel_ids, mode, limit, offset, include_archived)
+            .await
+    }
+
+    async fn blocks_not_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        (**self)
+            .blocks_not_in_channel(channel_id, limit, offset, include_archived)
+            .await
+    }
+
+    async fn blocks_with_channels(
+        &self,
+        block_ids: Option<&[BlockId]>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, Vec<Channel>)>> {
+        (**self)
+            .blocks_with_channels(block_ids, limit, offset, include_archived)
+            .await
+    }
+
+    async fn neighbors(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<(Option<Block>, Option<Block>)> {
+        (**self).neighbors(channel_id, block_id).await
+    }
+
+    async fn block_index_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<Option<(usize, usize)>> {
+        (**self).block_index_in_channel(channel_id, block_id).await
+    }
+
+    async fn get_channels_for_block(
+        &self,
+        block_id: &BlockId,
+        order: ChannelOrder,
+    ) -> RepoResult<Vec<Channel>> {
+        (**self).get_channels_for_block(block_id, order).await
+    }
+
+    async fn get_channels_for_block_paged(
+        &self,
+        block_id: &BlockId,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>> {
+        (**self)
+            .get_channels_for_block_paged(block_id, limit, offset)
+            .await
+    }
+
+    async fn channels_with_content_type(
+        &self,
+        content_type: &str,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>> {
+        (**self)
+            .channels_with_content_type(content_type, limit, offset)
+            .await
+    }
+
+    async fn get_connection(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+    ) -> RepoResult<Option<Connection>> {
+        (**self).get_connection(block_id, channel_id).await
+    }
+
+    async fn reorder(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+        new_position: i32,
+    ) -> RepoResult<()> {
+        (**self).reorder(channel_id, block_id, new_position).await
+    }
+
+    async fn swap_positions(
+        &self,
+        channel_id: &ChannelId,
+        block_a: &BlockId,
+        block_b: &BlockId,
+    ) -> RepoResult<()> {
+        (**self).swap_positions(channel_id, block_a, block_b).await
+    }
+
+    async fn move_relative(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+        anchor: &BlockId,
+        placement: Placement,
+        gap: i32,
+    ) -> RepoResult<()> {
+        (**self)
+            .move_relative(channel_id, block_id, anchor, placement, gap)
+            .await
+    }
+
+    async fn set_order(
+        &self,
+        channel_id: &ChannelId,
+        order: &[BlockId],
+        gap: i32,
+    ) -> RepoResult<()> {
+        (**self).set_order(channel_id, order, gap).await
+    }
+
+    async fn move_group(
+        &self,
+        channel_id: &ChannelId,
+        block_ids: &[BlockId],
+        target_index: usize,
+        gap: i32,
+    ) -> RepoResult<()> {
+        (**self)
+            .move_group(channel_id, block_ids, target_index, gap)
+            .await
+    }
+
+    async fn next_position(&self, channel_id: &ChannelId, gap: i32) -> RepoResult<Option<i32>> {
+        (**self).next_position(channel_id, gap).await
+    }
+
+    async fn sharing_stats(&self, channel_id: &ChannelId) -> RepoResult<SharingStats> {
+        (**self).sharing_stats(channel_id).await
+    }
+
+    async fn content_type_breakdown_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<HashMap<String, usize>> {
+        (**self).content_type_breakdown_in_channel(channel_id).await
+    }
+
+    async fn counts_by_channel(&self) -> RepoResult<HashMap<ChannelId, usize>> {
+        (**self).counts_by_channel().await
+    }
+
+    async fn channels_with_position_collisions(&self) -> RepoResult<Vec<ChannelId>> {
+        (**self).channels_with_position_collisions().await
+    }
+
+    async fn find_position_anomalies(
+        &self,
+        gap: i32,
+    ) -> RepoResult<Vec<(ChannelId, PositionIssue)>> {
+        (**self).find_position_anomalies(gap).await
+    }
+
+    async fn most_connected_blocks(
+        &self,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, usize)>> {
+        (**self)
+            .most_connected_blocks(limit, include_archived)
+            .await
+    }
+}
@@ -7,9 +7,9 @@
 use chrono::Utc;
 
 use crate::error::{DomainError, DomainResult};
-use crate::models::{Channel, ChannelId, ChannelUpdate, NewChannel};
+use crate::models::{Channel, ChannelId, ChannelUpdate, FieldUpdate, NewChannel};
 use crate::ports::ChannelRepository;
-use crate::validation::validate_channel_title;
+use crate::validation::{validate_channel_body, validate_channel_title};
 
 /// Create a new channel.
 #[deprecated(since = "0.1.0", note = "Use GardenService::create_channel instead")]
@@ -18,12 +18,16 @@ pub async fn create_channel(
     new_channel: NewChannel,
 ) -> DomainResult<Channel> {
     validate_channel_title(&new_channel.title)?;
+    if let Some(body) = &new_channel.body {
+        validate_channel_body(body)?;
+    }
 
-    let channel = if let Some(desc) = new_channel.description {
+    let mut channel = if let Some(desc) = new_channel.description {
         Channel::with_description(new_channel.title, desc)
     } else {
         Channel::new(new_channel.title)
     };
+    channel.body = new_channel.body;
 
     repo.create(&channel).await?;
     Ok(channel)
@@ -62,8 +66,13 @@ pub async fn update_channel(
         channel.title = title;
     }
 
-    // Apply description update using FieldUpdate
+    if let FieldUpdate::Set(body) = &update.body {
+        validate_channel_body(body)?;
+    }
+
+    // Apply description and body updates using FieldUpdate
     channel.description = update.description.apply(channel.description);
+    channel.body = update.body.apply(channel.body);
 
     channel.updated_at = Utc::now();
     repo.update(&channel).await?;
@@ -4,9 +4,11 @@
 //! For new code, prefer using [`GardenService`](super::GardenService) which
 //! provides the same functionality in a more ergonomic struct-based API.
 
-use crate::error::{DomainError, DomainResult};
-use crate::models::{Block, BlockId, Channel, ChannelId, Connection};
+use super::garden::already_connected;
+use crate::error::{DomainError, DomainResult, RepoError};
+use crate::models::{Block, BlockId, Channel, ChannelId, ChannelOrder, Connection};
 use crate::ports::{BlockRepository, ChannelRepository, ConnectionRepository};
+use crate::validation::validate_position;
 
 /// Connect a block to a channel.
 ///
@@ -20,6 +22,10 @@ pub async fn connect_block(
     channel_id: &ChannelId,
     position: Option<i32>,
 ) -> DomainResult<Connection> {
+    if let Some(p) = position {
+        validate_position(p)?;
+    }
+
     // Verify block and channel exist
     let _ = block_repo
         .get(block_id)
@@ -33,18 +39,25 @@ pub async fn connect_block(
 
     // Check if already connected
     if let Some(_existing) = conn_repo.get_connection(block_id, channel_id).await? {
-        return Err(DomainError::InvalidInput(
-            "block is already connected to this channel".to_string(),
-        ));
+        return Err(already_connected());
     }
 
     // Get position (append if not specified)
     let pos = match position {
         Some(p) => p,
-        None => conn_repo.next_position(channel_id).await?,
+        None => conn_repo
+            .next_position(channel_id, 1)
+            .await?
+            .ok_or_else(|| {
+                DomainError::InvalidInput("channel has no room left to append".to_string())
+            })?,
     };
 
-    conn_repo.connect(block_id, channel_id, pos).await?;
+    // A concurrent connect can slip past the check above
+    match conn_repo.connect(block_id, channel_id, pos).await {
+        Err(RepoError::Duplicate) => return Err(already_connected()),
+        result => result?,
+    }
 
     // Return the created connection
     conn_repo
@@ -79,7 +92,7 @@ pub async fn get_blocks_in_channel(
     conn_repo: &impl ConnectionRepository,
     channel_id: &ChannelId,
 ) -> DomainResult<Vec<Block>> {
-    let blocks_with_pos = conn_repo.get_blocks_in_channel(channel_id).await?;
+    let blocks_with_pos = conn_repo.get_blocks_in_channel(channel_id, true).await?;
     Ok(blocks_with_pos
         .into_iter()
         .map(|(block, _pos)| block)
@@ -95,7 +108,9 @@ pub async fn get_channels_for_block(
     conn_repo: &impl ConnectionRepository,
     block_id: &BlockId,
 ) -> DomainResult<Vec<Channel>> {
-    Ok(conn_repo.get_channels_for_block(block_id).await?)
+    Ok(conn_repo
+        .get_channels_for_block(block_id, ChannelOrder::default())
+        .await?)
 }
 
 /// Reorder a block within a channel.
@@ -106,6 +121,8 @@ pub async fn reorder_block(
     block_id: &BlockId,
     new_position: i32,
 ) -> DomainResult<()> {
+    validate_position(new_position)?;
+
     // Verify connection exists
     let _ = conn_repo
         .get_connection(block_id, channel_id)
@@ -120,5 +137,35 @@ pub async fn reorder_block(
 
 #[cfg(test)]
 mod tests {
-    // Tests would use mock repositories
+    use super::*;
+    use crate::ports::TestFixture;
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn connect_block_duplicate_from_repo_maps_to_already_connected() {
+        let fixture = TestFixture::new();
+        let block = Block::text("Test");
+        let channel = Channel::new("Test Channel");
+        fixture.block_repo().create(&block).await.unwrap();
+        fixture.channel_repo().create(&channel).await.unwrap();
+        fixture
+            .connection_repo()
+            .connect(&block.id, &channel.id, 0)
+            .await
+            .unwrap();
+
+        let result = connect_block(
+            &fixture.channel_repo(),
+            &fixture.block_repo(),
+            &fixture.connection_repo().with_stale_lookups(),
+            &block.id,
+            &channel.id,
+            Some(1),
+        )
+        .await;
+        match result {
+            Err(DomainError::InvalidInput(msg)) => assert!(msg.contains("already connected")),
+            other => panic!("expected already-connected error, got {other:?}"),
+        }
+    }
 }
@@ -9,15 +9,27 @@
 //! All service methods are instrumented with `tracing` spans for observability.
 //! To see traces, ensure a tracing subscriber is configured in your application.
 
-use chrono::Utc;
-use tracing::{info, instrument};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
 
-use crate::error::{DomainError, DomainResult};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use tokio::io::AsyncBufReadExt;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+use tracing::{info, warn};
+
+use crate::error::{DomainError, DomainResult, RepoError};
 use crate::models::{
-    Block, BlockContent, BlockId, BlockUpdate, Channel, ChannelId, ChannelUpdate, Connection,
-    NewBlock, NewChannel, Page,
+    channel_markdown, position_issues, ActivityEntry, ActivityKind, Block, BlockContent,
+    BlockContext, BlockId, BlockNeighbors, BlockPositionInfo, BlockSortKey, BlockUpdate, Channel,
+    ChannelExport, ChannelId, ChannelOrder, ChannelPreview, ChannelSetMode, ChannelUpdate,
+    Connection, ContentKind, FieldUpdate, GardenExport, ImportPlan, ImportStrategy, InvalidBlock,
+    LinkStatus, MetadataStats, NewBlock, NewChannel, OrderSnapshot, Page, Placement, PositionIssue,
+    SearchHit, SearchMode, SharingStats, UrlImportLine, UrlImportReport,
 };
-use crate::ports::{BlockRepository, ChannelRepository, ConnectionRepository};
+use crate::ports::{ActivityRepository, BlockRepository, ChannelRepository, ConnectionRepository};
+use crate::services::{MediaError, MediaService};
 
 /// Unified service for Garden domain operations.
 ///
@@ -31,7 +43,7 @@ use crate::ports::{BlockRepository, ChannelRepository, ConnectionRepository};
 /// let service = GardenService::new(channel_repo, block_repo, conn_repo);
 ///
 /// // Create a channel and block
-/// let channel = service.create_channel(NewChannel { title: "My Channel".into(), description: None }).await?;
+/// let channel = service.create_channel(NewChannel { title: "My Channel".into(), description: None, body: None }).await?;
 /// let block = service.create_block(NewBlock { content: BlockContent::text("Hello") }).await?;
 ///
 /// // Connect them
@@ -41,8 +53,50 @@ pub struct GardenService<CR, BR, CNR> {
     channels: CR,
     blocks: BR,
     connections: CNR,
+    idempotency_window: Duration,
+    markdown_media_base: String,
+    position_gap: i32,
+    activity: Option<Arc<dyn ActivityRepository>>,
+}
+
+/// A [`GardenService`] over repository trait objects.
+///
+/// Lets an app pick its storage backend at runtime instead of naming the
+/// concrete repository types. Build one with [`GardenService::new_dyn`].
+pub type DynGardenService = GardenService<
+    Arc<dyn ChannelRepository>,
+    Arc<dyn BlockRepository>,
+    Arc<dyn ConnectionRepository>,
+>;
+
+impl DynGardenService {
+    /// Create a GardenService over shared repository trait objects.
+    pub fn new_dyn(
+        channels: Arc<dyn ChannelRepository>,
+        blocks: Arc<dyn BlockRepository>,
+        connections: Arc<dyn ConnectionRepository>,
+    ) -> Self {
+        Self::new(channels, blocks, connections)
+    }
 }
 
+/// How long an idempotency key is remembered by default.
+pub const DEFAULT_IDEMPOTENCY_WINDOW: Duration = Duration::hours(24);
+
+/// Where Markdown exports expect media files by default, relative to the
+/// rendered document.
+pub const DEFAULT_MARKDOWN_MEDIA_BASE: &str = "media";
+
+/// Distance between appended blocks' positions by default.
+pub const DEFAULT_POSITION_GAP: i32 = 1;
+
+/// How many blocks [`GardenService::check_links`] reads at a time while
+/// looking for link blocks.
+const LINK_CHECK_BATCH_SIZE: usize = 500;
+
+/// How many blocks [`GardenService::find_duplicate_blocks`] reads at a time.
+const DUPLICATE_SCAN_BATCH_SIZE: usize = 500;
+
 impl<CR, BR, CNR> GardenService<CR, BR, CNR>
 where
     CR: ChannelRepository,
@@ -55,6 +109,77 @@ where
             channels,
             blocks,
             connections,
+            idempotency_window: DEFAULT_IDEMPOTENCY_WINDOW,
+            markdown_media_base: DEFAULT_MARKDOWN_MEDIA_BASE.to_string(),
+            position_gap: DEFAULT_POSITION_GAP,
+            activity: None,
+        }
+    }
+
+    /// Set how long idempotency keys are remembered.
+    ///
+    /// A create call that reuses a key older than this creates a new entity.
+    pub fn with_idempotency_window(mut self, window: Duration) -> Self {
+        self.idempotency_window = window;
+        self
+    }
+
+    /// Set the base path media files are referenced under in Markdown
+    /// exports.
+    ///
+    /// Use this to match the layout of the bundle the Markdown is written
+    /// into; an empty base references files by their stored path alone.
+    pub fn with_markdown_media_base(mut self, base: impl Into<String>) -> Self {
+        self.markdown_media_base = base.into();
+        self
+    }
+
+    /// Set the distance between positions of appended blocks.
+    ///
+    /// With a gap above 1, a block can later be inserted between two
+    /// neighbours at an unused position (see [`position_between`]) instead
+    /// of renumbering the channel. Gaps below 1 are treated as 1. Appends
+    /// that would place a block past `i32::MAX` fail with `InvalidInput`.
+    ///
+    /// [`position_between`]: crate::models::position_between
+    pub fn with_position_gap(mut self, gap: i32) -> Self {
+        self.position_gap = gap.max(1);
+        self
+    }
+
+    /// Record changes in `log`, to be read back with [`Self::activity_feed`].
+    ///
+    /// Creating, updating, and deleting channels and blocks is logged, as is
+    /// connecting, disconnecting, and moving blocks. Importing exports,
+    /// sorting channels, and repairing positions are not. A failure to record
+    /// is logged and never fails the change itself.
+    pub fn with_activity_log(mut self, log: Arc<dyn ActivityRepository>) -> Self {
+        self.activity = Some(log);
+        self
+    }
+
+    /// Append `entry` to the activity log, if there is one.
+    async fn record_activity(&self, entry: ActivityEntry) {
+        let Some(log) = &self.activity else {
+            return;
+        };
+        if let Err(e) = log.record(&entry).await {
+            warn!(error = %e, kind = entry.kind.as_str(), "Failed to record activity");
+        }
+    }
+
+    /// Get a page of the activity log, newest first.
+    ///
+    /// Empty unless the service was built [`with_activity_log`](Self::with_activity_log).
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn activity_feed(
+        &self,
+        limit: usize,
+        offset: usize,
+    ) -> DomainResult<Page<ActivityEntry>> {
+        match &self.activity {
+            Some(log) => Ok(log.list(limit, offset).await?),
+            None => Ok(Page::new(Vec::new(), 0, offset, limit)),
         }
     }
 
@@ -63,22 +188,123 @@ where
     // ─────────────────────────────────────────────────────────────────────────
 
     /// Create a new channel.
-    #[instrument(skip(self), fields(title = %new_channel.title))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(title = %new_channel.title)))]
     pub async fn create_channel(&self, new_channel: NewChannel) -> DomainResult<Channel> {
+        self.create_channel_idempotent(new_channel, None).await
+    }
+
+    /// Create a new channel, deduplicating retries by idempotency key.
+    ///
+    /// If `idempotency_key` was used within the idempotency window, the
+    /// channel created by that call is returned and nothing new is created.
+    ///
+    /// Returns `DuplicateTitle` if the store enforces unique channel titles
+    /// and another channel already has this one.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, idempotency_key), fields(title = %new_channel.title)))]
+    pub async fn create_channel_idempotent(
+        &self,
+        new_channel: NewChannel,
+        idempotency_key: Option<String>,
+    ) -> DomainResult<Channel> {
+        let channel = Self::build_channel(new_channel)?;
+        let new_id = channel.id.clone();
+
+        let channel = match idempotency_key {
+            Some(key) => {
+                crate::validation::validate_idempotency_key(&key)?;
+                let not_before = Utc::now() - self.idempotency_window;
+                self.channels
+                    .create_idempotent(&channel, &key, not_before)
+                    .await
+                    .map_err(channel_save_error(&channel.title))?
+            }
+            None => {
+                self.channels
+                    .create(&channel)
+                    .await
+                    .map_err(channel_save_error(&channel.title))?;
+                channel
+            }
+        };
+        // A reused key returns the earlier channel, which is already logged
+        if channel.id == new_id {
+            self.record_activity(ActivityEntry::channel(
+                ActivityKind::ChannelCreated,
+                &channel.id,
+            ))
+            .await;
+        }
+        Ok(channel)
+    }
+
+    /// Create a channel with an initial set of blocks, connected in order
+    /// one position gap apart. Used to instantiate channel templates.
+    ///
+    /// The channel and every block are validated before anything is
+    /// written, and everything is written in one transaction, so invalid
+    /// input or a failed write creates nothing at all.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, new_blocks), fields(title = %new_channel.title, count = new_blocks.len())))]
+    pub async fn create_channel_with_blocks(
+        &self,
+        new_channel: NewChannel,
+        new_blocks: Vec<NewBlock>,
+    ) -> DomainResult<(Channel, Vec<Block>)> {
+        let channel = Self::build_channel(new_channel)?;
+        let blocks = new_blocks
+            .into_iter()
+            .map(Self::build_block)
+            .collect::<DomainResult<Vec<_>>>()?;
+        let conns: Vec<_> = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, block)| {
+                let position = self.gapped_position(0, i)?;
+                Ok((block.id.clone(), channel.id.clone(), position))
+            })
+            .collect::<DomainResult<_>>()?;
+
+        self.connections
+            .create_connected(std::slice::from_ref(&channel), &blocks, &conns)
+            .await
+            .map_err(channel_save_error(&channel.title))?;
+
+        info!(channel_id = %channel.id.0, blocks = blocks.len(), "Channel created with blocks");
+        self.record_activity(ActivityEntry::channel(
+            ActivityKind::ChannelCreated,
+            &channel.id,
+        ))
+        .await;
+        for block in &blocks {
+            self.record_activity(ActivityEntry::block(ActivityKind::BlockCreated, &block.id))
+                .await;
+            self.record_activity(ActivityEntry::connection(
+                ActivityKind::BlockConnected,
+                &channel.id,
+                &block.id,
+            ))
+            .await;
+        }
+        Ok((channel, blocks))
+    }
+
+    /// Validate a new channel and build it, without saving.
+    fn build_channel(new_channel: NewChannel) -> DomainResult<Channel> {
         crate::validation::validate_channel_title(&new_channel.title)?;
+        if let Some(body) = &new_channel.body {
+            crate::validation::validate_channel_body(body)?;
+        }
 
-        let channel = if let Some(desc) = new_channel.description {
+        let mut channel = if let Some(desc) = new_channel.description {
             Channel::with_description(new_channel.title, desc)
         } else {
             Channel::new(new_channel.title)
         };
-
-        self.channels.create(&channel).await?;
+        channel.body = new_channel.body;
         Ok(channel)
     }
 
     /// Get a channel by ID.
-    #[instrument(skip(self), fields(channel_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
     pub async fn get_channel(&self, id: &ChannelId) -> DomainResult<Channel> {
         self.channels
             .get(id)
@@ -87,13 +313,16 @@ where
     }
 
     /// List channels with pagination.
-    #[instrument(skip(self))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn list_channels(&self, limit: usize, offset: usize) -> DomainResult<Page<Channel>> {
         Ok(self.channels.list(limit, offset).await?)
     }
 
     /// Update a channel.
-    #[instrument(skip(self, update), fields(channel_id = %id.0))]
+    ///
+    /// Returns `DuplicateTitle` if the store enforces unique channel titles
+    /// and another channel already has the new title.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, update), fields(channel_id = %id.0)))]
     pub async fn update_channel(
         &self,
         id: &ChannelId,
@@ -106,22 +335,34 @@ where
             channel.title = title;
         }
 
-        // Apply description update using FieldUpdate
+        if let FieldUpdate::Set(body) = &update.body {
+            crate::validation::validate_channel_body(body)?;
+        }
+
+        // Apply description and body updates using FieldUpdate
         channel.description = update.description.apply(channel.description);
+        channel.body = update.body.apply(channel.body);
 
         channel.updated_at = Utc::now();
-        self.channels.update(&channel).await?;
+        self.channels
+            .update(&channel)
+            .await
+            .map_err(channel_save_error(&channel.title))?;
         info!("Channel updated");
+        self.record_activity(ActivityEntry::channel(ActivityKind::ChannelUpdated, id))
+            .await;
         Ok(channel)
     }
 
     /// Delete a channel.
-    #[instrument(skip(self), fields(channel_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
     pub async fn delete_channel(&self, id: &ChannelId) -> DomainResult<()> {
         // Verify channel exists
         let _ = self.get_channel(id).await?;
         self.channels.delete(id).await?;
         info!("Channel deleted");
+        self.record_activity(ActivityEntry::channel(ActivityKind::ChannelDeleted, id))
+            .await;
         Ok(())
     }
 
@@ -130,31 +371,87 @@ where
         Ok(self.channels.count().await?)
     }
 
+    /// Record that a channel was opened.
+    ///
+    /// Updates `accessed_at` only, so views don't show up as edits.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
+    pub async fn mark_channel_accessed(&self, id: &ChannelId) -> DomainResult<()> {
+        match self.channels.touch_accessed(id).await {
+            Err(RepoError::NotFound) => Err(DomainError::ChannelNotFound(id.clone())),
+            result => Ok(result?),
+        }
+    }
+
+    /// List recently opened channels, most recent first.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn list_recently_accessed(&self, limit: usize) -> DomainResult<Vec<Channel>> {
+        Ok(self.channels.list_recently_accessed(limit).await?)
+    }
+
+    /// List up to `limit` channels, most recently edited first.
+    ///
+    /// Unlike [`Self::list_recently_accessed`], only edits count, not views.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn recently_updated_channels(&self, limit: usize) -> DomainResult<Vec<Channel>> {
+        Ok(self.channels.list_recently_updated(limit).await?)
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Block Operations
     // ─────────────────────────────────────────────────────────────────────────
 
     /// Create a new block.
-    #[instrument(skip(self, new_block))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, new_block)))]
     pub async fn create_block(&self, new_block: NewBlock) -> DomainResult<Block> {
-        Self::validate_content(&new_block.content)?;
-
-        let mut block = Block::new(new_block.content);
-        // Apply metadata from NewBlock
-        block.source_url = new_block.source_url;
-        block.source_title = new_block.source_title;
-        block.creator = new_block.creator;
-        block.original_date = new_block.original_date;
-        block.notes = new_block.notes;
+        self.create_block_idempotent(new_block, None).await
+    }
 
-        self.blocks.create(&block).await?;
+    /// Create a new block, deduplicating retries by idempotency key.
+    ///
+    /// If `idempotency_key` was used within the idempotency window, the block
+    /// created by that call is returned and nothing new is created.
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip(self, new_block, idempotency_key))
+    )]
+    pub async fn create_block_idempotent(
+        &self,
+        new_block: NewBlock,
+        idempotency_key: Option<String>,
+    ) -> DomainResult<Block> {
+        let block = Self::build_block(new_block)?;
+        let new_id = block.id.clone();
+        let block = match idempotency_key {
+            Some(key) => {
+                crate::validation::validate_idempotency_key(&key)?;
+                let not_before = Utc::now() - self.idempotency_window;
+                self.blocks
+                    .create_idempotent(&block, &key, not_before)
+                    .await?
+            }
+            None => {
+                self.blocks.create(&block).await?;
+                block
+            }
+        };
         info!(block_id = %block.id.0, "Block created");
+        // A reused key returns the earlier block, which is already logged
+        if block.id == new_id {
+            self.record_activity(ActivityEntry::block(ActivityKind::BlockCreated, &block.id))
+                .await;
+        }
         Ok(block)
     }
 
     /// Create multiple blocks at once.
-    #[instrument(skip(self, new_blocks), fields(count = new_blocks.len()))]
+    ///
+    /// An empty batch returns immediately without touching storage.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, new_blocks), fields(count = new_blocks.len())))]
     pub async fn create_blocks(&self, new_blocks: Vec<NewBlock>) -> DomainResult<Vec<Block>> {
+        if new_blocks.is_empty() {
+            return Ok(Vec::new());
+        }
+
         // Validate all first
         for new_block in &new_blocks {
             Self::validate_content(&new_block.content)?;
@@ -167,11 +464,15 @@ where
 
         self.blocks.create_batch(&blocks).await?;
         info!(count = blocks.len(), "Blocks created");
+        for block in &blocks {
+            self.record_activity(ActivityEntry::block(ActivityKind::BlockCreated, &block.id))
+                .await;
+        }
         Ok(blocks)
     }
 
     /// Get a block by ID.
-    #[instrument(skip(self), fields(block_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
     pub async fn get_block(&self, id: &BlockId) -> DomainResult<Block> {
         self.blocks
             .get(id)
@@ -180,809 +481,5633 @@ where
     }
 
     /// Update a block.
-    #[instrument(skip(self, update), fields(block_id = %id.0))]
-    pub async fn update_block(&self, id: &BlockId, update: BlockUpdate) -> DomainResult<Block> {
+    #[cfg_attr(feature = "tracing", instrument(skip(self, update), fields(block_id = %id.0)))]
+    pub async fn update_block(&self, id: &BlockId, mut update: BlockUpdate) -> DomainResult<Block> {
         let mut block = self.get_block(id).await?;
 
         // Update content if provided
-        if let Some(content) = update.content {
+        if let Some(content) = update.content.take() {
             Self::validate_content(&content)?;
             block.content = content;
         }
 
-        // Apply archive metadata field updates using FieldUpdate
-        // None means "keep" (field not provided), Some(FieldUpdate) applies the update
-        if let Some(field_update) = update.source_url {
-            block.source_url = field_update.apply(block.source_url);
+        Self::validate_metadata_update(&update)?;
+        Self::apply_metadata_update(&mut block, &update);
+
+        block.updated_at = Utc::now();
+        self.blocks.update(&block).await?;
+        info!("Block updated");
+        self.record_activity(ActivityEntry::block(ActivityKind::BlockUpdated, id))
+            .await;
+        Ok(block)
+    }
+
+    /// Apply the same metadata changes to several blocks at once, e.g. to
+    /// set one creator on a batch of images from the same source.
+    ///
+    /// All blocks are updated together or not at all. The update's
+    /// `content` is rejected, since identical content would clobber blocks
+    /// that differ. Repeated IDs are updated once. Returns the number of
+    /// blocks updated.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, ids, update), fields(count = ids.len())))]
+    pub async fn update_blocks_metadata(
+        &self,
+        ids: &[BlockId],
+        update: BlockUpdate,
+    ) -> DomainResult<usize> {
+        if update.content.is_some() {
+            return Err(DomainError::InvalidInput(
+                "content can't be updated in bulk; update blocks one at a time".into(),
+            ));
+        }
+        Self::validate_metadata_update(&update)?;
+
+        let mut seen = HashSet::new();
+        let mut blocks = Vec::with_capacity(ids.len());
+        let now = Utc::now();
+        for id in ids.iter().filter(|id| seen.insert(*id)) {
+            let mut block = self.get_block(id).await?;
+            Self::apply_metadata_update(&mut block, &update);
+            block.updated_at = now;
+            blocks.push(block);
         }
-        if let Some(field_update) = update.source_title {
-            block.source_title = field_update.apply(block.source_title);
+
+        self.blocks.update_batch(&blocks).await?;
+        info!(count = blocks.len(), "Block metadata updated");
+        for block in &blocks {
+            self.record_activity(ActivityEntry::block(ActivityKind::BlockUpdated, &block.id))
+                .await;
         }
-        if let Some(field_update) = update.creator {
-            block.creator = field_update.apply(block.creator);
+        Ok(blocks.len())
+    }
+
+    /// Check the metadata fields of an update before applying it.
+    fn validate_metadata_update(update: &BlockUpdate) -> DomainResult<()> {
+        if let Some(FieldUpdate::Set(url)) = &update.source_url {
+            crate::validation::validate_source_url(Some(url))?;
         }
-        if let Some(field_update) = update.original_date {
-            block.original_date = field_update.apply(block.original_date);
+        Ok(())
+    }
+
+    /// Apply an update's archive metadata fields to a block.
+    ///
+    /// `None` means "keep" (field not provided); `Some(FieldUpdate)`
+    /// applies the update.
+    fn apply_metadata_update(block: &mut Block, update: &BlockUpdate) {
+        fn apply(field: &mut Option<String>, update: &Option<FieldUpdate<String>>) {
+            if let Some(field_update) = update {
+                *field = field_update.clone().apply(field.take());
+            }
         }
-        if let Some(field_update) = update.notes {
-            block.notes = field_update.apply(block.notes);
+
+        apply(&mut block.source_url, &update.source_url);
+        apply(&mut block.source_title, &update.source_title);
+        apply(&mut block.creator, &update.creator);
+        apply(&mut block.original_date, &update.original_date);
+        apply(&mut block.notes, &update.notes);
+    }
+
+    /// Archive or unarchive a block.
+    ///
+    /// Archived blocks keep their content and connections but are left out
+    /// of search, date listings, and channel views unless archived blocks
+    /// are asked for. They can still be fetched by ID. Setting the current
+    /// state again is a no-op.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
+    pub async fn set_block_archived(&self, id: &BlockId, archived: bool) -> DomainResult<Block> {
+        let mut block = self.get_block(id).await?;
+        if block.archived == archived {
+            return Ok(block);
         }
 
+        block.archived = archived;
         block.updated_at = Utc::now();
         self.blocks.update(&block).await?;
-        info!("Block updated");
+        info!(archived, "Block archive state changed");
+        self.record_activity(ActivityEntry::block(ActivityKind::BlockUpdated, id))
+            .await;
+        Ok(block)
+    }
+
+    /// Change a block's content to another kind, keeping what carries over.
+    ///
+    /// Supported conversions:
+    /// - Text → Link, when the text is a single http(s) URL
+    /// - Link → Text, using the title (if any) followed by the URL
+    ///
+    /// Converting to the block's current kind leaves it unchanged. Archive
+    /// metadata, tags, and connections are kept either way.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for any other conversion, or when the text is
+    /// not a valid URL.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
+    pub async fn convert_block(&self, id: &BlockId, target: ContentKind) -> DomainResult<Block> {
+        let mut block = self.get_block(id).await?;
+        if block.content.kind() == target {
+            return Ok(block);
+        }
+
+        let content = match (&block.content, target) {
+            (BlockContent::Text { body }, ContentKind::Link) => {
+                let url = body.trim();
+                crate::validation::validate_url(url).map_err(|_| {
+                    DomainError::InvalidInput("text is not a valid URL".to_string())
+                })?;
+                BlockContent::link(url)
+            }
+            (BlockContent::Link { url, title, .. }, ContentKind::Text) => {
+                match title.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+                    Some(title) => BlockContent::text(format!("{}\n{}", title, url)),
+                    None => BlockContent::text(url.clone()),
+                }
+            }
+            (content, target) => {
+                return Err(DomainError::InvalidInput(format!(
+                    "cannot convert a {:?} block to {:?}",
+                    content.kind(),
+                    target
+                )));
+            }
+        };
+
+        block.content = content;
+        block.updated_at = Utc::now();
+        self.blocks.update(&block).await?;
+        info!(?target, "Block converted");
+        self.record_activity(ActivityEntry::block(ActivityKind::BlockUpdated, id))
+            .await;
         Ok(block)
     }
 
     /// Delete a block.
-    #[instrument(skip(self), fields(block_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
     pub async fn delete_block(&self, id: &BlockId) -> DomainResult<()> {
         // Verify block exists
         let _ = self.get_block(id).await?;
         self.blocks.delete(id).await?;
         info!("Block deleted");
+        self.record_activity(ActivityEntry::block(ActivityKind::BlockDeleted, id))
+            .await;
         Ok(())
     }
 
-    /// Validate block content using the centralized validation module.
-    fn validate_content(content: &BlockContent) -> DomainResult<()> {
-        crate::validation::validate_block_content(content)
-    }
+    /// Find groups of blocks with the same content, as judged by
+    /// [`BlockContent::fingerprint`].
+    ///
+    /// Each group lists its blocks in ID order and has at least two; groups
+    /// are ordered by their first ID. Archived blocks are included.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn find_duplicate_blocks(&self) -> DomainResult<Vec<Vec<BlockId>>> {
+        let mut by_fingerprint: HashMap<String, Vec<BlockId>> = HashMap::new();
+        self.blocks
+            .for_each(DUPLICATE_SCAN_BATCH_SIZE, &mut |block| {
+                by_fingerprint
+                    .entry(block.content.fingerprint())
+                    .or_default()
+                    .push(block.id);
+            })
+            .await?;
 
-    // ─────────────────────────────────────────────────────────────────────────
-    // Connection Operations
-    // ─────────────────────────────────────────────────────────────────────────
+        let mut groups: Vec<Vec<BlockId>> = by_fingerprint
+            .into_values()
+            .filter(|ids| ids.len() > 1)
+            .collect();
+        groups.sort_by(|a, b| a[0].0.cmp(&b[0].0));
+        info!(groups = groups.len(), "Duplicate blocks found");
+        Ok(groups)
+    }
 
-    /// Connect a block to a channel.
+    /// Fold the `merge` blocks into `keep`: `keep` takes over their place in
+    /// every channel it isn't already in, then they are deleted.
     ///
-    /// If position is None, the block is appended to the end.
-    #[instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
-    pub async fn connect_block(
+    /// Where `keep` is already in a channel it stays where it is. The whole
+    /// merge happens at once or not at all. Returns the channels `keep` was
+    /// added to. The blocks' content isn't compared; see
+    /// [`Self::find_duplicate_blocks`] for finding candidates.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, merge), fields(keep = %keep.0, merged = merge.len())))]
+    pub async fn merge_duplicate_blocks(
         &self,
-        block_id: &BlockId,
-        channel_id: &ChannelId,
-        position: Option<i32>,
-    ) -> DomainResult<Connection> {
-        // Verify block and channel exist
-        let _ = self.get_block(block_id).await?;
-        let _ = self.get_channel(channel_id).await?;
-
-        // Check if already connected
-        if self
-            .connections
-            .get_connection(block_id, channel_id)
-            .await?
-            .is_some()
-        {
+        keep: &BlockId,
+        merge: &[BlockId],
+    ) -> DomainResult<Vec<ChannelId>> {
+        if merge.contains(keep) {
             return Err(DomainError::InvalidInput(
-                "block is already connected to this channel".to_string(),
+                "a block cannot be merged into itself".into(),
             ));
         }
+        let mut seen = HashSet::new();
+        let merge: Vec<BlockId> = merge
+            .iter()
+            .filter(|id| seen.insert(*id))
+            .cloned()
+            .collect();
 
-        // Get position (append if not specified)
-        let pos = match position {
-            Some(p) => p,
-            None => self.connections.next_position(channel_id).await?,
-        };
+        let _ = self.get_block(keep).await?;
+        for id in &merge {
+            let _ = self.get_block(id).await?;
+        }
 
-        self.connections.connect(block_id, channel_id, pos).await?;
-        info!(position = pos, "Block connected to channel");
+        let joined = self.connections.merge_blocks(keep, &merge).await?;
+        info!(joined = joined.len(), "Duplicate blocks merged");
+        for id in &merge {
+            self.record_activity(ActivityEntry::block(ActivityKind::BlockDeleted, id))
+                .await;
+        }
+        for channel_id in &joined {
+            self.record_activity(ActivityEntry::connection(
+                ActivityKind::BlockConnected,
+                channel_id,
+                keep,
+            ))
+            .await;
+        }
+        Ok(joined)
+    }
 
-        // Return the created connection
-        self.connections
-            .get_connection(block_id, channel_id)
-            .await?
-            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
+    /// Get a block's tags, sorted alphabetically.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
+    pub async fn get_block_tags(&self, id: &BlockId) -> DomainResult<Vec<String>> {
+        let _ = self.get_block(id).await?;
+        Ok(self.blocks.get_tags(id).await?)
     }
 
-    /// Connect multiple blocks to a channel at once.
+    /// Add a tag to a block.
     ///
-    /// Blocks are connected in order, starting at the given position or
-    /// appending to the end if position is None.
-    pub async fn connect_blocks(
-        &self,
-        block_ids: &[BlockId],
-        channel_id: &ChannelId,
-        starting_position: Option<i32>,
-    ) -> DomainResult<Vec<Connection>> {
-        // Verify channel exists
-        let _ = self.get_channel(channel_id).await?;
-
-        // Verify all blocks exist and aren't already connected
-        for block_id in block_ids {
-            let _ = self.get_block(block_id).await?;
-            if self
-                .connections
-                .get_connection(block_id, channel_id)
-                .await?
-                .is_some()
-            {
-                return Err(DomainError::InvalidInput(format!(
-                    "block {} is already connected to this channel",
-                    block_id.0
-                )));
-            }
-        }
+    /// Returns true if the block did not already have the tag.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
+    pub async fn add_tag(&self, id: &BlockId, tag: &str) -> DomainResult<bool> {
+        let added = self
+            .add_tag_to_blocks(std::slice::from_ref(id), tag)
+            .await?;
+        Ok(added > 0)
+    }
 
-        // Determine starting position
-        let start_pos = match starting_position {
-            Some(p) => p,
-            None => self.connections.next_position(channel_id).await?,
-        };
+    /// Remove a tag from a block.
+    ///
+    /// Returns true if the block had the tag.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
+    pub async fn remove_tag(&self, id: &BlockId, tag: &str) -> DomainResult<bool> {
+        let removed = self
+            .remove_tag_from_blocks(std::slice::from_ref(id), tag)
+            .await?;
+        Ok(removed > 0)
+    }
 
-        // Build connection tuples
-        let conns: Vec<_> = block_ids
-            .iter()
-            .enumerate()
-            .map(|(i, block_id)| (block_id.clone(), channel_id.clone(), start_pos + i as i32))
-            .collect();
+    /// Add a tag to many blocks at once.
+    ///
+    /// The tag is normalized as for [`add_tag`](Self::add_tag). All blocks
+    /// are tagged in a single transaction; blocks that already have the tag
+    /// are skipped. Returns the number of blocks that gained the tag.
+    ///
+    /// Fails with `BlockNotFound`, tagging nothing, if any block is missing.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block_ids), fields(count = block_ids.len())))]
+    pub async fn add_tag_to_blocks(&self, block_ids: &[BlockId], tag: &str) -> DomainResult<usize> {
+        let tag = crate::validation::normalize_tag(tag)?;
+        for id in block_ids {
+            let _ = self.get_block(id).await?;
+        }
 
-        self.connections.connect_batch(&conns).await?;
+        let added = self.blocks.add_tag(block_ids, &tag).await?;
+        info!(tag = %tag, added, "Tag added to blocks");
+        Ok(added)
+    }
 
-        // Return created connections
-        let mut result = Vec::with_capacity(block_ids.len());
-        for block_id in block_ids {
-            if let Some(conn) = self
-                .connections
-                .get_connection(block_id, channel_id)
-                .await?
-            {
-                result.push(conn);
-            }
+    /// Remove a tag from many blocks at once.
+    ///
+    /// The tag is normalized as for [`add_tag`](Self::add_tag). All blocks
+    /// are untagged in a single transaction; blocks without the tag are
+    /// skipped. Returns the number of blocks that lost the tag.
+    ///
+    /// Fails with `BlockNotFound`, untagging nothing, if any block is missing.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block_ids), fields(count = block_ids.len())))]
+    pub async fn remove_tag_from_blocks(
+        &self,
+        block_ids: &[BlockId],
+        tag: &str,
+    ) -> DomainResult<usize> {
+        let tag = crate::validation::normalize_tag(tag)?;
+        for id in block_ids {
+            let _ = self.get_block(id).await?;
         }
 
-        Ok(result)
+        let removed = self.blocks.remove_tag(block_ids, &tag).await?;
+        info!(tag = %tag, removed, "Tag removed from blocks");
+        Ok(removed)
     }
 
-    /// Disconnect a block from a channel.
-    pub async fn disconnect_block(
+    /// Count blocks per source domain, for faceted browsing.
+    ///
+    /// Returns `(domain, count)` pairs such as `("nytimes.com", 42)`, most
+    /// common first. A leading `www.` is ignored.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn source_domain_facets(&self) -> DomainResult<Vec<(String, usize)>> {
+        Ok(self.blocks.source_domains().await?)
+    }
+
+    /// Count blocks that reference a media file, including gallery items.
+    ///
+    /// A media file may back more than one block, so it should only be
+    /// deleted from disk once this count reaches zero.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn media_reference_count(&self, file_path: &str) -> DomainResult<usize> {
+        Ok(self.blocks.count_file_references(file_path).await?)
+    }
+
+    /// Delete a media file from disk unless a block still references it.
+    ///
+    /// Returns `true` if the file was deleted, `false` if it is still in
+    /// use. A file that is already gone is not an error.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, media)))]
+    pub async fn delete_unreferenced_media(
         &self,
-        block_id: &BlockId,
-        channel_id: &ChannelId,
-    ) -> DomainResult<()> {
-        // Verify connection exists
-        let _ = self
-            .connections
-            .get_connection(block_id, channel_id)
-            .await?
-            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))?;
+        file_path: &str,
+        media: &MediaService,
+    ) -> DomainResult<bool> {
+        let references = self.media_reference_count(file_path).await?;
+        if references > 0 {
+            info!(references, "Media file still referenced, keeping it");
+            return Ok(false);
+        }
 
-        self.connections.disconnect(block_id, channel_id).await?;
-        Ok(())
+        media.delete(file_path).await?;
+        Ok(true)
     }
 
-    /// Get all blocks in a channel, ordered by position.
-    pub async fn get_blocks_in_channel(&self, channel_id: &ChannelId) -> DomainResult<Vec<Block>> {
-        let blocks_with_pos = self.connections.get_blocks_in_channel(channel_id).await?;
-        Ok(blocks_with_pos
-            .into_iter()
-            .map(|(block, _pos)| block)
-            .collect())
+    /// List blocks created between `start` and `end`, inclusive, newest first.
+    ///
+    /// Archived blocks are left out unless `include_archived` is set.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn blocks_in_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> DomainResult<Page<Block>> {
+        if start > end {
+            return Err(DomainError::InvalidInput(
+                "range start must not be after its end".into(),
+            ));
+        }
+        Ok(self
+            .blocks
+            .find_created_between(start, end, limit, offset, include_archived)
+            .await?)
     }
 
-    /// Get all blocks in a channel with their positions.
-    pub async fn get_blocks_in_channel_with_positions(
+    /// Count blocks created on each day from `start` to `end`, inclusive,
+    /// for an activity heatmap.
+    ///
+    /// Days are UTC. Returns `(day, count)` pairs in date order; days with no
+    /// new blocks are absent, so callers fill the gaps with zero.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn activity_heatmap(
         &self,
-        channel_id: &ChannelId,
-    ) -> DomainResult<Vec<(Block, i32)>> {
-        Ok(self.connections.get_blocks_in_channel(channel_id).await?)
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> DomainResult<Vec<(NaiveDate, usize)>> {
+        if start > end {
+            return Err(DomainError::InvalidInput(
+                "range start must not be after its end".into(),
+            ));
+        }
+        Ok(self.blocks.counts_by_day(start, end).await?)
+    }
+
+    /// List video and audio blocks between `min` and `max` seconds long,
+    /// newest first. Either bound may be left open.
+    ///
+    /// Archived blocks and media of unknown length are left out.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn blocks_by_duration(
+        &self,
+        min: Option<f32>,
+        max: Option<f32>,
+        limit: usize,
+        offset: usize,
+    ) -> DomainResult<Page<Block>> {
+        if [min, max]
+            .into_iter()
+            .flatten()
+            .any(|d| !d.is_finite() || d < 0.0)
+        {
+            return Err(DomainError::InvalidInput(
+                "duration bounds must be non-negative numbers".into(),
+            ));
+        }
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err(DomainError::InvalidInput(
+                    "minimum duration must not exceed the maximum".into(),
+                ));
+            }
+        }
+        Ok(self
+            .blocks
+            .find_by_duration(min, max, limit, offset)
+            .await?)
     }
 
-    /// Get all channels that contain a block.
-    pub async fn get_channels_for_block(&self, block_id: &BlockId) -> DomainResult<Vec<Channel>> {
-        Ok(self.connections.get_channels_for_block(block_id).await?)
+    /// Count the words archived across all blocks.
+    ///
+    /// See [`BlockContent::word_count`] for what counts.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn total_word_count(&self) -> DomainResult<usize> {
+        Ok(self.blocks.total_word_count().await?)
     }
 
-    /// Reorder a block within a channel.
-    pub async fn reorder_block(
+    /// Count the blocks missing each archive metadata field, for nudging
+    /// the user to finish archiving.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn metadata_stats(&self) -> DomainResult<MetadataStats> {
+        Ok(self.blocks.metadata_completeness().await?)
+    }
+
+    /// Check a block's media files against the hashes stored at import.
+    ///
+    /// Returns `true` only if every hashed file matches; files imported
+    /// before hashing are skipped. Fails if the block has no local media or
+    /// none of its files has a stored hash.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, media), fields(block_id = %block_id.0)))]
+    pub async fn verify_media(
         &self,
-        channel_id: &ChannelId,
         block_id: &BlockId,
-        new_position: i32,
-    ) -> DomainResult<()> {
-        // Verify connection exists
-        let _ = self
-            .connections
-            .get_connection(block_id, channel_id)
-            .await?
-            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))?;
+        media: &MediaService,
+    ) -> DomainResult<bool> {
+        let block = self.get_block(block_id).await?;
+        let files = block.content.media_files();
+        if files.is_empty() {
+            return Err(DomainError::InvalidInput(
+                "block has no local media to verify".into(),
+            ));
+        }
 
-        self.connections
-            .reorder(channel_id, block_id, new_position)
-            .await?;
-        Ok(())
+        let mut checked = 0;
+        for (file_path, hash) in files {
+            let Some(hash) = hash else {
+                continue;
+            };
+            if !media.verify(file_path, hash).await? {
+                info!(file_path, "Media failed verification");
+                return Ok(false);
+            }
+            checked += 1;
+        }
+        if checked == 0 {
+            return Err(DomainError::InvalidInput(
+                "block's media has no stored hash".into(),
+            ));
+        }
+
+        info!(files = checked, "Media verified");
+        Ok(true)
     }
 
-    /// Get a specific connection.
-    pub async fn get_connection(
+    /// Convert an image block's file to another format, e.g. PNG to WebP to
+    /// save space, pointing the block at the new file.
+    ///
+    /// The new file is written before the block changes, and the old one is
+    /// removed only once the block has been updated and no other block
+    /// still uses it, so the block never points at a missing file.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, media), fields(block_id = %block_id.0)))]
+    pub async fn reencode_block_media(
         &self,
         block_id: &BlockId,
+        media: &MediaService,
+        target_mime: &str,
+    ) -> DomainResult<Block> {
+        let mut block = self.get_block(block_id).await?;
+        let BlockContent::Image { file_path, .. } = &block.content else {
+            return Err(DomainError::InvalidInput(
+                "only image blocks can be re-encoded".into(),
+            ));
+        };
+        let old_path = file_path.clone();
+
+        let info = media.write_reencoded(&old_path, target_mime).await?;
+        if let BlockContent::Image {
+            file_path,
+            mime_type,
+            width,
+            height,
+            aspect_ratio,
+            content_hash,
+            ..
+        } = &mut block.content
+        {
+            *file_path = info.file_path.clone();
+            *mime_type = info.mime_type;
+            *width = info.width;
+            *height = info.height;
+            *aspect_ratio = info.aspect_ratio;
+            *content_hash = info.content_hash;
+        }
+        block.updated_at = Utc::now();
+
+        if let Err(e) = self.blocks.update(&block).await {
+            if let Err(cleanup) = media.delete(&info.file_path).await {
+                warn!(error = %cleanup, path = %info.file_path, "Failed to remove re-encoded file");
+            }
+            return Err(e.into());
+        }
+        if self.blocks.count_file_references(&old_path).await? == 0 {
+            if let Err(e) = media.delete(&old_path).await {
+                warn!(error = %e, path = %old_path, "Failed to remove original media file");
+            }
+        }
+
+        info!(from = %old_path, to = %info.file_path, "Block media re-encoded");
+        self.record_activity(ActivityEntry::block(ActivityKind::BlockUpdated, block_id))
+            .await;
+        Ok(block)
+    }
+
+    /// Find the media files a channel's blocks reference but which are not
+    /// on disk.
+    ///
+    /// Returns each missing file with its block, in channel order; galleries
+    /// can report several files. Blocks without local media are skipped.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, media), fields(channel_id = %channel_id.0)))]
+    pub async fn validate_channel_media(
+        &self,
         channel_id: &ChannelId,
-    ) -> DomainResult<Connection> {
-        self.connections
-            .get_connection(block_id, channel_id)
+        media: &MediaService,
+    ) -> DomainResult<Vec<(BlockId, String)>> {
+        let mut missing = Vec::new();
+        for (block, _) in self
+            .get_blocks_in_channel_with_positions(channel_id, true)
             .await?
-            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
+        {
+            for (file_path, _) in block.content.media_files() {
+                // A path that escapes the media root can't be served either
+                if !media.exists(file_path).unwrap_or(false) {
+                    missing.push((block.id.clone(), file_path.to_string()));
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            warn!(count = missing.len(), "Channel has missing media");
+        }
+        Ok(missing)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::models::FieldUpdate;
-    use crate::ports::{
-        InMemoryBlockRepository, InMemoryChannelRepository, InMemoryConnectionRepository,
-        TestFixture,
-    };
+    /// Check whether link blocks' URLs still resolve, for a link rot report.
+    ///
+    /// Checks up to `limit` link blocks in block ID order, one at a time so
+    /// the media service's rate limit applies. A URL that can't be checked
+    /// at all, such as a non-HTTP scheme, is reported as
+    /// [`LinkStatus::Error`] rather than failing the report.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, media)))]
+    pub async fn check_links(
+        &self,
+        media: &MediaService,
+        limit: usize,
+    ) -> DomainResult<Vec<(BlockId, LinkStatus)>> {
+        let mut links = Vec::new();
+        self.blocks
+            .for_each(LINK_CHECK_BATCH_SIZE, &mut |block| {
+                if links.len() < limit {
+                    if let BlockContent::Link { url, .. } = block.content {
+                        links.push((block.id, url));
+                    }
+                }
+            })
+            .await?;
 
-    /// Helper to create a test service with properly synchronized in-memory repositories.
-    fn test_service() -> GardenService<
-        InMemoryChannelRepository,
-        InMemoryBlockRepository,
-        InMemoryConnectionRepository,
-    > {
-        TestFixture::new().service()
+        let mut statuses = Vec::with_capacity(links.len());
+        for (block_id, url) in links {
+            let status = media
+                .check_link(&url)
+                .await
+                .unwrap_or_else(|e| LinkStatus::Error(e.to_string()));
+            statuses.push((block_id, status));
+        }
+
+        let broken = statuses
+            .iter()
+            .filter(|(_, status)| *status != LinkStatus::Ok)
+            .count();
+        info!(checked = statuses.len(), broken, "Links checked");
+        Ok(statuses)
+    }
+
+    /// Search blocks by text.
+    ///
+    /// All terms in `query` must match. In `SearchMode::Snippet`, each hit
+    /// includes a snippet with matched terms wrapped in the given delimiters.
+    /// Archived blocks are left out unless `include_archived` is set.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, mode), fields(query = %query)))]
+    pub async fn search_blocks(
+        &self,
+        query: &str,
+        mode: &SearchMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> DomainResult<Page<SearchHit>> {
+        if query.trim().is_empty() {
+            return Err(DomainError::InvalidInput(
+                "search query cannot be empty".into(),
+            ));
+        }
+        let page = self
+            .blocks
+            .search(query, mode, limit, offset, include_archived)
+            .await?;
+        info!(total = page.total, "Blocks searched");
+        Ok(page)
+    }
+
+    /// Validate a new block and build it with its metadata.
+    fn build_block(new_block: NewBlock) -> DomainResult<Block> {
+        Self::validate_content(&new_block.content)?;
+        crate::validation::validate_source_url(new_block.source_url.as_deref())?;
+
+        let mut block = Block::new(new_block.content);
+        block.source_url = new_block.source_url;
+        block.source_title = new_block.source_title;
+        block.creator = new_block.creator;
+        block.original_date = new_block.original_date;
+        block.notes = new_block.notes;
+        Ok(block)
+    }
+
+    /// Validate block content using the centralized validation module.
+    fn validate_content(content: &BlockContent) -> DomainResult<()> {
+        crate::validation::validate_block_content(content)
     }
 
     // ─────────────────────────────────────────────────────────────────────────
-    // Channel Tests
+    // Connection Operations
     // ─────────────────────────────────────────────────────────────────────────
 
-    #[tokio::test]
-    async fn create_channel_success() {
-        let service = test_service();
-        let channel = service
-            .create_channel(NewChannel {
-                title: "Test Channel".to_string(),
-                description: None,
+    /// Create blocks and connect them to a channel in one step.
+    ///
+    /// Everything is validated before anything is written. The blocks are
+    /// connected in order, starting at `start` or appending to the end of
+    /// the channel, one position gap apart. Blocks and connections are
+    /// written in one transaction, so a failed import leaves no orphans
+    /// behind.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, new_blocks), fields(count = new_blocks.len(), channel_id = %channel_id.0)))]
+    pub async fn create_and_connect_blocks(
+        &self,
+        new_blocks: Vec<NewBlock>,
+        channel_id: &ChannelId,
+        start: Option<i32>,
+    ) -> DomainResult<Vec<(Block, Connection)>> {
+        if let Some(p) = start {
+            crate::validation::validate_position(p)?;
+        }
+        let _ = self.get_channel(channel_id).await?;
+        if new_blocks.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let blocks = new_blocks
+            .into_iter()
+            .map(Self::build_block)
+            .collect::<DomainResult<Vec<_>>>()?;
+
+        let start_pos = match start {
+            Some(p) => p,
+            None => self.append_position(channel_id).await?,
+        };
+        let conns: Vec<_> = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, block)| {
+                let position = self.gapped_position(start_pos, i)?;
+                Ok((block.id.clone(), channel_id.clone(), position))
             })
-            .await
-            .unwrap();
+            .collect::<DomainResult<_>>()?;
 
-        assert_eq!(channel.title, "Test Channel");
-        assert!(channel.description.is_none());
+        self.connections
+            .create_connected(&[], &blocks, &conns)
+            .await?;
+
+        let mut result = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let conn = self
+                .connections
+                .get_connection(&block.id, channel_id)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::ConnectionNotFound(block.id.clone(), channel_id.clone())
+                })?;
+            result.push((block, conn));
+        }
+
+        info!(
+            count = result.len(),
+            start = start_pos,
+            "Blocks created and connected"
+        );
+        for (block, _) in &result {
+            self.record_activity(ActivityEntry::block(ActivityKind::BlockCreated, &block.id))
+                .await;
+            self.record_activity(ActivityEntry::connection(
+                ActivityKind::BlockConnected,
+                channel_id,
+                &block.id,
+            ))
+            .await;
+        }
+        Ok(result)
     }
 
-    #[tokio::test]
-    async fn create_channel_with_description() {
-        let service = test_service();
-        let channel = service
-            .create_channel(NewChannel {
-                title: "Test".to_string(),
-                description: Some("A description".to_string()),
-            })
-            .await
-            .unwrap();
+    /// The position one gap past the last block in a channel, or 0 if the
+    /// channel is empty.
+    async fn append_position(&self, channel_id: &ChannelId) -> DomainResult<i32> {
+        self.connections
+            .next_position(channel_id, self.position_gap)
+            .await?
+            .ok_or_else(position_overflow)
+    }
 
-        assert_eq!(channel.description, Some("A description".to_string()));
+    /// The position of the `index`th block in a run starting at `start`,
+    /// one position gap apart.
+    fn gapped_position(&self, start: i32, index: usize) -> DomainResult<i32> {
+        i32::try_from(index)
+            .ok()
+            .and_then(|i| i.checked_mul(self.position_gap))
+            .and_then(|offset| start.checked_add(offset))
+            .ok_or_else(position_overflow)
     }
 
-    #[tokio::test]
-    async fn create_channel_empty_title_fails() {
-        let service = test_service();
-        let result = service
-            .create_channel(NewChannel {
-                title: "   ".to_string(),
-                description: None,
-            })
-            .await;
+    /// Connect a block to a channel.
+    ///
+    /// If position is None, the block is appended to the end. Explicit
+    /// positions must be non-negative (see [`validate_position`]).
+    ///
+    /// [`validate_position`]: crate::validation::validate_position
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
+    pub async fn connect_block(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+        position: Option<i32>,
+    ) -> DomainResult<Connection> {
+        if let Some(p) = position {
+            crate::validation::validate_position(p)?;
+        }
 
-        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        // Verify block and channel exist
+        let _ = self.get_block(block_id).await?;
+        let _ = self.get_channel(channel_id).await?;
+
+        // Check if already connected
+        if self
+            .connections
+            .get_connection(block_id, channel_id)
+            .await?
+            .is_some()
+        {
+            return Err(already_connected());
+        }
+
+        // Get position (append if not specified)
+        let pos = match position {
+            Some(p) => p,
+            None => self.append_position(channel_id).await?,
+        };
+
+        // A concurrent connect can slip past the check above; the repository
+        // then reports a duplicate, which means the same thing.
+        match self.connections.connect(block_id, channel_id, pos).await {
+            Err(RepoError::Duplicate) => return Err(already_connected()),
+            result => result?,
+        }
+        info!(position = pos, "Block connected to channel");
+        self.record_activity(ActivityEntry::connection(
+            ActivityKind::BlockConnected,
+            channel_id,
+            block_id,
+        ))
+        .await;
+
+        // Return the created connection
+        self.connections
+            .get_connection(block_id, channel_id)
+            .await?
+            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
     }
 
-    #[tokio::test]
-    async fn get_channel_not_found() {
-        let service = test_service();
-        let result = service.get_channel(&ChannelId::new()).await;
+    /// Connect a block to the top of a channel.
+    ///
+    /// The block takes position 0 and every block already in the channel
+    /// moves down one, all in one transaction.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
+    pub async fn prepend_block(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+    ) -> DomainResult<Connection> {
+        let _ = self.get_block(block_id).await?;
+        let _ = self.get_channel(channel_id).await?;
 
-        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+        match self.connections.prepend(block_id, channel_id).await {
+            Err(RepoError::Duplicate) => return Err(already_connected()),
+            result => result?,
+        }
+        info!("Block prepended to channel");
+        self.record_activity(ActivityEntry::connection(
+            ActivityKind::BlockConnected,
+            channel_id,
+            block_id,
+        ))
+        .await;
+
+        self.connections
+            .get_connection(block_id, channel_id)
+            .await?
+            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
+    }
+
+    /// Make sure a block is in a channel at `position`, connecting it if it
+    /// isn't already and moving it there if it is.
+    ///
+    /// Unlike [`Self::connect_block`], calling this again is not an error,
+    /// which suits imports that may be retried. The position must be
+    /// non-negative.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
+    pub async fn ensure_connected(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+        position: i32,
+    ) -> DomainResult<Connection> {
+        crate::validation::validate_position(position)?;
+
+        let _ = self.get_block(block_id).await?;
+        let _ = self.get_channel(channel_id).await?;
+        let kind = match self
+            .connections
+            .get_connection(block_id, channel_id)
+            .await?
+        {
+            Some(_) => ActivityKind::BlockReordered,
+            None => ActivityKind::BlockConnected,
+        };
+
+        self.connections
+            .upsert(block_id, channel_id, position)
+            .await?;
+        self.record_activity(ActivityEntry::connection(kind, channel_id, block_id))
+            .await;
+
+        self.connections
+            .get_connection(block_id, channel_id)
+            .await?
+            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
+    }
+
+    /// Connect multiple blocks to a channel at once.
+    ///
+    /// Blocks are connected in order, one position gap apart, starting at
+    /// the given position or appending to the end if position is None. An empty `block_ids`
+    /// returns immediately without touching storage.
+    pub async fn connect_blocks(
+        &self,
+        block_ids: &[BlockId],
+        channel_id: &ChannelId,
+        starting_position: Option<i32>,
+    ) -> DomainResult<Vec<Connection>> {
+        if let Some(p) = starting_position {
+            crate::validation::validate_position(p)?;
+        }
+
+        if block_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Verify channel exists
+        let _ = self.get_channel(channel_id).await?;
+
+        // Verify all blocks exist and aren't already connected
+        for block_id in block_ids {
+            let _ = self.get_block(block_id).await?;
+            if self
+                .connections
+                .get_connection(block_id, channel_id)
+                .await?
+                .is_some()
+            {
+                return Err(DomainError::InvalidInput(format!(
+                    "block {} is already connected to this channel",
+                    block_id.0
+                )));
+            }
+        }
+
+        // Determine starting position
+        let start_pos = match starting_position {
+            Some(p) => p,
+            None => self.append_position(channel_id).await?,
+        };
+
+        // Build connection tuples
+        let conns: Vec<_> = block_ids
+            .iter()
+            .enumerate()
+            .map(|(i, block_id)| {
+                let position = self.gapped_position(start_pos, i)?;
+                Ok((block_id.clone(), channel_id.clone(), position))
+            })
+            .collect::<DomainResult<_>>()?;
+
+        self.connections.connect_batch(&conns).await?;
+        for block_id in block_ids {
+            self.record_activity(ActivityEntry::connection(
+                ActivityKind::BlockConnected,
+                channel_id,
+                block_id,
+            ))
+            .await;
+        }
+
+        // Return created connections
+        let mut result = Vec::with_capacity(block_ids.len());
+        for block_id in block_ids {
+            if let Some(conn) = self
+                .connections
+                .get_connection(block_id, channel_id)
+                .await?
+            {
+                result.push(conn);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Disconnect a block from a channel.
+    pub async fn disconnect_block(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+    ) -> DomainResult<()> {
+        self.check_connected(block_id, channel_id).await?;
+
+        self.connections.disconnect(block_id, channel_id).await?;
+        self.record_activity(ActivityEntry::connection(
+            ActivityKind::BlockDisconnected,
+            channel_id,
+            block_id,
+        ))
+        .await;
+        Ok(())
+    }
+
+    /// Get all blocks in a channel, ordered by position, leaving out
+    /// archived blocks unless `include_archived` is set.
+    pub async fn get_blocks_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        include_archived: bool,
+    ) -> DomainResult<Vec<Block>> {
+        let blocks_with_pos = self
+            .get_blocks_in_channel_with_positions(channel_id, include_archived)
+            .await?;
+        Ok(blocks_with_pos
+            .into_iter()
+            .map(|(block, _pos)| block)
+            .collect())
+    }
+
+    /// Get all blocks in a channel with their positions, leaving out
+    /// archived blocks unless `include_archived` is set.
+    pub async fn get_blocks_in_channel_with_positions(
+        &self,
+        channel_id: &ChannelId,
+        include_archived: bool,
+    ) -> DomainResult<Vec<(Block, i32)>> {
+        // Tell a missing channel apart from an empty one
+        let _ = self.get_channel(channel_id).await?;
+        Ok(self
+            .connections
+            .get_blocks_in_channel(channel_id, include_archived)
+            .await?)
+    }
+
+    /// Get the blocks most recently connected to a channel, newest first.
+    ///
+    /// Powers the "just added" highlight, independent of block order.
+    /// Archived blocks are left out unless `include_archived` is set.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    pub async fn recent_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        include_archived: bool,
+    ) -> DomainResult<Vec<(Block, Connection)>> {
+        self.get_channel(channel_id).await?;
+        Ok(self
+            .connections
+            .recent_connections_in_channel(channel_id, limit, include_archived)
+            .await?)
+    }
+
+    /// List the blocks in any or all of several channels, newest first.
+    ///
+    /// Fails with `ChannelNotFound` if any channel is missing, rather than
+    /// silently narrowing an intersection to nothing. Archived blocks are
+    /// left out unless `include_archived` is set.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, channel_ids), fields(channels = channel_ids.len())))]
+    pub async fn blocks_in_channels(
+        &self,
+        channel_ids: &[ChannelId],
+        mode: ChannelSetMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> DomainResult<Page<Block>> {
+        if channel_ids.is_empty() {
+            return Err(DomainError::InvalidInput(
+                "at least one channel is required".into(),
+            ));
+        }
+        for id in channel_ids {
+            let _ = self.get_channel(id).await?;
+        }
+
+        Ok(self
+            .connections
+            .blocks_in_channels(channel_ids, mode, limit, offset, include_archived)
+            .await?)
+    }
+
+    /// List the blocks that could be added to a channel: those not already
+    /// in it, newest first. Archived blocks are left out unless
+    /// `include_archived` is set.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    pub async fn available_blocks_for_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> DomainResult<Page<Block>> {
+        let _ = self.get_channel(channel_id).await?;
+        Ok(self
+            .connections
+            .blocks_not_in_channel(channel_id, limit, offset, include_archived)
+            .await?)
+    }
+
+    /// List all blocks, newest first, each with the channels it is in.
+    ///
+    /// Fetches a page of blocks and then their channels in one go, rather
+    /// than a channel lookup per block. Archived blocks are left out unless
+    /// `include_archived` is set.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn list_blocks_with_channels(
+        &self,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> DomainResult<Page<(Block, Vec<Channel>)>> {
+        Ok(self
+            .connections
+            .blocks_with_channels(None, limit, offset, include_archived)
+            .await?)
+    }
+
+    /// Get several blocks, each with the channels it is in.
+    ///
+    /// Runs a fixed number of queries however many blocks are requested.
+    /// Results follow the order of `ids`, with repeats dropped; an unknown
+    /// ID fails with `BlockNotFound`.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, ids), fields(count = ids.len())))]
+    pub async fn get_block_contexts(&self, ids: &[BlockId]) -> DomainResult<Vec<BlockContext>> {
+        let page = self
+            .connections
+            .blocks_with_channels(Some(ids), ids.len(), 0, true)
+            .await?;
+        let mut found: HashMap<BlockId, (Block, Vec<Channel>)> = page
+            .items
+            .into_iter()
+            .map(|(block, channels)| (block.id.clone(), (block, channels)))
+            .collect();
+
+        // Walk the requested IDs to restore their order, skipping repeats
+        let mut seen = HashSet::new();
+        let mut contexts = Vec::with_capacity(found.len());
+        for id in ids.iter().filter(|id| seen.insert(*id)) {
+            let (block, channels) = found
+                .remove(id)
+                .ok_or_else(|| DomainError::BlockNotFound(id.clone()))?;
+            contexts.push(BlockContext { block, channels });
+        }
+        Ok(contexts)
+    }
+
+    /// Get a channel with its block count and first `block_limit` blocks.
+    ///
+    /// Saves a second round trip when rendering channel cards. Archived
+    /// blocks are neither counted nor previewed.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
+    pub async fn get_channel_preview(
+        &self,
+        id: &ChannelId,
+        block_limit: usize,
+    ) -> DomainResult<ChannelPreview> {
+        let channel = self.get_channel(id).await?;
+        let page = self
+            .connections
+            .get_blocks_in_channel_paged(id, block_limit, 0, false)
+            .await?;
+
+        Ok(ChannelPreview {
+            channel,
+            block_count: page.total,
+            preview_blocks: page.items.into_iter().map(|(block, _pos)| block).collect(),
+        })
+    }
+
+    /// Count how many of a channel's blocks are exclusive to it and how
+    /// many also appear in other channels.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
+    pub async fn channel_block_sharing(&self, id: &ChannelId) -> DomainResult<SharingStats> {
+        let _ = self.get_channel(id).await?;
+        Ok(self.connections.sharing_stats(id).await?)
+    }
+
+    /// Count a channel's blocks by content type, for a summary such as
+    /// "12 images, 3 videos, 20 links".
+    ///
+    /// Keys are content type tags (see [`ContentKind::as_str`]); types the
+    /// channel has no blocks of are absent.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
+    pub async fn channel_composition(
+        &self,
+        id: &ChannelId,
+    ) -> DomainResult<HashMap<String, usize>> {
+        let _ = self.get_channel(id).await?;
+        Ok(self
+            .connections
+            .content_type_breakdown_in_channel(id)
+            .await?)
+    }
+
+    /// Get the blocks that appear in the most channels, with how many
+    /// channels each is in, most connected first. Archived blocks are left
+    /// out unless `include_archived` is set.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn popular_blocks(
+        &self,
+        limit: usize,
+        include_archived: bool,
+    ) -> DomainResult<Vec<(Block, usize)>> {
+        Ok(self
+            .connections
+            .most_connected_blocks(limit, include_archived)
+            .await?)
+    }
+
+    /// Get the blocks before and after a block in a channel.
+    ///
+    /// Used for previous/next navigation in the block detail view.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0)))]
+    pub async fn block_neighbors(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> DomainResult<BlockNeighbors> {
+        match self.connections.neighbors(channel_id, block_id).await {
+            Ok((previous, next)) => Ok(BlockNeighbors { previous, next }),
+            Err(RepoError::NotFound) => Err(DomainError::ConnectionNotFound(
+                block_id.clone(),
+                channel_id.clone(),
+            )),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get a block's index within a channel and the channel's block count.
+    ///
+    /// Used for the "3 of 42" indicator in the block detail view, alongside
+    /// [`Self::block_neighbors`].
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0)))]
+    pub async fn block_position_info(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> DomainResult<BlockPositionInfo> {
+        self.connections
+            .block_index_in_channel(channel_id, block_id)
+            .await?
+            .map(|(index, total)| BlockPositionInfo { index, total })
+            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
+    }
+
+    /// Get all channels that contain a block, in the given order.
+    ///
+    /// Unbounded; use [`Self::get_channels_for_block_paged`] for blocks that
+    /// may be in many channels.
+    pub async fn get_channels_for_block(
+        &self,
+        block_id: &BlockId,
+        order: ChannelOrder,
+    ) -> DomainResult<Vec<Channel>> {
+        Ok(self
+            .connections
+            .get_channels_for_block(block_id, order)
+            .await?)
+    }
+
+    /// Get a page of the channels that contain a block.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0)))]
+    pub async fn get_channels_for_block_paged(
+        &self,
+        block_id: &BlockId,
+        limit: usize,
+        offset: usize,
+    ) -> DomainResult<Page<Channel>> {
+        Ok(self
+            .connections
+            .get_channels_for_block_paged(block_id, limit, offset)
+            .await?)
+    }
+
+    /// Get a page of the channels that hold at least one block of `kind`,
+    /// newest channel first.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn channels_with_content_type(
+        &self,
+        kind: ContentKind,
+        limit: usize,
+        offset: usize,
+    ) -> DomainResult<Page<Channel>> {
+        Ok(self
+            .connections
+            .channels_with_content_type(kind.as_str(), limit, offset)
+            .await?)
+    }
+
+    /// Reorder a block within a channel.
+    ///
+    /// The new position must be non-negative.
+    pub async fn reorder_block(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+        new_position: i32,
+    ) -> DomainResult<()> {
+        crate::validation::validate_position(new_position)?;
+
+        self.check_connected(block_id, channel_id).await?;
+
+        self.connections
+            .reorder(channel_id, block_id, new_position)
+            .await?;
+        self.record_activity(ActivityEntry::connection(
+            ActivityKind::BlockReordered,
+            channel_id,
+            block_id,
+        ))
+        .await;
+        Ok(())
+    }
+
+    /// Swap the positions of two blocks in a channel.
+    ///
+    /// Both positions change in one step, so the channel never shows the
+    /// blocks at the same position mid-swap.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_a = %block_a.0, block_b = %block_b.0)))]
+    pub async fn swap_blocks(
+        &self,
+        channel_id: &ChannelId,
+        block_a: &BlockId,
+        block_b: &BlockId,
+    ) -> DomainResult<()> {
+        if block_a == block_b {
+            return Err(DomainError::InvalidInput(
+                "cannot swap a block with itself".into(),
+            ));
+        }
+        for id in [block_a, block_b] {
+            if self
+                .connections
+                .get_connection(id, channel_id)
+                .await?
+                .is_none()
+            {
+                return Err(DomainError::ConnectionNotFound(
+                    id.clone(),
+                    channel_id.clone(),
+                ));
+            }
+        }
+
+        self.connections
+            .swap_positions(channel_id, block_a, block_b)
+            .await?;
+        info!("Blocks swapped");
+        for id in [block_a, block_b] {
+            self.record_activity(ActivityEntry::connection(
+                ActivityKind::BlockReordered,
+                channel_id,
+                id,
+            ))
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Move a block directly before or after another block in a channel.
+    ///
+    /// The channel's positions are renumbered one position gap apart, so the
+    /// moved block never collides with its new neighbours.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0)))]
+    pub async fn move_block_relative(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+        anchor: &BlockId,
+        placement: Placement,
+    ) -> DomainResult<()> {
+        if block_id == anchor {
+            return Err(DomainError::InvalidInput(
+                "cannot move a block relative to itself".into(),
+            ));
+        }
+        for id in [block_id, anchor] {
+            if self
+                .connections
+                .get_connection(id, channel_id)
+                .await?
+                .is_none()
+            {
+                return Err(DomainError::ConnectionNotFound(
+                    id.clone(),
+                    channel_id.clone(),
+                ));
+            }
+        }
+
+        self.connections
+            .move_relative(channel_id, block_id, anchor, placement, self.position_gap)
+            .await?;
+        info!(anchor = %anchor.0, ?placement, "Block moved");
+        self.record_activity(ActivityEntry::connection(
+            ActivityKind::BlockReordered,
+            channel_id,
+            block_id,
+        ))
+        .await;
+        Ok(())
+    }
+
+    /// Move several blocks in a channel to `target_index` as one contiguous
+    /// run, keeping their current relative order.
+    ///
+    /// `target_index` counts positions among the channel's other blocks and
+    /// is clamped to the end. The channel's positions are renumbered from
+    /// zero, one position gap apart, in one step.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block_ids), fields(channel_id = %channel_id.0, blocks = block_ids.len())))]
+    pub async fn move_block_group(
+        &self,
+        channel_id: &ChannelId,
+        block_ids: &[BlockId],
+        target_index: usize,
+    ) -> DomainResult<()> {
+        if block_ids.is_empty() {
+            return Err(DomainError::InvalidInput("no blocks to move".into()));
+        }
+        for id in block_ids {
+            if self
+                .connections
+                .get_connection(id, channel_id)
+                .await?
+                .is_none()
+            {
+                return Err(DomainError::ConnectionNotFound(
+                    id.clone(),
+                    channel_id.clone(),
+                ));
+            }
+        }
+
+        self.connections
+            .move_group(channel_id, block_ids, target_index, self.position_gap)
+            .await?;
+        info!(target_index, "Block group moved");
+        for block_id in block_ids {
+            self.record_activity(ActivityEntry::connection(
+                ActivityKind::BlockReordered,
+                channel_id,
+                block_id,
+            ))
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Sort a channel's blocks by `key`, renumbering their positions from
+    /// zero.
+    ///
+    /// Ties are broken by creation time, then ID, so sorting is stable
+    /// across calls.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    pub async fn sort_channel(
+        &self,
+        channel_id: &ChannelId,
+        key: BlockSortKey,
+        ascending: bool,
+    ) -> DomainResult<()> {
+        let _ = self.get_channel(channel_id).await?;
+
+        let mut entries: Vec<(Block, DateTime<Utc>)> = self
+            .connections
+            .connections_in_channel(channel_id)
+            .await?
+            .into_iter()
+            .map(|(block, connection)| {
+                let at = match key {
+                    BlockSortKey::ConnectedAt => connection.connected_at,
+                    _ => block.created_at,
+                };
+                (block, at)
+            })
+            .collect();
+
+        let tiebreak = |a: &Block, b: &Block| (a.created_at, &a.id.0).cmp(&(b.created_at, &b.id.0));
+        match key {
+            BlockSortKey::Title => entries.sort_by(|(a, _), (b, _)| {
+                a.display_title()
+                    .to_lowercase()
+                    .cmp(&b.display_title().to_lowercase())
+                    .then_with(|| tiebreak(a, b))
+            }),
+            BlockSortKey::CreatedAt | BlockSortKey::ConnectedAt => {
+                entries.sort_by(|(a, at_a), (b, at_b)| at_a.cmp(at_b).then_with(|| tiebreak(a, b)))
+            }
+        }
+        if !ascending {
+            entries.reverse();
+        }
+
+        let order: Vec<BlockId> = entries.into_iter().map(|(block, _)| block.id).collect();
+        self.connections
+            .set_order(channel_id, &order, self.position_gap)
+            .await?;
+        info!(?key, ascending, blocks = order.len(), "Channel sorted");
+        Ok(())
+    }
+
+    /// Record a channel's current block order, so a later sort or
+    /// rearrangement can be undone with [`Self::restore_channel_order`].
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    pub async fn snapshot_channel_order(
+        &self,
+        channel_id: &ChannelId,
+    ) -> DomainResult<OrderSnapshot> {
+        let _ = self.get_channel(channel_id).await?;
+
+        let positions = self
+            .connections
+            .get_blocks_in_channel(channel_id, true)
+            .await?
+            .into_iter()
+            .map(|(block, position)| (block.id, position))
+            .collect();
+        Ok(OrderSnapshot {
+            channel_id: channel_id.clone(),
+            positions,
+            taken_at: Utc::now(),
+        })
+    }
+
+    /// Put a channel's blocks back in the order recorded by `snapshot`,
+    /// renumbering positions from zero in one transaction.
+    ///
+    /// Blocks disconnected since the snapshot are skipped; blocks connected
+    /// since follow the snapshot's blocks in their current order.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, snapshot), fields(channel_id = %channel_id.0)))]
+    pub async fn restore_channel_order(
+        &self,
+        channel_id: &ChannelId,
+        snapshot: &OrderSnapshot,
+    ) -> DomainResult<()> {
+        if &snapshot.channel_id != channel_id {
+            return Err(DomainError::InvalidInput(
+                "snapshot belongs to a different channel".into(),
+            ));
+        }
+        let _ = self.get_channel(channel_id).await?;
+
+        let current: HashSet<BlockId> = self
+            .connections
+            .get_blocks_in_channel(channel_id, true)
+            .await?
+            .into_iter()
+            .map(|(block, _)| block.id)
+            .collect();
+        let mut positions = snapshot.positions.clone();
+        positions.sort_by_key(|(_, position)| *position);
+        let order: Vec<BlockId> = positions
+            .into_iter()
+            .map(|(block_id, _)| block_id)
+            .filter(|block_id| current.contains(block_id))
+            .collect();
+
+        self.connections
+            .set_order(channel_id, &order, self.position_gap)
+            .await?;
+        info!(
+            restored = order.len(),
+            skipped = snapshot.positions.len() - order.len(),
+            "Channel order restored"
+        );
+        Ok(())
+    }
+
+    /// Renumber a channel's positions from zero if any is negative, shared
+    /// by two or more blocks, or leaves a gap wider than the position gap
+    /// (see [`PositionIssue`]).
+    ///
+    /// Blocks keep their current order, with ties broken by when they were
+    /// connected and then by block ID, so repeated repairs agree. Returns
+    /// whether anything was renumbered.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    pub async fn repair_channel_positions(&self, channel_id: &ChannelId) -> DomainResult<bool> {
+        let _ = self.get_channel(channel_id).await?;
+
+        let mut connections = self.connections.connections_in_channel(channel_id).await?;
+        let positions: Vec<i32> = connections.iter().map(|(_, c)| c.position).collect();
+        let issues = position_issues(&positions, self.position_gap);
+        if issues.is_empty() {
+            return Ok(false);
+        }
+
+        connections.sort_by(|(a, ca), (b, cb)| {
+            (ca.position, ca.connected_at, &a.id.0).cmp(&(cb.position, cb.connected_at, &b.id.0))
+        });
+        let order: Vec<BlockId> = connections.into_iter().map(|(block, _)| block.id).collect();
+        self.connections
+            .set_order(channel_id, &order, self.position_gap)
+            .await?;
+        warn!(blocks = order.len(), ?issues, "Repaired block positions");
+        Ok(true)
+    }
+
+    /// Find channels whose block positions are negative, shared, or have
+    /// gaps wider than the position gap, one entry per channel and kind of
+    /// issue.
+    ///
+    /// Each can be fixed with [`Self::repair_channel_positions`].
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn position_anomalies(&self) -> DomainResult<Vec<(ChannelId, PositionIssue)>> {
+        Ok(self
+            .connections
+            .find_position_anomalies(self.position_gap)
+            .await?)
+    }
+
+    /// Repair positions in every channel with colliding positions.
+    ///
+    /// Returns the channels that were renumbered.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn repair_all_positions(&self) -> DomainResult<Vec<ChannelId>> {
+        let channels = self.connections.channels_with_position_collisions().await?;
+        let mut repaired = Vec::new();
+        for channel_id in channels {
+            if self.repair_channel_positions(&channel_id).await? {
+                repaired.push(channel_id);
+            }
+        }
+        Ok(repaired)
+    }
+
+    /// Check that a block is connected to a channel.
+    ///
+    /// Reports a missing block or channel as such, so `ConnectionNotFound`
+    /// only means both exist but aren't linked.
+    async fn check_connected(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+    ) -> DomainResult<()> {
+        let _ = self.get_block(block_id).await?;
+        let _ = self.get_channel(channel_id).await?;
+        self.connections
+            .get_connection(block_id, channel_id)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
+    }
+
+    /// Get a specific connection.
+    pub async fn get_connection(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+    ) -> DomainResult<Connection> {
+        self.connections
+            .get_connection(block_id, channel_id)
+            .await?
+            .ok_or_else(|| DomainError::ConnectionNotFound(block_id.clone(), channel_id.clone()))
+    }
+
+    /// Count blocks in every channel at once.
+    ///
+    /// Channels with no blocks are not in the map; treat a missing entry as 0.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn block_counts_by_channel(&self) -> DomainResult<HashMap<ChannelId, usize>> {
+        Ok(self.connections.counts_by_channel().await?)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Import and Export Operations
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Export a single channel with its blocks and connections.
+    ///
+    /// Blocks are in channel order, with connections in the same order.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
+    pub async fn export_channel(&self, id: &ChannelId) -> DomainResult<ChannelExport> {
+        let channel = self.get_channel(id).await?;
+        let positioned = self.connections.get_blocks_in_channel(id, true).await?;
+
+        let mut blocks = Vec::with_capacity(positioned.len());
+        let mut connections = Vec::with_capacity(positioned.len());
+        for (block, _pos) in positioned {
+            // Fetch the connection for its timestamp; skip one removed mid-export
+            if let Some(conn) = self.connections.get_connection(&block.id, id).await? {
+                connections.push(conn);
+                blocks.push(block);
+            }
+        }
+
+        info!(blocks = blocks.len(), "Channel exported");
+        Ok(ChannelExport::new(channel, blocks, connections))
+    }
+
+    /// Render a channel and its blocks as Markdown, in channel order.
+    ///
+    /// Media is referenced under the configured Markdown media base.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
+    pub async fn export_channel_markdown(&self, id: &ChannelId) -> DomainResult<String> {
+        let channel = self.get_channel(id).await?;
+        let blocks: Vec<Block> = self
+            .connections
+            .get_blocks_in_channel(id, true)
+            .await?
+            .into_iter()
+            .map(|(block, _pos)| block)
+            .collect();
+
+        info!(blocks = blocks.len(), "Channel exported as Markdown");
+        Ok(channel_markdown(
+            &channel,
+            &blocks,
+            &self.markdown_media_base,
+        ))
+    }
+
+    /// Import a channel export, recreating the channel, its blocks, and
+    /// their positions.
+    ///
+    /// The whole export is validated before anything is written, and the
+    /// channel, blocks, and connections are written in one transaction, so
+    /// a failed import leaves the garden untouched. Returns `DuplicateTitle`
+    /// if the store enforces unique channel titles and the title is taken.
+    /// Blocks keep their position from the export; blocks without a
+    /// connection are placed after the rest, in export order.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, export), fields(channel_id = %export.channel.id.0, blocks = export.blocks.len())))]
+    pub async fn import_channel(
+        &self,
+        export: ChannelExport,
+        strategy: ImportStrategy,
+    ) -> DomainResult<Channel> {
+        if export.version > ChannelExport::VERSION {
+            return Err(DomainError::InvalidInput(format!(
+                "unsupported export version {} (this build reads up to {})",
+                export.version,
+                ChannelExport::VERSION
+            )));
+        }
+        crate::validation::validate_channel_title(&export.channel.title)?;
+        if let Some(body) = &export.channel.body {
+            crate::validation::validate_channel_body(body)?;
+        }
+        for block in &export.blocks {
+            crate::validation::validate_block(block)
+                .map_err(|e| DomainError::InvalidInput(format!("block {}: {}", block.id.0, e)))?;
+        }
+
+        let positions: HashMap<&BlockId, i32> = export
+            .connections
+            .iter()
+            .filter(|c| c.channel_id == export.channel.id && c.position >= 0)
+            .map(|c| (&c.block_id, c.position))
+            .collect();
+        let mut next_position = positions.values().max().map_or(0, |p| p + 1);
+
+        let mut channel = export.channel.clone();
+        if strategy == ImportStrategy::RemapIds {
+            channel.id = ChannelId::new();
+            channel.accessed_at = None;
+        } else if self.channels.get(&channel.id).await?.is_some() {
+            return Err(DomainError::InvalidInput(format!(
+                "channel {} already exists",
+                channel.id.0
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let mut new_blocks = Vec::new();
+        let mut connections = Vec::new();
+        for block in &export.blocks {
+            if !seen.insert(&block.id) {
+                continue;
+            }
+            let position = match positions.get(&block.id) {
+                Some(&p) => p,
+                None => {
+                    next_position += 1;
+                    next_position - 1
+                }
+            };
+
+            let mut block = block.clone();
+            if strategy == ImportStrategy::RemapIds {
+                block.id = BlockId::new();
+                new_blocks.push(block.clone());
+            } else if self.blocks.get(&block.id).await?.is_none() {
+                new_blocks.push(block.clone());
+            }
+            connections.push((block.id, channel.id.clone(), position));
+        }
+
+        self.connections
+            .create_connected(std::slice::from_ref(&channel), &new_blocks, &connections)
+            .await
+            .map_err(channel_save_error(&channel.title))?;
+
+        info!(
+            channel_id = %channel.id.0,
+            new_blocks = new_blocks.len(),
+            connections = connections.len(),
+            "Channel imported"
+        );
+        Ok(channel)
+    }
+
+    /// Work out what importing an export would do, without writing anything.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, export), fields(channels = export.channels.len(), blocks = export.blocks.len())))]
+    pub async fn plan_import(&self, export: &GardenExport) -> DomainResult<ImportPlan> {
+        Ok(self.analyze_import(export).await?.plan)
+    }
+
+    /// Import a garden export.
+    ///
+    /// Channels and blocks whose IDs already exist are left untouched, blocks
+    /// that fail validation are skipped, and a connection is only created when
+    /// both its block and channel are part of the import. With `dry_run` set,
+    /// nothing is written and the returned plan describes what would happen.
+    ///
+    /// Fails with `InvalidInput` if a channel's title or body is invalid.
+    /// Everything is written in one transaction, so a failed import, e.g.
+    /// one racing another write of the same IDs, leaves the garden untouched.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, export), fields(channels = export.channels.len(), blocks = export.blocks.len())))]
+    pub async fn import_garden(
+        &self,
+        export: GardenExport,
+        dry_run: bool,
+    ) -> DomainResult<ImportPlan> {
+        let analysis = self.analyze_import(&export).await?;
+        if dry_run {
+            info!(
+                new_blocks = analysis.plan.new_blocks,
+                invalid_blocks = analysis.plan.invalid_blocks.len(),
+                "Import planned (dry run)"
+            );
+            return Ok(analysis.plan);
+        }
+
+        let ImportAnalysis {
+            plan,
+            mut new_channels,
+            mut new_blocks,
+            connections,
+        } = analysis;
+        // Removing each ID as it is taken keeps only its first copy
+        let channels: Vec<Channel> = export
+            .channels
+            .into_iter()
+            .filter(|c| new_channels.remove(&c.id))
+            .collect();
+        let blocks: Vec<Block> = export
+            .blocks
+            .into_iter()
+            .filter(|b| crate::validation::validate_block(b).is_ok() && new_blocks.remove(&b.id))
+            .collect();
+        self.connections
+            .create_connected(&channels, &blocks, &connections)
+            .await?;
+
+        info!(
+            new_channels = plan.new_channels,
+            new_blocks = plan.new_blocks,
+            new_connections = plan.new_connections,
+            "Garden imported"
+        );
+        Ok(plan)
+    }
+
+    /// Import a newline-delimited list of URLs as link blocks in a channel.
+    ///
+    /// The file is read line by line. Blank lines and lines starting with
+    /// `#` are ignored; every other line is reported, either with the block
+    /// created for it or with why it was skipped (usually an invalid URL).
+    /// Each block is appended to the channel as its line is read, so an
+    /// error part-way keeps the lines before it.
+    ///
+    /// With `media`, links are enriched with their page's title and
+    /// description; a page that can't be fetched still imports as a bare
+    /// link.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, path, media), fields(path = %path.as_ref().display(), channel_id = %channel_id.0)))]
+    pub async fn import_urls_from_file(
+        &self,
+        path: impl AsRef<Path>,
+        channel_id: &ChannelId,
+        media: Option<&MediaService>,
+    ) -> DomainResult<UrlImportReport> {
+        let _ = self.get_channel(channel_id).await?;
+        let file = tokio::fs::File::open(path.as_ref())
+            .await
+            .map_err(MediaError::from)?;
+
+        let mut report = UrlImportReport::default();
+        let mut lines = tokio::io::BufReader::new(file).lines();
+        let mut line = 0;
+        while let Some(text) = lines.next_line().await.map_err(MediaError::from)? {
+            line += 1;
+            let url = text.trim();
+            if url.is_empty() || url.starts_with('#') {
+                continue;
+            }
+
+            let (block_id, error) = match self.import_url(url, channel_id, media).await {
+                Ok(block) => {
+                    report.imported += 1;
+                    (Some(block.id), None)
+                }
+                Err(e) => {
+                    report.skipped += 1;
+                    (None, Some(e.to_string()))
+                }
+            };
+            report.lines.push(UrlImportLine {
+                line,
+                url: url.to_string(),
+                block_id,
+                error,
+            });
+        }
+
+        info!(
+            imported = report.imported,
+            skipped = report.skipped,
+            "URL list imported"
+        );
+        Ok(report)
+    }
+
+    /// Create a link block for `url` and append it to a channel.
+    async fn import_url(
+        &self,
+        url: &str,
+        channel_id: &ChannelId,
+        media: Option<&MediaService>,
+    ) -> DomainResult<Block> {
+        crate::validation::validate_url(url)?;
+
+        let content = match media {
+            Some(media) => match media.fetch_link_metadata(url).await {
+                Ok(meta) => BlockContent::link_with_meta(url, meta.title, meta.description, None),
+                Err(e) => {
+                    warn!(error = %e, url, "Failed to fetch link metadata");
+                    BlockContent::link(url)
+                }
+            },
+            None => BlockContent::link(url),
+        };
+
+        let mut created = self
+            .create_and_connect_blocks(vec![NewBlock::new(content)], channel_id, None)
+            .await?;
+        let (block, _) = created.remove(0);
+        Ok(block)
+    }
+
+    /// Validate an export and detect collisions against the current garden.
+    async fn analyze_import(&self, export: &GardenExport) -> DomainResult<ImportAnalysis> {
+        if export.version > GardenExport::VERSION {
+            return Err(DomainError::InvalidInput(format!(
+                "unsupported export version {} (this build reads up to {})",
+                export.version,
+                GardenExport::VERSION
+            )));
+        }
+
+        let mut plan = ImportPlan::default();
+
+        // Channels and blocks in the export that will exist after import,
+        // whether newly created or already present.
+        let mut channel_ids = HashSet::new();
+        let mut new_channels = HashSet::new();
+        for channel in &export.channels {
+            crate::validation::validate_channel_title(&channel.title).map_err(|e| {
+                DomainError::InvalidInput(format!("channel {}: {}", channel.id.0, e))
+            })?;
+            if let Some(body) = &channel.body {
+                crate::validation::validate_channel_body(body).map_err(|e| {
+                    DomainError::InvalidInput(format!("channel {}: {}", channel.id.0, e))
+                })?;
+            }
+            if !channel_ids.insert(channel.id.clone()) {
+                continue;
+            }
+            if self.channels.get(&channel.id).await?.is_some() {
+                plan.channel_collisions += 1;
+            } else {
+                new_channels.insert(channel.id.clone());
+            }
+        }
+
+        let mut block_ids = HashSet::new();
+        let mut new_blocks = HashSet::new();
+        for block in &export.blocks {
+            if let Err(e) = crate::validation::validate_block(block) {
+                plan.invalid_blocks.push(InvalidBlock {
+                    block_id: block.id.clone(),
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+            if !block_ids.insert(block.id.clone()) {
+                continue;
+            }
+            if self.blocks.get(&block.id).await?.is_some() {
+                plan.block_collisions += 1;
+            } else {
+                new_blocks.insert(block.id.clone());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut connections = Vec::new();
+        for conn in &export.connections {
+            let importable = block_ids.contains(&conn.block_id)
+                && channel_ids.contains(&conn.channel_id)
+                && conn.position >= 0
+                && seen.insert((conn.block_id.clone(), conn.channel_id.clone()));
+            // Only connections between two existing entities can already exist
+            let exists = importable
+                && !new_blocks.contains(&conn.block_id)
+                && !new_channels.contains(&conn.channel_id)
+                && self
+                    .connections
+                    .get_connection(&conn.block_id, &conn.channel_id)
+                    .await?
+                    .is_some();
+            if importable && !exists {
+                connections.push((
+                    conn.block_id.clone(),
+                    conn.channel_id.clone(),
+                    conn.position,
+                ));
+            } else {
+                plan.skipped_connections += 1;
+            }
+        }
+
+        plan.new_channels = new_channels.len();
+        plan.new_blocks = new_blocks.len();
+        plan.new_connections = connections.len();
+
+        Ok(ImportAnalysis {
+            plan,
+            new_channels,
+            new_blocks,
+            connections,
+        })
+    }
+}
+
+/// Result of analyzing an export: the public plan plus what to write.
+struct ImportAnalysis {
+    plan: ImportPlan,
+    new_channels: HashSet<ChannelId>,
+    new_blocks: HashSet<BlockId>,
+    connections: Vec<(BlockId, ChannelId, i32)>,
+}
+
+/// Map errors from saving a channel, reporting a taken title as
+/// [`DomainError::DuplicateTitle`].
+fn channel_save_error(title: &str) -> impl FnOnce(RepoError) -> DomainError + '_ {
+    move |err| match err {
+        RepoError::DuplicateTitle => DomainError::DuplicateTitle(title.to_string()),
+        other => other.into(),
+    }
+}
+
+/// Error returned when new positions would pass `i32::MAX`.
+fn position_overflow() -> DomainError {
+    DomainError::InvalidInput("positions would pass the largest allowed position".to_string())
+}
+
+/// Error returned when connecting a block that is already in the channel.
+pub(super) fn already_connected() -> DomainError {
+    DomainError::InvalidInput("block is already connected to this channel".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{FieldUpdate, GalleryItem};
+    use crate::ports::{
+        InMemoryActivityRepository, InMemoryBlockRepository, InMemoryChannelRepository,
+        InMemoryConnectionRepository, TestFixture,
+    };
+    use crate::services::MediaService;
+
+    /// Helper to create a test service with properly synchronized in-memory repositories.
+    fn test_service() -> GardenService<
+        InMemoryChannelRepository,
+        InMemoryBlockRepository,
+        InMemoryConnectionRepository,
+    > {
+        TestFixture::new().service()
+    }
+
+    #[tokio::test]
+    async fn dyn_service_round_trip() {
+        let fixture = TestFixture::new();
+        let service = GardenService::new_dyn(
+            Arc::new(fixture.channel_repo()),
+            Arc::new(fixture.block_repo()),
+            Arc::new(fixture.connection_repo()),
+        );
+
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Runtime backend".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let block = service.create_block(NewBlock::text("Hello")).await.unwrap();
+        service
+            .connect_block(&block.id, &channel.id, None)
+            .await
+            .unwrap();
+
+        let fetched = service.get_channel(&channel.id).await.unwrap();
+        assert_eq!(fetched.title, "Runtime backend");
+        let blocks = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id, block.id);
+    }
+
+    #[tokio::test]
+    async fn activity_feed_lists_changes_newest_first() {
+        let service = test_service().with_activity_log(Arc::new(InMemoryActivityRepository::new()));
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Reading".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let block = service.create_block(NewBlock::text("Note")).await.unwrap();
+        service
+            .connect_block(&block.id, &channel.id, None)
+            .await
+            .unwrap();
+        service
+            .update_channel(
+                &channel.id,
+                ChannelUpdate {
+                    title: Some("Reading list".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        service
+            .disconnect_block(&block.id, &channel.id)
+            .await
+            .unwrap();
+        service.delete_block(&block.id).await.unwrap();
+
+        let feed = service.activity_feed(10, 0).await.unwrap();
+
+        let kinds: Vec<_> = feed.items.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ActivityKind::BlockDeleted,
+                ActivityKind::BlockDisconnected,
+                ActivityKind::ChannelUpdated,
+                ActivityKind::BlockConnected,
+                ActivityKind::BlockCreated,
+                ActivityKind::ChannelCreated,
+            ]
+        );
+        assert_eq!(feed.total, 6);
+        assert!(feed.items.windows(2).all(|w| w[0].at >= w[1].at));
+
+        let deleted = &feed.items[0];
+        assert_eq!(deleted.entity_id, block.id.0);
+        assert_eq!(deleted.block_id.as_ref(), Some(&block.id));
+        assert!(deleted.channel_id.is_none());
+        let connected = &feed.items[3];
+        assert_eq!(connected.entity_id, block.id.0);
+        assert_eq!(connected.channel_id.as_ref(), Some(&channel.id));
+        assert_eq!(connected.block_id.as_ref(), Some(&block.id));
+        let created = &feed.items[5];
+        assert_eq!(created.entity_id, channel.id.0);
+        assert_eq!(created.channel_id.as_ref(), Some(&channel.id));
+        assert!(created.block_id.is_none());
+
+        let page = service.activity_feed(2, 4).await.unwrap();
+        assert_eq!(page.items, feed.items[4..].to_vec());
+        assert!(!page.has_next());
+    }
+
+    #[tokio::test]
+    async fn activity_feed_is_empty_without_a_log() {
+        let service = test_service();
+        service.create_block(NewBlock::text("Note")).await.unwrap();
+
+        let feed = service.activity_feed(10, 0).await.unwrap();
+        assert!(feed.items.is_empty());
+        assert_eq!(feed.total, 0);
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Channel Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn create_channel_success() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test Channel".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(channel.title, "Test Channel");
+        assert!(channel.description.is_none());
+    }
+
+    #[tokio::test]
+    async fn create_channel_with_description() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: Some("A description".to_string()),
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(channel.description, Some("A description".to_string()));
+    }
+
+    #[tokio::test]
+    async fn create_channel_empty_title_fails() {
+        let service = test_service();
+        let result = service
+            .create_channel(NewChannel {
+                title: "   ".to_string(),
+                description: None,
+                body: None,
+            })
+            .await;
+
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn create_channel_with_same_idempotency_key_returns_original() {
+        let service = test_service();
+        let new_channel = NewChannel {
+            title: "Retried".to_string(),
+            description: None,
+            body: None,
+        };
+
+        let first = service
+            .create_channel_idempotent(new_channel.clone(), Some("retry-1".to_string()))
+            .await
+            .unwrap();
+        let second = service
+            .create_channel_idempotent(new_channel, Some("retry-1".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_eq!(service.count_channels().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn create_channel_with_expired_idempotency_key_creates_new() {
+        let service = test_service().with_idempotency_window(Duration::zero());
+        let new_channel = NewChannel {
+            title: "Retried".to_string(),
+            description: None,
+            body: None,
+        };
+
+        let first = service
+            .create_channel_idempotent(new_channel.clone(), Some("retry-1".to_string()))
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let second = service
+            .create_channel_idempotent(new_channel, Some("retry-1".to_string()))
+            .await
+            .unwrap();
+
+        assert_ne!(first.id, second.id);
+        assert_eq!(service.count_channels().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn create_channel_empty_idempotency_key_fails() {
+        let service = test_service();
+        let result = service
+            .create_channel_idempotent(
+                NewChannel {
+                    title: "Retried".to_string(),
+                    description: None,
+                    body: None,
+                },
+                Some(String::new()),
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn get_channel_not_found() {
+        let service = test_service();
+        let result = service.get_channel(&ChannelId::new()).await;
+
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn list_channels_pagination() {
+        let service = test_service();
+
+        // Create 5 channels
+        for i in 0..5 {
+            service
+                .create_channel(NewChannel {
+                    title: format!("Channel {}", i),
+                    description: None,
+                    body: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        // First page
+        let page1 = service.list_channels(2, 0).await.unwrap();
+        assert_eq!(page1.items.len(), 2);
+        assert_eq!(page1.total, 5);
+        assert!(page1.has_next());
+
+        // Second page
+        let page2 = service.list_channels(2, 2).await.unwrap();
+        assert_eq!(page2.items.len(), 2);
+        assert!(page2.has_next());
+
+        // Last page
+        let page3 = service.list_channels(2, 4).await.unwrap();
+        assert_eq!(page3.items.len(), 1);
+        assert!(!page3.has_next());
+    }
+
+    #[tokio::test]
+    async fn update_channel_title() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Original".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_channel(
+                &channel.id,
+                ChannelUpdate {
+                    title: Some("Updated".to_string()),
+                    description: FieldUpdate::Keep,
+                    body: FieldUpdate::Keep,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.title, "Updated");
+    }
+
+    #[tokio::test]
+    async fn update_channel_set_description() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_channel(
+                &channel.id,
+                ChannelUpdate {
+                    title: None,
+                    description: FieldUpdate::Set("New description".to_string()),
+                    body: FieldUpdate::Keep,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.description, Some("New description".to_string()));
+    }
+
+    #[tokio::test]
+    async fn update_channel_clear_description() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: Some("Has description".to_string()),
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_channel(
+                &channel.id,
+                ChannelUpdate {
+                    title: None,
+                    description: FieldUpdate::Clear,
+                    body: FieldUpdate::Keep,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(updated.description.is_none());
+    }
+
+    #[tokio::test]
+    async fn convert_text_block_to_link() {
+        let service = test_service();
+        let block = service
+            .create_block(
+                NewBlock::text(" https://example.com/article \n")
+                    .with_source_url("https://example.com"),
+            )
+            .await
+            .unwrap();
+
+        let converted = service
+            .convert_block(&block.id, ContentKind::Link)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            &converted.content,
+            BlockContent::Link { url, .. } if url == "https://example.com/article"
+        ));
+        assert_eq!(converted.source_url.as_deref(), Some("https://example.com"));
+        assert!(converted.updated_at > block.updated_at);
+
+        let stored = service.get_block(&block.id).await.unwrap();
+        assert_eq!(stored.content.kind(), ContentKind::Link);
+    }
+
+    #[tokio::test]
+    async fn convert_text_block_to_image_rejected() {
+        let service = test_service();
+        let block = service
+            .create_block(NewBlock::text("https://example.com/photo.png"))
+            .await
+            .unwrap();
+
+        let result = service.convert_block(&block.id, ContentKind::Image).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        let not_url = service
+            .create_block(NewBlock::text("just some words"))
+            .await
+            .unwrap();
+        let result = service.convert_block(&not_url.id, ContentKind::Link).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        let stored = service.get_block(&block.id).await.unwrap();
+        assert_eq!(stored.content.kind(), ContentKind::Text);
+    }
+
+    #[tokio::test]
+    async fn update_channel_body_set_keep_clear() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: Some("Summary".to_string()),
+                body: Some("# About".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(channel.body.as_deref(), Some("# About"));
+
+        let update = |body| ChannelUpdate {
+            title: None,
+            description: FieldUpdate::Keep,
+            body,
+        };
+
+        let set = service
+            .update_channel(
+                &channel.id,
+                update(FieldUpdate::Set("## Longer\n\nText".to_string())),
+            )
+            .await
+            .unwrap();
+        assert_eq!(set.body.as_deref(), Some("## Longer\n\nText"));
+
+        let kept = service
+            .update_channel(&channel.id, update(FieldUpdate::Keep))
+            .await
+            .unwrap();
+        assert_eq!(kept.body.as_deref(), Some("## Longer\n\nText"));
+        assert_eq!(kept.description.as_deref(), Some("Summary"));
+
+        let cleared = service
+            .update_channel(&channel.id, update(FieldUpdate::Clear))
+            .await
+            .unwrap();
+        assert!(cleared.body.is_none());
+        assert_eq!(cleared.description.as_deref(), Some("Summary"));
+    }
+
+    #[tokio::test]
+    async fn channel_body_too_long_rejected() {
+        let service = test_service();
+        let too_long = "x".repeat(crate::validation::MAX_CHANNEL_BODY_LEN + 1);
+
+        let result = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: None,
+                body: Some(too_long.clone()),
+            })
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let result = service
+            .update_channel(
+                &channel.id,
+                ChannelUpdate {
+                    title: None,
+                    description: FieldUpdate::Keep,
+                    body: FieldUpdate::Set(too_long),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn delete_channel_success() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        service.delete_channel(&channel.id).await.unwrap();
+
+        let result = service.get_channel(&channel.id).await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn recently_accessed_follows_access_order() {
+        let service = test_service();
+        let mut ids = Vec::new();
+        for title in ["First", "Second", "Never opened"] {
+            let channel = service
+                .create_channel(NewChannel {
+                    title: title.to_string(),
+                    description: None,
+                    body: None,
+                })
+                .await
+                .unwrap();
+            ids.push(channel.id);
+        }
+
+        // Open the older channel last
+        service.mark_channel_accessed(&ids[1]).await.unwrap();
+        service.mark_channel_accessed(&ids[0]).await.unwrap();
+
+        let recent = service.list_recently_accessed(10).await.unwrap();
+        let recent_ids: Vec<_> = recent.iter().map(|c| c.id.clone()).collect();
+        assert_eq!(recent_ids, vec![ids[0].clone(), ids[1].clone()]);
+
+        let limited = service.list_recently_accessed(1).await.unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].id, ids[0]);
+    }
+
+    #[tokio::test]
+    async fn recently_updated_puts_edited_channel_first() {
+        let service = test_service();
+        let mut ids = Vec::new();
+        for title in ["Older", "Newer"] {
+            let channel = service
+                .create_channel(NewChannel {
+                    title: title.to_string(),
+                    description: None,
+                    body: None,
+                })
+                .await
+                .unwrap();
+            ids.push(channel.id);
+        }
+        let before = service.recently_updated_channels(10).await.unwrap();
+        assert_eq!(before[0].id, ids[1]);
+
+        service
+            .update_channel(
+                &ids[0],
+                ChannelUpdate {
+                    title: Some("Older, edited".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let recent = service.recently_updated_channels(10).await.unwrap();
+        let recent_ids: Vec<_> = recent.iter().map(|c| c.id.clone()).collect();
+        assert_eq!(recent_ids, vec![ids[0].clone(), ids[1].clone()]);
+        assert_eq!(service.recently_updated_channels(1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn mark_channel_accessed_keeps_updated_at() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Viewed".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        service.mark_channel_accessed(&channel.id).await.unwrap();
+
+        let fetched = service.get_channel(&channel.id).await.unwrap();
+        assert!(fetched.accessed_at.is_some());
+        assert_eq!(fetched.updated_at, channel.updated_at);
+    }
+
+    #[tokio::test]
+    async fn mark_channel_accessed_not_found() {
+        let service = test_service();
+        let result = service.mark_channel_accessed(&ChannelId::new()).await;
+
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Block Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn create_text_block_success() {
+        let service = test_service();
+        let block = service
+            .create_block(NewBlock::text("Hello, world!"))
+            .await
+            .unwrap();
+
+        match &block.content {
+            BlockContent::Text { body } => assert_eq!(body, "Hello, world!"),
+            _ => panic!("Expected text block"),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_link_block_success() {
+        let service = test_service();
+        let block = service
+            .create_block(NewBlock::link("https://example.com"))
+            .await
+            .unwrap();
+
+        match &block.content {
+            BlockContent::Link { url, .. } => assert_eq!(url, "https://example.com"),
+            _ => panic!("Expected link block"),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_block_empty_text_fails() {
+        let service = test_service();
+        let result = service.create_block(NewBlock::text("   ")).await;
+
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn create_block_invalid_url_fails() {
+        let service = test_service();
+        let result = service.create_block(NewBlock::link("not-a-url")).await;
+
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn create_block_with_same_idempotency_key_returns_original() {
+        let service = test_service();
+
+        let first = service
+            .create_block_idempotent(NewBlock::text("Hello"), Some("retry-1".to_string()))
+            .await
+            .unwrap();
+        let second = service
+            .create_block_idempotent(NewBlock::text("Hello"), Some("retry-1".to_string()))
+            .await
+            .unwrap();
+        let other = service
+            .create_block_idempotent(NewBlock::text("Hello"), Some("retry-2".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_ne!(first.id, other.id);
+    }
+
+    #[tokio::test]
+    async fn create_blocks_batch() {
+        let service = test_service();
+        let blocks = service
+            .create_blocks(vec![
+                NewBlock::text("One"),
+                NewBlock::text("Two"),
+                NewBlock::text("Three"),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(blocks.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn create_blocks_empty_batch() {
+        let service = test_service();
+        let blocks = service.create_blocks(vec![]).await.unwrap();
+
+        assert!(blocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_block_not_found() {
+        let service = test_service();
+        let result = service.get_block(&BlockId::new()).await;
+
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn update_block_content() {
+        let service = test_service();
+        let block = service
+            .create_block(NewBlock::text("Original"))
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_block(
+                &block.id,
+                BlockUpdate {
+                    content: Some(BlockContent::text("Updated")),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        match &updated.content {
+            BlockContent::Text { body } => assert_eq!(body, "Updated"),
+            _ => panic!("Expected text block"),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_block_metadata() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+
+        // Set metadata
+        let updated = service
+            .update_block(
+                &block.id,
+                BlockUpdate {
+                    source_url: Some(FieldUpdate::Set("https://example.com".to_string())),
+                    creator: Some(FieldUpdate::Set("John Doe".to_string())),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.source_url, Some("https://example.com".to_string()));
+        assert_eq!(updated.creator, Some("John Doe".to_string()));
+
+        // Clear one field, keep another (or omit to keep)
+        let updated2 = service
+            .update_block(
+                &block.id,
+                BlockUpdate {
+                    source_url: Some(FieldUpdate::Clear),
+                    // creator is omitted (None), which means keep
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated2.source_url, None);
+        assert_eq!(updated2.creator, Some("John Doe".to_string()));
+    }
+
+    #[tokio::test]
+    async fn update_block_set_notes_sets_and_clears() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+
+        let updated = service
+            .update_block(&block.id, BlockUpdate::set_notes(Some("Keep".to_string())))
+            .await
+            .unwrap();
+        assert_eq!(updated.notes, Some("Keep".to_string()));
+
+        let cleared = service
+            .update_block(&block.id, BlockUpdate::set_notes(None))
+            .await
+            .unwrap();
+        assert_eq!(cleared.notes, None);
+    }
+
+    #[tokio::test]
+    async fn update_block_set_source_url_leaves_other_fields() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+        service
+            .update_block(&block.id, BlockUpdate::set_notes(Some("Keep".to_string())))
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_block(
+                &block.id,
+                BlockUpdate::set_source_url(Some("https://example.com".to_string())),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.source_url, Some("https://example.com".to_string()));
+        assert_eq!(updated.notes, Some("Keep".to_string()));
+
+        let cleared = service
+            .update_block(&block.id, BlockUpdate::set_source_url(None))
+            .await
+            .unwrap();
+        assert_eq!(cleared.source_url, None);
+    }
+
+    #[tokio::test]
+    async fn create_block_unsafe_source_url_fails() {
+        let service = test_service();
+        let result = service
+            .create_block(NewBlock::text("Test").with_source_url("javascript:alert(1)"))
+            .await;
+
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn update_block_unsafe_source_url_fails() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+
+        let result = service
+            .update_block(
+                &block.id,
+                BlockUpdate::set_source_url(Some("data:text/html,hi".to_string())),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        let unchanged = service.get_block(&block.id).await.unwrap();
+        assert_eq!(unchanged.source_url, None);
+    }
+
+    #[tokio::test]
+    async fn update_blocks_metadata_sets_creator_on_each_block() {
+        let service = test_service();
+        let mut ids = Vec::new();
+        for body in ["One", "Two", "Three"] {
+            ids.push(service.create_block(NewBlock::text(body)).await.unwrap().id);
+        }
+
+        let update = BlockUpdate {
+            creator: Some(FieldUpdate::Set("Jane Doe".to_string())),
+            ..Default::default()
+        };
+        let updated = service.update_blocks_metadata(&ids, update).await.unwrap();
+        assert_eq!(updated, 3);
+
+        for (id, body) in ids.iter().zip(["One", "Two", "Three"]) {
+            let block = service.get_block(id).await.unwrap();
+            assert_eq!(block.creator.as_deref(), Some("Jane Doe"));
+            assert!(matches!(&block.content, BlockContent::Text { body: b } if b == body));
+        }
+    }
+
+    #[tokio::test]
+    async fn update_blocks_metadata_rejects_content_and_missing_blocks() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("Keep")).await.unwrap();
+
+        let result = service
+            .update_blocks_metadata(
+                std::slice::from_ref(&block.id),
+                BlockUpdate {
+                    content: Some(BlockContent::text("Clobbered")),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        let result = service
+            .update_blocks_metadata(
+                &[block.id.clone(), BlockId::new()],
+                BlockUpdate::set_notes(Some("Note".to_string())),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+
+        let unchanged = service.get_block(&block.id).await.unwrap();
+        assert!(matches!(&unchanged.content, BlockContent::Text { body } if body == "Keep"));
+        assert_eq!(unchanged.notes, None);
+    }
+
+    #[tokio::test]
+    async fn delete_block_success() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+
+        service.delete_block(&block.id).await.unwrap();
+
+        let result = service.get_block(&block.id).await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn add_tag_to_blocks_skips_already_tagged() {
+        let service = test_service();
+        let mut ids = Vec::new();
+        for text in ["One", "Two", "Three"] {
+            ids.push(service.create_block(NewBlock::text(text)).await.unwrap().id);
+        }
+        assert!(service.add_tag(&ids[0], "reading").await.unwrap());
+
+        let added = service.add_tag_to_blocks(&ids, " Reading ").await.unwrap();
+
+        assert_eq!(added, 2);
+        for id in &ids {
+            assert_eq!(service.get_block_tags(id).await.unwrap(), vec!["reading"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn remove_tag_from_blocks_skips_untagged() {
+        let service = test_service();
+        let a = service.create_block(NewBlock::text("A")).await.unwrap();
+        let b = service.create_block(NewBlock::text("B")).await.unwrap();
+        service.add_tag(&a.id, "later").await.unwrap();
+
+        let removed = service
+            .remove_tag_from_blocks(&[a.id.clone(), b.id.clone()], "LATER")
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(service.get_block_tags(&a.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn add_tag_to_blocks_missing_block_tags_nothing() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("A")).await.unwrap();
+        let missing = BlockId("missing".to_string());
+
+        let result = service
+            .add_tag_to_blocks(&[block.id.clone(), missing], "reading")
+            .await;
+
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+        assert!(service.get_block_tags(&block.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_blocks_with_snippet() {
+        let service = test_service();
+        service
+            .create_block(NewBlock::text("A walled garden of links"))
+            .await
+            .unwrap();
+        service
+            .create_block(NewBlock::text("Something else"))
+            .await
+            .unwrap();
+
+        let page = service
+            .search_blocks("garden", &SearchMode::snippet(), 10, 0, false)
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 1);
+        let snippet = page.items[0].snippet.as_deref().unwrap();
+        assert!(snippet.contains("<mark>garden</mark>"));
+    }
+
+    #[tokio::test]
+    async fn archived_block_is_hidden_but_retrievable() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+        service
+            .connect_block(&block.id, &channel.id, None)
+            .await
+            .unwrap();
+
+        let archived = service.set_block_archived(&block.id, true).await.unwrap();
+        assert!(archived.archived);
+
+        let hits = service
+            .search_blocks("Test", &SearchMode::Plain, 10, 0, false)
+            .await
+            .unwrap();
+        assert_eq!(hits.total, 0);
+        let now = Utc::now();
+        let listed = service
+            .blocks_in_range(now - Duration::days(1), now, 10, 0, false)
+            .await
+            .unwrap();
+        assert_eq!(listed.total, 0);
+        let preview = service.get_channel_preview(&channel.id, 10).await.unwrap();
+        assert_eq!(preview.block_count, 0);
+        let in_channel = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
+        assert!(in_channel.is_empty());
+        let in_channels = service
+            .blocks_in_channels(
+                std::slice::from_ref(&channel.id),
+                ChannelSetMode::Union,
+                10,
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(in_channels.total, 0);
+        assert!(service.popular_blocks(10, false).await.unwrap().is_empty());
+
+        assert!(service.get_block(&block.id).await.unwrap().archived);
+        let in_channel = service
+            .get_blocks_in_channel(&channel.id, true)
+            .await
+            .unwrap();
+        assert_eq!(in_channel.len(), 1);
+        let contexts = service
+            .get_block_contexts(std::slice::from_ref(&block.id))
+            .await
+            .unwrap();
+        assert!(contexts[0].block.archived);
+        let hits = service
+            .search_blocks("Test", &SearchMode::Plain, 10, 0, true)
+            .await
+            .unwrap();
+        assert_eq!(hits.total, 1);
+
+        service.set_block_archived(&block.id, false).await.unwrap();
+        let preview = service.get_channel_preview(&channel.id, 10).await.unwrap();
+        assert_eq!(preview.block_count, 1);
+    }
+
+    #[tokio::test]
+    async fn total_word_count_sums_blocks() {
+        let service = test_service();
+        assert_eq!(service.total_word_count().await.unwrap(), 0);
+
+        service
+            .create_block(NewBlock::text("three little words"))
+            .await
+            .unwrap();
+        service
+            .create_block(NewBlock::new(BlockContent::link_with_meta(
+                "https://example.com",
+                Some("Example".to_string()),
+                None,
+                None,
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(service.total_word_count().await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn metadata_stats_counts_missing_fields() {
+        let service = test_service();
+        let blocks = [
+            NewBlock::text("Bare"),
+            NewBlock {
+                source_url: Some("https://example.com/a".to_string()),
+                creator: Some("Ada".to_string()),
+                ..NewBlock::text("Sourced")
+            },
+            NewBlock {
+                source_url: Some("https://example.com/b".to_string()),
+                original_date: Some("1843".to_string()),
+                notes: Some(String::new()),
+                ..NewBlock::text("Dated")
+            },
+        ];
+        for block in blocks {
+            service.create_block(block).await.unwrap();
+        }
+
+        let stats = service.metadata_stats().await.unwrap();
+
+        assert_eq!(
+            stats,
+            MetadataStats {
+                total: 3,
+                missing_source_url: 1,
+                missing_creator: 2,
+                missing_original_date: 2,
+                missing_notes: 3,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn search_blocks_empty_query_fails() {
+        let service = test_service();
+        let result = service
+            .search_blocks("  ", &SearchMode::Plain, 10, 0, false)
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn blocks_in_range_includes_boundaries() {
+        let fixture = TestFixture::new();
+        let service = fixture.service();
+        let base = Utc::now() - Duration::days(30);
+
+        let blocks: Vec<Block> = (0..5)
+            .map(|day| {
+                let mut block = Block::new(BlockContent::text(format!("Day {}", day)));
+                block.created_at = base + Duration::days(day);
+                block
+            })
+            .collect();
+        fixture.block_repo().create_batch(&blocks).await.unwrap();
+
+        let page = service
+            .blocks_in_range(
+                base + Duration::days(1),
+                base + Duration::days(3),
+                10,
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 3);
+        let ids: Vec<_> = page.items.iter().map(|b| b.id.clone()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                blocks[3].id.clone(),
+                blocks[2].id.clone(),
+                blocks[1].id.clone()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn activity_heatmap_counts_blocks_per_day() {
+        let fixture = TestFixture::new();
+        let service = fixture.service();
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 5, d).unwrap();
+        let at = |d: u32, hour: u32| day(d).and_hms_opt(hour, 0, 0).unwrap().and_utc();
+
+        // Two blocks on the 1st, none on the 2nd, one on the 3rd and 4th
+        let blocks: Vec<Block> = [at(1, 0), at(1, 23), at(3, 12), at(4, 6)]
+            .into_iter()
+            .map(|created_at| {
+                let mut block = Block::new(BlockContent::text("Saved"));
+                block.created_at = created_at;
+                block
+            })
+            .collect();
+        fixture.block_repo().create_batch(&blocks).await.unwrap();
+
+        let counts = service.activity_heatmap(day(1), day(3)).await.unwrap();
+
+        assert_eq!(counts, vec![(day(1), 2), (day(3), 1)]);
+        assert!(matches!(
+            service.activity_heatmap(day(3), day(1)).await,
+            Err(DomainError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn blocks_in_range_rejects_inverted_range() {
+        let service = test_service();
+        let now = Utc::now();
+        let result = service
+            .blocks_in_range(now, now - Duration::days(1), 10, 0, false)
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn merge_duplicate_blocks_keeps_both_channels() {
+        let service = test_service();
+        let first = service
+            .create_channel(NewChannel {
+                title: "First".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let second = service
+            .create_channel(NewChannel {
+                title: "Second".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let keep = service
+            .create_block(NewBlock::text("Same words"))
+            .await
+            .unwrap();
+        let dupe = service
+            .create_block(NewBlock::text("Same words"))
+            .await
+            .unwrap();
+        service
+            .create_block(NewBlock::text("Other words"))
+            .await
+            .unwrap();
+        service
+            .connect_block(&keep.id, &first.id, None)
+            .await
+            .unwrap();
+        service
+            .connect_block(&dupe.id, &second.id, None)
+            .await
+            .unwrap();
+
+        let groups = service.find_duplicate_blocks().await.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 2);
+        assert!(groups[0].contains(&keep.id) && groups[0].contains(&dupe.id));
+
+        let joined = service
+            .merge_duplicate_blocks(&keep.id, std::slice::from_ref(&dupe.id))
+            .await
+            .unwrap();
+        assert_eq!(joined, vec![second.id.clone()]);
+
+        assert!(matches!(
+            service.get_block(&dupe.id).await,
+            Err(DomainError::BlockNotFound(_))
+        ));
+        let channels = service
+            .get_channels_for_block(&keep.id, ChannelOrder::ByTitleAsc)
+            .await
+            .unwrap();
+        let channel_ids: Vec<_> = channels.into_iter().map(|c| c.id).collect();
+        assert_eq!(channel_ids, vec![first.id, second.id]);
+        assert!(service.find_duplicate_blocks().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn merge_duplicate_blocks_rejects_self_merge() {
+        let (service, _, block) = service_with_channel_and_block().await;
+        let result = service
+            .merge_duplicate_blocks(&block.id, std::slice::from_ref(&block.id))
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn blocks_by_duration_filters_on_min() {
+        let fixture = TestFixture::new();
+        let service = fixture.service();
+        let audio = |path: &str, seconds: f32| {
+            Block::new(BlockContent::audio_with_meta(
+                path,
+                "audio/mpeg",
+                None,
+                Some(seconds),
+                None,
+                None,
+            ))
+        };
+        let short = audio("audio/short.mp3", 60.0);
+        let long = audio("audio/long.mp3", 2400.0);
+        let text = Block::new(BlockContent::text("No duration"));
+        fixture
+            .block_repo()
+            .create_batch(&[short, long.clone(), text])
+            .await
+            .unwrap();
+
+        let page = service
+            .blocks_by_duration(Some(1800.0), None, 10, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].id, long.id);
+    }
+
+    #[tokio::test]
+    async fn blocks_by_duration_rejects_inverted_bounds() {
+        let service = test_service();
+        let result = service
+            .blocks_by_duration(Some(600.0), Some(60.0), 10, 0)
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn source_domain_facets_group_by_host() {
+        let service = test_service();
+        let sources = [
+            Some("https://www.nytimes.com/2024/one"),
+            Some("https://nytimes.com/2024/two"),
+            Some("https://youtube.com/watch?v=abc"),
+            None,
+        ];
+        for source in sources {
+            let mut new_block = NewBlock::text("Archived");
+            if let Some(url) = source {
+                new_block = new_block.with_source_url(url);
+            }
+            service.create_block(new_block).await.unwrap();
+        }
+
+        let facets = service.source_domain_facets().await.unwrap();
+        assert_eq!(
+            facets,
+            vec![
+                ("nytimes.com".to_string(), 2),
+                ("youtube.com".to_string(), 1)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn create_remote_image_block() {
+        let service = test_service();
+        let block = service
+            .create_block(NewBlock::new(BlockContent::remote_image(
+                "https://example.com/hotlinked.gif",
+            )))
+            .await
+            .unwrap();
+
+        let fetched = service.get_block(&block.id).await.unwrap();
+        assert!(matches!(
+            fetched.content,
+            BlockContent::RemoteImage { ref url, .. } if url == "https://example.com/hotlinked.gif"
+        ));
+
+        let result = service
+            .create_block(NewBlock::new(BlockContent::remote_image(
+                "ftp://example.com/a.gif",
+            )))
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn shared_media_file_survives_until_last_reference_is_deleted() {
+        let service = test_service();
+        let root = std::env::temp_dir().join(format!("garden-refcount-{}", uuid::Uuid::new_v4()));
+        MediaService::prepare_root(&root).unwrap();
+        let media = MediaService::new(&root);
+        let file_path = "images/shared.jpg";
+        std::fs::write(media.get_full_path(file_path).unwrap(), b"jpeg").unwrap();
+
+        let image = service
+            .create_block(NewBlock::image(file_path, "image/jpeg"))
+            .await
+            .unwrap();
+        let gallery = service
+            .create_block(NewBlock::new(BlockContent::gallery(vec![
+                GalleryItem::new(file_path, "image/jpeg"),
+            ])))
+            .await
+            .unwrap();
+        assert_eq!(service.media_reference_count(file_path).await.unwrap(), 2);
+
+        // Delete each block, removing the file only once nothing uses it
+        for (block, remaining) in [(image, 1), (gallery, 0)] {
+            service.delete_block(&block.id).await.unwrap();
+            let deleted = service
+                .delete_unreferenced_media(file_path, &media)
+                .await
+                .unwrap();
+            assert_eq!(deleted, remaining == 0);
+            assert_eq!(media.exists(file_path).unwrap(), remaining > 0);
+        }
+        // Already gone
+        assert!(service
+            .delete_unreferenced_media(file_path, &media)
+            .await
+            .unwrap());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn verify_media_detects_tampering() {
+        let service = test_service();
+        let root = std::env::temp_dir().join(format!("garden-verify-{}", uuid::Uuid::new_v4()));
+        MediaService::prepare_root(&root).unwrap();
+        let media = MediaService::new(root.join("media"));
+        let source = root.join("photo.png");
+        std::fs::write(&source, b"original bytes").unwrap();
+
+        let info = media.import_from_file(&source).await.unwrap();
+        let full_path = media.get_full_path(&info.file_path).unwrap();
+        let block = service
+            .create_block(NewBlock::new(info.into_block_content()))
+            .await
+            .unwrap();
+        assert!(service.verify_media(&block.id, &media).await.unwrap());
+
+        std::fs::write(&full_path, b"tampered bytes").unwrap();
+        assert!(!service.verify_media(&block.id, &media).await.unwrap());
+
+        let text = service
+            .create_block(NewBlock::text("No media"))
+            .await
+            .unwrap();
+        let result = service.verify_media(&text.id, &media).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn reencode_block_media_moves_block_to_new_file() {
+        let service = test_service();
+        let root = std::env::temp_dir().join(format!("garden-reencode-{}", uuid::Uuid::new_v4()));
+        MediaService::prepare_root(&root).unwrap();
+        let media = MediaService::new(&root);
+        let source = root.join("photo.png");
+        image::RgbImage::new(4, 2).save(&source).unwrap();
+
+        let info = media.import_from_file(&source).await.unwrap();
+        let old_path = info.file_path.clone();
+        let block = service
+            .create_block(NewBlock::new(info.into_block_content()))
+            .await
+            .unwrap();
+
+        let updated = service
+            .reencode_block_media(&block.id, &media, "image/webp")
+            .await
+            .unwrap();
+
+        let BlockContent::Image {
+            file_path,
+            mime_type,
+            width,
+            height,
+            ..
+        } = &updated.content
+        else {
+            panic!("expected image content, got {:?}", updated.content);
+        };
+        assert_eq!(mime_type, "image/webp");
+        assert!(file_path.starts_with("images/") && file_path.ends_with(".webp"));
+        assert_eq!((*width, *height), (Some(4), Some(2)));
+        let stored_block = service.get_block(&block.id).await.unwrap();
+        assert_eq!(stored_block.content.mime_type(), Some("image/webp"));
+        assert_eq!(stored_block.content.file_path(), Some(file_path.as_str()));
+        assert!(!media.exists(&old_path).unwrap());
+        let stored = image::open(media.get_full_path(file_path).unwrap()).unwrap();
+        assert_eq!((stored.width(), stored.height()), (4, 2));
+        assert!(service.verify_media(&block.id, &media).await.unwrap());
+
+        let text = service
+            .create_block(NewBlock::text("No media"))
+            .await
+            .unwrap();
+        let result = service
+            .reencode_block_media(&text.id, &media, "image/webp")
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn validate_channel_media_reports_missing_files() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let channel_id = channel.id;
+        let root = std::env::temp_dir().join(format!("garden-missing-{}", uuid::Uuid::new_v4()));
+        MediaService::prepare_root(&root).unwrap();
+        let media = MediaService::new(&root);
+
+        let mut blocks = Vec::new();
+        for file_path in ["images/kept.jpg", "images/lost.jpg"] {
+            std::fs::write(media.get_full_path(file_path).unwrap(), b"jpeg").unwrap();
+            let block = service
+                .create_block(NewBlock::image(file_path, "image/jpeg"))
+                .await
+                .unwrap();
+            service
+                .connect_block(&block.id, &channel_id, None)
+                .await
+                .unwrap();
+            blocks.push(block);
+        }
+        media.delete("images/lost.jpg").await.unwrap();
+
+        let missing = service
+            .validate_channel_media(&channel_id, &media)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            missing,
+            vec![(blocks[1].id.clone(), "images/lost.jpg".to_string())]
+        );
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Connection Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Helper to create a service with a pre-existing channel and block.
+    async fn service_with_channel_and_block() -> (
+        GardenService<
+            InMemoryChannelRepository,
+            InMemoryBlockRepository,
+            InMemoryConnectionRepository,
+        >,
+        Channel,
+        Block,
+    ) {
+        let fixture = TestFixture::new();
+        let service = fixture.service();
+
+        // Create channel and block through the service
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test Channel".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let block = service
+            .create_block(NewBlock::text("Test Block"))
+            .await
+            .unwrap();
+
+        (service, channel, block)
+    }
+
+    #[tokio::test]
+    async fn connect_block_success() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        let connection = service
+            .connect_block(&block.id, &channel.id, None)
+            .await
+            .unwrap();
+
+        assert_eq!(connection.block_id, block.id);
+        assert_eq!(connection.channel_id, channel.id);
+        assert_eq!(connection.position, 0);
+    }
+
+    #[tokio::test]
+    async fn connect_block_with_position() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        let connection = service
+            .connect_block(&block.id, &channel.id, Some(5))
+            .await
+            .unwrap();
+
+        assert_eq!(connection.position, 5);
+    }
+
+    #[tokio::test]
+    async fn connect_block_nonexistent_block_fails() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let result = service
+            .connect_block(&BlockId::new(), &channel.id, None)
+            .await;
+
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn connect_block_nonexistent_channel_fails() {
+        let service = test_service();
+        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+
+        let result = service
+            .connect_block(&block.id, &ChannelId::new(), None)
+            .await;
+
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn connect_block_already_connected_fails() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        // First connection succeeds
+        service
+            .connect_block(&block.id, &channel.id, None)
+            .await
+            .unwrap();
+
+        // Second connection fails
+        let result = service.connect_block(&block.id, &channel.id, None).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn prepend_block_sorts_before_appended_block() {
+        let (service, channel, appended) = service_with_channel_and_block().await;
+        service
+            .connect_block(&appended.id, &channel.id, None)
+            .await
+            .unwrap();
+        let prepended = service.create_block(NewBlock::text("Top")).await.unwrap();
+
+        let connection = service
+            .prepend_block(&prepended.id, &channel.id)
+            .await
+            .unwrap();
+
+        assert_eq!(connection.position, 0);
+        let order: Vec<(BlockId, i32)> = service
+            .get_blocks_in_channel_with_positions(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(block, pos)| (block.id, pos))
+            .collect();
+        assert_eq!(order, vec![(prepended.id.clone(), 0), (appended.id, 1)]);
+
+        let result = service.prepend_block(&prepended.id, &channel.id).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn ensure_connected_twice_keeps_one_connection_at_latest_position() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        let first = service
+            .ensure_connected(&block.id, &channel.id, 2)
+            .await
+            .unwrap();
+        let second = service
+            .ensure_connected(&block.id, &channel.id, 7)
+            .await
+            .unwrap();
+
+        assert_eq!(first.position, 2);
+        assert_eq!(second.position, 7);
+        assert_eq!(second.connected_at, first.connected_at);
+        let blocks = service
+            .get_blocks_in_channel_with_positions(&channel.id, false)
+            .await
+            .unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].1, 7);
+    }
+
+    #[tokio::test]
+    async fn connect_block_duplicate_from_repo_maps_to_already_connected() {
+        let fixture = TestFixture::new();
+        let service = GardenService::new(
+            fixture.channel_repo(),
+            fixture.block_repo(),
+            fixture.connection_repo().with_stale_lookups(),
+        );
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test Channel".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+
+        // Connect behind the service's back so its pre-check can't see it
+        fixture
+            .connection_repo()
+            .connect(&block.id, &channel.id, 0)
+            .await
+            .unwrap();
+
+        let result = service.connect_block(&block.id, &channel.id, None).await;
+        match result {
+            Err(DomainError::InvalidInput(msg)) => assert!(msg.contains("already connected")),
+            other => panic!("expected already-connected error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn block_counts_by_channel_omits_empty_channels() {
+        let service = test_service();
+        let mut channels = Vec::new();
+        for title in ["One", "Two", "Empty"] {
+            let channel = service
+                .create_channel(NewChannel {
+                    title: title.to_string(),
+                    description: None,
+                    body: None,
+                })
+                .await
+                .unwrap();
+            channels.push(channel);
+        }
+        let a = service.create_block(NewBlock::text("A")).await.unwrap();
+        let b = service.create_block(NewBlock::text("B")).await.unwrap();
+
+        for (block, channel) in [(&a, &channels[0]), (&b, &channels[0]), (&a, &channels[1])] {
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+
+        let counts = service.block_counts_by_channel().await.unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&channels[0].id], 2);
+        assert_eq!(counts[&channels[1].id], 1);
+        assert!(!counts.contains_key(&channels[2].id));
+    }
+
+    #[tokio::test]
+    async fn connect_blocks_empty_batch() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+
+        let connections = service
+            .connect_blocks(&[], &channel.id, None)
+            .await
+            .unwrap();
+
+        assert!(connections.is_empty());
+        let blocks = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
+        assert!(blocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_and_connect_blocks_appends_in_order() {
+        let (service, channel, existing) = service_with_channel_and_block().await;
+        service
+            .connect_block(&existing.id, &channel.id, None)
+            .await
+            .unwrap();
+
+        let mut with_source = NewBlock::text("Two");
+        with_source.source_url = Some("https://example.com/two".to_string());
+        let created = service
+            .create_and_connect_blocks(
+                vec![NewBlock::text("One"), with_source, NewBlock::text("Three")],
+                &channel.id,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(created.len(), 3);
+        let positions: Vec<_> = created.iter().map(|(_, c)| c.position).collect();
+        assert_eq!(positions, vec![1, 2, 3]);
+        assert_eq!(
+            created[1].0.source_url.as_deref(),
+            Some("https://example.com/two")
+        );
+
+        let in_channel = service
+            .get_blocks_in_channel_with_positions(&channel.id, false)
+            .await
+            .unwrap();
+        let ids: Vec<_> = in_channel.iter().map(|(b, _)| b.id.clone()).collect();
+        let mut expected = vec![existing.id];
+        expected.extend(created.iter().map(|(b, _)| b.id.clone()));
+        assert_eq!(ids, expected);
+    }
+
+    #[tokio::test]
+    async fn create_and_connect_blocks_invalid_block_creates_nothing() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+
+        let mut bad = NewBlock::text("Bad");
+        bad.source_url = Some("javascript:alert(1)".to_string());
+        let result = service
+            .create_and_connect_blocks(vec![NewBlock::text("Good"), bad], &channel.id, Some(0))
+            .await;
+
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        let blocks = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
+        assert!(blocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_channel_with_blocks_connects_in_order() {
+        let service = test_service();
+
+        let (channel, blocks) = service
+            .create_channel_with_blocks(
+                NewChannel {
+                    title: "Reading list".to_string(),
+                    description: None,
+                    body: None,
+                },
+                vec![NewBlock::text("One"), NewBlock::text("Two")],
+            )
+            .await
+            .unwrap();
+
+        let order: Vec<(BlockId, i32)> = service
+            .get_blocks_in_channel_with_positions(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(block, pos)| (block.id, pos))
+            .collect();
+        assert_eq!(
+            order,
+            vec![(blocks[0].id.clone(), 0), (blocks[1].id.clone(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn create_channel_with_blocks_invalid_block_creates_nothing() {
+        let service = test_service();
+
+        let mut bad = NewBlock::text("Bad");
+        bad.source_url = Some("javascript:alert(1)".to_string());
+        let result = service
+            .create_channel_with_blocks(
+                NewChannel {
+                    title: "Template".to_string(),
+                    description: None,
+                    body: None,
+                },
+                vec![NewBlock::text("Good"), bad],
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        assert_eq!(service.list_channels(10, 0).await.unwrap().total, 0);
+        assert_eq!(
+            service
+                .list_blocks_with_channels(10, 0, false)
+                .await
+                .unwrap()
+                .total,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn create_and_connect_blocks_missing_channel() {
+        let service = test_service();
+        let result = service
+            .create_and_connect_blocks(
+                vec![NewBlock::text("Orphan")],
+                &ChannelId("missing".to_string()),
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn disconnect_block_success() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        service
+            .connect_block(&block.id, &channel.id, None)
+            .await
+            .unwrap();
+        service
+            .disconnect_block(&block.id, &channel.id)
+            .await
+            .unwrap();
+
+        let result = service.get_connection(&block.id, &channel.id).await;
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+    }
+
+    #[tokio::test]
+    async fn disconnect_nonexistent_connection_fails() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+        let result = service.disconnect_block(&block.id, &channel.id).await;
+
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+    }
+
+    #[tokio::test]
+    async fn disconnect_missing_block_or_channel_reports_which() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        let result = service.disconnect_block(&BlockId::new(), &channel.id).await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+
+        let result = service.disconnect_block(&block.id, &ChannelId::new()).await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn get_blocks_in_channel_missing_vs_empty() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Empty".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let blocks = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
+        assert!(blocks.is_empty());
+
+        let result = service
+            .get_blocks_in_channel(&ChannelId::new(), false)
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+        let result = service
+            .get_blocks_in_channel_with_positions(&ChannelId::new(), false)
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn get_blocks_in_channel() {
+        let service = test_service();
+
+        // Create channel and blocks through the service
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Test Channel".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let block1 = service
+            .create_block(NewBlock::text("Block 1"))
+            .await
+            .unwrap();
+
+        let block2 = service
+            .create_block(NewBlock::text("Block 2"))
+            .await
+            .unwrap();
+
+        // Connect both blocks
+        service
+            .connect_block(&block1.id, &channel.id, Some(0))
+            .await
+            .unwrap();
+        service
+            .connect_block(&block2.id, &channel.id, Some(1))
+            .await
+            .unwrap();
+
+        let blocks = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap();
+        assert_eq!(blocks.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_channel_preview_limits_blocks() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Preview".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..5 {
+            let block = service
+                .create_block(NewBlock::text(format!("Block {}", i)))
+                .await
+                .unwrap();
+            service
+                .connect_block(&block.id, &channel.id, Some(i))
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
+
+        let preview = service.get_channel_preview(&channel.id, 3).await.unwrap();
+        assert_eq!(preview.channel.id, channel.id);
+        assert_eq!(preview.block_count, 5);
+        let preview_ids: Vec<_> = preview
+            .preview_blocks
+            .iter()
+            .map(|b| b.id.clone())
+            .collect();
+        assert_eq!(preview_ids, ids[..3]);
+    }
+
+    #[tokio::test]
+    async fn blocks_in_channels_union_and_intersection() {
+        let service = test_service();
+        let mut channels = Vec::new();
+        for title in ["A", "B"] {
+            channels.push(
+                service
+                    .create_channel(NewChannel {
+                        title: title.to_string(),
+                        description: None,
+                        body: None,
+                    })
+                    .await
+                    .unwrap(),
+            );
+        }
+        let only_a = service
+            .create_block(NewBlock::text("Only A"))
+            .await
+            .unwrap();
+        let both = service.create_block(NewBlock::text("Both")).await.unwrap();
+        let only_b = service
+            .create_block(NewBlock::text("Only B"))
+            .await
+            .unwrap();
+        for (block, channel) in [
+            (&only_a, &channels[0]),
+            (&both, &channels[0]),
+            (&both, &channels[1]),
+            (&only_b, &channels[1]),
+        ] {
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+        let ids = [channels[0].id.clone(), channels[1].id.clone()];
+
+        let union = service
+            .blocks_in_channels(&ids, ChannelSetMode::Union, 10, 0, false)
+            .await
+            .unwrap();
+        let mut union: Vec<BlockId> = union.items.into_iter().map(|b| b.id).collect();
+        union.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut expected = vec![only_a.id, both.id.clone(), only_b.id];
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(union, expected);
+
+        let intersection = service
+            .blocks_in_channels(&ids, ChannelSetMode::Intersection, 10, 0, false)
+            .await
+            .unwrap();
+        assert_eq!(intersection.total, 1);
+        assert_eq!(intersection.items[0].id, both.id);
+
+        let result = service
+            .blocks_in_channels(
+                &[ids[0].clone(), ChannelId::new()],
+                ChannelSetMode::Intersection,
+                10,
+                0,
+                false,
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+
+        let result = service
+            .blocks_in_channels(&[], ChannelSetMode::Union, 10, 0, false)
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn available_blocks_for_channel_skips_connected_blocks() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Picks".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let mut blocks = Vec::new();
+        for body in ["One", "Two", "Three"] {
+            blocks.push(service.create_block(NewBlock::text(body)).await.unwrap());
+        }
+        service
+            .connect_block(&blocks[0].id, &channel.id, None)
+            .await
+            .unwrap();
+
+        let page = service
+            .available_blocks_for_channel(&channel.id, 10, 0, false)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 2);
+        let mut available: Vec<BlockId> = page.items.into_iter().map(|b| b.id).collect();
+        available.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut expected = vec![blocks[1].id.clone(), blocks[2].id.clone()];
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(available, expected);
+
+        let first = service
+            .available_blocks_for_channel(&channel.id, 1, 0, false)
+            .await
+            .unwrap();
+        assert_eq!((first.items.len(), first.total), (1, 2));
+        assert_eq!(first.total_pages(), 2);
+        assert!(first.has_next());
+
+        service
+            .set_block_archived(&blocks[1].id, true)
+            .await
+            .unwrap();
+        let unarchived = service
+            .available_blocks_for_channel(&channel.id, 10, 0, false)
+            .await
+            .unwrap();
+        assert_eq!(unarchived.total, 1);
+        let everything = service
+            .available_blocks_for_channel(&channel.id, 10, 0, true)
+            .await
+            .unwrap();
+        assert_eq!(everything.total, 2);
+
+        let result = service
+            .available_blocks_for_channel(&ChannelId::new(), 10, 0, false)
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn list_blocks_with_channels_carries_memberships() {
+        let service = test_service();
+        let mut channels = Vec::new();
+        for title in ["Beta", "alpha"] {
+            channels.push(
+                service
+                    .create_channel(NewChannel {
+                        title: title.to_string(),
+                        description: None,
+                        body: None,
+                    })
+                    .await
+                    .unwrap(),
+            );
+        }
+        let only_beta = service
+            .create_block(NewBlock::text("Only Beta"))
+            .await
+            .unwrap();
+        let both = service.create_block(NewBlock::text("Both")).await.unwrap();
+        let loose = service.create_block(NewBlock::text("Loose")).await.unwrap();
+        for (block, channel) in [
+            (&only_beta, &channels[0]),
+            (&both, &channels[0]),
+            (&both, &channels[1]),
+        ] {
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+
+        let page = service
+            .list_blocks_with_channels(10, 0, false)
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 3);
+        let memberships: HashMap<BlockId, Vec<String>> = page
+            .items
+            .into_iter()
+            .map(|(block, chs)| (block.id, chs.into_iter().map(|c| c.title).collect()))
+            .collect();
+        assert_eq!(memberships[&only_beta.id], vec!["Beta"]);
+        assert_eq!(memberships[&both.id], vec!["alpha", "Beta"]);
+        assert!(memberships[&loose.id].is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_block_contexts_groups_overlapping_channels() {
+        let service = test_service();
+        let mut channels = Vec::new();
+        for title in ["Red", "Green", "Blue"] {
+            channels.push(
+                service
+                    .create_channel(NewChannel {
+                        title: title.to_string(),
+                        description: None,
+                        body: None,
+                    })
+                    .await
+                    .unwrap(),
+            );
+        }
+        let (red, green, blue) = (&channels[0], &channels[1], &channels[2]);
+        let mut blocks = Vec::new();
+        for body in ["First", "Second", "Third"] {
+            blocks.push(service.create_block(NewBlock::text(body)).await.unwrap());
+        }
+        for (block, channel) in [
+            (&blocks[0], red),
+            (&blocks[0], green),
+            (&blocks[1], green),
+            (&blocks[1], blue),
+            (&blocks[2], red),
+        ] {
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+
+        let ids: Vec<BlockId> = [&blocks[2], &blocks[0], &blocks[1]]
+            .iter()
+            .map(|b| b.id.clone())
+            .collect();
+        let contexts = service.get_block_contexts(&ids).await.unwrap();
+
+        let listed: Vec<(BlockId, Vec<String>)> = contexts
+            .into_iter()
+            .map(|ctx| {
+                let titles = ctx.channels.into_iter().map(|c| c.title).collect();
+                (ctx.block.id, titles)
+            })
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                (blocks[2].id.clone(), vec!["Red".to_string()]),
+                (
+                    blocks[0].id.clone(),
+                    vec!["Green".to_string(), "Red".to_string()]
+                ),
+                (
+                    blocks[1].id.clone(),
+                    vec!["Blue".to_string(), "Green".to_string()]
+                ),
+            ]
+        );
+
+        let missing = BlockId::from_string("missing");
+        let result = service
+            .get_block_contexts(&[blocks[0].id.clone(), missing.clone()])
+            .await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(id)) if id == missing));
+    }
+
+    #[tokio::test]
+    async fn channel_composition_counts_blocks_by_type() {
+        let fixture = TestFixture::new();
+        let service = fixture.service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Mixed".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let blocks = vec![
+            Block::new(BlockContent::image("images/a.png", "image/png")),
+            Block::new(BlockContent::image("images/b.jpg", "image/jpeg")),
+            Block::new(BlockContent::video("videos/c.mp4", "video/mp4")),
+            Block::new(BlockContent::link("https://example.com/1")),
+            Block::new(BlockContent::link("https://example.com/2")),
+            Block::new(BlockContent::link("https://example.com/3")),
+        ];
+        fixture.block_repo().create_batch(&blocks).await.unwrap();
+        for block in &blocks {
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+        // A block in another channel doesn't count
+        let other = service
+            .create_channel(NewChannel {
+                title: "Other".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let text = service
+            .create_block(NewBlock::text("Elsewhere"))
+            .await
+            .unwrap();
+        service
+            .connect_block(&text.id, &other.id, None)
+            .await
+            .unwrap();
+
+        let composition = service.channel_composition(&channel.id).await.unwrap();
+
+        assert_eq!(composition.len(), 3);
+        assert_eq!(composition["image"], 2);
+        assert_eq!(composition["video"], 1);
+        assert_eq!(composition["link"], 3);
+        assert!(!composition.contains_key("text"));
+
+        let result = service.channel_composition(&ChannelId::new()).await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn channel_block_sharing_counts_exclusive_and_shared() {
+        let (service, channel, exclusive) = service_with_channel_and_block().await;
+        let other = service
+            .create_channel(NewChannel {
+                title: "Other".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let shared = service
+            .create_block(NewBlock::text("Shared"))
+            .await
+            .unwrap();
+        for (block, channel) in [
+            (&exclusive, &channel),
+            (&shared, &channel),
+            (&shared, &other),
+        ] {
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+
+        let stats = service.channel_block_sharing(&channel.id).await.unwrap();
+        assert_eq!(
+            stats,
+            SharingStats {
+                total: 2,
+                exclusive: 1,
+                shared: 1,
+            }
+        );
+
+        let result = service.channel_block_sharing(&ChannelId::new()).await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn popular_blocks_ranks_by_channel_count() {
+        let (service, channel, single) = service_with_channel_and_block().await;
+        let popular = service
+            .create_block(NewBlock::text("Popular"))
+            .await
+            .unwrap();
+        service
+            .connect_block(&single.id, &channel.id, None)
+            .await
+            .unwrap();
+        for title in ["A", "B", "C"] {
+            let other = service
+                .create_channel(NewChannel {
+                    title: title.to_string(),
+                    description: None,
+                    body: None,
+                })
+                .await
+                .unwrap();
+            service
+                .connect_block(&popular.id, &other.id, None)
+                .await
+                .unwrap();
+        }
+
+        let ranked = service.popular_blocks(10, false).await.unwrap();
+
+        let ranked: Vec<_> = ranked.iter().map(|(b, n)| (b.id.clone(), *n)).collect();
+        assert_eq!(ranked, vec![(popular.id, 3), (single.id, 1)]);
+    }
+
+    #[tokio::test]
+    async fn get_channel_preview_not_found() {
+        let service = test_service();
+        let result = service.get_channel_preview(&ChannelId::new(), 3).await;
+
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn block_neighbors_by_position() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Neighbors".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let block = service
+                .create_block(NewBlock::text(format!("Block {}", i)))
+                .await
+                .unwrap();
+            service
+                .connect_block(&block.id, &channel.id, Some(i * 10))
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
+
+        let middle = service.block_neighbors(&channel.id, &ids[1]).await.unwrap();
+        assert_eq!(middle.previous.map(|b| b.id), Some(ids[0].clone()));
+        assert_eq!(middle.next.map(|b| b.id), Some(ids[2].clone()));
+
+        let first = service.block_neighbors(&channel.id, &ids[0]).await.unwrap();
+        assert!(first.previous.is_none());
+        assert_eq!(first.next.map(|b| b.id), Some(ids[1].clone()));
+
+        let last = service.block_neighbors(&channel.id, &ids[2]).await.unwrap();
+        assert_eq!(last.previous.map(|b| b.id), Some(ids[1].clone()));
+        assert!(last.next.is_none());
+    }
+
+    #[tokio::test]
+    async fn block_neighbors_not_in_channel() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Neighbors".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let block = service.create_block(NewBlock::text("Loose")).await.unwrap();
+
+        let result = service.block_neighbors(&channel.id, &block.id).await;
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+    }
+
+    #[tokio::test]
+    async fn block_position_info_counts_by_position() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Viewer".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let block = service
+                .create_block(NewBlock::text(format!("Block {}", i)))
+                .await
+                .unwrap();
+            service
+                .connect_block(&block.id, &channel.id, Some(i * 10))
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
+
+        let middle = service
+            .block_position_info(&channel.id, &ids[1])
+            .await
+            .unwrap();
+        assert_eq!(middle, BlockPositionInfo { index: 1, total: 3 });
+
+        let loose = service.create_block(NewBlock::text("Loose")).await.unwrap();
+        let result = service.block_position_info(&channel.id, &loose.id).await;
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+    }
+
+    #[tokio::test]
+    async fn get_channels_for_block() {
+        let service = test_service();
+
+        // Create channels and block through the service
+        let channel1 = service
+            .create_channel(NewChannel {
+                title: "Channel 1".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let channel2 = service
+            .create_channel(NewChannel {
+                title: "Channel 2".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+
+        let block = service
+            .create_block(NewBlock::text("Test Block"))
+            .await
+            .unwrap();
+
+        // Connect block to both channels
+        service
+            .connect_block(&block.id, &channel1.id, None)
+            .await
+            .unwrap();
+        service
+            .connect_block(&block.id, &channel2.id, None)
+            .await
+            .unwrap();
+
+        let channels = service
+            .get_channels_for_block(&block.id, ChannelOrder::default())
+            .await
+            .unwrap();
+        assert_eq!(channels.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_channels_for_block_orderings() {
+        let service = test_service();
+        let block = service
+            .create_block(NewBlock::text("Shared"))
+            .await
+            .unwrap();
+        for title in ["Apple", "Zebra"] {
+            let channel = service
+                .create_channel(NewChannel {
+                    title: title.to_string(),
+                    description: None,
+                    body: None,
+                })
+                .await
+                .unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+            // Keep connection timestamps distinct
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        }
+
+        let titles = |channels: Vec<Channel>| -> Vec<String> {
+            channels.into_iter().map(|c| c.title).collect()
+        };
+        let recent = service
+            .get_channels_for_block(&block.id, ChannelOrder::ByConnectedAtDesc)
+            .await
+            .unwrap();
+        assert_eq!(titles(recent), vec!["Zebra", "Apple"]);
+        let alphabetical = service
+            .get_channels_for_block(&block.id, ChannelOrder::ByTitleAsc)
+            .await
+            .unwrap();
+        assert_eq!(titles(alphabetical), vec!["Apple", "Zebra"]);
+    }
+
+    #[tokio::test]
+    async fn get_channels_for_block_paged_slices() {
+        let service = test_service();
+        let block = service
+            .create_block(NewBlock::text("Popular"))
+            .await
+            .unwrap();
+
+        for i in 0..7 {
+            let channel = service
+                .create_channel(NewChannel {
+                    title: format!("Channel {}", i),
+                    description: None,
+                    body: None,
+                })
+                .await
+                .unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+
+        let all = service
+            .get_channels_for_block_paged(&block.id, 100, 0)
+            .await
+            .unwrap();
+        assert_eq!(all.total, 7);
+        assert_eq!(all.items.len(), 7);
+
+        let page = service
+            .get_channels_for_block_paged(&block.id, 3, 3)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 7);
+        let page_ids: Vec<_> = page.items.iter().map(|c| c.id.clone()).collect();
+        let expected: Vec<_> = all.items[3..6].iter().map(|c| c.id.clone()).collect();
+        assert_eq!(page_ids, expected);
+
+        let tail = service
+            .get_channels_for_block_paged(&block.id, 3, 6)
+            .await
+            .unwrap();
+        assert_eq!(tail.items.len(), 1);
+        assert!(!tail.has_next());
+    }
+
+    #[tokio::test]
+    async fn reorder_block_success() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        service
+            .connect_block(&block.id, &channel.id, Some(0))
+            .await
+            .unwrap();
+        service
+            .reorder_block(&channel.id, &block.id, 10)
+            .await
+            .unwrap();
+
+        let connection = service
+            .get_connection(&block.id, &channel.id)
+            .await
+            .unwrap();
+        assert_eq!(connection.position, 10);
+    }
+
+    #[tokio::test]
+    async fn recent_in_channel_orders_by_connection_time() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let mut ids = Vec::new();
+        for body in ["First", "Second", "Third"] {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            ids.push(block.id);
+        }
+
+        // Connect in order, but put the last one at the top of the channel
+        for (id, position) in [(&ids[0], None), (&ids[1], None), (&ids[2], Some(0))] {
+            service
+                .connect_block(id, &channel.id, position)
+                .await
+                .unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        }
+
+        let recent = service
+            .recent_in_channel(&channel.id, 2, false)
+            .await
+            .unwrap();
+        let recent_ids: Vec<_> = recent.iter().map(|(b, _)| b.id.clone()).collect();
+        assert_eq!(recent_ids, vec![ids[2].clone(), ids[1].clone()]);
+        assert_eq!(recent[0].1.position, 0);
+
+        let result = service
+            .recent_in_channel(&ChannelId("missing".to_string()), 2, false)
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn move_block_relative_before_first() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let mut ids = Vec::new();
+        for body in ["A", "B", "C"] {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
+
+        service
+            .move_block_relative(&channel.id, &ids[2], &ids[0], Placement::Before)
+            .await
+            .unwrap();
+
+        let order: Vec<_> = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.id)
+            .collect();
+        assert_eq!(order, vec![ids[2].clone(), ids[0].clone(), ids[1].clone()]);
+    }
+
+    #[tokio::test]
+    async fn swap_blocks_exchanges_positions() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let mut ids = Vec::new();
+        for (position, body) in ["A", "B"].into_iter().enumerate() {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            service
+                .connect_block(&block.id, &channel.id, Some(position as i32))
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
+
+        service
+            .swap_blocks(&channel.id, &ids[0], &ids[1])
+            .await
+            .unwrap();
+
+        for (id, position) in [(&ids[0], 1), (&ids[1], 0)] {
+            let connection = service.get_connection(id, &channel.id).await.unwrap();
+            assert_eq!(connection.position, position);
+        }
+
+        let result = service
+            .swap_blocks(&channel.id, &ids[0], &BlockId::new())
+            .await;
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(..))));
+    }
+
+    #[tokio::test]
+    async fn move_block_group_to_front() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let mut ids = Vec::new();
+        for body in ["A", "B", "C", "D", "E"] {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
+
+        service
+            .move_block_group(&channel.id, &[ids[3].clone(), ids[1].clone()], 0)
+            .await
+            .unwrap();
+
+        let order: Vec<_> = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.id)
+            .collect();
+        let expected: Vec<_> = [1, 3, 0, 2, 4].iter().map(|&i| ids[i].clone()).collect();
+        assert_eq!(order, expected);
+    }
+
+    #[tokio::test]
+    async fn sort_channel_by_title_ascending() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        for body in ["cherry", "Apple", "banana"] {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+
+        service
+            .sort_channel(&channel.id, BlockSortKey::Title, true)
+            .await
+            .unwrap();
+
+        let titles: Vec<_> = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap()
+            .iter()
+            .map(|b| b.display_title().to_string())
+            .collect();
+        assert_eq!(titles, vec!["Apple", "banana", "cherry"]);
+
+        let result = service
+            .sort_channel(&ChannelId("missing".to_string()), BlockSortKey::Title, true)
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn restore_channel_order_undoes_sort() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let mut ids = Vec::new();
+        for body in ["cherry", "Apple", "banana", "date"] {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
+        let snapshot = service.snapshot_channel_order(&channel.id).await.unwrap();
+        assert_eq!(snapshot.positions.len(), 4);
+
+        service
+            .sort_channel(&channel.id, BlockSortKey::Title, true)
+            .await
+            .unwrap();
+        service
+            .disconnect_block(&ids[3], &channel.id)
+            .await
+            .unwrap();
+        service
+            .restore_channel_order(&channel.id, &snapshot)
+            .await
+            .unwrap();
+
+        let order: Vec<_> = service
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.id)
+            .collect();
+        assert_eq!(order, ids[..3]);
     }
 
     #[tokio::test]
-    async fn list_channels_pagination() {
-        let service = test_service();
+    async fn restore_channel_order_rejects_other_channel() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let mut snapshot = service.snapshot_channel_order(&channel.id).await.unwrap();
+        snapshot.channel_id = ChannelId("elsewhere".to_string());
 
-        // Create 5 channels
-        for i in 0..5 {
+        let result = service.restore_channel_order(&channel.id, &snapshot).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn repair_channel_positions_breaks_ties_by_connected_at() {
+        let (service, channel, _) = service_with_channel_and_block().await;
+        let mut ids = Vec::new();
+        for body in ["First", "Second", "Third"] {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            ids.push(block.id);
+        }
+        // Two blocks at position 0, as left behind by legacy data
+        for (id, position) in [(&ids[2], 5), (&ids[0], 0), (&ids[1], 0)] {
             service
-                .create_channel(NewChannel {
-                    title: format!("Channel {}", i),
-                    description: None,
-                })
+                .connect_block(id, &channel.id, Some(position))
                 .await
                 .unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
         }
 
-        // First page
-        let page1 = service.list_channels(2, 0).await.unwrap();
-        assert_eq!(page1.items.len(), 2);
-        assert_eq!(page1.total, 5);
-        assert!(page1.has_next());
-
-        // Second page
-        let page2 = service.list_channels(2, 2).await.unwrap();
-        assert_eq!(page2.items.len(), 2);
-        assert!(page2.has_next());
+        assert!(service.repair_channel_positions(&channel.id).await.unwrap());
 
-        // Last page
-        let page3 = service.list_channels(2, 4).await.unwrap();
-        assert_eq!(page3.items.len(), 1);
-        assert!(!page3.has_next());
+        let order: Vec<(BlockId, i32)> = service
+            .get_blocks_in_channel_with_positions(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(b, pos)| (b.id, pos))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (ids[0].clone(), 0),
+                (ids[1].clone(), 1),
+                (ids[2].clone(), 2)
+            ]
+        );
+        assert!(!service.repair_channel_positions(&channel.id).await.unwrap());
+        assert!(service.repair_all_positions().await.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn update_channel_title() {
-        let service = test_service();
-        let channel = service
+    async fn position_anomalies_reports_issue_kinds() {
+        let (service, negative, _) = service_with_channel_and_block().await;
+        let duplicate = service
             .create_channel(NewChannel {
-                title: "Original".to_string(),
+                title: "Duplicates".to_string(),
                 description: None,
+                body: None,
             })
             .await
             .unwrap();
-
-        let updated = service
-            .update_channel(
-                &channel.id,
-                ChannelUpdate {
-                    title: Some("Updated".to_string()),
-                    description: FieldUpdate::Keep,
-                },
-            )
+        let mut ids = Vec::new();
+        for body in ["A", "B", "C"] {
+            ids.push(service.create_block(NewBlock::text(body)).await.unwrap().id);
+        }
+        for (id, channel, position) in [
+            (&ids[0], &negative, 0),
+            (&ids[1], &negative, 1),
+            (&ids[0], &duplicate, 0),
+            (&ids[1], &duplicate, 0),
+            (&ids[2], &duplicate, 1),
+        ] {
+            service
+                .connect_block(id, &channel.id, Some(position))
+                .await
+                .unwrap();
+        }
+        // Validation rejects negative positions, so inject one directly
+        service
+            .connections
+            .reorder(&negative.id, &ids[1], -1)
             .await
             .unwrap();
 
-        assert_eq!(updated.title, "Updated");
+        let anomalies = service.position_anomalies().await.unwrap();
+
+        assert_eq!(anomalies.len(), 2);
+        assert!(anomalies.contains(&(negative.id.clone(), PositionIssue::Negative)));
+        assert!(anomalies.contains(&(duplicate.id.clone(), PositionIssue::Duplicate)));
+
+        for channel in [&negative, &duplicate] {
+            assert!(service.repair_channel_positions(&channel.id).await.unwrap());
+        }
+        assert!(service.position_anomalies().await.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn update_channel_set_description() {
-        let service = test_service();
+    async fn position_anomalies_allow_the_position_gap() {
+        let service = test_service().with_position_gap(100);
         let channel = service
             .create_channel(NewChannel {
-                title: "Test".to_string(),
+                title: "Gapped".to_string(),
                 description: None,
+                body: None,
             })
             .await
             .unwrap();
+        let mut ids = Vec::new();
+        for body in ["A", "B", "C"] {
+            let block = service.create_block(NewBlock::text(body)).await.unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
 
-        let updated = service
-            .update_channel(
-                &channel.id,
-                ChannelUpdate {
-                    title: None,
-                    description: FieldUpdate::Set("New description".to_string()),
-                },
-            )
+        // Appends at 0, 100 and 200 are how the channel should look
+        assert!(service.position_anomalies().await.unwrap().is_empty());
+        assert!(!service.repair_channel_positions(&channel.id).await.unwrap());
+
+        // A step wider than the gap is still reported and repaired
+        service
+            .connections
+            .reorder(&channel.id, &ids[2], 201)
             .await
             .unwrap();
-
-        assert_eq!(updated.description, Some("New description".to_string()));
+        assert_eq!(
+            service.position_anomalies().await.unwrap(),
+            vec![(channel.id.clone(), PositionIssue::Gap)]
+        );
+        assert!(service.repair_channel_positions(&channel.id).await.unwrap());
+        assert!(service.position_anomalies().await.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn update_channel_clear_description() {
+    async fn channels_with_content_type_filters_by_block_kind() {
         let service = test_service();
-        let channel = service
+        let videos = service
             .create_channel(NewChannel {
-                title: "Test".to_string(),
-                description: Some("Has description".to_string()),
+                title: "Videos".to_string(),
+                description: None,
+                body: None,
             })
             .await
             .unwrap();
-
-        let updated = service
-            .update_channel(
-                &channel.id,
-                ChannelUpdate {
-                    title: None,
-                    description: FieldUpdate::Clear,
-                },
-            )
-            .await
-            .unwrap();
-
-        assert!(updated.description.is_none());
-    }
-
-    #[tokio::test]
-    async fn delete_channel_success() {
-        let service = test_service();
-        let channel = service
+        let notes = service
             .create_channel(NewChannel {
-                title: "Test".to_string(),
+                title: "Notes".to_string(),
                 description: None,
+                body: None,
             })
             .await
             .unwrap();
-
-        service.delete_channel(&channel.id).await.unwrap();
-
-        let result = service.get_channel(&channel.id).await;
-        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
-    }
-
-    // ─────────────────────────────────────────────────────────────────────────
-    // Block Tests
-    // ─────────────────────────────────────────────────────────────────────────
-
-    #[tokio::test]
-    async fn create_text_block_success() {
-        let service = test_service();
-        let block = service
-            .create_block(NewBlock::text("Hello, world!"))
+        let video = service
+            .create_block(NewBlock::new(BlockContent::video(
+                "videos/clip.mp4",
+                "video/mp4",
+            )))
+            .await
+            .unwrap();
+        let text = service.create_block(NewBlock::text("Note")).await.unwrap();
+        service
+            .connect_blocks(&[video.id, text.id.clone()], &videos.id, None)
+            .await
+            .unwrap();
+        service
+            .connect_block(&text.id, &notes.id, None)
             .await
             .unwrap();
 
-        match &block.content {
-            BlockContent::Text { body } => assert_eq!(body, "Hello, world!"),
-            _ => panic!("Expected text block"),
-        }
+        let page = service
+            .channels_with_content_type(ContentKind::Video, 10, 0)
+            .await
+            .unwrap();
+        let ids: Vec<_> = page.items.iter().map(|c| c.id.clone()).collect();
+        assert_eq!(ids, vec![videos.id]);
+        assert_eq!(page.total, 1);
     }
 
     #[tokio::test]
-    async fn create_link_block_success() {
-        let service = test_service();
-        let block = service
-            .create_block(NewBlock::link("https://example.com"))
+    async fn move_block_relative_requires_anchor_in_channel() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+        service
+            .connect_block(&block.id, &channel.id, None)
+            .await
+            .unwrap();
+        let outside = service
+            .create_block(NewBlock::text("Elsewhere"))
             .await
             .unwrap();
 
-        match &block.content {
-            BlockContent::Link { url, .. } => assert_eq!(url, "https://example.com"),
-            _ => panic!("Expected link block"),
-        }
-    }
-
-    #[tokio::test]
-    async fn create_block_empty_text_fails() {
-        let service = test_service();
-        let result = service.create_block(NewBlock::text("   ")).await;
+        let result = service
+            .move_block_relative(&channel.id, &block.id, &outside.id, Placement::After)
+            .await;
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
 
+        let result = service
+            .move_block_relative(&channel.id, &block.id, &block.id, Placement::After)
+            .await;
         assert!(matches!(result, Err(DomainError::InvalidInput(_))));
     }
 
     #[tokio::test]
-    async fn create_block_invalid_url_fails() {
-        let service = test_service();
-        let result = service.create_block(NewBlock::link("not-a-url")).await;
+    async fn connect_block_negative_position_fails() {
+        let (service, channel, block) = service_with_channel_and_block().await;
 
+        let result = service
+            .connect_block(&block.id, &channel.id, Some(-1))
+            .await;
         assert!(matches!(result, Err(DomainError::InvalidInput(_))));
-    }
-
-    #[tokio::test]
-    async fn create_blocks_batch() {
-        let service = test_service();
-        let blocks = service
-            .create_blocks(vec![
-                NewBlock::text("One"),
-                NewBlock::text("Two"),
-                NewBlock::text("Three"),
-            ])
-            .await
-            .unwrap();
-
-        assert_eq!(blocks.len(), 3);
-    }
-
-    #[tokio::test]
-    async fn get_block_not_found() {
-        let service = test_service();
-        let result = service.get_block(&BlockId::new()).await;
 
-        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+        // Nothing was connected
+        let result = service.get_connection(&block.id, &channel.id).await;
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
     }
 
     #[tokio::test]
-    async fn update_block_content() {
-        let service = test_service();
-        let block = service
-            .create_block(NewBlock::text("Original"))
-            .await
-            .unwrap();
+    async fn connect_block_large_position_succeeds() {
+        let (service, channel, block) = service_with_channel_and_block().await;
 
-        let updated = service
-            .update_block(
-                &block.id,
-                BlockUpdate {
-                    content: Some(BlockContent::text("Updated")),
-                    ..Default::default()
-                },
-            )
+        let connection = service
+            .connect_block(&block.id, &channel.id, Some(10_000))
             .await
             .unwrap();
-
-        match &updated.content {
-            BlockContent::Text { body } => assert_eq!(body, "Updated"),
-            _ => panic!("Expected text block"),
-        }
+        assert_eq!(connection.position, 10_000);
     }
 
     #[tokio::test]
-    async fn update_block_metadata() {
-        let service = test_service();
-        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+    async fn position_gap_overflow_is_rejected() {
+        let service = test_service().with_position_gap(i32::MAX / 2 + 1);
 
-        // Set metadata
-        let updated = service
-            .update_block(
-                &block.id,
-                BlockUpdate {
-                    source_url: Some(FieldUpdate::Set("https://example.com".to_string())),
-                    creator: Some(FieldUpdate::Set("John Doe".to_string())),
-                    ..Default::default()
+        // The third block would land past i32::MAX
+        let result = service
+            .create_channel_with_blocks(
+                NewChannel {
+                    title: "Too wide".to_string(),
+                    description: None,
+                    body: None,
                 },
+                vec![
+                    NewBlock::text("One"),
+                    NewBlock::text("Two"),
+                    NewBlock::text("Three"),
+                ],
             )
-            .await
-            .unwrap();
-
-        assert_eq!(updated.source_url, Some("https://example.com".to_string()));
-        assert_eq!(updated.creator, Some("John Doe".to_string()));
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        assert_eq!(service.count_channels().await.unwrap(), 0);
 
-        // Clear one field, keep another (or omit to keep)
-        let updated2 = service
-            .update_block(
-                &block.id,
-                BlockUpdate {
-                    source_url: Some(FieldUpdate::Clear),
-                    // creator is omitted (None), which means keep
-                    ..Default::default()
-                },
-            )
+        // Appending after a block at the ceiling fails instead of colliding
+        let (service, channel, block) = service_with_channel_and_block().await;
+        service
+            .connect_block(&block.id, &channel.id, Some(i32::MAX))
             .await
             .unwrap();
-
-        assert_eq!(updated2.source_url, None);
-        assert_eq!(updated2.creator, Some("John Doe".to_string()));
+        let next = service.create_block(NewBlock::text("Next")).await.unwrap();
+        let result = service.connect_block(&next.id, &channel.id, None).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        let result = service
+            .create_and_connect_blocks(
+                vec![NewBlock::text("More"), NewBlock::text("Even more")],
+                &channel.id,
+                Some(i32::MAX),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
     }
 
     #[tokio::test]
-    async fn delete_block_success() {
-        let service = test_service();
-        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
-
-        service.delete_block(&block.id).await.unwrap();
+    async fn position_gap_spaces_appends_and_leaves_room() {
+        let service = test_service().with_position_gap(100);
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Gapped".to_string(),
+                description: None,
+                body: None,
+            })
+            .await
+            .unwrap();
+        let mut blocks = Vec::new();
+        for i in 0..4 {
+            blocks.push(
+                service
+                    .create_block(NewBlock::text(format!("Block {}", i)))
+                    .await
+                    .unwrap(),
+            );
+        }
 
-        let result = service.get_block(&block.id).await;
-        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
-    }
+        let first = service
+            .connect_block(&blocks[0].id, &channel.id, None)
+            .await
+            .unwrap();
+        let second = service
+            .connect_block(&blocks[1].id, &channel.id, None)
+            .await
+            .unwrap();
+        assert_eq!((first.position, second.position), (0, 100));
 
-    // ─────────────────────────────────────────────────────────────────────────
-    // Connection Tests
-    // ─────────────────────────────────────────────────────────────────────────
+        let batch = service
+            .connect_blocks(&[blocks[2].id.clone()], &channel.id, None)
+            .await
+            .unwrap();
+        assert_eq!(batch[0].position, 200);
 
-    /// Helper to create a service with a pre-existing channel and block.
-    async fn service_with_channel_and_block() -> (
-        GardenService<
-            InMemoryChannelRepository,
-            InMemoryBlockRepository,
-            InMemoryConnectionRepository,
-        >,
-        Channel,
-        Block,
-    ) {
-        let fixture = TestFixture::new();
-        let service = fixture.service();
+        // Insert between the first two without touching their positions
+        let between = crate::models::position_between(first.position, second.position).unwrap();
+        service
+            .connect_block(&blocks[3].id, &channel.id, Some(between))
+            .await
+            .unwrap();
+        let order: Vec<(BlockId, i32)> = service
+            .get_blocks_in_channel_with_positions(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(block, position)| (block.id, position))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (blocks[0].id.clone(), 0),
+                (blocks[3].id.clone(), 50),
+                (blocks[1].id.clone(), 100),
+                (blocks[2].id.clone(), 200),
+            ]
+        );
+        assert_eq!(crate::models::position_between(50, 51), None);
+    }
 
-        // Create channel and block through the service
+    #[tokio::test]
+    async fn position_gap_survives_reordering() {
+        let service = test_service().with_position_gap(100);
         let channel = service
             .create_channel(NewChannel {
-                title: "Test Channel".to_string(),
+                title: "Gapped".to_string(),
                 description: None,
+                body: None,
             })
             .await
             .unwrap();
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let block = service
+                .create_block(NewBlock::text(format!("Block {}", i)))
+                .await
+                .unwrap();
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+            ids.push(block.id);
+        }
 
-        let block = service
-            .create_block(NewBlock::text("Test Block"))
+        service
+            .move_block_relative(&channel.id, &ids[2], &ids[0], Placement::Before)
             .await
             .unwrap();
 
-        (service, channel, block)
+        let order: Vec<(BlockId, i32)> = service
+            .get_blocks_in_channel_with_positions(&channel.id, false)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(block, position)| (block.id, position))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (ids[2].clone(), 0),
+                (ids[0].clone(), 100),
+                (ids[1].clone(), 200),
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn connect_block_success() {
+    async fn reorder_block_negative_position_fails() {
         let (service, channel, block) = service_with_channel_and_block().await;
 
-        let connection = service
-            .connect_block(&block.id, &channel.id, None)
+        service
+            .connect_block(&block.id, &channel.id, Some(3))
             .await
             .unwrap();
 
-        assert_eq!(connection.block_id, block.id);
-        assert_eq!(connection.channel_id, channel.id);
+        let result = service.reorder_block(&channel.id, &block.id, -5).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+
+        // Zero is a valid position
+        service
+            .reorder_block(&channel.id, &block.id, 0)
+            .await
+            .unwrap();
+        let connection = service
+            .get_connection(&block.id, &channel.id)
+            .await
+            .unwrap();
         assert_eq!(connection.position, 0);
     }
 
     #[tokio::test]
-    async fn connect_block_with_position() {
+    async fn reorder_nonexistent_connection_fails() {
         let (service, channel, block) = service_with_channel_and_block().await;
+        let result = service.reorder_block(&channel.id, &block.id, 5).await;
 
-        let connection = service
-            .connect_block(&block.id, &channel.id, Some(5))
-            .await
-            .unwrap();
+        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+    }
 
-        assert_eq!(connection.position, 5);
+    #[tokio::test]
+    async fn reorder_missing_block_or_channel_reports_which() {
+        let (service, channel, block) = service_with_channel_and_block().await;
+
+        let result = service.reorder_block(&channel.id, &BlockId::new(), 5).await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+
+        let result = service.reorder_block(&ChannelId::new(), &block.id, 5).await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
     }
 
+    // ─────────────────────────────────────────────────────────────────────────
+    // Import Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// An export with one channel, one valid block, and one invalid block,
+    /// both connected to the channel.
     #[tokio::test]
-    async fn connect_block_nonexistent_block_fails() {
+    async fn export_channel_preserves_order_and_content() {
         let service = test_service();
         let channel = service
             .create_channel(NewChannel {
-                title: "Test".to_string(),
+                title: "Reading".to_string(),
                 description: None,
+                body: None,
             })
             .await
             .unwrap();
+        let mut ids = Vec::new();
+        for text in ["First", "Second", "Third"] {
+            let block = service.create_block(NewBlock::text(text)).await.unwrap();
+            ids.push(block.id);
+        }
+        // Connect out of order so the export has to sort by position
+        for (id, position) in ids.iter().zip([2, 0, 1]) {
+            service
+                .connect_block(id, &channel.id, Some(position))
+                .await
+                .unwrap();
+        }
 
-        let result = service
-            .connect_block(&BlockId::new(), &channel.id, None)
-            .await;
+        let export = service.export_channel(&channel.id).await.unwrap();
 
-        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+        assert_eq!(export.version, ChannelExport::VERSION);
+        assert_eq!(export.channel.id, channel.id);
+        let bodies: Vec<_> = export
+            .blocks
+            .iter()
+            .map(|b| match &b.content {
+                BlockContent::Text { body } => body.as_str(),
+                other => panic!("unexpected content: {:?}", other),
+            })
+            .collect();
+        assert_eq!(bodies, vec!["Second", "Third", "First"]);
+        let positions: Vec<_> = export.connections.iter().map(|c| c.position).collect();
+        assert_eq!(positions, vec![0, 1, 2]);
+        for (block, conn) in export.blocks.iter().zip(&export.connections) {
+            assert_eq!(block.id, conn.block_id);
+        }
     }
 
     #[tokio::test]
-    async fn connect_block_nonexistent_channel_fails() {
-        let service = test_service();
-        let block = service.create_block(NewBlock::text("Test")).await.unwrap();
+    async fn import_channel_round_trips_into_fresh_garden() {
+        let source = test_service();
+        let channel = source
+            .create_channel(NewChannel {
+                title: "Reading".to_string(),
+                description: Some("Things to read".to_string()),
+                body: None,
+            })
+            .await
+            .unwrap();
+        for text in ["First", "Second", "Third"] {
+            let block = source.create_block(NewBlock::text(text)).await.unwrap();
+            source
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
+        let export = source.export_channel(&channel.id).await.unwrap();
 
-        let result = service
-            .connect_block(&block.id, &ChannelId::new(), None)
-            .await;
+        let target = test_service();
+        let imported = target
+            .import_channel(export.clone(), ImportStrategy::RemapIds)
+            .await
+            .unwrap();
 
-        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+        assert_ne!(imported.id, channel.id);
+        assert_eq!(imported.title, "Reading");
+        assert_eq!(imported.description, channel.description);
+        let blocks = target
+            .get_blocks_in_channel_with_positions(&imported.id, false)
+            .await
+            .unwrap();
+        assert_eq!(blocks.len(), 3);
+        for ((block, position), (original, conn)) in blocks
+            .iter()
+            .zip(export.blocks.iter().zip(&export.connections))
+        {
+            assert_ne!(block.id, original.id);
+            assert_eq!(
+                serde_json::to_value(&block.content).unwrap(),
+                serde_json::to_value(&original.content).unwrap()
+            );
+            assert_eq!(*position, conn.position);
+        }
     }
 
     #[tokio::test]
-    async fn connect_block_already_connected_fails() {
-        let (service, channel, block) = service_with_channel_and_block().await;
-
-        // First connection succeeds
-        service
-            .connect_block(&block.id, &channel.id, None)
+    async fn import_channel_preserve_ids_rejects_existing_channel() {
+        let service = test_service();
+        let channel = service
+            .create_channel(NewChannel {
+                title: "Reading".to_string(),
+                description: None,
+                body: None,
+            })
             .await
             .unwrap();
+        let export = service.export_channel(&channel.id).await.unwrap();
 
-        // Second connection fails
-        let result = service.connect_block(&block.id, &channel.id, None).await;
+        let result = service
+            .import_channel(export.clone(), ImportStrategy::PreserveIds)
+            .await;
         assert!(matches!(result, Err(DomainError::InvalidInput(_))));
-    }
-
-    #[tokio::test]
-    async fn disconnect_block_success() {
-        let (service, channel, block) = service_with_channel_and_block().await;
 
-        service
-            .connect_block(&block.id, &channel.id, None)
-            .await
-            .unwrap();
-        service
-            .disconnect_block(&block.id, &channel.id)
+        // Remapping the same export makes a copy
+        let copy = service
+            .import_channel(export, ImportStrategy::RemapIds)
             .await
             .unwrap();
-
-        let result = service.get_connection(&block.id, &channel.id).await;
-        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+        assert_ne!(copy.id, channel.id);
+        assert_eq!(service.count_channels().await.unwrap(), 2);
     }
 
     #[tokio::test]
-    async fn disconnect_nonexistent_connection_fails() {
+    async fn import_channel_rejects_invalid_block() {
         let service = test_service();
+        let channel = Channel::new("Broken");
+        let mut block = Block::new(BlockContent::text("Bad source"));
+        block.source_url = Some("javascript:alert(1)".to_string());
+        let conn = Connection::new(block.id.clone(), channel.id.clone(), 0);
+        let export = ChannelExport::new(channel, vec![block], vec![conn]);
+
         let result = service
-            .disconnect_block(&BlockId::new(), &ChannelId::new())
+            .import_channel(export, ImportStrategy::PreserveIds)
             .await;
 
-        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        assert_eq!(service.count_channels().await.unwrap(), 0);
     }
 
     #[tokio::test]
-    async fn get_blocks_in_channel() {
+    async fn export_channel_not_found() {
         let service = test_service();
+        let result = service
+            .export_channel(&ChannelId("missing".to_string()))
+            .await;
+        assert!(matches!(result, Err(DomainError::ChannelNotFound(_))));
+    }
 
-        // Create channel and blocks through the service
+    #[tokio::test]
+    async fn export_channel_markdown_renders_text_and_links() {
+        let service = test_service().with_markdown_media_base("bundle/media");
         let channel = service
             .create_channel(NewChannel {
-                title: "Test Channel".to_string(),
-                description: None,
+                title: "Reading".to_string(),
+                description: Some("Things worth reading".to_string()),
+                body: None,
             })
             .await
             .unwrap();
-
-        let block1 = service
-            .create_block(NewBlock::text("Block 1"))
-            .await
-            .unwrap();
-
-        let block2 = service
-            .create_block(NewBlock::text("Block 2"))
+        let text = service
+            .create_block(NewBlock::text("A note on gardens"))
             .await
             .unwrap();
-
-        // Connect both blocks
-        service
-            .connect_block(&block1.id, &channel.id, Some(0))
+        let link = service
+            .create_block(NewBlock::new(BlockContent::link_with_meta(
+                "https://example.com/essay",
+                Some("An Essay".to_string()),
+                None,
+                None,
+            )))
             .await
             .unwrap();
-        service
-            .connect_block(&block2.id, &channel.id, Some(1))
+        let image = service
+            .create_block(NewBlock::new(BlockContent::image(
+                "images/cover.jpg",
+                "image/jpeg",
+            )))
             .await
             .unwrap();
+        for block in [&text, &link, &image] {
+            service
+                .connect_block(&block.id, &channel.id, None)
+                .await
+                .unwrap();
+        }
 
-        let blocks = service.get_blocks_in_channel(&channel.id).await.unwrap();
-        assert_eq!(blocks.len(), 2);
+        let markdown = service.export_channel_markdown(&channel.id).await.unwrap();
+
+        assert!(markdown.starts_with("# Reading\n\nThings worth reading\n"));
+        assert!(markdown.contains("- A note on gardens\n"));
+        assert!(markdown.contains("- [An Essay](https://example.com/essay)\n"));
+        assert!(markdown.contains("- ![](bundle/media/images/cover.jpg)\n"));
+    }
+
+    fn export_with_invalid_block() -> (GardenExport, Block, Block) {
+        let channel = Channel::new("Imported");
+        let valid = Block::new(BlockContent::text("Hello"));
+        let invalid = Block::new(BlockContent::text("   "));
+        let connections = vec![
+            Connection::new(valid.id.clone(), channel.id.clone(), 0),
+            Connection::new(invalid.id.clone(), channel.id.clone(), 1),
+        ];
+        let export = GardenExport::new(
+            vec![channel],
+            vec![valid.clone(), invalid.clone()],
+            connections,
+        );
+        (export, valid, invalid)
     }
 
     #[tokio::test]
-    async fn get_channels_for_block() {
+    async fn import_dry_run_flags_invalid_block_without_writing() {
         let service = test_service();
+        let (export, valid, invalid) = export_with_invalid_block();
 
-        // Create channels and block through the service
-        let channel1 = service
-            .create_channel(NewChannel {
-                title: "Channel 1".to_string(),
-                description: None,
-            })
+        let plan = service.import_garden(export, true).await.unwrap();
+
+        assert_eq!(plan.new_channels, 1);
+        assert_eq!(plan.new_blocks, 1);
+        assert_eq!(plan.new_connections, 1);
+        assert_eq!(plan.skipped_connections, 1);
+        assert_eq!(plan.invalid_blocks.len(), 1);
+        assert_eq!(plan.invalid_blocks[0].block_id, invalid.id);
+
+        // Nothing was created
+        assert_eq!(service.count_channels().await.unwrap(), 0);
+        let result = service.get_block(&valid.id).await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn import_flags_block_with_unsafe_source_url() {
+        let service = test_service();
+        let mut block = Block::new(BlockContent::text("Hello"));
+        block.source_url = Some("javascript:alert(1)".to_string());
+        let export = GardenExport::new(Vec::new(), vec![block.clone()], Vec::new());
+
+        let plan = service.plan_import(&export).await.unwrap();
+
+        assert_eq!(plan.new_blocks, 0);
+        assert_eq!(plan.invalid_blocks.len(), 1);
+        assert_eq!(plan.invalid_blocks[0].block_id, block.id);
+    }
+
+    #[tokio::test]
+    async fn import_writes_what_the_plan_reports() {
+        let service = test_service();
+        let (export, valid, invalid) = export_with_invalid_block();
+        let channel_id = export.channels[0].id.clone();
+
+        let planned = service.plan_import(&export).await.unwrap();
+        let imported = service.import_garden(export, false).await.unwrap();
+        assert_eq!(planned, imported);
+
+        let blocks = service
+            .get_blocks_in_channel(&channel_id, false)
             .await
             .unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id, valid.id);
+        let result = service.get_block(&invalid.id).await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
+    }
 
-        let channel2 = service
+    #[tokio::test]
+    async fn import_urls_from_file_skips_invalid_lines() {
+        let service = test_service();
+        let channel = service
             .create_channel(NewChannel {
-                title: "Channel 2".to_string(),
+                title: "Bookmarks".to_string(),
                 description: None,
+                body: None,
             })
             .await
             .unwrap();
-
-        let block = service
-            .create_block(NewBlock::text("Test Block"))
+        let path = std::env::temp_dir().join(format!("garden-urls-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "https://example.com/one\n\n# comment\nnot a url\n  https://example.com/two  \n",
+        )
+        .unwrap();
+
+        let report = service
+            .import_urls_from_file(&path, &channel.id, None)
             .await
             .unwrap();
+        std::fs::remove_file(&path).unwrap();
 
-        // Connect block to both channels
-        service
-            .connect_block(&block.id, &channel1.id, None)
-            .await
-            .unwrap();
-        service
-            .connect_block(&block.id, &channel2.id, None)
+        assert_eq!((report.imported, report.skipped), (2, 1));
+        assert_eq!(report.lines.len(), 3);
+        assert_eq!(report.lines[1].line, 4);
+        assert!(report.lines[1].block_id.is_none());
+        assert!(report.lines[1].error.is_some());
+
+        let blocks = service
+            .get_blocks_in_channel(&channel.id, false)
             .await
             .unwrap();
+        let urls: Vec<_> = blocks
+            .iter()
+            .map(|b| match &b.content {
+                BlockContent::Link { url, .. } => url.as_str(),
+                other => panic!("expected a link, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(urls, ["https://example.com/one", "https://example.com/two"]);
+        assert_eq!(blocks[0].id, *report.lines[0].block_id.as_ref().unwrap());
+    }
 
-        let channels = service.get_channels_for_block(&block.id).await.unwrap();
-        assert_eq!(channels.len(), 2);
+    #[tokio::test]
+    async fn import_twice_reports_collisions() {
+        let service = test_service();
+        let (export, _, _) = export_with_invalid_block();
+
+        service.import_garden(export.clone(), false).await.unwrap();
+        let plan = service.plan_import(&export).await.unwrap();
+
+        assert!(plan.is_empty());
+        assert_eq!(plan.channel_collisions, 1);
+        assert_eq!(plan.block_collisions, 1);
+        assert_eq!(plan.skipped_connections, 2);
     }
 
     #[tokio::test]
-    async fn reorder_block_success() {
-        let (service, channel, block) = service_with_channel_and_block().await;
+    async fn import_rejects_invalid_channel_title() {
+        let service = test_service();
+        let (mut export, valid, _) = export_with_invalid_block();
+        export.channels[0].title = "   ".to_string();
 
-        service
-            .connect_block(&block.id, &channel.id, Some(0))
-            .await
-            .unwrap();
-        service
-            .reorder_block(&channel.id, &block.id, 10)
-            .await
-            .unwrap();
+        let result = service.import_garden(export, false).await;
 
-        let connection = service
-            .get_connection(&block.id, &channel.id)
-            .await
-            .unwrap();
-        assert_eq!(connection.position, 10);
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
+        assert_eq!(service.count_channels().await.unwrap(), 0);
+        let result = service.get_block(&valid.id).await;
+        assert!(matches!(result, Err(DomainError::BlockNotFound(_))));
     }
 
     #[tokio::test]
-    async fn reorder_nonexistent_connection_fails() {
+    async fn import_rejects_newer_export_version() {
         let service = test_service();
-        let result = service
-            .reorder_block(&ChannelId::new(), &BlockId::new(), 5)
-            .await;
+        let (mut export, _, _) = export_with_invalid_block();
+        export.version = GardenExport::VERSION + 1;
 
-        assert!(matches!(result, Err(DomainError::ConnectionNotFound(_, _))));
+        let result = service.plan_import(&export).await;
+        assert!(matches!(result, Err(DomainError::InvalidInput(_))));
     }
 }
@@ -3,49 +3,26 @@
 //! This module provides functionality for importing, storing, and managing
 //! media files (images, videos, audio) for Garden blocks.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use image::GenericImageView;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
-use tokio::io::AsyncWriteExt;
-use tracing::{error, info, instrument};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use crate::models::BlockContent;
-
-/// Media type classification.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum MediaType {
-    Image,
-    Video,
-    Audio,
-}
-
-impl MediaType {
-    /// Get the subdirectory name for this media type.
-    pub fn subdir(&self) -> &'static str {
-        match self {
-            MediaType::Image => "images",
-            MediaType::Video => "videos",
-            MediaType::Audio => "audio",
-        }
-    }
-
-    /// Detect media type from MIME type string.
-    pub fn from_mime(mime: &str) -> Option<Self> {
-        if mime.starts_with("image/") {
-            Some(MediaType::Image)
-        } else if mime.starts_with("video/") {
-            Some(MediaType::Video)
-        } else if mime.starts_with("audio/") {
-            Some(MediaType::Audio)
-        } else {
-            None
-        }
-    }
-}
+pub use crate::models::MediaType;
+use crate::models::{
+    aspect_ratio, BlockContent, GalleryItem, LinkStatus, MimeType, StorageUsage, SupportedType,
+};
 
 /// Information about an imported media file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,39 +30,52 @@ pub struct MediaInfo {
     /// Relative path within media directory (e.g., "images/{uuid}.jpg").
     pub file_path: String,
     /// MIME type of the file.
-    pub mime_type: String,
+    pub mime_type: MimeType,
     /// Image/video width in pixels (None for audio).
     pub width: Option<u32>,
     /// Image/video height in pixels (None for audio).
     pub height: Option<u32>,
+    /// Width divided by height, when both are known.
+    #[serde(default)]
+    pub aspect_ratio: Option<f32>,
     /// Duration in seconds (for video/audio).
     pub duration: Option<f32>,
     /// Original URL if imported from web.
     pub original_url: Option<String>,
+    /// SHA-256 of the stored file, hex-encoded.
+    pub content_hash: Option<String>,
+    /// Why metadata extraction failed, e.g. a corrupt image.
+    ///
+    /// `None` when extraction succeeded or does not apply, so missing
+    /// dimensions with no error mean there was nothing to measure.
+    #[serde(default)]
+    pub metadata_error: Option<String>,
 }
 
 impl MediaInfo {
     /// Convert this MediaInfo into a BlockContent variant.
     pub fn into_block_content(self) -> BlockContent {
-        let media_type = MediaType::from_mime(&self.mime_type);
-
-        match media_type {
+        match self.mime_type.media_type() {
             Some(MediaType::Image) => BlockContent::Image {
                 file_path: self.file_path,
                 original_url: self.original_url,
                 width: self.width,
                 height: self.height,
+                aspect_ratio: self.aspect_ratio,
                 mime_type: self.mime_type,
                 alt_text: None,
+                content_hash: self.content_hash,
             },
             Some(MediaType::Video) => BlockContent::Video {
                 file_path: self.file_path,
                 original_url: self.original_url,
                 width: self.width,
                 height: self.height,
+                aspect_ratio: self.aspect_ratio,
                 duration: self.duration,
                 mime_type: self.mime_type,
                 alt_text: None,
+                content_hash: self.content_hash,
             },
             Some(MediaType::Audio) => BlockContent::Audio {
                 file_path: self.file_path,
@@ -94,6 +84,7 @@ impl MediaInfo {
                 mime_type: self.mime_type,
                 title: None,
                 artist: None,
+                content_hash: self.content_hash,
             },
             None => {
                 // Fallback to image if we can't determine type
@@ -102,17 +93,69 @@ impl MediaInfo {
                     original_url: self.original_url,
                     width: self.width,
                     height: self.height,
+                    aspect_ratio: self.aspect_ratio,
                     mime_type: self.mime_type,
                     alt_text: None,
+                    content_hash: self.content_hash,
                 }
             }
         }
     }
+
+    /// Convert this MediaInfo into an item for a gallery block.
+    pub fn into_gallery_item(self) -> GalleryItem {
+        GalleryItem {
+            file_path: self.file_path,
+            mime_type: self.mime_type,
+            width: self.width,
+            height: self.height,
+            alt_text: None,
+            content_hash: self.content_hash,
+        }
+    }
+}
+
+/// Title and description of a web page, from its Open Graph tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkMetadata {
+    /// `og:title`, falling back to the page's `<title>`.
+    pub title: Option<String>,
+    /// `og:description`, falling back to `<meta name="description">`.
+    pub description: Option<String>,
 }
 
 /// Maximum file size for media imports (100 MB).
 const MAX_DOWNLOAD_SIZE: u64 = 100 * 1024 * 1024;
 
+/// How much of a page is read when looking for link metadata (512 KB).
+///
+/// Metadata lives in `<head>`, so there is no need to read the whole page.
+const MAX_PAGE_PREFIX: usize = 512 * 1024;
+
+/// Content types that say nothing about the media, as sent by some CDNs.
+///
+/// A URL import that receives one of these falls back to guessing from the
+/// URL's file extension.
+pub const DEFAULT_GENERIC_CONTENT_TYPES: &[&str] = &[MimeType::OCTET_STREAM, "binary/octet-stream"];
+
+/// How many fresh names an import tries before giving up on finding a free
+/// path.
+const MAX_FILENAME_ATTEMPTS: usize = 8;
+
+/// JPEG quality used when a downscaled image is re-encoded, by default.
+pub const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+/// Index of URL imports, kept in the media root and keyed by source URL.
+const SOURCE_INDEX_FILE: &str = "sources.jsonl";
+
+/// One line of the source index.
+#[derive(Serialize, Deserialize)]
+struct SourceEntry {
+    url: String,
+    #[serde(flatten)]
+    info: MediaInfo,
+}
+
 /// Errors that can occur during media operations.
 #[derive(Debug, Error)]
 pub enum MediaError {
@@ -158,6 +201,23 @@ pub struct MediaService {
     media_root: PathBuf,
     /// HTTP client for downloading media.
     http_client: reqwest::Client,
+    /// HTTP client for link checks, which reports redirects instead of
+    /// following them.
+    link_client: reqwest::Client,
+    /// Content types treated like a missing header on URL imports.
+    generic_content_types: Vec<MimeType>,
+    /// Whether stored filenames keep the original name after the UUID.
+    original_filenames: bool,
+    /// Limits outbound downloads; `None` means unlimited.
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Longest edge an imported image may have; `None` means no cap.
+    max_image_dimension: Option<u32>,
+    /// JPEG quality (1-100) for re-encoding downscaled images.
+    jpeg_quality: u8,
+    /// Whether URL imports return a previous import of the same URL.
+    reuse_source_urls: bool,
+    /// Keeps source index reads from seeing a half-written entry.
+    source_index_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl MediaService {
@@ -170,6 +230,443 @@ impl MediaService {
         Self {
             media_root: media_root.into(),
             http_client: reqwest::Client::new(),
+            link_client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .expect("failed to build link check HTTP client"),
+            generic_content_types: DEFAULT_GENERIC_CONTENT_TYPES
+                .iter()
+                .map(|mime| MimeType::new(mime))
+                .collect(),
+            original_filenames: false,
+            rate_limiter: None,
+            max_image_dimension: None,
+            jpeg_quality: DEFAULT_JPEG_QUALITY,
+            reuse_source_urls: false,
+            source_index_lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Limit URL imports to `per_second` downloads per second, across all
+    /// hosts.
+    ///
+    /// Downloads are spaced evenly rather than allowed in bursts, and the
+    /// limit is shared by clones of this service. `0` removes the limit,
+    /// which is the default.
+    pub fn with_rate_limit(mut self, per_second: u32) -> Self {
+        self.rate_limiter = RateLimiter::new(per_second, false).map(Arc::new);
+        self
+    }
+
+    /// Like [`Self::with_rate_limit`], but each host gets its own limit, so
+    /// a slow host does not hold up downloads from others.
+    pub fn with_per_host_rate_limit(mut self, per_second: u32) -> Self {
+        self.rate_limiter = RateLimiter::new(per_second, true).map(Arc::new);
+        self
+    }
+
+    /// Keep the original filename when storing media.
+    ///
+    /// When enabled, files are stored as `{uuid}-{name}.{ext}` instead of
+    /// `{uuid}.{ext}`, where `name` is the source file's name (or the last
+    /// segment of the URL path) with anything outside letters, digits, `-`,
+    /// and `_` replaced by `-`. The UUID still keeps names unique.
+    pub fn with_original_filenames(mut self, enabled: bool) -> Self {
+        self.original_filenames = enabled;
+        self
+    }
+
+    /// Downscale imported images whose longest edge exceeds `px` pixels.
+    ///
+    /// The aspect ratio is kept and the image is re-encoded in its original
+    /// format, so the stored dimensions and hash describe the smaller file.
+    /// GIFs are left alone so animations survive. `0` removes the cap,
+    /// which is the default.
+    pub fn with_max_image_dimension(mut self, px: u32) -> Self {
+        self.max_image_dimension = (px > 0).then_some(px);
+        self
+    }
+
+    /// Set the JPEG quality (1-100) used when re-encoding downscaled
+    /// images. Defaults to [`DEFAULT_JPEG_QUALITY`].
+    pub fn with_jpeg_quality(mut self, quality: u8) -> Self {
+        self.jpeg_quality = quality.clamp(1, 100);
+        self
+    }
+
+    /// Skip the download when a URL has been imported before.
+    ///
+    /// When enabled, [`Self::import_from_url`] returns the earlier import's
+    /// `MediaInfo` (see [`Self::find_by_source_url`]) without making a
+    /// request, as long as its file is still on disk. Off by default.
+    pub fn with_source_url_reuse(mut self, enabled: bool) -> Self {
+        self.reuse_source_urls = enabled;
+        self
+    }
+
+    /// Downscale a stored image to the configured maximum dimension,
+    /// overwriting it in place. Returns whether the file was rewritten.
+    ///
+    /// Images that cannot be decoded are left for metadata extraction to
+    /// report.
+    fn downscale_image(&self, path: &Path, mime_type: &MimeType) -> MediaResult<bool> {
+        let Some(max) = self.max_image_dimension else {
+            return Ok(false);
+        };
+        let format = match image::ImageFormat::from_mime_type(mime_type.as_str()) {
+            Some(format) if format != image::ImageFormat::Gif => format,
+            _ => return Ok(false),
+        };
+        let Ok(img) = image::open(path) else {
+            return Ok(false);
+        };
+        if img.width().max(img.height()) <= max {
+            return Ok(false);
+        }
+
+        let resized = img.resize(max, max, image::imageops::FilterType::Lanczos3);
+        let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+        let encoded = match format {
+            image::ImageFormat::Jpeg => resized.write_with_encoder(
+                image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, self.jpeg_quality),
+            ),
+            _ => resized.write_to(&mut out, format),
+        };
+        encoded.map_err(|e| MediaError::FileWrite(format!("cannot re-encode image: {}", e)))?;
+        std::io::Write::flush(&mut out)?;
+
+        info!(
+            from = %format!("{}x{}", img.width(), img.height()),
+            to = %format!("{}x{}", resized.width(), resized.height()),
+            "Image downscaled"
+        );
+        Ok(true)
+    }
+
+    /// Build the stored filename for an import.
+    fn stored_filename(&self, id: Uuid, original_name: Option<&str>, extension: &str) -> String {
+        let name = original_name
+            .filter(|_| self.original_filenames)
+            .and_then(sanitize_file_stem);
+        match name {
+            Some(name) => format!("{}-{}.{}", id, name, extension),
+            None => format!("{}.{}", id, extension),
+        }
+    }
+
+    /// Create a new, empty media file under `subdir`.
+    ///
+    /// Never overwrites: the file is opened with `create_new`, and a name
+    /// that is already taken is retried with a fresh UUID.
+    ///
+    /// # Returns
+    ///
+    /// The relative path, the full path, and the open file
+    async fn create_media_file(
+        &self,
+        subdir: &str,
+        original_name: Option<&str>,
+        extension: &str,
+    ) -> MediaResult<(String, PathBuf, tokio::fs::File)> {
+        self.create_media_file_with(subdir, original_name, extension, Uuid::new_v4)
+            .await
+    }
+
+    /// [`Self::create_media_file`] with the UUID source injected.
+    async fn create_media_file_with(
+        &self,
+        subdir: &str,
+        original_name: Option<&str>,
+        extension: &str,
+        mut next_id: impl FnMut() -> Uuid,
+    ) -> MediaResult<(String, PathBuf, tokio::fs::File)> {
+        tokio::fs::create_dir_all(self.media_root.join(subdir)).await?;
+
+        for _ in 0..MAX_FILENAME_ATTEMPTS {
+            let filename = self.stored_filename(next_id(), original_name, extension);
+            let relative_path = format!("{}/{}", subdir, filename);
+            let full_path = self.media_root.join(&relative_path);
+
+            match tokio::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&full_path)
+                .await
+            {
+                Ok(file) => return Ok((relative_path, full_path, file)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    warn!(path = %relative_path, "Media path already exists, retrying");
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(MediaError::FileWrite(format!(
+            "no free filename in {} after {} attempts",
+            subdir, MAX_FILENAME_ATTEMPTS
+        )))
+    }
+
+    /// Set which content types URL imports treat as unknown.
+    ///
+    /// When a download's `Content-Type` is one of these, the type is guessed
+    /// from the URL's extension instead. Pass an empty list to trust the
+    /// header whenever it is present.
+    pub fn with_generic_content_types<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.generic_content_types = types
+            .into_iter()
+            .map(|mime| MimeType::new(mime.as_ref()))
+            .collect();
+        self
+    }
+
+    /// Work out the content type of a download.
+    ///
+    /// Uses the `Content-Type` header unless it is missing or generic, in
+    /// which case the type is guessed from the URL path. Returns `None` if
+    /// neither says more than "some bytes".
+    fn resolve_content_type(&self, header: Option<&str>, url: &url::Url) -> Option<MimeType> {
+        header
+            .map(MimeType::new)
+            .filter(|mime| !mime.is_empty() && !self.generic_content_types.contains(mime))
+            .or_else(|| {
+                mime_guess::from_path(url.path())
+                    .first()
+                    .map(|m| MimeType::new(m.as_ref()))
+            })
+            .or_else(|| header.map(MimeType::new))
+            .filter(|mime| !mime.is_empty() && *mime != MimeType::OCTET_STREAM)
+    }
+
+    /// The media formats Garden recognizes, each with its stored file
+    /// extension and media type.
+    ///
+    /// Drives the frontend's file picker filter, so it can't drift from
+    /// what imports accept.
+    pub fn supported_types() -> Vec<SupportedType> {
+        SupportedType::all()
+    }
+
+    /// Get the root directory for media storage.
+    pub fn media_root(&self) -> &Path {
+        &self.media_root
+    }
+
+    /// Prepare a directory for use as a media root.
+    ///
+    /// Creates the root and its per-type subdirectories, then checks the
+    /// root is writable by creating and removing a probe file. Use this
+    /// before pointing a `MediaService` at a user-chosen directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MediaError::FileWrite` if the directories cannot be created
+    /// or the root is not writable.
+    pub fn prepare_root(root: &Path) -> MediaResult<()> {
+        for media_type in [MediaType::Image, MediaType::Video, MediaType::Audio] {
+            let dir = root.join(media_type.subdir());
+            std::fs::create_dir_all(&dir).map_err(|e| {
+                MediaError::FileWrite(format!("cannot create {}: {}", dir.display(), e))
+            })?;
+        }
+
+        let probe = root.join(format!(".write-test-{}", Uuid::new_v4()));
+        std::fs::write(&probe, b"")
+            .and_then(|_| std::fs::remove_file(&probe))
+            .map_err(|e| {
+                MediaError::FileWrite(format!("{} is not writable: {}", root.display(), e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Move all media files into another root directory.
+    ///
+    /// Every file under the current root, nested directories included, is
+    /// copied to the same relative path under `new_root`, and the originals
+    /// are removed only once every copy has succeeded. Relative file paths
+    /// are unchanged, so stored blocks keep working once the service points
+    /// at `new_root`.
+    ///
+    /// # Returns
+    ///
+    /// The number of files moved.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MediaError::InvalidPath` if `new_root` is the current root,
+    /// or either root is inside the other. Returns `MediaError::FileWrite`
+    /// if a file already exists in `new_root` or cannot be copied; the
+    /// copies made so far are removed, so the current root stays intact and
+    /// nothing in `new_root` is overwritten.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, new_root), fields(new_root = %new_root.display())))]
+    pub async fn migrate_to(&self, new_root: &Path) -> MediaResult<usize> {
+        Self::prepare_root(new_root)?;
+        let old_root = match tokio::fs::canonicalize(&self.media_root).await {
+            Ok(root) => root,
+            // Nothing stored yet, so there is nothing to move
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let new_root = tokio::fs::canonicalize(new_root).await?;
+        if new_root.starts_with(&old_root) || old_root.starts_with(&new_root) {
+            return Err(MediaError::InvalidPath(format!(
+                "{} overlaps the current media root",
+                new_root.display()
+            )));
+        }
+
+        let mut files = Vec::new();
+        let mut dirs = Vec::new();
+        let mut pending = vec![old_root.clone()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = tokio::fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file_type = entry.file_type().await?;
+                if file_type.is_dir() {
+                    pending.push(entry.path());
+                } else if file_type.is_file() {
+                    files.push(entry.path());
+                }
+            }
+            dirs.push(dir);
+        }
+
+        let mut copied = Vec::with_capacity(files.len());
+        for path in &files {
+            let relative = path.strip_prefix(&old_root).unwrap_or(path);
+            let dest = new_root.join(relative);
+            if let Err(e) = copy_new(path, &dest).await {
+                for copy in &copied {
+                    let _ = tokio::fs::remove_file(copy).await;
+                }
+                return Err(MediaError::FileWrite(format!(
+                    "cannot copy to {}: {}",
+                    dest.display(),
+                    e
+                )));
+            }
+            copied.push(dest);
+        }
+
+        for path in &files {
+            if let Err(e) = tokio::fs::remove_file(path).await {
+                error!(error = %e, path = %path.display(), "Failed to remove migrated media file");
+            }
+        }
+        // Deepest first, so emptied parents can go too; anything left over
+        // (e.g. a file that appeared meanwhile) keeps its directory
+        for dir in dirs.iter().rev() {
+            let _ = tokio::fs::remove_dir(dir).await;
+        }
+
+        info!(count = files.len(), "Media files migrated");
+        Ok(files.len())
+    }
+
+    /// Move the whole media directory to `new_root` and use it from now on.
+    ///
+    /// Moves files as [`Self::migrate_to`] does, then points this service at
+    /// `new_root`. On error the service keeps its current root.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, new_root), fields(new_root = %new_root.display())))]
+    pub async fn relocate(&mut self, new_root: &Path) -> MediaResult<()> {
+        self.migrate_to(new_root).await?;
+        self.media_root = new_root.to_path_buf();
+        Ok(())
+    }
+
+    /// Measure the disk space used by stored media.
+    ///
+    /// Walks the image, video, and audio directories one entry at a time,
+    /// so only running totals are held in memory. Anything else under the
+    /// media root, such as generated thumbnails, is not counted.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn storage_usage(&self) -> MediaResult<StorageUsage> {
+        let mut usage = StorageUsage::default();
+        for media_type in [MediaType::Image, MediaType::Video, MediaType::Audio] {
+            let mut pending = vec![self.media_root.join(media_type.subdir())];
+            while let Some(dir) = pending.pop() {
+                let mut entries = match tokio::fs::read_dir(&dir).await {
+                    Ok(entries) => entries,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e.into()),
+                };
+                while let Some(entry) = entries.next_entry().await? {
+                    let metadata = entry.metadata().await?;
+                    if metadata.is_dir() {
+                        pending.push(entry.path());
+                    } else if metadata.is_file() {
+                        usage.add(media_type, metadata.len());
+                    }
+                }
+            }
+        }
+
+        info!(
+            bytes = usage.total_bytes,
+            files = usage.file_count,
+            "Measured media storage"
+        );
+        Ok(usage)
+    }
+
+    /// Find an earlier import of `url`.
+    ///
+    /// Every successful URL import is appended to an index in the media
+    /// root. Returns `None` if the URL was never imported or its file has
+    /// since been removed; a later import of the same URL wins.
+    pub async fn find_by_source_url(&self, url: &str) -> MediaResult<Option<MediaInfo>> {
+        let guard = self.source_index_lock.lock().await;
+        let index = match tokio::fs::read_to_string(self.media_root.join(SOURCE_INDEX_FILE)).await {
+            Ok(index) => index,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        drop(guard);
+
+        let mut found = None;
+        for line in index.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<SourceEntry>(line) {
+                Ok(entry) if entry.url == url => found = Some(entry.info),
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Skipping corrupt media source index entry"),
+            }
+        }
+        match found {
+            Some(info) if self.exists(&info.file_path)? => Ok(Some(info)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Record a URL import in the source index.
+    ///
+    /// Entries are appended one per line, so the index is never rewritten
+    /// and a corrupt entry only loses itself. The file is already stored, so
+    /// a failure is logged rather than failing the import.
+    async fn record_source(&self, url: &str, info: &MediaInfo) {
+        let entry = SourceEntry {
+            url: url.to_string(),
+            info: info.clone(),
+        };
+        let _guard = self.source_index_lock.lock().await;
+        let result = async {
+            // The leading newline ends any entry cut short by a crash
+            let line = format!("\n{}\n", serde_json::to_string(&entry)?);
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.media_root.join(SOURCE_INDEX_FILE))
+                .await?;
+            file.write_all(line.as_bytes()).await?;
+            file.flush().await
+        }
+        .await;
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to record media source");
         }
     }
 
@@ -213,30 +710,45 @@ impl MediaService {
     ///
     /// Downloads the file, detects its type, extracts metadata, and stores it.
     ///
+    /// Cancelling `cancel` stops the download, removes the partly written
+    /// file, and returns `MediaError::Download("cancelled")`.
+    ///
     /// # Arguments
     ///
     /// * `url` - The URL to download from
+    /// * `cancel` - Cancels the import when triggered
     ///
     /// # Returns
     ///
     /// `MediaInfo` containing the stored file path and metadata
-    #[instrument(skip(self), fields(url = %url))]
-    pub async fn import_from_url(&self, url: &str) -> MediaResult<MediaInfo> {
+    #[cfg_attr(feature = "tracing", instrument(skip(self, cancel), fields(url = %url)))]
+    pub async fn import_from_url(
+        &self,
+        url: &str,
+        cancel: &CancellationToken,
+    ) -> MediaResult<MediaInfo> {
         info!("Downloading media from URL");
 
-        // Validate URL scheme (only allow HTTP/HTTPS)
-        let parsed_url = url::Url::parse(url)
-            .map_err(|e| MediaError::InvalidUrl(format!("Invalid URL: {}", e)))?;
+        let parsed_url = parse_http_url(url)?;
 
-        if !["http", "https"].contains(&parsed_url.scheme()) {
-            return Err(MediaError::InvalidUrl(format!(
-                "Only HTTP/HTTPS URLs allowed, got: {}",
-                parsed_url.scheme()
-            )));
+        if self.reuse_source_urls {
+            if let Some(info) = self.find_by_source_url(url).await? {
+                info!(path = %info.file_path, "Reusing earlier import of URL");
+                return Ok(info);
+            }
+        }
+
+        if let Some(limiter) = &self.rate_limiter {
+            limiter
+                .acquire(parsed_url.host_str().unwrap_or_default())
+                .await;
         }
 
         // Download the file
-        let response = self.http_client.get(url).send().await?;
+        let mut response = cancel
+            .run_until_cancelled(self.http_client.get(url).send())
+            .await
+            .ok_or_else(download_cancelled)??;
 
         if !response.status().is_success() {
             return Err(MediaError::Download(format!(
@@ -257,58 +769,208 @@ impl MediaService {
         }
 
         // Get content type from headers, or guess from URL
-        let content_type = response
+        let header = response
             .headers()
             .get(reqwest::header::CONTENT_TYPE)
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.split(';').next().unwrap_or(s).trim().to_string())
+            .and_then(|v| v.to_str().ok());
+        let content_type = self
+            .resolve_content_type(header, &parsed_url)
+            .ok_or_else(unknown_media_type)?;
+
+        let media_type = content_type
+            .media_type()
+            .ok_or_else(|| MediaError::UnsupportedType(content_type.to_string()))?;
+
+        // Get the file extension; without one the stored file couldn't be
+        // served back with the right type
+        let extension = content_type
+            .extension()
             .or_else(|| {
-                mime_guess::from_path(url)
-                    .first()
-                    .map(|m| m.to_string())
+                Path::new(parsed_url.path())
+                    .extension()
+                    .and_then(|e| e.to_str())
             })
-            .unwrap_or_else(|| "application/octet-stream".to_string());
-
-        let media_type = MediaType::from_mime(&content_type)
-            .ok_or_else(|| MediaError::UnsupportedType(content_type.clone()))?;
-
-        // Get the file extension
-        let extension = get_extension_for_mime(&content_type)
-            .or_else(|| Path::new(url).extension().and_then(|e| e.to_str()))
-            .unwrap_or("bin");
+            .ok_or_else(unknown_media_type)?;
 
         // Generate filename and path
-        let filename = format!("{}.{}", Uuid::new_v4(), extension);
-        let relative_path = format!("{}/{}", media_type.subdir(), filename);
-        let full_path = self.media_root.join(&relative_path);
-
-        // Ensure directory exists
-        if let Some(parent) = full_path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
+        let original_name = parsed_url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .map(percent_decode);
+        let (relative_path, full_path, mut file) = self
+            .create_media_file(media_type.subdir(), original_name.as_deref(), extension)
+            .await?;
+
+        // Download and write file, removing it if the download doesn't finish
+        let written = write_download(&mut response, &mut file, cancel).await;
+        drop(file);
+        let mut content_hash = match written {
+            Ok(hash) => hash,
+            Err(e) => {
+                if let Err(cleanup) = tokio::fs::remove_file(&full_path).await {
+                    warn!(error = %cleanup, path = %relative_path, "Failed to remove partial download");
+                }
+                return Err(e);
+            }
+        };
+        if self.downscale_image(&full_path, &content_type)? {
+            content_hash = sha256_file(&full_path).await?;
         }
 
-        // Download and write file
-        let bytes = response.bytes().await?;
-        let mut file = tokio::fs::File::create(&full_path).await?;
-        file.write_all(&bytes).await?;
-
         info!(path = %relative_path, "Media file saved");
 
-        // Extract metadata
-        let (width, height) = if media_type == MediaType::Image {
-            extract_image_dimensions(&full_path).unwrap_or((None, None))
-        } else {
-            (None, None)
-        };
+        // Extract metadata; a failure is reported, not fatal
+        let (width, height, metadata_error) = extract_dimensions(&full_path, media_type);
+        reject_empty_image(&full_path, width, height).await?;
 
-        Ok(MediaInfo {
+        let info = MediaInfo {
             file_path: relative_path,
             mime_type: content_type,
             width,
             height,
+            aspect_ratio: aspect_ratio(width, height),
             duration: None, // TODO: Extract duration for video/audio
             original_url: Some(url.to_string()),
-        })
+            content_hash: Some(content_hash),
+            metadata_error,
+        };
+        self.record_source(url, &info).await;
+        Ok(info)
+    }
+
+    /// Fetch a web page's title and description for a link block.
+    ///
+    /// Reads the start of the page and picks out its Open Graph tags,
+    /// falling back to `<title>` and `<meta name="description">`. Respects
+    /// the rate limit, like media downloads.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(url = %url)))]
+    pub async fn fetch_link_metadata(&self, url: &str) -> MediaResult<LinkMetadata> {
+        let parsed_url = parse_http_url(url)?;
+
+        if let Some(limiter) = &self.rate_limiter {
+            limiter
+                .acquire(parsed_url.host_str().unwrap_or_default())
+                .await;
+        }
+
+        let mut response = self.http_client.get(url).send().await?;
+        if !response.status().is_success() {
+            return Err(MediaError::Download(format!(
+                "HTTP {} from {}",
+                response.status(),
+                url
+            )));
+        }
+
+        let mut page = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            page.extend_from_slice(&chunk);
+            if page.len() >= MAX_PAGE_PREFIX {
+                break;
+            }
+        }
+
+        Ok(parse_link_metadata(&String::from_utf8_lossy(&page)))
+    }
+
+    /// Check whether a link block's URL still resolves.
+    ///
+    /// Sends a HEAD request, confirming with GET if HEAD fails, since some
+    /// servers reject HEAD outright. Redirects are reported rather than
+    /// followed. Only HTTP/HTTPS URLs are allowed, and each request respects
+    /// the rate limit, like media downloads.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(url = %url)))]
+    pub async fn check_link(&self, url: &str) -> MediaResult<LinkStatus> {
+        let parsed_url = parse_http_url(url)?;
+
+        let status = self
+            .request_link_status(reqwest::Method::HEAD, &parsed_url)
+            .await;
+        if matches!(status, LinkStatus::Ok | LinkStatus::Redirected(_)) {
+            return Ok(status);
+        }
+        Ok(self
+            .request_link_status(reqwest::Method::GET, &parsed_url)
+            .await)
+    }
+
+    /// Send one link check request and classify the response.
+    async fn request_link_status(&self, method: reqwest::Method, url: &url::Url) -> LinkStatus {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire(url.host_str().unwrap_or_default()).await;
+        }
+
+        let response = match self.link_client.request(method, url.clone()).send().await {
+            Ok(response) => response,
+            Err(e) => return LinkStatus::Error(e.to_string()),
+        };
+        let status = response.status();
+        if status.is_success() {
+            return LinkStatus::Ok;
+        }
+        if status.is_redirection() {
+            let target = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|location| url.join(location).ok());
+            if let Some(target) = target {
+                return LinkStatus::Redirected(target.to_string());
+            }
+        }
+        match status {
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE => LinkStatus::NotFound,
+            _ => LinkStatus::Error(format!("HTTP {}", status)),
+        }
+    }
+
+    /// Import several images or videos from URLs for a gallery block.
+    ///
+    /// Files are imported in order. If any import fails, or a URL turns out
+    /// to be audio, the files already imported by this call are deleted so
+    /// a failed gallery doesn't leave orphans behind.
+    ///
+    /// # Arguments
+    ///
+    /// * `urls` - The URLs to download from, in gallery order
+    ///
+    /// # Returns
+    ///
+    /// `MediaInfo` for each stored file, in the same order as `urls`
+    #[cfg_attr(feature = "tracing", instrument(skip(self, urls), fields(count = urls.len())))]
+    pub async fn import_gallery(&self, urls: &[String]) -> MediaResult<Vec<MediaInfo>> {
+        if urls.is_empty() {
+            return Err(MediaError::InvalidUrl(
+                "gallery needs at least one URL".to_string(),
+            ));
+        }
+
+        let mut imported: Vec<MediaInfo> = Vec::with_capacity(urls.len());
+        for url in urls {
+            let err = match self.import_from_url(url, &CancellationToken::new()).await {
+                Ok(info) if is_visual(&info.mime_type) => {
+                    imported.push(info);
+                    continue;
+                }
+                Ok(info) => {
+                    let err = MediaError::UnsupportedType(info.mime_type.to_string());
+                    // Track it so it gets cleaned up with the rest
+                    imported.push(info);
+                    err
+                }
+                Err(e) => e,
+            };
+
+            for info in &imported {
+                if let Err(e) = self.delete(&info.file_path).await {
+                    error!(error = %e, path = %info.file_path, "Failed to clean up gallery file");
+                }
+            }
+            return Err(err);
+        }
+
+        info!(count = imported.len(), "Gallery imported");
+        Ok(imported)
     }
 
     /// Import media from a local file.
@@ -322,7 +984,7 @@ impl MediaService {
     /// # Returns
     ///
     /// `MediaInfo` containing the stored file path and metadata
-    #[instrument(skip(self, source_path))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, source_path)))]
     pub async fn import_from_file(&self, source_path: impl AsRef<Path>) -> MediaResult<MediaInfo> {
         let source_path = source_path.as_ref();
         info!("Importing media from local file");
@@ -330,48 +992,55 @@ impl MediaService {
         // Detect MIME type
         let mime_type = mime_guess::from_path(source_path)
             .first()
-            .map(|m| m.to_string())
-            .unwrap_or_else(|| "application/octet-stream".to_string());
+            .map(|m| MimeType::new(m.as_ref()))
+            .ok_or_else(unknown_media_type)?;
 
-        let media_type = MediaType::from_mime(&mime_type)
-            .ok_or_else(|| MediaError::UnsupportedType(mime_type.clone()))?;
+        let media_type = mime_type
+            .media_type()
+            .ok_or_else(|| MediaError::UnsupportedType(mime_type.to_string()))?;
 
         // Get extension from source file or MIME type
         let extension = source_path
             .extension()
             .and_then(|e| e.to_str())
-            .or_else(|| get_extension_for_mime(&mime_type))
-            .unwrap_or("bin");
+            .or_else(|| mime_type.extension())
+            .ok_or_else(unknown_media_type)?;
 
         // Generate filename and path
-        let filename = format!("{}.{}", Uuid::new_v4(), extension);
-        let relative_path = format!("{}/{}", media_type.subdir(), filename);
-        let full_path = self.media_root.join(&relative_path);
-
-        // Ensure directory exists
-        if let Some(parent) = full_path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
+        let original_name = source_path.file_name().and_then(|n| n.to_str());
+        let mut source = tokio::fs::File::open(source_path).await?;
+        let (relative_path, full_path, mut file) = self
+            .create_media_file(media_type.subdir(), original_name, extension)
+            .await?;
 
         // Copy file
-        tokio::fs::copy(source_path, &full_path).await?;
+        let copied = tokio::io::copy(&mut source, &mut file).await?;
+        file.flush().await?;
+        if copied == 0 {
+            drop(file);
+            tokio::fs::remove_file(&full_path).await?;
+            return Err(MediaError::Download("empty response".into()));
+        }
+        drop(file);
+        self.downscale_image(&full_path, &mime_type)?;
+        let content_hash = sha256_file(&full_path).await?;
 
         info!(path = %relative_path, "Media file imported");
 
-        // Extract metadata
-        let (width, height) = if media_type == MediaType::Image {
-            extract_image_dimensions(&full_path).unwrap_or((None, None))
-        } else {
-            (None, None)
-        };
+        // Extract metadata; a failure is reported, not fatal
+        let (width, height, metadata_error) = extract_dimensions(&full_path, media_type);
+        reject_empty_image(&full_path, width, height).await?;
 
         Ok(MediaInfo {
             file_path: relative_path,
             mime_type,
             width,
             height,
+            aspect_ratio: aspect_ratio(width, height),
             duration: None, // TODO: Extract duration for video/audio
             original_url: None,
+            content_hash: Some(content_hash),
+            metadata_error,
         })
     }
 
@@ -380,7 +1049,7 @@ impl MediaService {
     /// # Arguments
     ///
     /// * `file_path` - Relative path within media directory
-    #[instrument(skip(self), fields(path = %file_path))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(path = %file_path)))]
     pub async fn delete(&self, file_path: &str) -> MediaResult<()> {
         let full_path = self.validate_path(file_path)?;
 
@@ -392,6 +1061,85 @@ impl MediaService {
         Ok(())
     }
 
+    /// Convert a stored image to another format, saving it under a new name
+    /// and removing the original.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Relative path of the image within media directory
+    /// * `target_mime` - The format to convert to, e.g. `image/webp`
+    ///
+    /// # Returns
+    ///
+    /// `MediaInfo` for the new file. WebP output is lossless.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MediaError::UnsupportedType` if the target isn't an image
+    /// format Garden can write or the file can't be decoded as an image.
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(path = %file_path)))]
+    pub async fn reencode(&self, file_path: &str, target_mime: &str) -> MediaResult<MediaInfo> {
+        let info = self.write_reencoded(file_path, target_mime).await?;
+        self.delete(file_path).await?;
+        Ok(info)
+    }
+
+    /// Like [`Self::reencode`], but leaves the original file in place, so
+    /// callers can switch references over before removing it.
+    pub(crate) async fn write_reencoded(
+        &self,
+        file_path: &str,
+        target_mime: &str,
+    ) -> MediaResult<MediaInfo> {
+        let source = self.validate_path(file_path)?;
+        let target = MimeType::new(target_mime);
+        let (Some(MediaType::Image), Some(extension), Some(format)) = (
+            target.media_type(),
+            target.extension(),
+            image::ImageFormat::from_mime_type(target.as_str()),
+        ) else {
+            return Err(MediaError::UnsupportedType(target.to_string()));
+        };
+
+        let img = image::open(&source).map_err(|e| match e {
+            image::ImageError::IoError(e) => MediaError::FileRead(e),
+            e => MediaError::UnsupportedType(format!("cannot decode {}: {}", file_path, e)),
+        })?;
+
+        let mut bytes = Vec::new();
+        let mut out = std::io::Cursor::new(&mut bytes);
+        // JPEG has no alpha channel and the WebP encoder takes only 8-bit color
+        let encoded = match format {
+            image::ImageFormat::Jpeg => img.to_rgb8().write_with_encoder(
+                image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, self.jpeg_quality),
+            ),
+            image::ImageFormat::WebP => img.to_rgba8().write_to(&mut out, format),
+            _ => img.write_to(&mut out, format),
+        };
+        encoded.map_err(|e| MediaError::FileWrite(format!("cannot re-encode image: {}", e)))?;
+
+        let (relative_path, _, mut file) = self
+            .create_media_file(MediaType::Image.subdir(), None, extension)
+            .await?;
+        file.write_all(&bytes).await?;
+        file.flush().await?;
+        drop(file);
+
+        info!(from = %file_path, to = %relative_path, "Image re-encoded");
+        let (width, height) = img.dimensions();
+        Ok(MediaInfo {
+            file_path: relative_path,
+            mime_type: target,
+            width: Some(width),
+            height: Some(height),
+            aspect_ratio: aspect_ratio(Some(width), Some(height)),
+            duration: None,
+            original_url: None,
+            content_hash: Some(format!("{:x}", Sha256::digest(&bytes))),
+            metadata_error: None,
+        })
+    }
+
     /// Get the full filesystem path for a media file.
     ///
     /// # Arguments
@@ -418,44 +1166,393 @@ impl MediaService {
         let full_path = self.validate_path(file_path)?;
         Ok(full_path.exists())
     }
+
+    /// Check a media file against the hash recorded when it was imported.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - Relative path within media directory
+    /// * `expected_hash` - Hex-encoded SHA-256, in either case
+    ///
+    /// # Returns
+    ///
+    /// `true` if the file's current SHA-256 matches, `false` if it differs
+    /// or the file is missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MediaError::InvalidPath` if the path attempts traversal outside media directory.
+    #[cfg_attr(feature = "tracing", instrument(skip(self, expected_hash)))]
+    pub async fn verify(&self, file_path: &str, expected_hash: &str) -> MediaResult<bool> {
+        let full_path = self.validate_path(file_path)?;
+        let actual = match sha256_file(&full_path).await {
+            Ok(hash) => hash,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                warn!("Media file missing");
+                return Ok(false);
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let matches = actual.eq_ignore_ascii_case(expected_hash.trim());
+        if !matches {
+            warn!("Media file hash mismatch");
+        }
+        Ok(matches)
+    }
+}
+
+/// The error for an import whose type can't be worked out from its
+/// `Content-Type` or file extension.
+fn unknown_media_type() -> MediaError {
+    MediaError::UnsupportedType("could not determine media type".into())
+}
+
+/// The error for an import stopped through its cancellation token.
+fn download_cancelled() -> MediaError {
+    MediaError::Download("cancelled".into())
+}
+
+/// Stream a response body into `file`, returning its SHA-256 hash.
+///
+/// Stops early if `cancel` is triggered or the body grows past
+/// [`MAX_DOWNLOAD_SIZE`]; the caller removes the partial file.
+async fn write_download(
+    response: &mut reqwest::Response,
+    file: &mut tokio::fs::File,
+    cancel: &CancellationToken,
+) -> MediaResult<String> {
+    let mut hasher = Sha256::new();
+    let mut size: u64 = 0;
+    while let Some(chunk) = cancel
+        .run_until_cancelled(response.chunk())
+        .await
+        .ok_or_else(download_cancelled)??
+    {
+        size += chunk.len() as u64;
+        if size > MAX_DOWNLOAD_SIZE {
+            return Err(MediaError::FileTooLarge {
+                size,
+                max: MAX_DOWNLOAD_SIZE,
+            });
+        }
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    if size == 0 {
+        return Err(MediaError::Download("empty response".into()));
+    }
+
+    // tokio buffers file writes; flush so later metadata reads see them
+    file.flush().await?;
+    Ok(format!("{:x}", hasher.finalize()))
 }
 
-/// Extract image dimensions from a file.
-fn extract_image_dimensions(path: &Path) -> Option<(Option<u32>, Option<u32>)> {
+/// Parse `url`, allowing only HTTP and HTTPS.
+fn parse_http_url(url: &str) -> MediaResult<url::Url> {
+    let parsed =
+        url::Url::parse(url).map_err(|e| MediaError::InvalidUrl(format!("Invalid URL: {}", e)))?;
+    if !["http", "https"].contains(&parsed.scheme()) {
+        return Err(MediaError::InvalidUrl(format!(
+            "Only HTTP/HTTPS URLs allowed, got: {}",
+            parsed.scheme()
+        )));
+    }
+    Ok(parsed)
+}
+
+/// Hash a file with SHA-256 without reading it into memory at once.
+async fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Copy a file to `to`, creating its parent directory.
+///
+/// The destination is opened with `create_new`, so an existing file is never
+/// overwritten.
+async fn copy_new(from: &Path, to: &Path) -> std::io::Result<u64> {
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut source = tokio::fs::File::open(from).await?;
+    let mut dest = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(to)
+        .await?;
+    let copied = tokio::io::copy(&mut source, &mut dest).await;
+    match copied {
+        Ok(bytes) => dest.flush().await.map(|_| bytes),
+        Err(e) => {
+            drop(dest);
+            let _ = tokio::fs::remove_file(to).await;
+            Err(e)
+        }
+    }
+}
+
+/// Extract a stored file's dimensions.
+///
+/// Returns the width, height, and why extraction failed, if it did. Only
+/// images are measured; other media report no dimensions and no error.
+fn extract_dimensions(
+    path: &Path,
+    media_type: MediaType,
+) -> (Option<u32>, Option<u32>, Option<String>) {
+    if media_type != MediaType::Image {
+        return (None, None, None);
+    }
     match image::open(path) {
         Ok(img) => {
             let (width, height) = img.dimensions();
-            Some((Some(width), Some(height)))
+            (Some(width), Some(height), None)
         }
         Err(e) => {
             error!(error = %e, "Failed to read image dimensions");
-            None
+            (None, None, Some(e.to_string()))
+        }
+    }
+}
+
+/// Reject an image that decoded to zero width or height, removing its file.
+async fn reject_empty_image(
+    path: &Path,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> MediaResult<()> {
+    if width == Some(0) || height == Some(0) {
+        tokio::fs::remove_file(path).await?;
+        return Err(MediaError::Metadata("image has no pixels".into()));
+    }
+    Ok(())
+}
+
+/// Maximum length of the original name kept in a stored filename, in chars.
+const MAX_ORIGINAL_NAME_LEN: usize = 64;
+
+/// Reduce a filename to a stem that is safe to embed in a stored path.
+///
+/// Drops the extension, replaces everything but letters, digits, `-`, and
+/// `_` with `-`, and collapses repeats. Path separators, dots, and control
+/// characters can never survive, so the result cannot escape the media
+/// directory. Returns `None` if nothing usable is left.
+fn sanitize_file_stem(name: &str) -> Option<String> {
+    let stem = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.contains(['/', '\\']) => stem,
+        _ => name,
+    };
+
+    let mut sanitized = String::new();
+    for c in stem.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            sanitized.push(c);
+        } else if !sanitized.is_empty() && !sanitized.ends_with('-') {
+            sanitized.push('-');
         }
     }
+    let sanitized: String = sanitized
+        .trim_end_matches('-')
+        .chars()
+        .take(MAX_ORIGINAL_NAME_LEN)
+        .collect();
+    let sanitized = sanitized.trim_end_matches('-');
+
+    (!sanitized.is_empty()).then(|| sanitized.to_string())
 }
 
-/// Get file extension for a MIME type.
-fn get_extension_for_mime(mime: &str) -> Option<&'static str> {
-    match mime {
-        // Images
-        "image/jpeg" => Some("jpg"),
-        "image/png" => Some("png"),
-        "image/gif" => Some("gif"),
-        "image/webp" => Some("webp"),
-        "image/svg+xml" => Some("svg"),
-        // Videos
-        "video/mp4" => Some("mp4"),
-        "video/webm" => Some("webm"),
-        "video/quicktime" => Some("mov"),
-        "video/x-msvideo" => Some("avi"),
-        // Audio
-        "audio/mpeg" => Some("mp3"),
-        "audio/ogg" => Some("ogg"),
-        "audio/wav" => Some("wav"),
-        "audio/webm" => Some("webm"),
-        "audio/flac" => Some("flac"),
-        _ => None,
+/// Decode `%XX` escapes in a URL path segment, replacing invalid UTF-8.
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
     }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Pick the title and description out of an HTML page.
+fn parse_link_metadata(html: &str) -> LinkMetadata {
+    // ASCII lowercasing keeps byte offsets, so matches index `html` too
+    let lower = html.to_ascii_lowercase();
+    let mut og_title = None;
+    let mut og_description = None;
+    let mut description = None;
+
+    let mut rest = 0;
+    while let Some(found) = lower[rest..].find("<meta") {
+        let start = rest + found;
+        let Some(len) = lower[start..].find('>') else {
+            break;
+        };
+        let tag = &html[start..start + len];
+        rest = start + len;
+
+        let key = html_attribute(tag, "property").or_else(|| html_attribute(tag, "name"));
+        let Some(content) = html_attribute(tag, "content") else {
+            continue;
+        };
+        let slot = match key.map(|k| k.to_ascii_lowercase()).as_deref() {
+            Some("og:title") => &mut og_title,
+            Some("og:description") => &mut og_description,
+            Some("description") => &mut description,
+            _ => continue,
+        };
+        if slot.is_none() {
+            *slot = Some(content);
+        }
+    }
+
+    let title = og_title.or_else(|| {
+        let start = lower.find("<title")?;
+        let start = start + lower[start..].find('>')? + 1;
+        let len = lower[start..].find("</title")?;
+        Some(decode_html_entities(&html[start..start + len]))
+    });
+
+    let clean = |text: Option<String>| {
+        text.map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|t| !t.is_empty())
+    };
+    LinkMetadata {
+        title: clean(title),
+        description: clean(og_description.or(description)),
+    }
+}
+
+/// The decoded value of attribute `name` in an HTML tag, if present.
+fn html_attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut rest = 0;
+    while let Some(found) = lower[rest..].find(name) {
+        let start = rest + found;
+        rest = start + name.len();
+        // Must be a whole attribute name, e.g. not `data-name`
+        let before = lower[..start].chars().next_back();
+        if !before.is_some_and(char::is_whitespace) {
+            continue;
+        }
+        let after = tag[rest..].trim_start();
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let raw = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default(),
+        };
+        return Some(decode_html_entities(raw));
+    }
+    None
+}
+
+/// Decode the handful of HTML entities common in titles.
+fn decode_html_entities(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Token-bucket limiter for outbound downloads.
+///
+/// Buckets hold at most one token, so downloads are spaced `1 / rate` apart.
+/// A caller that finds the bucket empty takes the token anyway, leaving it
+/// in debt, and sleeps until the debt is paid; concurrent callers therefore
+/// queue up rather than all waking at once.
+#[derive(Debug)]
+struct RateLimiter {
+    /// Tokens added per second.
+    rate: f64,
+    /// Whether each host gets its own bucket.
+    per_host: bool,
+    /// Buckets by host, or a single bucket under `""`.
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// A limiter allowing `per_second` downloads per second, or `None` for
+    /// no limit.
+    fn new(per_second: u32, per_host: bool) -> Option<Self> {
+        (per_second > 0).then(|| Self {
+            rate: f64::from(per_second),
+            per_host,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Wait until a download from `host` is allowed.
+    async fn acquire(&self, host: &str) {
+        let wait = self.reserve(host, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Take a token from `host`'s bucket, returning how long to wait before
+    /// it is actually available.
+    fn reserve(&self, host: &str, now: Instant) -> Duration {
+        let key = if self.per_host { host } else { "" };
+        // A poisoned lock only means another import panicked mid-update;
+        // the bucket is still usable
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: 1.0,
+            refilled_at: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(1.0);
+        bucket.refilled_at = now;
+        bucket.tokens -= 1.0;
+
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        }
+    }
+}
+
+/// Returns true if the MIME type is an image or video (allowed in galleries).
+fn is_visual(mime: &MimeType) -> bool {
+    matches!(mime.media_type(), Some(MediaType::Image | MediaType::Video))
 }
 
 #[cfg(test)]
@@ -478,22 +1575,843 @@ mod tests {
     }
 
     #[test]
-    fn test_get_extension_for_mime() {
-        assert_eq!(get_extension_for_mime("image/jpeg"), Some("jpg"));
-        assert_eq!(get_extension_for_mime("video/mp4"), Some("mp4"));
-        assert_eq!(get_extension_for_mime("audio/mpeg"), Some("mp3"));
-        assert_eq!(get_extension_for_mime("unknown/type"), None);
+    fn test_media_info_into_gallery_item() {
+        let info = MediaInfo {
+            file_path: "images/test.jpg".to_string(),
+            mime_type: MimeType::new("image/jpeg"),
+            width: Some(800),
+            height: Some(600),
+            aspect_ratio: Some(800.0 / 600.0),
+            duration: None,
+            original_url: Some("https://example.com/test.jpg".to_string()),
+            content_hash: Some("abc123".to_string()),
+            metadata_error: None,
+        };
+
+        let item = info.into_gallery_item();
+        assert_eq!(item.file_path, "images/test.jpg");
+        assert_eq!(item.mime_type, "image/jpeg");
+        assert_eq!(item.width, Some(800));
+        assert_eq!(item.content_hash.as_deref(), Some("abc123"));
+        assert!(item.alt_text.is_none());
+    }
+
+    /// Create a unique scratch directory under the system temp dir.
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("garden-media-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_import_lands_in_override_root() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+
+        let root = scratch.join("external-drive");
+        MediaService::prepare_root(&root).unwrap();
+        let service = MediaService::new(&root);
+
+        let info = service.import_from_file(&source).await.unwrap();
+        assert!(info.file_path.starts_with("images/"));
+        assert!(root.join(&info.file_path).exists());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reencode_replaces_file() {
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, png_bytes(3, 2)).unwrap();
+        let original = service.import_from_file(&source).await.unwrap();
+
+        let result = service.reencode(&original.file_path, "text/plain").await;
+        assert!(matches!(result, Err(MediaError::UnsupportedType(_))));
+        assert!(service.exists(&original.file_path).unwrap());
+
+        let info = service
+            .reencode(&original.file_path, "image/jpeg")
+            .await
+            .unwrap();
+        assert_eq!(info.mime_type, "image/jpeg");
+        assert!(info.file_path.ends_with(".jpg"));
+        assert_eq!((info.width, info.height), (Some(3), Some(2)));
+        assert!(!service.exists(&original.file_path).unwrap());
+        assert!(service
+            .verify(&info.file_path, info.content_hash.as_deref().unwrap())
+            .await
+            .unwrap());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_moves_files() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+
+        let old_root = scratch.join("old");
+        let new_root = scratch.join("new");
+        let service = MediaService::new(&old_root);
+        let info = service.import_from_file(&source).await.unwrap();
+
+        let moved = service.migrate_to(&new_root).await.unwrap();
+
+        assert_eq!(moved, 1);
+        assert!(new_root.join(&info.file_path).exists());
+        assert!(!old_root.join(&info.file_path).exists());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_rejects_overlapping_roots() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+
+        let root = scratch.join("media");
+        let service = MediaService::new(&root);
+        let info = service.import_from_file(&source).await.unwrap();
+
+        for new_root in [root.clone(), root.join("nested"), scratch.clone()] {
+            let result = service.migrate_to(&new_root).await;
+            assert!(matches!(result, Err(MediaError::InvalidPath(_))));
+        }
+        assert!(root.join(&info.file_path).exists());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_never_overwrites() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+
+        let old_root = scratch.join("old");
+        let new_root = scratch.join("new");
+        let service = MediaService::new(&old_root);
+        let first = service.import_from_file(&source).await.unwrap();
+        let second = service.import_from_file(&source).await.unwrap();
+        MediaService::prepare_root(&new_root).unwrap();
+        std::fs::write(new_root.join(&second.file_path), b"keep me").unwrap();
+
+        let result = service.migrate_to(&new_root).await;
+
+        assert!(matches!(result, Err(MediaError::FileWrite(_))));
+        assert_eq!(
+            std::fs::read(new_root.join(&second.file_path)).unwrap(),
+            b"keep me"
+        );
+        assert!(!new_root.join(&first.file_path).exists());
+        assert!(old_root.join(&first.file_path).exists());
+        assert!(old_root.join(&second.file_path).exists());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_relocate_moves_media_directory() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+
+        let old_root = scratch.join("old");
+        let new_root = scratch.join("new");
+        let mut service = MediaService::new(&old_root);
+        let info = service.import_from_file(&source).await.unwrap();
+
+        service.relocate(&new_root).await.unwrap();
+
+        assert_eq!(service.media_root(), new_root.as_path());
+        assert!(service.exists(&info.file_path).unwrap());
+        assert!(new_root.join(&info.file_path).exists());
+        assert!(!old_root.join(&info.file_path).exists());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    /// Serve one HTTP response on a local port and return the server address.
+    async fn serve_once(content_type: &'static str, body: Vec<u8>) -> std::net::SocketAddr {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                content_type,
+                body.len()
+            );
+            socket.write_all(head.as_bytes()).await.unwrap();
+            socket.write_all(&body).await.unwrap();
+        });
+        addr
+    }
+
+    /// Encode a tiny PNG image.
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::DynamicImage::new_rgb8(width, height)
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        bytes
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_spaces_url_imports() {
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch).with_rate_limit(10);
+
+        let mut urls = Vec::new();
+        for _ in 0..4 {
+            let addr = serve_once("image/png", png_bytes(1, 1)).await;
+            urls.push(format!("http://{}/photo.png", addr));
+        }
+
+        let started = Instant::now();
+        let imported = service.import_gallery(&urls).await.unwrap();
+        let elapsed = started.elapsed();
+
+        // The first download goes straight away, the other three wait 100ms
+        // each; leave slack for coarse timers
+        assert_eq!(imported.len(), 4);
+        assert!(elapsed >= Duration::from_millis(250), "took {:?}", elapsed);
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[test]
+    fn test_rate_limiter_per_host_buckets() {
+        let limiter = RateLimiter::new(2, true).unwrap();
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve("a.example", now), Duration::ZERO);
+        assert_eq!(limiter.reserve("b.example", now), Duration::ZERO);
+        assert_eq!(
+            limiter.reserve("a.example", now),
+            Duration::from_millis(500)
+        );
+        assert_eq!(limiter.reserve("a.example", now), Duration::from_secs(1));
+        assert!(RateLimiter::new(0, true).is_none());
+    }
+
+    #[test]
+    fn test_parse_link_metadata() {
+        let html = r#"<html><head>
+            <title>Fallback</title>
+            <meta name="description" content="Plain description">
+            <meta property="og:title" content="Tom &amp; Jerry" />
+            <META PROPERTY='og:description' CONTENT='A "classic"'>
+            </head><body>...</body></html>"#;
+
+        let meta = parse_link_metadata(html);
+
+        assert_eq!(meta.title.as_deref(), Some("Tom & Jerry"));
+        assert_eq!(meta.description.as_deref(), Some("A \"classic\""));
+
+        let meta = parse_link_metadata(
+            "<title>\n  Just a title\n</title><meta name=\"description\" content=\"Desc\">",
+        );
+        assert_eq!(meta.title.as_deref(), Some("Just a title"));
+        assert_eq!(meta.description.as_deref(), Some("Desc"));
+        assert_eq!(parse_link_metadata("no tags"), LinkMetadata::default());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_link_metadata() {
+        let page = r#"<head><meta property="og:title" content="Served"></head>"#;
+        let addr = serve_once("text/html", page.as_bytes().to_vec()).await;
+        let service = MediaService::new(std::env::temp_dir());
+
+        let meta = service
+            .fetch_link_metadata(&format!("http://{}/article", addr))
+            .await
+            .unwrap();
+
+        assert_eq!(meta.title.as_deref(), Some("Served"));
+        assert!(meta.description.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_import_from_url_octet_stream_uses_extension() {
+        let addr = serve_once("application/octet-stream", png_bytes(3, 2)).await;
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+
+        let info = service
+            .import_from_url(
+                &format!("http://{}/cdn/photo.png", addr),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(info.mime_type, "image/png");
+        assert!(info.file_path.starts_with("images/"));
+        assert!(info.file_path.ends_with(".png"));
+        assert_eq!((info.width, info.height), (Some(3), Some(2)));
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_from_url_trusts_header_when_fallback_disabled() {
+        let addr = serve_once("application/octet-stream", png_bytes(1, 1)).await;
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch).with_generic_content_types(Vec::<String>::new());
+
+        let result = service
+            .import_from_url(
+                &format!("http://{}/cdn/photo.png", addr),
+                &CancellationToken::new(),
+            )
+            .await;
+
+        assert!(
+            matches!(&result, Err(MediaError::UnsupportedType(msg)) if msg == "could not determine media type"),
+            "{result:?}"
+        );
+        assert_eq!(count_files(&scratch), 0);
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_from_url_without_extension_or_type_fails() {
+        let addr = serve_once("application/octet-stream", png_bytes(1, 1)).await;
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+
+        let result = service
+            .import_from_url(
+                &format!("http://{}/cdn/download", addr),
+                &CancellationToken::new(),
+            )
+            .await;
+
+        assert!(
+            matches!(&result, Err(MediaError::UnsupportedType(msg)) if msg == "could not determine media type"),
+            "{result:?}"
+        );
+        assert_eq!(count_files(&scratch), 0);
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[test]
+    fn test_supported_types_match_extension_table() {
+        let types = MediaService::supported_types();
+
+        for (mime, extension, media_type) in [
+            ("image/jpeg", "jpg", MediaType::Image),
+            ("video/mp4", "mp4", MediaType::Video),
+            ("audio/mpeg", "mp3", MediaType::Audio),
+        ] {
+            assert!(types
+                .iter()
+                .any(|t| t.mime == mime && t.extension == extension && t.media_type == media_type));
+        }
+        for supported in &types {
+            assert_eq!(
+                supported.mime.extension(),
+                Some(supported.extension.as_str())
+            );
+            assert_eq!(supported.mime.media_type(), Some(supported.media_type));
+        }
+    }
+
+    #[test]
+    fn test_resolve_content_type_prefers_specific_header() {
+        let service = MediaService::new(std::env::temp_dir());
+        let url = url::Url::parse("https://example.com/photo.png?v=2").unwrap();
+
+        assert_eq!(
+            service.resolve_content_type(Some("image/jpeg"), &url),
+            Some(MimeType::new("image/jpeg"))
+        );
+        assert_eq!(
+            service.resolve_content_type(Some("binary/octet-stream"), &url),
+            Some(MimeType::new("image/png"))
+        );
+        assert_eq!(
+            service.resolve_content_type(None, &url),
+            Some(MimeType::new("image/png"))
+        );
+
+        let bare = url::Url::parse("https://example.com/download").unwrap();
+        assert_eq!(
+            service.resolve_content_type(Some("application/octet-stream"), &bare),
+            None
+        );
+        assert_eq!(service.resolve_content_type(None, &bare), None);
+    }
+
+    #[tokio::test]
+    async fn test_source_index_keeps_corrupt_entries() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+        let service = MediaService::new(&scratch);
+        let info = service.import_from_file(&source).await.unwrap();
+        let index = scratch.join(SOURCE_INDEX_FILE);
+        std::fs::write(&index, "{\"url\": truncated").unwrap();
+
+        let url = "https://example.com/photo.png";
+        service.record_source(url, &info).await;
+
+        let found = service.find_by_source_url(url).await.unwrap().unwrap();
+        assert_eq!(found.file_path, info.file_path);
+        assert!(std::fs::read_to_string(&index)
+            .unwrap()
+            .starts_with("{\"url\": truncated\n"));
+        assert!(service
+            .find_by_source_url("https://example.com/other.png")
+            .await
+            .unwrap()
+            .is_none());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_source_url_reuse_skips_second_download() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            let body = png_bytes(1, 1);
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: image/png\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                socket.write_all(head.as_bytes()).await.unwrap();
+                socket.write_all(&body).await.unwrap();
+            }
+        });
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch).with_source_url_reuse(true);
+        let url = format!("http://{}/photo.png", addr);
+
+        let first = service
+            .import_from_url(&url, &CancellationToken::new())
+            .await
+            .unwrap();
+        let second = service
+            .import_from_url(&url, &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(second.file_path, first.file_path);
+        assert_eq!(second.content_hash, first.content_hash);
+        assert_eq!(count_files(&scratch), 1);
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_link_classifies_responses() {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0u8; 1024];
+                let n = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..n]);
+                let mut line = request.split_whitespace();
+                let (method, path) = (line.next().unwrap_or(""), line.next().unwrap_or(""));
+                let head = match (method, path) {
+                    (_, "/ok") => "200 OK\r\n",
+                    (_, "/moved") => "301 Moved Permanently\r\nLocation: /ok\r\n",
+                    ("HEAD", "/get-only") => "405 Method Not Allowed\r\n",
+                    ("GET", "/get-only") => "200 OK\r\n",
+                    _ => "404 Not Found\r\n",
+                };
+                let response = format!(
+                    "HTTP/1.1 {}Content-Length: 0\r\nConnection: close\r\n\r\n",
+                    head
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        let service = MediaService::new(std::env::temp_dir());
+        let url = |path: &str| format!("http://{}{}", addr, path);
+
+        assert_eq!(
+            service.check_link(&url("/ok")).await.unwrap(),
+            LinkStatus::Ok
+        );
+        assert_eq!(
+            service.check_link(&url("/moved")).await.unwrap(),
+            LinkStatus::Redirected(url("/ok"))
+        );
+        assert_eq!(
+            service.check_link(&url("/gone")).await.unwrap(),
+            LinkStatus::NotFound
+        );
+        assert_eq!(
+            service.check_link(&url("/get-only")).await.unwrap(),
+            LinkStatus::Ok
+        );
+        assert!(matches!(
+            service.check_link("file:///etc/passwd").await,
+            Err(MediaError::InvalidUrl(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_import_from_file_keeps_original_name() {
+        let scratch = temp_dir();
+        let source = scratch.join("Summer Trip.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+        let service = MediaService::new(scratch.join("media")).with_original_filenames(true);
+
+        let info = service.import_from_file(&source).await.unwrap();
+
+        assert!(info.file_path.starts_with("images/"));
+        assert!(info.file_path.ends_with("-Summer-Trip.png"));
+        assert!(service.exists(&info.file_path).unwrap());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_from_url_keeps_original_name() {
+        let addr = serve_once("image/png", png_bytes(1, 1)).await;
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch).with_original_filenames(true);
+
+        let info = service
+            .import_from_url(
+                &format!("http://{}/cdn/sunset%20beach.png?w=200", addr),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+        assert!(info.file_path.ends_with("-sunset-beach.png"));
+        assert!(service.exists(&info.file_path).unwrap());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_original_name_off_by_default() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+        let service = MediaService::new(scratch.join("media"));
+
+        let info = service.import_from_file(&source).await.unwrap();
+
+        assert!(!info.file_path.contains("photo"));
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_image_reports_metadata_error() {
+        let scratch = temp_dir();
+        let corrupt = scratch.join("broken.png");
+        std::fs::write(&corrupt, b"not really a png").unwrap();
+        let valid = scratch.join("fine.png");
+        std::fs::write(&valid, png_bytes(4, 3)).unwrap();
+        let service = MediaService::new(scratch.join("media"));
+
+        let info = service.import_from_file(&corrupt).await.unwrap();
+
+        assert!(service.exists(&info.file_path).unwrap());
+        assert_eq!((info.width, info.height), (None, None));
+        assert!(info.metadata_error.is_some());
+
+        let info = service.import_from_file(&valid).await.unwrap();
+        assert_eq!((info.width, info.height), (Some(4), Some(3)));
+        assert!(info.metadata_error.is_none());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    /// Count the files stored in a media root's type subdirectories.
+    fn count_files(root: &Path) -> usize {
+        [MediaType::Image, MediaType::Video, MediaType::Audio]
+            .iter()
+            .filter_map(|t| std::fs::read_dir(root.join(t.subdir())).ok())
+            .flatten()
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_download_removes_partial_file() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Send the headers and a first chunk, then stall as a slow server would
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let head = "HTTP/1.1 200 OK\r\nContent-Type: video/mp4\r\nContent-Length: 1000000\r\nConnection: close\r\n\r\n";
+            socket.write_all(head.as_bytes()).await.unwrap();
+            socket.write_all(&[0u8; 1024]).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(30)).await;
+        });
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+        let cancel = CancellationToken::new();
+
+        let trigger = cancel.clone();
+        let videos = scratch.join(MediaType::Video.subdir());
+        tokio::spawn(async move {
+            // Cancel once the partial file is on disk
+            while std::fs::read_dir(&videos).map_or(0, |d| d.count()) == 0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            trigger.cancel();
+        });
+        let result = tokio::time::timeout(
+            Duration::from_secs(10),
+            service.import_from_url(&format!("http://{}/big.mp4", addr), &cancel),
+        )
+        .await
+        .expect("cancellation should stop the download");
+
+        assert!(matches!(result, Err(MediaError::Download(ref m)) if m == "cancelled"));
+        assert_eq!(count_files(&scratch), 0);
+        let _ = std::fs::remove_dir_all(&scratch);
+    }
+
+    #[tokio::test]
+    async fn test_empty_download_rejected() {
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+        let addr = serve_once("image/png", Vec::new()).await;
+
+        let result = service
+            .import_from_url(
+                &format!("http://{}/empty.png", addr),
+                &CancellationToken::new(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(MediaError::Download(ref m)) if m == "empty response"));
+        assert_eq!(count_files(&scratch), 0);
+        let _ = std::fs::remove_dir_all(&scratch);
+    }
+
+    #[tokio::test]
+    async fn test_empty_local_file_rejected() {
+        let scratch = temp_dir();
+        let empty = scratch.join("empty.png");
+        std::fs::write(&empty, b"").unwrap();
+        let media_root = scratch.join("media");
+        let service = MediaService::new(&media_root);
+
+        let result = service.import_from_file(&empty).await;
+
+        assert!(matches!(result, Err(MediaError::Download(ref m)) if m == "empty response"));
+        assert_eq!(count_files(&media_root), 0);
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_storage_usage_totals() {
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+        assert_eq!(
+            service.storage_usage().await.unwrap(),
+            StorageUsage::default()
+        );
+
+        std::fs::create_dir_all(scratch.join("images")).unwrap();
+        std::fs::create_dir_all(scratch.join("audio")).unwrap();
+        std::fs::create_dir_all(scratch.join("thumbs")).unwrap();
+        std::fs::write(scratch.join("images/a.png"), vec![0u8; 100]).unwrap();
+        std::fs::write(scratch.join("images/b.png"), vec![0u8; 50]).unwrap();
+        std::fs::write(scratch.join("audio/c.mp3"), vec![0u8; 7]).unwrap();
+        std::fs::write(scratch.join("thumbs/a.png"), vec![0u8; 1000]).unwrap();
+
+        let usage = service.storage_usage().await.unwrap();
+
+        assert_eq!((usage.total_bytes, usage.file_count), (157, 3));
+        assert_eq!((usage.images.bytes, usage.images.file_count), (150, 2));
+        assert_eq!((usage.audio.bytes, usage.audio.file_count), (7, 1));
+        assert_eq!(usage.videos.file_count, 0);
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_max_image_dimension_downscales_long_edge() {
+        let scratch = temp_dir();
+        let large = scratch.join("large.png");
+        std::fs::write(&large, png_bytes(400, 200)).unwrap();
+        let small = scratch.join("small.png");
+        std::fs::write(&small, png_bytes(40, 20)).unwrap();
+        let service = MediaService::new(scratch.join("media")).with_max_image_dimension(100);
+
+        let info = service.import_from_file(&large).await.unwrap();
+
+        assert_eq!((info.width, info.height), (Some(100), Some(50)));
+        let stored = image::open(service.get_full_path(&info.file_path).unwrap()).unwrap();
+        assert_eq!(stored.dimensions(), (100, 50));
+        let hash = info.content_hash.unwrap();
+        assert!(service.verify(&info.file_path, &hash).await.unwrap());
+
+        let info = service.import_from_file(&small).await.unwrap();
+        assert_eq!((info.width, info.height), (Some(40), Some(20)));
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_stores_aspect_ratio() {
+        let scratch = temp_dir();
+        let source = scratch.join("landscape.png");
+        std::fs::write(&source, png_bytes(800, 600)).unwrap();
+        let service = MediaService::new(scratch.join("media"));
+
+        let info = service.import_from_file(&source).await.unwrap();
+        let ratio = info.aspect_ratio.unwrap();
+        assert!((ratio - 4.0 / 3.0).abs() < 1e-3, "ratio {}", ratio);
+
+        match info.into_block_content() {
+            BlockContent::Image { aspect_ratio, .. } => assert_eq!(aspect_ratio, Some(ratio)),
+            _ => panic!("Expected Image content"),
+        }
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_matches_import_hash() {
+        let scratch = temp_dir();
+        let source = scratch.join("photo.png");
+        std::fs::write(&source, b"not really a png").unwrap();
+        let service = MediaService::new(scratch.join("media"));
+
+        let info = service.import_from_file(&source).await.unwrap();
+        let hash = info.content_hash.unwrap();
+        assert_eq!(hash.len(), 64);
+        assert!(service.verify(&info.file_path, &hash).await.unwrap());
+        assert!(service
+            .verify(&info.file_path, &hash.to_uppercase())
+            .await
+            .unwrap());
+
+        // Tampered and missing files both fail
+        let full_path = service.get_full_path(&info.file_path).unwrap();
+        std::fs::write(&full_path, b"tampered").unwrap();
+        assert!(!service.verify(&info.file_path, &hash).await.unwrap());
+        std::fs::remove_file(&full_path).unwrap();
+        assert!(!service.verify(&info.file_path, &hash).await.unwrap());
+
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_media_file_skips_taken_path() {
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+        let taken = Uuid::new_v4();
+        let fresh = Uuid::new_v4();
+        std::fs::create_dir_all(scratch.join("images")).unwrap();
+        std::fs::write(scratch.join(format!("images/{}.png", taken)), b"existing").unwrap();
+
+        let mut ids = [taken, fresh].into_iter();
+        let (relative_path, _, _) = service
+            .create_media_file_with("images", None, "png", || ids.next().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(relative_path, format!("images/{}.png", fresh));
+        assert_eq!(
+            std::fs::read(scratch.join(format!("images/{}.png", taken))).unwrap(),
+            b"existing"
+        );
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_media_file_gives_up_after_retries() {
+        let scratch = temp_dir();
+        let service = MediaService::new(&scratch);
+        let taken = Uuid::new_v4();
+        std::fs::create_dir_all(scratch.join("images")).unwrap();
+        std::fs::write(scratch.join(format!("images/{}.png", taken)), b"existing").unwrap();
+
+        let result = service
+            .create_media_file_with("images", None, "png", || taken)
+            .await;
+
+        assert!(matches!(result, Err(MediaError::FileWrite(_))));
+        std::fs::remove_dir_all(&scratch).unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_file_stem_strips_hostile_names() {
+        assert_eq!(
+            sanitize_file_stem("../../etc/passwd").as_deref(),
+            Some("etc-passwd")
+        );
+        assert_eq!(
+            sanitize_file_stem("..\\..\\boot.ini").as_deref(),
+            Some("boot")
+        );
+        assert_eq!(
+            sanitize_file_stem("evil\u{0}\nname\u{202e}gnp.exe").as_deref(),
+            Some("evil-name-gnp")
+        );
+        assert_eq!(sanitize_file_stem("...."), None);
+        assert_eq!(sanitize_file_stem("\u{7}"), None);
+        assert_eq!(
+            sanitize_file_stem(&"a".repeat(200)).map(|s| s.len()),
+            Some(MAX_ORIGINAL_NAME_LEN)
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("sunset%20beach.png"), "sunset beach.png");
+        assert_eq!(percent_decode("caf%C3%A9"), "café");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[tokio::test]
+    async fn test_import_gallery_requires_urls() {
+        let service = MediaService::new(std::env::temp_dir());
+        let result = service.import_gallery(&[]).await;
+        assert!(matches!(result, Err(MediaError::InvalidUrl(_))));
     }
 
     #[test]
     fn test_media_info_into_block_content() {
         let info = MediaInfo {
             file_path: "images/test.jpg".to_string(),
-            mime_type: "image/jpeg".to_string(),
+            mime_type: MimeType::new("image/jpeg"),
             width: Some(800),
             height: Some(600),
+            aspect_ratio: Some(800.0 / 600.0),
             duration: None,
             original_url: Some("https://example.com/test.jpg".to_string()),
+            content_hash: Some("abc123".to_string()),
+            metadata_error: None,
         };
 
         let content = info.into_block_content();
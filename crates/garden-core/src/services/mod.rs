@@ -15,7 +15,7 @@
 //! use garden_core::services::GardenService;
 //!
 //! let service = GardenService::new(channel_repo, block_repo, conn_repo);
-//! let channel = service.create_channel(NewChannel { title: "My Channel".into(), description: None }).await?;
+//! let channel = service.create_channel(NewChannel { title: "My Channel".into(), description: None, body: None }).await?;
 //! ```
 
 pub mod block;
@@ -27,5 +27,12 @@ pub mod media;
 pub use block::*;
 pub use channel::*;
 pub use connection::*;
-pub use garden::GardenService;
-pub use media::{MediaError, MediaInfo, MediaResult, MediaService, MediaType};
+pub use garden::{
+    DynGardenService, GardenService, DEFAULT_IDEMPOTENCY_WINDOW, DEFAULT_MARKDOWN_MEDIA_BASE,
+    DEFAULT_POSITION_GAP,
+};
+pub use media::{
+    LinkMetadata, MediaError, MediaInfo, MediaResult, MediaService, MediaType,
+    DEFAULT_GENERIC_CONTENT_TYPES,
+};
+pub use tokio_util::sync::CancellationToken;
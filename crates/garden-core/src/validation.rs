@@ -6,7 +6,24 @@
 use url::Url;
 
 use crate::error::{DomainError, DomainResult};
-use crate::models::BlockContent;
+use crate::models::{Block, BlockContent, MediaType, MimeType};
+
+/// Validate a whole block: its content and its archive metadata.
+pub fn validate_block(block: &Block) -> DomainResult<()> {
+    validate_block_content(&block.content)?;
+    validate_source_url(block.source_url.as_deref())
+}
+
+/// Validate a block's source URL.
+///
+/// The UI renders the source URL as a clickable link, so it gets the same
+/// http/https-only check as link content.
+pub fn validate_source_url(source_url: Option<&str>) -> DomainResult<()> {
+    match source_url {
+        Some(url) => validate_url(url),
+        None => Ok(()),
+    }
+}
 
 /// Validate block content.
 pub fn validate_block_content(content: &BlockContent) -> DomainResult<()> {
@@ -35,10 +52,12 @@ pub fn validate_block_content(content: &BlockContent) -> DomainResult<()> {
             mime_type,
             alt_text,
             original_url,
+            aspect_ratio,
             ..
         } => {
             validate_file_path(file_path)?;
-            validate_mime_type(mime_type, "image")?;
+            validate_mime_type(mime_type, MediaType::Image)?;
+            validate_aspect_ratio(*aspect_ratio)?;
             if let Some(a) = alt_text {
                 validate_optional_text("alt_text", a)?;
             }
@@ -52,10 +71,12 @@ pub fn validate_block_content(content: &BlockContent) -> DomainResult<()> {
             mime_type,
             alt_text,
             original_url,
+            aspect_ratio,
             ..
         } => {
             validate_file_path(file_path)?;
-            validate_mime_type(mime_type, "video")?;
+            validate_mime_type(mime_type, MediaType::Video)?;
+            validate_aspect_ratio(*aspect_ratio)?;
             if let Some(a) = alt_text {
                 validate_optional_text("alt_text", a)?;
             }
@@ -73,7 +94,7 @@ pub fn validate_block_content(content: &BlockContent) -> DomainResult<()> {
             ..
         } => {
             validate_file_path(file_path)?;
-            validate_mime_type(mime_type, "audio")?;
+            validate_mime_type(mime_type, MediaType::Audio)?;
             if let Some(t) = title {
                 validate_optional_text("title", t)?;
             }
@@ -85,6 +106,37 @@ pub fn validate_block_content(content: &BlockContent) -> DomainResult<()> {
             }
             Ok(())
         }
+        BlockContent::RemoteImage { url, alt_text, .. } => {
+            validate_url(url)?;
+            if let Some(a) = alt_text {
+                validate_optional_text("alt_text", a)?;
+            }
+            Ok(())
+        }
+        BlockContent::Gallery { items } => {
+            if items.is_empty() {
+                return Err(DomainError::InvalidInput(
+                    "gallery must contain at least one item".to_string(),
+                ));
+            }
+            for item in items {
+                validate_file_path(&item.file_path)?;
+                // Galleries are visual; audio files don't belong in them
+                if !matches!(
+                    item.mime_type.media_type(),
+                    Some(MediaType::Image | MediaType::Video)
+                ) {
+                    return Err(DomainError::InvalidInput(format!(
+                        "gallery items must be images or videos, got '{}'",
+                        item.mime_type
+                    )));
+                }
+                if let Some(a) = &item.alt_text {
+                    validate_optional_text("alt_text", a)?;
+                }
+            }
+            Ok(())
+        }
     }
 }
 
@@ -141,18 +193,19 @@ fn validate_file_path(path: &str) -> DomainResult<()> {
 }
 
 /// Validate a MIME type matches the expected media category.
-fn validate_mime_type(mime_type: &str, expected_category: &str) -> DomainResult<()> {
-    if mime_type.trim().is_empty() {
+fn validate_mime_type(mime_type: &MimeType, expected: MediaType) -> DomainResult<()> {
+    if mime_type.is_empty() {
         return Err(DomainError::InvalidInput(
             "MIME type cannot be empty".to_string(),
         ));
     }
 
-    // Check that MIME type starts with expected category (image/, video/, audio/)
-    if !mime_type.starts_with(&format!("{}/", expected_category)) {
+    // Check that MIME type belongs to the expected category (image/, video/, audio/)
+    if mime_type.media_type() != Some(expected) {
         return Err(DomainError::InvalidInput(format!(
             "expected {} MIME type, got '{}'",
-            expected_category, mime_type
+            expected.category(),
+            mime_type
         )));
     }
 
@@ -204,9 +257,98 @@ pub fn validate_channel_title(title: &str) -> DomainResult<()> {
     Ok(())
 }
 
+/// Maximum length of a channel body, in characters.
+pub const MAX_CHANNEL_BODY_LEN: usize = 100_000;
+
+/// Validate a channel's Markdown body.
+pub fn validate_channel_body(body: &str) -> DomainResult<()> {
+    validate_optional_text("channel body", body)?;
+    if body.chars().count() > MAX_CHANNEL_BODY_LEN {
+        return Err(DomainError::InvalidInput(format!(
+            "channel body cannot be longer than {} characters",
+            MAX_CHANNEL_BODY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a block position within a channel.
+///
+/// Negative positions are rejected because they sort before every real block.
+/// Positions past the end of a channel are allowed: gaps are harmless and the
+/// block simply sorts last.
+pub fn validate_position(position: i32) -> DomainResult<()> {
+    if position < 0 {
+        return Err(DomainError::InvalidInput(format!(
+            "position cannot be negative, got {}",
+            position
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a media aspect ratio, if present.
+///
+/// Ratios are derived from pixel dimensions, so anything other than a
+/// positive, finite number means the content was built by hand or corrupted.
+pub fn validate_aspect_ratio(aspect_ratio: Option<f32>) -> DomainResult<()> {
+    match aspect_ratio {
+        Some(ratio) if !(ratio.is_finite() && ratio > 0.0) => Err(DomainError::InvalidInput(
+            format!("aspect ratio must be positive, got {}", ratio),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Maximum length of an idempotency key.
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Validate an idempotency key supplied by a client.
+pub fn validate_idempotency_key(key: &str) -> DomainResult<()> {
+    if key.trim().is_empty() {
+        return Err(DomainError::InvalidInput(
+            "idempotency key cannot be empty".to_string(),
+        ));
+    }
+    if key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(DomainError::InvalidInput(format!(
+            "idempotency key cannot be longer than {} bytes",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Maximum length of a tag, in characters.
+pub const MAX_TAG_LEN: usize = 64;
+
+/// Normalize a tag and check that it is usable.
+///
+/// Tags are trimmed, lowercased, and have inner runs of whitespace collapsed
+/// to a single space, so `"  Field  Notes "` and `"field notes"` are the same
+/// tag.
+pub fn normalize_tag(tag: &str) -> DomainResult<String> {
+    let normalized = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    if normalized.is_empty() {
+        return Err(DomainError::InvalidInput("tag cannot be empty".to_string()));
+    }
+    if normalized.chars().count() > MAX_TAG_LEN {
+        return Err(DomainError::InvalidInput(format!(
+            "tag cannot be longer than {} characters",
+            MAX_TAG_LEN
+        )));
+    }
+    Ok(normalized)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::GalleryItem;
 
     // ─────────────────────────────────────────────────────────────────────────
     // URL Validation Tests
@@ -341,6 +483,61 @@ mod tests {
         assert!(validate_channel_title("   ").is_err());
     }
 
+    // ─────────────────────────────────────────────────────────────────────────
+    // Position Validation Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn valid_positions() {
+        assert!(validate_position(0).is_ok());
+        assert!(validate_position(1).is_ok());
+        assert!(validate_position(i32::MAX).is_ok());
+    }
+
+    #[test]
+    fn negative_position_fails() {
+        assert!(validate_position(-1).is_err());
+        assert!(validate_position(i32::MIN).is_err());
+    }
+
+    #[test]
+    fn non_positive_aspect_ratio_fails() {
+        assert!(validate_aspect_ratio(None).is_ok());
+        assert!(validate_aspect_ratio(Some(4.0 / 3.0)).is_ok());
+        assert!(validate_aspect_ratio(Some(0.0)).is_err());
+        assert!(validate_aspect_ratio(Some(-1.5)).is_err());
+        assert!(validate_aspect_ratio(Some(f32::NAN)).is_err());
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Idempotency Key Validation Tests
+    // ─────────────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn valid_idempotency_key() {
+        assert!(validate_idempotency_key("3f2b9c1e-retry").is_ok());
+    }
+
+    #[test]
+    fn empty_or_oversized_idempotency_key_fails() {
+        assert!(validate_idempotency_key("").is_err());
+        assert!(validate_idempotency_key("  ").is_err());
+        assert!(validate_idempotency_key(&"k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn normalize_tag_trims_and_lowercases() {
+        assert_eq!(normalize_tag("  Field  Notes ").unwrap(), "field notes");
+        assert_eq!(normalize_tag("TYPE").unwrap(), "type");
+    }
+
+    #[test]
+    fn invalid_tag() {
+        assert!(normalize_tag("").is_err());
+        assert!(normalize_tag(" \t ").is_err());
+        assert!(normalize_tag(&"t".repeat(MAX_TAG_LEN + 1)).is_err());
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // File Path Validation Tests
     // ─────────────────────────────────────────────────────────────────────────
@@ -376,37 +573,37 @@ mod tests {
 
     #[test]
     fn valid_image_mime_types() {
-        assert!(validate_mime_type("image/jpeg", "image").is_ok());
-        assert!(validate_mime_type("image/png", "image").is_ok());
-        assert!(validate_mime_type("image/gif", "image").is_ok());
-        assert!(validate_mime_type("image/webp", "image").is_ok());
+        assert!(validate_mime_type(&MimeType::new("image/jpeg"), MediaType::Image).is_ok());
+        assert!(validate_mime_type(&MimeType::new("image/png"), MediaType::Image).is_ok());
+        assert!(validate_mime_type(&MimeType::new("image/gif"), MediaType::Image).is_ok());
+        assert!(validate_mime_type(&MimeType::new("image/webp"), MediaType::Image).is_ok());
     }
 
     #[test]
     fn valid_video_mime_types() {
-        assert!(validate_mime_type("video/mp4", "video").is_ok());
-        assert!(validate_mime_type("video/webm", "video").is_ok());
-        assert!(validate_mime_type("video/quicktime", "video").is_ok());
+        assert!(validate_mime_type(&MimeType::new("video/mp4"), MediaType::Video).is_ok());
+        assert!(validate_mime_type(&MimeType::new("video/webm"), MediaType::Video).is_ok());
+        assert!(validate_mime_type(&MimeType::new("video/quicktime"), MediaType::Video).is_ok());
     }
 
     #[test]
     fn valid_audio_mime_types() {
-        assert!(validate_mime_type("audio/mpeg", "audio").is_ok());
-        assert!(validate_mime_type("audio/ogg", "audio").is_ok());
-        assert!(validate_mime_type("audio/wav", "audio").is_ok());
+        assert!(validate_mime_type(&MimeType::new("audio/mpeg"), MediaType::Audio).is_ok());
+        assert!(validate_mime_type(&MimeType::new("audio/ogg"), MediaType::Audio).is_ok());
+        assert!(validate_mime_type(&MimeType::new("audio/wav"), MediaType::Audio).is_ok());
     }
 
     #[test]
     fn wrong_mime_category_fails() {
-        assert!(validate_mime_type("video/mp4", "image").is_err());
-        assert!(validate_mime_type("audio/mpeg", "video").is_err());
-        assert!(validate_mime_type("image/jpeg", "audio").is_err());
+        assert!(validate_mime_type(&MimeType::new("video/mp4"), MediaType::Image).is_err());
+        assert!(validate_mime_type(&MimeType::new("audio/mpeg"), MediaType::Video).is_err());
+        assert!(validate_mime_type(&MimeType::new("image/jpeg"), MediaType::Audio).is_err());
     }
 
     #[test]
     fn empty_mime_type_fails() {
-        assert!(validate_mime_type("", "image").is_err());
-        assert!(validate_mime_type("   ", "video").is_err());
+        assert!(validate_mime_type(&MimeType::new(""), MediaType::Image).is_err());
+        assert!(validate_mime_type(&MimeType::new("   "), MediaType::Video).is_err());
     }
 
     // ─────────────────────────────────────────────────────────────────────────
@@ -450,6 +647,104 @@ mod tests {
         assert!(validate_block_content(&content).is_ok());
     }
 
+    #[test]
+    fn valid_gallery_block() {
+        let content = BlockContent::gallery(vec![
+            GalleryItem::new("images/one.jpg", "image/jpeg"),
+            GalleryItem::new("images/two.png", "image/png"),
+        ]);
+        assert!(validate_block_content(&content).is_ok());
+    }
+
+    #[test]
+    fn empty_gallery_fails() {
+        let content = BlockContent::gallery(vec![]);
+        assert!(validate_block_content(&content).is_err());
+    }
+
+    #[test]
+    fn gallery_with_invalid_item_fails() {
+        let content = BlockContent::gallery(vec![
+            GalleryItem::new("images/one.jpg", "image/jpeg"),
+            GalleryItem::new("../secret.jpg", "image/jpeg"),
+        ]);
+        assert!(validate_block_content(&content).is_err());
+
+        let content = BlockContent::gallery(vec![GalleryItem::new("audio/song.mp3", "audio/mpeg")]);
+        assert!(validate_block_content(&content).is_err());
+    }
+
+    #[test]
+    fn valid_remote_image_block() {
+        let content = BlockContent::RemoteImage {
+            url: "https://example.com/cat.png".to_string(),
+            width: Some(640),
+            height: Some(480),
+            alt_text: Some("A cat".to_string()),
+        };
+        assert!(validate_block_content(&content).is_ok());
+    }
+
+    #[test]
+    fn remote_image_invalid_url_fails() {
+        let content = BlockContent::remote_image("not-a-valid-url");
+        assert!(validate_block_content(&content).is_err());
+
+        let content = BlockContent::remote_image("file:///etc/passwd");
+        assert!(validate_block_content(&content).is_err());
+    }
+
+    /// URLs with schemes that must never be rendered as clickable links.
+    const UNSAFE_URLS: &[&str] = &[
+        "data:text/html,<script>alert(1)</script>",
+        "blob:https://example.com/550e8400-e29b-41d4-a716-446655440000",
+        "file:///etc/passwd",
+        "javascript:alert(1)",
+    ];
+
+    #[test]
+    fn unsafe_schemes_rejected_in_link_url() {
+        for url in UNSAFE_URLS {
+            let content = BlockContent::link(*url);
+            assert!(validate_block_content(&content).is_err(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn unsafe_schemes_rejected_in_media_original_url() {
+        for url in UNSAFE_URLS {
+            let image = BlockContent::image_with_meta(
+                "images/test.jpg",
+                "image/jpeg",
+                Some(url.to_string()),
+                None,
+                None,
+                None,
+            );
+            assert!(validate_block_content(&image).is_err(), "{}", url);
+
+            let remote = BlockContent::remote_image(*url);
+            assert!(validate_block_content(&remote).is_err(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn unsafe_schemes_rejected_in_source_url() {
+        for url in UNSAFE_URLS {
+            assert!(validate_source_url(Some(url)).is_err(), "{}", url);
+
+            let mut block = Block::text("Hello");
+            block.source_url = Some(url.to_string());
+            assert!(validate_block(&block).is_err(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn valid_source_url() {
+        assert!(validate_source_url(None).is_ok());
+        assert!(validate_source_url(Some("https://example.com/post")).is_ok());
+    }
+
     #[test]
     fn media_block_invalid_original_url_fails() {
         let content = BlockContent::image_with_meta(
@@ -0,0 +1,54 @@
+-- Full-text search index over blocks
+-- Indexes the same fields as Block::searchable_text in garden-core
+
+CREATE VIRTUAL TABLE IF NOT EXISTS blocks_fts USING fts5(
+    block_id UNINDEXED,
+    content
+);
+
+-- Keep the index in sync with the blocks table
+CREATE TRIGGER IF NOT EXISTS blocks_fts_insert AFTER INSERT ON blocks BEGIN
+    INSERT INTO blocks_fts (block_id, content)
+    VALUES (
+        new.id,
+        coalesce(json_extract(new.content_json, '$.body'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.title'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.url'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.description'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.alt_text'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.artist'), '') || ' ' ||
+        coalesce(new.notes, '')
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS blocks_fts_update AFTER UPDATE ON blocks BEGIN
+    DELETE FROM blocks_fts WHERE block_id = old.id;
+    INSERT INTO blocks_fts (block_id, content)
+    VALUES (
+        new.id,
+        coalesce(json_extract(new.content_json, '$.body'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.title'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.url'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.description'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.alt_text'), '') || ' ' ||
+        coalesce(json_extract(new.content_json, '$.artist'), '') || ' ' ||
+        coalesce(new.notes, '')
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS blocks_fts_delete AFTER DELETE ON blocks BEGIN
+    DELETE FROM blocks_fts WHERE block_id = old.id;
+END;
+
+-- Backfill existing blocks
+INSERT INTO blocks_fts (block_id, content)
+SELECT
+    id,
+    coalesce(json_extract(content_json, '$.body'), '') || ' ' ||
+    coalesce(json_extract(content_json, '$.title'), '') || ' ' ||
+    coalesce(json_extract(content_json, '$.url'), '') || ' ' ||
+    coalesce(json_extract(content_json, '$.description'), '') || ' ' ||
+    coalesce(json_extract(content_json, '$.alt_text'), '') || ' ' ||
+    coalesce(json_extract(content_json, '$.artist'), '') || ' ' ||
+    coalesce(notes, '')
+FROM blocks;
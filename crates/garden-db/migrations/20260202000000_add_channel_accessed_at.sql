@@ -0,0 +1,6 @@
+-- Track when each channel was last opened, separately from updated_at,
+-- so the "recently viewed" list is not affected by edits
+
+ALTER TABLE channels ADD COLUMN accessed_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_channels_accessed_at ON channels(accessed_at DESC);
@@ -0,0 +1,10 @@
+-- Short-lived idempotency keys for create commands, so a retried request
+-- returns the entity created by the first attempt instead of a duplicate
+
+CREATE TABLE IF NOT EXISTS idempotency (
+    key TEXT PRIMARY KEY NOT NULL,
+    entity_id TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_created_at ON idempotency(created_at);
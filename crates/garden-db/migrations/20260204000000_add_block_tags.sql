@@ -0,0 +1,14 @@
+-- Free-form tags on blocks. Tags are normalized (trimmed, lowercased) before
+-- they are stored, so the pair (block_id, tag) is unique.
+
+CREATE TABLE IF NOT EXISTS block_tags (
+    block_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+
+    PRIMARY KEY (block_id, tag),
+    FOREIGN KEY (block_id) REFERENCES blocks(id) ON DELETE CASCADE
+);
+
+-- Index for finding blocks by tag
+CREATE INDEX IF NOT EXISTS idx_block_tags_tag ON block_tags(tag);
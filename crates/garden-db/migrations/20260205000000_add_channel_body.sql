@@ -0,0 +1,4 @@
+-- Longer Markdown "about" text for channels. The existing description stays
+-- the one-line summary.
+
+ALTER TABLE channels ADD COLUMN body TEXT;
@@ -0,0 +1,14 @@
+-- Append-only log of changes to channels, blocks, and connections, read
+-- newest first as an activity feed. No foreign keys: entries outlive the
+-- channels and blocks they describe.
+
+CREATE TABLE IF NOT EXISTS activity_log (
+    id TEXT PRIMARY KEY NOT NULL,
+    kind TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    channel_id TEXT,
+    block_id TEXT,
+    at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_activity_log_at ON activity_log(at DESC);
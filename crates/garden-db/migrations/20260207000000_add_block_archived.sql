@@ -0,0 +1,4 @@
+-- Archived blocks are hidden from default listings, search, and channel views
+-- without being deleted or disconnected
+
+ALTER TABLE blocks ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,10 @@
+-- Video and audio blocks keep their duration inside content_json; surface it
+-- as a column so duration filters can use an index
+
+ALTER TABLE blocks ADD COLUMN duration REAL GENERATED ALWAYS AS (
+    CASE WHEN content_type IN ('video', 'audio')
+        THEN json_extract(content_json, '$.duration')
+    END
+) VIRTUAL;
+
+CREATE INDEX IF NOT EXISTS idx_blocks_duration ON blocks(duration) WHERE duration IS NOT NULL;
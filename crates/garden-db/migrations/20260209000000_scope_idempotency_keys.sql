@@ -0,0 +1,21 @@
+-- Give channels and blocks separate idempotency keyspaces, so a key reused
+-- for a different kind of entity doesn't replace the other's entry
+
+CREATE TABLE idempotency_scoped (
+    kind TEXT NOT NULL,
+    key TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (kind, key)
+);
+
+INSERT INTO idempotency_scoped (kind, key, entity_id, created_at)
+SELECT CASE WHEN entity_id IN (SELECT id FROM channels) THEN 'channel' ELSE 'block' END,
+       key, entity_id, created_at
+FROM idempotency;
+
+DROP TABLE idempotency;
+
+ALTER TABLE idempotency_scoped RENAME TO idempotency;
+
+CREATE INDEX idx_idempotency_created_at ON idempotency(created_at);
@@ -34,6 +34,14 @@ pub enum DbError {
     #[error("invalid datetime format in field '{field}': {value}")]
     InvalidDatetime { field: &'static str, value: String },
 
+    /// Unrecognized kind in the activity log.
+    #[error("unknown activity kind: {0}")]
+    UnknownActivityKind(String),
+
+    /// Block content is too large to store.
+    #[error("block content is {size} bytes, over the {max}-byte limit")]
+    ContentTooLarge { size: usize, max: usize },
+
     /// Schema validation failed.
     #[error("database schema invalid: {0}")]
     SchemaInvalid(String),
@@ -47,11 +55,18 @@ impl From<DbError> for RepoError {
         match err {
             DbError::NotFound => RepoError::NotFound,
             DbError::Duplicate => RepoError::Duplicate,
+            DbError::ContentTooLarge { size, max } => RepoError::ContentTooLarge { size, max },
             DbError::Sqlx(e) => {
                 // Check for specific SQLite errors
                 let msg = e.to_string();
-                if msg.contains("UNIQUE constraint failed") {
+                if msg.contains("UNIQUE constraint failed: channels.title") {
+                    // Only possible with unique channel titles turned on
+                    RepoError::DuplicateTitle
+                } else if msg.contains("UNIQUE constraint failed") {
                     RepoError::Duplicate
+                } else if msg.contains("FOREIGN KEY constraint failed") {
+                    // A connection to a block or channel that does not exist
+                    RepoError::NotFound
                 } else {
                     RepoError::Database(msg)
                 }
@@ -5,6 +5,8 @@
 //!
 //! - `sqlite` (default) - SQLite for desktop/embedded use
 //! - `postgres` - PostgreSQL for server deployments
+//! - `tracing` (default) - `#[instrument]` spans on every repository and
+//!   service method; disable for high-throughput batch imports
 //!
 //! # Usage
 //!
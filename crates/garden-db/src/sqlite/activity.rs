@@ -0,0 +1,136 @@
+//! SQLite implementation of ActivityRepository.
+
+use std::time::Instant;
+
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+use tracing::{info, warn};
+
+use garden_core::error::RepoResult;
+use garden_core::models::{ActivityEntry, ActivityKind, BlockId, ChannelId, Page};
+use garden_core::ports::ActivityRepository;
+
+use super::slow_query::QueryMonitor;
+
+/// SQLite-backed activity log.
+#[derive(Clone)]
+pub struct SqliteActivityRepository {
+    pool: SqlitePool,
+    queries: QueryMonitor,
+}
+
+impl SqliteActivityRepository {
+    /// Create a new repository with the given connection pool.
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::with_monitor(pool, QueryMonitor::default())
+    }
+
+    /// Create a repository that reports slow queries to `queries`.
+    pub(crate) fn with_monitor(pool: SqlitePool, queries: QueryMonitor) -> Self {
+        Self { pool, queries }
+    }
+}
+
+#[async_trait]
+impl ActivityRepository for SqliteActivityRepository {
+    #[cfg_attr(feature = "tracing", instrument(skip(self, entry), fields(kind = entry.kind.as_str())))]
+    async fn record(&self, entry: &ActivityEntry) -> RepoResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO activity_log (id, kind, entity_id, channel_id, block_id, at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(&entry.id)
+        .bind(entry.kind.as_str())
+        .bind(&entry.entity_id)
+        .bind(entry.channel_id.as_ref().map(|id| &id.0))
+        .bind(entry.block_id.as_ref().map(|id| &id.0))
+        .bind(entry.at.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn list(&self, limit: usize, offset: usize) -> RepoResult<Page<ActivityEntry>> {
+        let start = Instant::now();
+
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM activity_log")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        // Entries recorded within the same instant keep insertion order
+        let rows = sqlx::query_as::<_, ActivityRow>(
+            r#"
+            SELECT id, kind, entity_id, channel_id, block_id, at
+            FROM activity_log
+            ORDER BY at DESC, rowid DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items: Vec<ActivityEntry> = rows
+            .into_iter()
+            .map(|r| r.into_entry())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let elapsed = start.elapsed();
+        if self
+            .queries
+            .record("activity_log.list", elapsed, items.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Slow query: list activity"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Listed activity"
+            );
+        }
+
+        Ok(Page::new(items, total.0 as usize, offset, limit))
+    }
+}
+
+/// Internal row type for SQLite queries.
+#[derive(sqlx::FromRow)]
+struct ActivityRow {
+    id: String,
+    kind: String,
+    entity_id: String,
+    channel_id: Option<String>,
+    block_id: Option<String>,
+    at: String,
+}
+
+impl ActivityRow {
+    fn into_entry(self) -> Result<ActivityEntry, crate::error::DbError> {
+        use super::util::parse_datetime;
+
+        let kind = ActivityKind::from_tag(&self.kind)
+            .ok_or(crate::error::DbError::UnknownActivityKind(self.kind))?;
+        Ok(ActivityEntry {
+            id: self.id,
+            kind,
+            entity_id: self.entity_id,
+            channel_id: self.channel_id.map(ChannelId),
+            block_id: self.block_id.map(BlockId),
+            at: parse_datetime(&self.at, "at")?,
+        })
+    }
+}
@@ -1,37 +1,201 @@
 //! SQLite implementation of BlockRepository.
 
+use std::time::Instant;
+
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::TryStreamExt;
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection, SqlitePool};
+#[cfg(feature = "tracing")]
 use tracing::instrument;
+use tracing::{info, warn};
 
 use garden_core::error::RepoResult;
-use garden_core::models::{Block, BlockContent, BlockId};
+use garden_core::models::{
+    count_source_domains, Block, BlockContent, BlockId, MetadataStats, Page, SearchHit, SearchMode,
+};
 use garden_core::ports::BlockRepository;
 
+use super::config::{DEFAULT_MAX_BATCH_PARAMS, DEFAULT_MAX_CONTENT_BYTES};
+use super::slow_query::QueryMonitor;
+use super::util::{find_idempotency_key, record_idempotency_key, rows_per_statement};
+
+/// Blocks read and rewritten per transaction when migrating content.
+const CONTENT_MIGRATION_BATCH: usize = 500;
+
+/// Rewrite every stored column of one block, shared by `update` and
+/// `update_batch`.
+const UPDATE_BLOCK_SQL: &str = r#"
+    UPDATE blocks
+    SET content_type = $2, content_json = $3, updated_at = $4,
+        source_url = $5, source_title = $6, creator = $7,
+        original_date = $8, notes = $9, archived = $10
+    WHERE id = $1
+"#;
+
 /// SQLite-backed block repository.
 #[derive(Clone)]
 pub struct SqliteBlockRepository {
     pool: SqlitePool,
+    queries: QueryMonitor,
+    max_content_bytes: usize,
+    max_batch_params: usize,
 }
 
 impl SqliteBlockRepository {
     /// Create a new repository with the given connection pool.
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self::with_monitor(pool, QueryMonitor::default())
+    }
+
+    /// Create a repository that reports slow queries to `queries`.
+    pub(crate) fn with_monitor(pool: SqlitePool, queries: QueryMonitor) -> Self {
+        Self {
+            pool,
+            queries,
+            max_content_bytes: DEFAULT_MAX_CONTENT_BYTES,
+            max_batch_params: DEFAULT_MAX_BATCH_PARAMS,
+        }
+    }
+
+    /// Reject block content whose JSON is larger than `max` bytes.
+    ///
+    /// Defaults to [`DEFAULT_MAX_CONTENT_BYTES`].
+    pub fn with_max_content_bytes(mut self, max: usize) -> Self {
+        self.max_content_bytes = max;
+        self
+    }
+
+    /// Cap the bound parameters in each statement of a batch insert.
+    ///
+    /// Defaults to [`DEFAULT_MAX_BATCH_PARAMS`].
+    pub fn with_max_batch_params(mut self, max: usize) -> Self {
+        self.max_batch_params = max;
+        self
+    }
+
+    /// Rewrite every block's stored content through `upgrade`.
+    ///
+    /// Blocks are read in ID order, one batch per transaction, and only rows
+    /// whose serialized content changes are written back. `updated_at` is
+    /// left alone since this isn't an edit. Because stored JSON is compared
+    /// after re-serializing, rows missing fields that serde fills in on read
+    /// are rewritten even by an identity `upgrade`. Returns the number of
+    /// rows rewritten.
+    pub async fn migrate_content(
+        &self,
+        upgrade: impl Fn(BlockContent) -> BlockContent,
+    ) -> RepoResult<usize> {
+        Ok(migrate_content(&self.pool, upgrade, self.max_content_bytes).await?)
     }
 }
 
+/// Rewrite stored block content in batches; see
+/// [`SqliteBlockRepository::migrate_content`].
+#[cfg_attr(feature = "tracing", instrument(skip_all))]
+pub(crate) async fn migrate_content(
+    pool: &SqlitePool,
+    upgrade: impl Fn(BlockContent) -> BlockContent,
+    max_content_bytes: usize,
+) -> crate::error::DbResult<usize> {
+    let mut after = String::new();
+    let mut rewritten = 0;
+
+    loop {
+        let mut tx = pool.begin().await?;
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id, content_json FROM blocks WHERE id > $1 ORDER BY id LIMIT $2",
+        )
+        .bind(&after)
+        .bind(CONTENT_MIGRATION_BATCH as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for (id, json) in &rows {
+            let content: BlockContent = serde_json::from_str(json)?;
+            let (content_type, upgraded_json) =
+                serialize_content(&upgrade(content), max_content_bytes)?;
+            if upgraded_json == *json {
+                continue;
+            }
+
+            sqlx::query("UPDATE blocks SET content_type = $2, content_json = $3 WHERE id = $1")
+                .bind(id)
+                .bind(content_type)
+                .bind(&upgraded_json)
+                .execute(&mut *tx)
+                .await?;
+            rewritten += 1;
+        }
+        tx.commit().await?;
+
+        match rows.last() {
+            Some((last, _)) if rows.len() == CONTENT_MIGRATION_BATCH => after = last.clone(),
+            _ => break,
+        }
+    }
+
+    info!(rewritten, "Block content migrated");
+    Ok(rewritten)
+}
+
+/// Insert block rows inside a caller's transaction, batching rows per
+/// statement.
+///
+/// All content is serialized first, so oversized content fails before any
+/// write.
+pub(crate) async fn insert_blocks(
+    conn: &mut SqliteConnection,
+    blocks: &[Block],
+    max_content_bytes: usize,
+    max_batch_params: usize,
+) -> RepoResult<()> {
+    let contents = blocks
+        .iter()
+        .map(|block| serialize_content(&block.content, max_content_bytes))
+        .collect::<crate::error::DbResult<Vec<_>>>()?;
+
+    // 11 parameters per row; stay under SQLite's bound parameter limit
+    let rows: Vec<_> = blocks.iter().zip(&contents).collect();
+    for chunk in rows.chunks(rows_per_statement(max_batch_params, 11)) {
+        let mut insert = QueryBuilder::<Sqlite>::new(
+            "INSERT INTO blocks (id, content_type, content_json, created_at, updated_at, \
+             source_url, source_title, creator, original_date, notes, archived) ",
+        );
+        insert.push_values(chunk, |mut row, (block, (content_type, content_json))| {
+            row.push_bind(&block.id.0)
+                .push_bind(content_type)
+                .push_bind(content_json)
+                .push_bind(block.created_at.to_rfc3339())
+                .push_bind(block.updated_at.to_rfc3339())
+                .push_bind(&block.source_url)
+                .push_bind(&block.source_title)
+                .push_bind(&block.creator)
+                .push_bind(&block.original_date)
+                .push_bind(&block.notes)
+                .push_bind(block.archived);
+        });
+        insert
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(crate::error::DbError::from)?;
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl BlockRepository for SqliteBlockRepository {
-    #[instrument(skip(self, block), fields(block_id = %block.id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block), fields(block_id = %block.id.0)))]
     async fn create(&self, block: &Block) -> RepoResult<()> {
-        let (content_type, content_json) = serialize_content(&block.content)?;
+        let (content_type, content_json) =
+            serialize_content(&block.content, self.max_content_bytes)?;
 
         sqlx::query(
             r#"
             INSERT INTO blocks (id, content_type, content_json, created_at, updated_at,
-                               source_url, source_title, creator, original_date, notes)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                               source_url, source_title, creator, original_date, notes, archived)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
         .bind(&block.id.0)
@@ -44,6 +208,7 @@ impl BlockRepository for SqliteBlockRepository {
         .bind(&block.creator)
         .bind(&block.original_date)
         .bind(&block.notes)
+        .bind(block.archived)
         .execute(&self.pool)
         .await
         .map_err(crate::error::DbError::from)?;
@@ -51,50 +216,101 @@ impl BlockRepository for SqliteBlockRepository {
         Ok(())
     }
 
-    #[instrument(skip(self, blocks), fields(count = blocks.len()))]
-    async fn create_batch(&self, blocks: &[Block]) -> RepoResult<()> {
-        // Use a transaction for atomicity
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block), fields(block_id = %block.id.0)))]
+    async fn create_idempotent(
+        &self,
+        block: &Block,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Block> {
+        let (content_type, content_json) =
+            serialize_content(&block.content, self.max_content_bytes)?;
+
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(crate::error::DbError::from)?;
 
-        for block in blocks {
-            let (content_type, content_json) = serialize_content(&block.content)?;
-
-            sqlx::query(
+        if let Some(existing_id) = find_idempotency_key(&mut tx, "block", key, not_before).await? {
+            let row = sqlx::query_as::<_, BlockRow>(
                 r#"
-                INSERT INTO blocks (id, content_type, content_json, created_at, updated_at,
-                                   source_url, source_title, creator, original_date, notes)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                SELECT id, content_type, content_json, created_at, updated_at,
+                       source_url, source_title, creator, original_date, notes, archived
+                FROM blocks
+                WHERE id = $1
                 "#,
             )
-            .bind(&block.id.0)
-            .bind(&content_type)
-            .bind(&content_json)
-            .bind(block.created_at.to_rfc3339())
-            .bind(block.updated_at.to_rfc3339())
-            .bind(&block.source_url)
-            .bind(&block.source_title)
-            .bind(&block.creator)
-            .bind(&block.original_date)
-            .bind(&block.notes)
-            .execute(&mut *tx)
+            .bind(&existing_id)
+            .fetch_optional(&mut *tx)
             .await
             .map_err(crate::error::DbError::from)?;
+
+            if let Some(row) = row {
+                tx.commit().await.map_err(crate::error::DbError::from)?;
+                info!(block_id = %existing_id, "Idempotency key matched existing block");
+                return row.into_block();
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO blocks (id, content_type, content_json, created_at, updated_at,
+                               source_url, source_title, creator, original_date, notes, archived)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(&block.id.0)
+        .bind(&content_type)
+        .bind(&content_json)
+        .bind(block.created_at.to_rfc3339())
+        .bind(block.updated_at.to_rfc3339())
+        .bind(&block.source_url)
+        .bind(&block.source_title)
+        .bind(&block.creator)
+        .bind(&block.original_date)
+        .bind(&block.notes)
+        .bind(block.archived)
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        record_idempotency_key(&mut tx, "block", key, &block.id.0).await?;
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+
+        Ok(block.clone())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, blocks), fields(count = blocks.len())))]
+    async fn create_batch(&self, blocks: &[Block]) -> RepoResult<()> {
+        // Nothing to insert; skip opening a transaction
+        if blocks.is_empty() {
+            return Ok(());
         }
 
+        // Use a transaction for atomicity
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+        insert_blocks(
+            &mut tx,
+            blocks,
+            self.max_content_bytes,
+            self.max_batch_params,
+        )
+        .await?;
         tx.commit().await.map_err(crate::error::DbError::from)?;
         Ok(())
     }
 
-    #[instrument(skip(self), fields(block_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
     async fn get(&self, id: &BlockId) -> RepoResult<Option<Block>> {
         let row = sqlx::query_as::<_, BlockRow>(
             r#"
             SELECT id, content_type, content_json, created_at, updated_at,
-                   source_url, source_title, creator, original_date, notes
+                   source_url, source_title, creator, original_date, notes, archived
             FROM blocks
             WHERE id = $1
             "#,
@@ -110,31 +326,25 @@ impl BlockRepository for SqliteBlockRepository {
         }
     }
 
-    #[instrument(skip(self, block), fields(block_id = %block.id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block), fields(block_id = %block.id.0)))]
     async fn update(&self, block: &Block) -> RepoResult<()> {
-        let (content_type, content_json) = serialize_content(&block.content)?;
+        let (content_type, content_json) =
+            serialize_content(&block.content, self.max_content_bytes)?;
 
-        let result = sqlx::query(
-            r#"
-            UPDATE blocks
-            SET content_type = $2, content_json = $3, updated_at = $4,
-                source_url = $5, source_title = $6, creator = $7,
-                original_date = $8, notes = $9
-            WHERE id = $1
-            "#,
-        )
-        .bind(&block.id.0)
-        .bind(&content_type)
-        .bind(&content_json)
-        .bind(block.updated_at.to_rfc3339())
-        .bind(&block.source_url)
-        .bind(&block.source_title)
-        .bind(&block.creator)
-        .bind(&block.original_date)
-        .bind(&block.notes)
-        .execute(&self.pool)
-        .await
-        .map_err(crate::error::DbError::from)?;
+        let result = sqlx::query(UPDATE_BLOCK_SQL)
+            .bind(&block.id.0)
+            .bind(&content_type)
+            .bind(&content_json)
+            .bind(block.updated_at.to_rfc3339())
+            .bind(&block.source_url)
+            .bind(&block.source_title)
+            .bind(&block.creator)
+            .bind(&block.original_date)
+            .bind(&block.notes)
+            .bind(block.archived)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(garden_core::error::RepoError::NotFound);
@@ -143,7 +353,50 @@ impl BlockRepository for SqliteBlockRepository {
         Ok(())
     }
 
-    #[instrument(skip(self), fields(block_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, blocks), fields(count = blocks.len())))]
+    async fn update_batch(&self, blocks: &[Block]) -> RepoResult<()> {
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let contents = blocks
+            .iter()
+            .map(|block| serialize_content(&block.content, self.max_content_bytes))
+            .collect::<crate::error::DbResult<Vec<_>>>()?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        for (block, (content_type, content_json)) in blocks.iter().zip(&contents) {
+            let result = sqlx::query(UPDATE_BLOCK_SQL)
+                .bind(&block.id.0)
+                .bind(content_type)
+                .bind(content_json)
+                .bind(block.updated_at.to_rfc3339())
+                .bind(&block.source_url)
+                .bind(&block.source_title)
+                .bind(&block.creator)
+                .bind(&block.original_date)
+                .bind(&block.notes)
+                .bind(block.archived)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::from)?;
+
+            // Dropping the transaction rolls back the blocks already updated
+            if result.rows_affected() == 0 {
+                return Err(garden_core::error::RepoError::NotFound);
+            }
+        }
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %id.0)))]
     async fn delete(&self, id: &BlockId) -> RepoResult<()> {
         let result = sqlx::query("DELETE FROM blocks WHERE id = $1")
             .bind(&id.0)
@@ -157,26 +410,512 @@ impl BlockRepository for SqliteBlockRepository {
 
         Ok(())
     }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn source_domains(&self) -> RepoResult<Vec<(String, usize)>> {
+        // Host extraction is awkward in SQL, so group in Rust
+        let urls: Vec<(String,)> =
+            sqlx::query_as("SELECT source_url FROM blocks WHERE source_url IS NOT NULL")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(crate::error::DbError::from)?;
+
+        Ok(count_source_domains(urls.iter().map(|(url,)| url.as_str())))
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn count_file_references(&self, file_path: &str) -> RepoResult<usize> {
+        let (count,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM blocks
+            WHERE (
+                content_type IN ('image', 'video', 'audio')
+                AND json_extract(content_json, '$.file_path') = $1
+            ) OR (
+                content_type = 'gallery'
+                AND EXISTS (
+                    SELECT 1 FROM json_each(content_json, '$.items')
+                    WHERE json_extract(value, '$.file_path') = $1
+                )
+            )
+            "#,
+        )
+        .bind(file_path)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(count as usize)
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn find_created_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        let query_start = Instant::now();
+        // created_at is stored as RFC 3339 in UTC, which sorts lexicographically
+        let start = start.to_rfc3339();
+        let end = end.to_rfc3339();
+
+        let total: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM blocks WHERE created_at BETWEEN $1 AND $2 AND ($3 OR archived = 0)",
+        )
+        .bind(&start)
+        .bind(&end)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let rows = sqlx::query_as::<_, BlockRow>(
+            r#"
+            SELECT id, content_type, content_json, created_at, updated_at,
+                   source_url, source_title, creator, original_date, notes, archived
+            FROM blocks
+            WHERE created_at BETWEEN $1 AND $2 AND ($5 OR archived = 0)
+            ORDER BY created_at DESC, id ASC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(&start)
+        .bind(&end)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items = rows
+            .into_iter()
+            .map(|r| r.into_block())
+            .collect::<RepoResult<Vec<_>>>()?;
+
+        let elapsed = query_start.elapsed();
+        if self
+            .queries
+            .record("blocks.find_created_between", elapsed, items.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Slow query: find blocks created between"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Found blocks created between"
+            );
+        }
+
+        Ok(Page::new(items, total.0 as usize, offset, limit))
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn counts_by_day(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> RepoResult<Vec<(NaiveDate, usize)>> {
+        // created_at is RFC 3339 in UTC, so its first ten characters are the
+        // UTC date, and comparing against a bare date keeps the index usable
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT substr(created_at, 1, 10) AS day, COUNT(*)
+            FROM blocks
+            WHERE created_at >= $1 AND substr(created_at, 1, 10) <= $2
+            GROUP BY day
+            ORDER BY day
+            "#,
+        )
+        .bind(start.to_string())
+        .bind(end.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        rows.into_iter()
+            .map(|(day, count)| {
+                let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d").map_err(|_| {
+                    crate::error::DbError::InvalidDatetime {
+                        field: "created_at",
+                        value: day.clone(),
+                    }
+                })?;
+                Ok((day, count as usize))
+            })
+            .collect()
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn find_by_duration(
+        &self,
+        min: Option<f32>,
+        max: Option<f32>,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Block>> {
+        let query_start = Instant::now();
+
+        // duration is a generated column, NULL for everything but video and
+        // audio with a known length
+        let total: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM blocks
+            WHERE duration IS NOT NULL AND archived = 0
+              AND ($1 IS NULL OR duration >= $1) AND ($2 IS NULL OR duration <= $2)
+            "#,
+        )
+        .bind(min)
+        .bind(max)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let rows = sqlx::query_as::<_, BlockRow>(
+            r#"
+            SELECT id, content_type, content_json, created_at, updated_at,
+                   source_url, source_title, creator, original_date, notes, archived
+            FROM blocks
+            WHERE duration IS NOT NULL AND archived = 0
+              AND ($1 IS NULL OR duration >= $1) AND ($2 IS NULL OR duration <= $2)
+            ORDER BY created_at DESC, id ASC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(min)
+        .bind(max)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items = rows
+            .into_iter()
+            .map(|r| r.into_block())
+            .collect::<RepoResult<Vec<_>>>()?;
+
+        let elapsed = query_start.elapsed();
+        if self
+            .queries
+            .record("blocks.find_by_duration", elapsed, items.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Slow query: find blocks by duration"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Found blocks by duration"
+            );
+        }
+
+        Ok(Page::new(items, total.0 as usize, offset, limit))
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn total_word_count(&self) -> RepoResult<usize> {
+        // Only text and link content has words; skip decoding media rows.
+        // Rows are streamed so the whole library's text is never in memory.
+        let mut rows = sqlx::query_as::<_, (String,)>(
+            "SELECT content_json FROM blocks WHERE content_type IN ('text', 'link')",
+        )
+        .fetch(&self.pool);
+
+        let mut total = 0;
+        while let Some((json,)) = rows.try_next().await.map_err(crate::error::DbError::from)? {
+            let content: BlockContent =
+                serde_json::from_str(&json).map_err(crate::error::DbError::from)?;
+            total += content.word_count();
+        }
+        Ok(total)
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn metadata_completeness(&self) -> RepoResult<MetadataStats> {
+        let (total, source_url, creator, original_date, notes): (i64, i64, i64, i64, i64) =
+            sqlx::query_as(
+                r#"
+                SELECT
+                    COUNT(*),
+                    COUNT(*) - COUNT(NULLIF(source_url, '')),
+                    COUNT(*) - COUNT(NULLIF(creator, '')),
+                    COUNT(*) - COUNT(NULLIF(original_date, '')),
+                    COUNT(*) - COUNT(NULLIF(notes, ''))
+                FROM blocks
+                "#,
+            )
+            .fetch_one(&self.pool)
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        Ok(MetadataStats {
+            total: total as usize,
+            missing_source_url: source_url as usize,
+            missing_creator: creator as usize,
+            missing_original_date: original_date as usize,
+            missing_notes: notes as usize,
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, f)))]
+    async fn for_each(
+        &self,
+        batch_size: usize,
+        f: &mut (dyn FnMut(Block) + Send),
+    ) -> RepoResult<usize> {
+        let batch_size = batch_size.max(1);
+        let mut after = String::new();
+        let mut visited = 0;
+
+        loop {
+            let rows = sqlx::query_as::<_, BlockRow>(
+                r#"
+                SELECT id, content_type, content_json, created_at, updated_at,
+                       source_url, source_title, creator, original_date, notes, archived
+                FROM blocks
+                WHERE id > $1
+                ORDER BY id
+                LIMIT $2
+                "#,
+            )
+            .bind(&after)
+            .bind(batch_size as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+            let full = rows.len() == batch_size;
+            if let Some(last) = rows.last() {
+                after = last.id.clone();
+            }
+            for row in rows {
+                f(row.into_block()?);
+                visited += 1;
+            }
+            if !full {
+                info!(visited, "Visited all blocks");
+                return Ok(visited);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0)))]
+    async fn get_tags(&self, block_id: &BlockId) -> RepoResult<Vec<String>> {
+        let rows: Vec<(String,)> =
+            sqlx::query_as("SELECT tag FROM block_tags WHERE block_id = $1 ORDER BY tag")
+                .bind(&block_id.0)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(crate::error::DbError::from)?;
+
+        Ok(rows.into_iter().map(|(tag,)| tag).collect())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block_ids), fields(count = block_ids.len(), tag = %tag)))]
+    async fn add_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize> {
+        if block_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        let now = Utc::now().to_rfc3339();
+        let mut added = 0;
+        for block_id in block_ids {
+            // A missing block fails the foreign key and rolls everything back
+            let result = sqlx::query(
+                r#"
+                INSERT INTO block_tags (block_id, tag, created_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (block_id, tag) DO NOTHING
+                "#,
+            )
+            .bind(&block_id.0)
+            .bind(tag)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::from)?;
+            added += result.rows_affected() as usize;
+        }
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(added)
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block_ids), fields(count = block_ids.len(), tag = %tag)))]
+    async fn remove_tag(&self, block_ids: &[BlockId], tag: &str) -> RepoResult<usize> {
+        if block_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        let mut removed = 0;
+        for block_id in block_ids {
+            let result = sqlx::query("DELETE FROM block_tags WHERE block_id = $1 AND tag = $2")
+                .bind(&block_id.0)
+                .bind(tag)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::from)?;
+            removed += result.rows_affected() as usize;
+        }
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(removed)
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, mode), fields(query = %query)))]
+    async fn search(
+        &self,
+        query: &str,
+        mode: &SearchMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<SearchHit>> {
+        let start = Instant::now();
+
+        let Some(match_expr) = fts_match_expression(query) else {
+            return Ok(Page::new(Vec::new(), 0, offset, limit));
+        };
+
+        let total: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM blocks_fts
+            JOIN blocks b ON b.id = blocks_fts.block_id
+            WHERE blocks_fts MATCH $1 AND ($2 OR b.archived = 0)
+            "#,
+        )
+        .bind(&match_expr)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        // snippet() needs delimiters even in plain mode; the result is discarded
+        let (open, close) = match mode {
+            SearchMode::Plain => ("", ""),
+            SearchMode::Snippet { open, close } => (open.as_str(), close.as_str()),
+        };
+
+        let rows = sqlx::query_as::<_, SearchRow>(
+            r#"
+            SELECT b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                   b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                   snippet(blocks_fts, 1, $2, $3, '…', 12) AS snippet
+            FROM blocks_fts
+            JOIN blocks b ON b.id = blocks_fts.block_id
+            WHERE blocks_fts MATCH $1 AND ($6 OR b.archived = 0)
+            ORDER BY rank
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(&match_expr)
+        .bind(open)
+        .bind(close)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items = rows
+            .into_iter()
+            .map(|r| {
+                let snippet = match mode {
+                    SearchMode::Plain => None,
+                    SearchMode::Snippet { .. } => Some(r.snippet),
+                };
+                Ok(SearchHit {
+                    block: r.block.into_block()?,
+                    snippet,
+                })
+            })
+            .collect::<RepoResult<Vec<_>>>()?;
+
+        let elapsed = start.elapsed();
+        if self.queries.record("blocks.search", elapsed, items.len()) {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Slow query: search blocks"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                rows = items.len(),
+                "Searched blocks"
+            );
+        }
+
+        Ok(Page::new(items, total.0 as usize, offset, limit))
+    }
+}
+
+/// Build an FTS5 MATCH expression from a user query.
+///
+/// Each whitespace-separated term is quoted so FTS5 operators and syntax
+/// characters in user input are matched literally. Terms are implicitly
+/// ANDed. Returns `None` for an empty query.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
 }
 
 /// Serialize block content to (type, json) tuple.
-fn serialize_content(content: &BlockContent) -> RepoResult<(String, String)> {
-    let content_type = match content {
-        BlockContent::Text { .. } => "text",
-        BlockContent::Link { .. } => "link",
-        BlockContent::Image { .. } => "image",
-        BlockContent::Video { .. } => "video",
-        BlockContent::Audio { .. } => "audio",
-    };
-
-    let content_json = serde_json::to_string(content).map_err(crate::error::DbError::from)?;
-
-    Ok((content_type.to_string(), content_json))
+///
+/// Fails with `ContentTooLarge` if the JSON is over `max_bytes`, so
+/// oversized rows never reach the `blocks` table.
+fn serialize_content(
+    content: &BlockContent,
+    max_bytes: usize,
+) -> crate::error::DbResult<(String, String)> {
+    let content_json = serde_json::to_string(content)?;
+    if content_json.len() > max_bytes {
+        return Err(crate::error::DbError::ContentTooLarge {
+            size: content_json.len(),
+            max: max_bytes,
+        });
+    }
+
+    Ok((content_type(content).to_string(), content_json))
+}
+
+/// The `content_type` column value for block content.
+fn content_type(content: &BlockContent) -> &'static str {
+    content.kind().as_str()
 }
 
 /// Internal row type for SQLite queries.
 #[derive(sqlx::FromRow)]
-struct BlockRow {
+pub(crate) struct BlockRow {
     id: String,
     #[allow(dead_code)]
     content_type: String, // Used for debugging, actual parsing is from JSON
@@ -189,10 +928,19 @@ struct BlockRow {
     creator: Option<String>,
     original_date: Option<String>,
     notes: Option<String>,
+    archived: bool,
+}
+
+/// Row type for search queries: a block plus its FTS snippet.
+#[derive(sqlx::FromRow)]
+struct SearchRow {
+    #[sqlx(flatten)]
+    block: BlockRow,
+    snippet: String,
 }
 
 impl BlockRow {
-    fn into_block(self) -> RepoResult<Block> {
+    pub(crate) fn into_block(self) -> RepoResult<Block> {
         use super::util::parse_datetime;
 
         let content: BlockContent =
@@ -208,6 +956,25 @@ impl BlockRow {
             creator: self.creator,
             original_date: self.original_date,
             notes: self.notes,
+            archived: self.archived,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fts_match_expression_quotes_terms() {
+        assert_eq!(
+            fts_match_expression("garden AND \"links"),
+            Some("\"garden\" \"AND\" \"\"\"links\"".to_string())
+        );
+    }
+
+    #[test]
+    fn fts_match_expression_empty() {
+        assert_eq!(fts_match_expression("   "), None);
+    }
+}
@@ -1,38 +1,113 @@
 //! SQLite implementation of ChannelRepository.
 
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use sqlx::{SqliteConnection, SqlitePool};
 use std::time::Instant;
-use tracing::{info, instrument, warn};
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+use tracing::{info, warn};
 
 use garden_core::error::RepoResult;
 use garden_core::models::{Channel, ChannelId, Page};
 use garden_core::ports::ChannelRepository;
 
-/// Threshold for logging slow queries (50ms).
-const SLOW_QUERY_THRESHOLD_MS: u128 = 50;
+use super::slow_query::QueryMonitor;
+use super::util::{find_idempotency_key, record_idempotency_key};
 
 /// SQLite-backed channel repository.
 #[derive(Clone)]
 pub struct SqliteChannelRepository {
     pool: SqlitePool,
+    queries: QueryMonitor,
 }
 
 impl SqliteChannelRepository {
     /// Create a new repository with the given connection pool.
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self::with_monitor(pool, QueryMonitor::default())
     }
+
+    /// Create a repository that reports slow queries to `queries`.
+    pub(crate) fn with_monitor(pool: SqlitePool, queries: QueryMonitor) -> Self {
+        Self { pool, queries }
+    }
+}
+
+/// Insert a channel row, on a connection or inside a caller's transaction.
+pub(crate) async fn insert_channel(
+    conn: &mut SqliteConnection,
+    channel: &Channel,
+) -> Result<(), crate::error::DbError> {
+    sqlx::query(
+        r#"
+        INSERT INTO channels (id, title, description, created_at, updated_at, accessed_at, body)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(&channel.id.0)
+    .bind(&channel.title)
+    .bind(&channel.description)
+    .bind(channel.created_at.to_rfc3339())
+    .bind(channel.updated_at.to_rfc3339())
+    .bind(channel.accessed_at.map(|t| t.to_rfc3339()))
+    .bind(&channel.body)
+    .execute(conn)
+    .await?;
+    Ok(())
 }
 
 #[async_trait]
 impl ChannelRepository for SqliteChannelRepository {
-    #[instrument(skip(self, channel), fields(channel_id = %channel.id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, channel), fields(channel_id = %channel.id.0)))]
     async fn create(&self, channel: &Channel) -> RepoResult<()> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(crate::error::DbError::from)?;
+        insert_channel(&mut conn, channel).await?;
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, channel), fields(channel_id = %channel.id.0)))]
+    async fn create_idempotent(
+        &self,
+        channel: &Channel,
+        key: &str,
+        not_before: DateTime<Utc>,
+    ) -> RepoResult<Channel> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        if let Some(existing_id) = find_idempotency_key(&mut tx, "channel", key, not_before).await?
+        {
+            let row = sqlx::query_as::<_, ChannelRow>(
+                r#"
+                SELECT id, title, description, created_at, updated_at, accessed_at, body
+                FROM channels
+                WHERE id = $1
+                "#,
+            )
+            .bind(&existing_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+            if let Some(row) = row {
+                tx.commit().await.map_err(crate::error::DbError::from)?;
+                info!(channel_id = %existing_id, "Idempotency key matched existing channel");
+                return Ok(row.into_channel()?);
+            }
+        }
+
         sqlx::query(
             r#"
-            INSERT INTO channels (id, title, description, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO channels (id, title, description, created_at, updated_at, accessed_at, body)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
         )
         .bind(&channel.id.0)
@@ -40,18 +115,23 @@ impl ChannelRepository for SqliteChannelRepository {
         .bind(&channel.description)
         .bind(channel.created_at.to_rfc3339())
         .bind(channel.updated_at.to_rfc3339())
-        .execute(&self.pool)
+        .bind(channel.accessed_at.map(|t| t.to_rfc3339()))
+        .bind(&channel.body)
+        .execute(&mut *tx)
         .await
         .map_err(crate::error::DbError::from)?;
 
-        Ok(())
+        record_idempotency_key(&mut tx, "channel", key, &channel.id.0).await?;
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+
+        Ok(channel.clone())
     }
 
-    #[instrument(skip(self), fields(channel_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
     async fn get(&self, id: &ChannelId) -> RepoResult<Option<Channel>> {
         let row = sqlx::query_as::<_, ChannelRow>(
             r#"
-            SELECT id, title, description, created_at, updated_at
+            SELECT id, title, description, created_at, updated_at, accessed_at, body
             FROM channels
             WHERE id = $1
             "#,
@@ -67,7 +147,7 @@ impl ChannelRepository for SqliteChannelRepository {
         }
     }
 
-    #[instrument(skip(self), err)]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), err))]
     async fn list(&self, limit: usize, offset: usize) -> RepoResult<Page<Channel>> {
         let start = Instant::now();
 
@@ -80,9 +160,9 @@ impl ChannelRepository for SqliteChannelRepository {
         // Get paginated items
         let rows = sqlx::query_as::<_, ChannelRow>(
             r#"
-            SELECT id, title, description, created_at, updated_at
+            SELECT id, title, description, created_at, updated_at, accessed_at, body
             FROM channels
-            ORDER BY created_at DESC
+            ORDER BY created_at DESC, id DESC
             LIMIT $1 OFFSET $2
             "#,
         )
@@ -98,7 +178,7 @@ impl ChannelRepository for SqliteChannelRepository {
             .collect::<Result<Vec<_>, _>>()?;
 
         let elapsed = start.elapsed();
-        if elapsed.as_millis() > SLOW_QUERY_THRESHOLD_MS {
+        if self.queries.record("channels.list", elapsed, items.len()) {
             warn!(
                 elapsed_ms = elapsed.as_millis(),
                 rows = items.len(),
@@ -115,12 +195,12 @@ impl ChannelRepository for SqliteChannelRepository {
         Ok(Page::new(items, total.0 as usize, offset, limit))
     }
 
-    #[instrument(skip(self, channel), fields(channel_id = %channel.id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self, channel), fields(channel_id = %channel.id.0)))]
     async fn update(&self, channel: &Channel) -> RepoResult<()> {
         let result = sqlx::query(
             r#"
             UPDATE channels
-            SET title = $2, description = $3, updated_at = $4
+            SET title = $2, description = $3, updated_at = $4, body = $5
             WHERE id = $1
             "#,
         )
@@ -128,6 +208,7 @@ impl ChannelRepository for SqliteChannelRepository {
         .bind(&channel.title)
         .bind(&channel.description)
         .bind(channel.updated_at.to_rfc3339())
+        .bind(&channel.body)
         .execute(&self.pool)
         .await
         .map_err(crate::error::DbError::from)?;
@@ -139,7 +220,7 @@ impl ChannelRepository for SqliteChannelRepository {
         Ok(())
     }
 
-    #[instrument(skip(self), fields(channel_id = %id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
     async fn delete(&self, id: &ChannelId) -> RepoResult<()> {
         let result = sqlx::query("DELETE FROM channels WHERE id = $1")
             .bind(&id.0)
@@ -154,7 +235,7 @@ impl ChannelRepository for SqliteChannelRepository {
         Ok(())
     }
 
-    #[instrument(skip(self))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     async fn count(&self) -> RepoResult<usize> {
         let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM channels")
             .fetch_one(&self.pool)
@@ -163,6 +244,65 @@ impl ChannelRepository for SqliteChannelRepository {
 
         Ok(count as usize)
     }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %id.0)))]
+    async fn touch_accessed(&self, id: &ChannelId) -> RepoResult<()> {
+        let result = sqlx::query("UPDATE channels SET accessed_at = $2 WHERE id = $1")
+            .bind(&id.0)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(garden_core::error::RepoError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn list_recently_accessed(&self, limit: usize) -> RepoResult<Vec<Channel>> {
+        let rows = sqlx::query_as::<_, ChannelRow>(
+            r#"
+            SELECT id, title, description, created_at, updated_at, accessed_at, body
+            FROM channels
+            WHERE accessed_at IS NOT NULL
+            ORDER BY accessed_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        rows.into_iter()
+            .map(|r| r.into_channel())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn list_recently_updated(&self, limit: usize) -> RepoResult<Vec<Channel>> {
+        let rows = sqlx::query_as::<_, ChannelRow>(
+            r#"
+            SELECT id, title, description, created_at, updated_at, accessed_at, body
+            FROM channels
+            ORDER BY updated_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        rows.into_iter()
+            .map(|r| r.into_channel())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
 }
 
 /// Internal row type for SQLite queries.
@@ -173,6 +313,8 @@ struct ChannelRow {
     description: Option<String>,
     created_at: String,
     updated_at: String,
+    accessed_at: Option<String>,
+    body: Option<String>,
 }
 
 impl ChannelRow {
@@ -183,8 +325,13 @@ impl ChannelRow {
             id: ChannelId(self.id),
             title: self.title,
             description: self.description,
+            body: self.body,
             created_at: parse_datetime(&self.created_at, "created_at")?,
             updated_at: parse_datetime(&self.updated_at, "updated_at")?,
+            accessed_at: self
+                .accessed_at
+                .map(|t| parse_datetime(&t, "accessed_at"))
+                .transpose()?,
         })
     }
 }
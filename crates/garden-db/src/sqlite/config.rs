@@ -0,0 +1,100 @@
+//! Connection settings for opening a SQLite database.
+
+use sqlx::sqlite::SqliteJournalMode;
+
+/// Default cap on a block's serialized content, in bytes (10 MiB).
+pub const DEFAULT_MAX_CONTENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Default cap on bound parameters in one batch statement.
+///
+/// 999 is the lowest limit any SQLite build enforces (older builds cap at
+/// 999, newer ones at 32766), so the default is safe everywhere.
+pub const DEFAULT_MAX_BATCH_PARAMS: usize = 999;
+
+/// How SQLite journals writes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JournalMode {
+    /// Write-ahead log in `-wal` and `-shm` files next to the database.
+    ///
+    /// Readers don't block the writer, so this is the default for apps.
+    #[default]
+    Wal,
+    /// Rollback journal in a `-journal` file, deleted after each commit.
+    Delete,
+    /// Rollback journal kept in memory; nothing extra is written to disk.
+    Memory,
+}
+
+impl From<JournalMode> for SqliteJournalMode {
+    fn from(mode: JournalMode) -> Self {
+        match mode {
+            JournalMode::Wal => SqliteJournalMode::Wal,
+            JournalMode::Delete => SqliteJournalMode::Delete,
+            JournalMode::Memory => SqliteJournalMode::Memory,
+        }
+    }
+}
+
+/// Settings for [`SqliteDatabase::with_config`](super::SqliteDatabase::with_config)
+/// and [`SqliteDatabase::in_memory_with_config`](super::SqliteDatabase::in_memory_with_config).
+///
+/// The defaults match [`SqliteDatabase::new`](super::SqliteDatabase::new).
+///
+/// # Example
+///
+/// ```ignore
+/// // Leaves no `-wal` or `-shm` files behind, e.g. for tests
+/// let config = SqliteConfig::new().journal_mode(JournalMode::Delete);
+/// let db = SqliteDatabase::with_config("garden.db", &config).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SqliteConfig {
+    pub(crate) journal_mode: JournalMode,
+    pub(crate) max_content_bytes: usize,
+    pub(crate) max_batch_params: usize,
+}
+
+impl Default for SqliteConfig {
+    fn default() -> Self {
+        Self {
+            journal_mode: JournalMode::default(),
+            max_content_bytes: DEFAULT_MAX_CONTENT_BYTES,
+            max_batch_params: DEFAULT_MAX_BATCH_PARAMS,
+        }
+    }
+}
+
+impl SqliteConfig {
+    /// Create a config with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the journal mode. Defaults to [`JournalMode::Wal`].
+    pub fn journal_mode(mut self, mode: JournalMode) -> Self {
+        self.journal_mode = mode;
+        self
+    }
+
+    /// Set the largest block content, as serialized JSON, that may be
+    /// stored. Defaults to [`DEFAULT_MAX_CONTENT_BYTES`].
+    ///
+    /// Enforced by the block repository itself, so it holds even for
+    /// writes that skip domain validation. Oversized content fails with
+    /// [`DbError::ContentTooLarge`](crate::DbError::ContentTooLarge).
+    pub fn max_content_bytes(mut self, max: usize) -> Self {
+        self.max_content_bytes = max;
+        self
+    }
+
+    /// Set how many bound parameters one statement may use when writing a
+    /// batch. Defaults to [`DEFAULT_MAX_BATCH_PARAMS`].
+    ///
+    /// Batch writes such as `create_batch` and `connect_batch` are split
+    /// into as many rows per statement as fit under this limit, all in one
+    /// transaction. Always fits at least one row per statement.
+    pub fn max_batch_params(mut self, max: usize) -> Self {
+        self.max_batch_params = max;
+        self
+    }
+}
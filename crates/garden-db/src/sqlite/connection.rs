@@ -1,33 +1,195 @@
 //! SQLite implementation of ConnectionRepository.
 
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
 use std::time::Instant;
-use tracing::{info, instrument, warn};
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+use tracing::{info, warn};
 
 use garden_core::error::RepoResult;
-use garden_core::models::{Block, BlockContent, BlockId, Channel, ChannelId, Connection};
+use garden_core::models::{
+    complete_order, gapped_position, move_group, move_relative, Block, BlockId, Channel, ChannelId,
+    ChannelOrder, ChannelSetMode, Connection, Page, Placement, PositionIssue, SharingStats,
+};
 use garden_core::ports::ConnectionRepository;
 
-/// Threshold for logging slow queries (50ms).
-const SLOW_QUERY_THRESHOLD_MS: u128 = 50;
+use super::block::{insert_blocks, BlockRow};
+use super::channel::insert_channel;
+use super::config::{DEFAULT_MAX_BATCH_PARAMS, DEFAULT_MAX_CONTENT_BYTES};
+use super::slow_query::QueryMonitor;
+use super::util::rows_per_statement;
 
 /// SQLite-backed connection repository.
 #[derive(Clone)]
 pub struct SqliteConnectionRepository {
     pool: SqlitePool,
+    queries: QueryMonitor,
+    max_batch_params: usize,
+    max_content_bytes: usize,
 }
 
 impl SqliteConnectionRepository {
     /// Create a new repository with the given connection pool.
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self::with_monitor(pool, QueryMonitor::default())
+    }
+
+    /// Create a repository that reports slow queries to `queries`.
+    pub(crate) fn with_monitor(pool: SqlitePool, queries: QueryMonitor) -> Self {
+        Self {
+            pool,
+            queries,
+            max_batch_params: DEFAULT_MAX_BATCH_PARAMS,
+            max_content_bytes: DEFAULT_MAX_CONTENT_BYTES,
+        }
+    }
+
+    /// Set the largest serialized block content, in bytes, that
+    /// [`ConnectionRepository::create_connected`] accepts.
+    ///
+    /// Defaults to [`DEFAULT_MAX_CONTENT_BYTES`].
+    pub fn with_max_content_bytes(mut self, max: usize) -> Self {
+        self.max_content_bytes = max;
+        self
+    }
+
+    /// Cap the bound parameters in each statement of a batch insert.
+    ///
+    /// Defaults to [`DEFAULT_MAX_BATCH_PARAMS`].
+    pub fn with_max_batch_params(mut self, max: usize) -> Self {
+        self.max_batch_params = max;
+        self
+    }
+
+    /// Pair each block with the channels it is in, ordered by title, using
+    /// one query for all of them.
+    async fn attach_channels(&self, blocks: Vec<Block>) -> RepoResult<Vec<(Block, Vec<Channel>)>> {
+        let ids: Vec<&str> = blocks.iter().map(|b| b.id.0.as_str()).collect();
+        let ids = serde_json::to_string(&ids).map_err(crate::error::DbError::from)?;
+        let rows = sqlx::query_as::<_, BlockChannelRow>(
+            r#"
+            SELECT
+                c.block_id,
+                ch.id, ch.title, ch.description, ch.created_at, ch.updated_at, ch.accessed_at,
+                ch.body
+            FROM connections c
+            INNER JOIN channels ch ON ch.id = c.channel_id
+            WHERE c.block_id IN (SELECT value FROM json_each($1))
+            ORDER BY ch.title COLLATE NOCASE ASC, ch.id ASC
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let mut memberships: HashMap<String, Vec<Channel>> = HashMap::new();
+        for row in rows {
+            memberships
+                .entry(row.block_id)
+                .or_default()
+                .push(row.channel.into_channel()?);
+        }
+        Ok(blocks
+            .into_iter()
+            .map(|block| {
+                let channels = memberships.remove(&block.id.0).unwrap_or_default();
+                (block, channels)
+            })
+            .collect())
+    }
+
+    /// Renumber a channel's connections `gap` apart from zero, in the order
+    /// `arrange` makes from the current one, in one transaction. Only rows
+    /// whose position changes are written; `arrange` returning `None` means
+    /// a block it was given is not in the channel.
+    async fn apply_order<F>(&self, channel_id: &ChannelId, gap: i32, arrange: F) -> RepoResult<()>
+    where
+        F: FnOnce(&[BlockId]) -> Option<Vec<BlockId>> + Send,
+    {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        let rows: Vec<(String, i32)> = sqlx::query_as(
+            r#"
+            SELECT block_id, position
+            FROM connections
+            WHERE channel_id = $1
+            ORDER BY position ASC, block_id ASC
+            "#,
+        )
+        .bind(&channel_id.0)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let current: Vec<BlockId> = rows.iter().map(|(id, _)| BlockId(id.clone())).collect();
+        let order = arrange(&current).ok_or(garden_core::error::RepoError::NotFound)?;
+        let new_positions = (0..order.len())
+            .map(|index| gapped_position(index, gap))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| {
+                garden_core::error::RepoError::Database("positions would pass i32::MAX".into())
+            })?;
+        let positions: HashMap<&str, i32> = rows.iter().map(|(id, p)| (id.as_str(), *p)).collect();
+
+        for (id, position) in order.iter().zip(new_positions) {
+            if positions.get(id.0.as_str()) == Some(&position) {
+                continue;
+            }
+            sqlx::query(
+                "UPDATE connections SET position = $3 WHERE block_id = $1 AND channel_id = $2",
+            )
+            .bind(&id.0)
+            .bind(&channel_id.0)
+            .bind(position)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::from)?;
+        }
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(())
     }
 }
 
+/// Insert connection rows inside a caller's transaction, batching rows per
+/// statement. All of them get the same `connected_at`.
+async fn insert_connections(
+    conn: &mut SqliteConnection,
+    connections: &[(BlockId, ChannelId, i32)],
+    max_batch_params: usize,
+) -> RepoResult<()> {
+    let connected_at = chrono::Utc::now().to_rfc3339();
+
+    // 4 parameters per row; stay under SQLite's bound parameter limit
+    for chunk in connections.chunks(rows_per_statement(max_batch_params, 4)) {
+        let mut insert = QueryBuilder::<Sqlite>::new(
+            "INSERT INTO connections (block_id, channel_id, position, connected_at) ",
+        );
+        insert.push_values(chunk, |mut row, (block_id, channel_id, position)| {
+            row.push_bind(&block_id.0)
+                .push_bind(&channel_id.0)
+                .push_bind(*position)
+                .push_bind(&connected_at);
+        });
+        insert
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(crate::error::DbError::from)?;
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl ConnectionRepository for SqliteConnectionRepository {
-    #[instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
     async fn connect(
         &self,
         block_id: &BlockId,
@@ -53,121 +215,881 @@ impl ConnectionRepository for SqliteConnectionRepository {
         Ok(())
     }
 
-    #[instrument(skip(self, connections), fields(count = connections.len()))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
+    async fn upsert(
+        &self,
+        block_id: &BlockId,
+        channel_id: &ChannelId,
+        position: i32,
+    ) -> RepoResult<()> {
+        let connected_at = chrono::Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO connections (block_id, channel_id, position, connected_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(block_id, channel_id) DO UPDATE SET position = excluded.position
+            "#,
+        )
+        .bind(&block_id.0)
+        .bind(&channel_id.0)
+        .bind(position)
+        .bind(&connected_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, connections), fields(count = connections.len())))]
     async fn connect_batch(&self, connections: &[(BlockId, ChannelId, i32)]) -> RepoResult<()> {
+        // Nothing to insert; skip opening a transaction
+        if connections.is_empty() {
+            return Ok(());
+        }
+
         let mut tx = self
             .pool
             .begin()
             .await
             .map_err(crate::error::DbError::from)?;
+        insert_connections(&mut tx, connections, self.max_batch_params).await?;
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, channels, blocks, connections), fields(channels = channels.len(), blocks = blocks.len(), connections = connections.len())))]
+    async fn create_connected(
+        &self,
+        channels: &[Channel],
+        blocks: &[Block],
+        connections: &[(BlockId, ChannelId, i32)],
+    ) -> RepoResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        for channel in channels {
+            insert_channel(&mut tx, channel).await?;
+        }
+        insert_blocks(
+            &mut tx,
+            blocks,
+            self.max_content_bytes,
+            self.max_batch_params,
+        )
+        .await?;
+        insert_connections(&mut tx, connections, self.max_batch_params).await?;
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
+    async fn prepend(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        // Insert first, so a duplicate fails before anything has shifted
+        sqlx::query(
+            r#"
+            INSERT INTO connections (block_id, channel_id, position, connected_at)
+            VALUES ($1, $2, 0, $3)
+            "#,
+        )
+        .bind(&block_id.0)
+        .bind(&channel_id.0)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        sqlx::query(
+            "UPDATE connections SET position = position + 1 WHERE channel_id = $1 AND block_id != $2",
+        )
+        .bind(&channel_id.0)
+        .bind(&block_id.0)
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, merge), fields(keep = %keep.0, merged = merge.len())))]
+    async fn merge_blocks(&self, keep: &BlockId, merge: &[BlockId]) -> RepoResult<Vec<ChannelId>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        let exists: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM blocks WHERE id = $1")
+            .bind(&keep.0)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::from)?;
+        if exists.is_none() {
+            return Err(garden_core::error::RepoError::NotFound);
+        }
+
+        let mut joined = Vec::new();
+        for id in merge {
+            // The channels this block brings that keep isn't in yet
+            let channels: Vec<(String,)> = sqlx::query_as(
+                r#"
+                SELECT channel_id FROM connections
+                WHERE block_id = $1
+                  AND channel_id NOT IN (SELECT channel_id FROM connections WHERE block_id = $2)
+                "#,
+            )
+            .bind(&id.0)
+            .bind(&keep.0)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+            // Rows that would collide with keep's are skipped, then cascade
+            // away with the block
+            sqlx::query("UPDATE OR IGNORE connections SET block_id = $1 WHERE block_id = $2")
+                .bind(&keep.0)
+                .bind(&id.0)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::from)?;
+
+            let deleted = sqlx::query("DELETE FROM blocks WHERE id = $1")
+                .bind(&id.0)
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::DbError::from)?;
+            if deleted.rows_affected() == 0 {
+                // Dropping the transaction rolls back the blocks merged so far
+                return Err(garden_core::error::RepoError::NotFound);
+            }
+
+            joined.extend(channels.into_iter().map(|(c,)| ChannelId(c)));
+        }
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        info!(joined = joined.len(), "Blocks merged");
+        Ok(joined)
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
+    async fn disconnect(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
+        let result = sqlx::query("DELETE FROM connections WHERE block_id = $1 AND channel_id = $2")
+            .bind(&block_id.0)
+            .bind(&channel_id.0)
+            .execute(&self.pool)
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(garden_core::error::RepoError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0), err))]
+    async fn get_blocks_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, i32)>> {
+        let start = Instant::now();
+
+        let rows = sqlx::query_as::<_, BlockWithPositionRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                c.position
+            FROM blocks b
+            INNER JOIN connections c ON b.id = c.block_id
+            WHERE c.channel_id = $1 AND ($2 OR b.archived = 0)
+            ORDER BY c.position ASC
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let result: Vec<(Block, i32)> = rows
+            .into_iter()
+            .map(|r| r.into_block_with_position())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let elapsed = start.elapsed();
+        if self
+            .queries
+            .record("connections.get_blocks_in_channel", elapsed, result.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = result.len(),
+                "Slow query: get blocks in channel"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = result.len(),
+                "Retrieved blocks in channel"
+            );
+        }
+
+        Ok(result)
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0), err))]
+    async fn connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<Vec<(Block, Connection)>> {
+        let rows = sqlx::query_as::<_, BlockConnectionRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                c.position, c.connected_at
+            FROM blocks b
+            INNER JOIN connections c ON b.id = c.block_id
+            WHERE c.channel_id = $1
+            ORDER BY c.position ASC, c.block_id ASC
+            "#,
+        )
+        .bind(&channel_id.0)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        rows.into_iter()
+            .map(|r| r.into_block_with_connection(channel_id))
+            .collect()
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0), err))]
+    async fn recent_connections_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, Connection)>> {
+        let rows = sqlx::query_as::<_, BlockConnectionRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                c.position, c.connected_at
+            FROM blocks b
+            INNER JOIN connections c ON b.id = c.block_id
+            WHERE c.channel_id = $1 AND ($3 OR b.archived = 0)
+            ORDER BY c.connected_at DESC, c.block_id ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(limit as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        rows.into_iter()
+            .map(|r| r.into_block_with_connection(channel_id))
+            .collect()
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, channel_ids), fields(channels = channel_ids.len()), err))]
+    async fn blocks_in_channels(
+        &self,
+        channel_ids: &[ChannelId],
+        mode: ChannelSetMode,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        let start = Instant::now();
+
+        let mut ids: Vec<&str> = channel_ids.iter().map(|id| id.0.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        if ids.is_empty() {
+            return Ok(Page::new(Vec::new(), 0, offset, limit));
+        }
+        let required = match mode {
+            ChannelSetMode::Union => 1,
+            ChannelSetMode::Intersection => ids.len(),
+        };
+        // Bound as one JSON array rather than a variable-length IN list
+        let ids = serde_json::to_string(&ids).map_err(crate::error::DbError::from)?;
+
+        let (total,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM blocks b
+            INNER JOIN (
+                SELECT block_id FROM connections
+                WHERE channel_id IN (SELECT value FROM json_each($1))
+                GROUP BY block_id
+                HAVING COUNT(DISTINCT channel_id) >= $2
+            ) m ON b.id = m.block_id
+            WHERE $3 OR b.archived = 0
+            "#,
+        )
+        .bind(&ids)
+        .bind(required as i64)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let rows = sqlx::query_as::<_, BlockRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived
+            FROM blocks b
+            INNER JOIN (
+                SELECT block_id FROM connections
+                WHERE channel_id IN (SELECT value FROM json_each($1))
+                GROUP BY block_id
+                HAVING COUNT(DISTINCT channel_id) >= $2
+            ) m ON b.id = m.block_id
+            WHERE $5 OR b.archived = 0
+            ORDER BY b.created_at DESC, b.id ASC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(&ids)
+        .bind(required as i64)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items = rows
+            .into_iter()
+            .map(|r| r.into_block())
+            .collect::<RepoResult<Vec<_>>>()?;
+
+        let elapsed = start.elapsed();
+        if self
+            .queries
+            .record("connections.blocks_in_channels", elapsed, items.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Slow query: blocks in channels"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Retrieved blocks in channels"
+            );
+        }
+
+        Ok(Page::new(items, total as usize, offset, limit))
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0), err))]
+    async fn blocks_not_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<Block>> {
+        let start = Instant::now();
+
+        let (total,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM blocks
+            WHERE ($2 OR archived = 0)
+              AND id NOT IN (SELECT block_id FROM connections WHERE channel_id = $1)
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let rows = sqlx::query_as::<_, BlockRow>(
+            r#"
+            SELECT
+                id, content_type, content_json, created_at, updated_at,
+                source_url, source_title, creator, original_date, notes, archived
+            FROM blocks
+            WHERE ($4 OR archived = 0)
+              AND id NOT IN (SELECT block_id FROM connections WHERE channel_id = $1)
+            ORDER BY created_at DESC, id ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items = rows
+            .into_iter()
+            .map(|r| r.into_block())
+            .collect::<RepoResult<Vec<_>>>()?;
+
+        let elapsed = start.elapsed();
+        if self
+            .queries
+            .record("connections.blocks_not_in_channel", elapsed, items.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Slow query: blocks not in channel"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Retrieved blocks not in channel"
+            );
+        }
+
+        Ok(Page::new(items, total as usize, offset, limit))
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block_ids), err))]
+    async fn blocks_with_channels(
+        &self,
+        block_ids: Option<&[BlockId]>,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, Vec<Channel>)>> {
+        let start = Instant::now();
+
+        let ids = block_ids
+            .map(|ids| {
+                let ids: Vec<&str> = ids.iter().map(|id| id.0.as_str()).collect();
+                serde_json::to_string(&ids)
+            })
+            .transpose()
+            .map_err(crate::error::DbError::from)?;
+
+        let (total,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM blocks
+            WHERE ($1 IS NULL OR id IN (SELECT value FROM json_each($1)))
+              AND ($2 OR archived = 0)
+            "#,
+        )
+        .bind(&ids)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        // Page the blocks first and hydrate their channels separately, so a
+        // block in many channels does not multiply the rows being paged
+        let rows = sqlx::query_as::<_, BlockRow>(
+            r#"
+            SELECT
+                id, content_type, content_json, created_at, updated_at,
+                source_url, source_title, creator, original_date, notes, archived
+            FROM blocks
+            WHERE ($1 IS NULL OR id IN (SELECT value FROM json_each($1)))
+              AND ($4 OR archived = 0)
+            ORDER BY created_at DESC, id ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(&ids)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let blocks = rows
+            .into_iter()
+            .map(|r| r.into_block())
+            .collect::<RepoResult<Vec<_>>>()?;
+        let items = self.attach_channels(blocks).await?;
+
+        let elapsed = start.elapsed();
+        if self
+            .queries
+            .record("connections.blocks_with_channels", elapsed, items.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Slow query: blocks with channels"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Retrieved page of blocks with channels"
+            );
+        }
+
+        Ok(Page::new(items, total as usize, offset, limit))
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0), err))]
+    async fn get_blocks_in_channel_paged(
+        &self,
+        channel_id: &ChannelId,
+        limit: usize,
+        offset: usize,
+        include_archived: bool,
+    ) -> RepoResult<Page<(Block, i32)>> {
+        let start = Instant::now();
+
+        let (total,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM connections c
+            INNER JOIN blocks b ON b.id = c.block_id
+            WHERE c.channel_id = $1 AND ($2 OR b.archived = 0)
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(include_archived)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let rows = sqlx::query_as::<_, BlockWithPositionRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                c.position
+            FROM blocks b
+            INNER JOIN connections c ON b.id = c.block_id
+            WHERE c.channel_id = $1 AND ($4 OR b.archived = 0)
+            ORDER BY c.position ASC, c.block_id ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items: Vec<(Block, i32)> = rows
+            .into_iter()
+            .map(|r| r.into_block_with_position())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let elapsed = start.elapsed();
+        if self.queries.record(
+            "connections.get_blocks_in_channel_paged",
+            elapsed,
+            items.len(),
+        ) {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Slow query: get blocks in channel (paged)"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Retrieved page of blocks in channel"
+            );
+        }
+
+        Ok(Page::new(items, total as usize, offset, limit))
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0), err))]
+    async fn neighbors(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<(Option<Block>, Option<Block>)> {
+        let (position,): (i32,) = sqlx::query_as(
+            "SELECT position FROM connections WHERE channel_id = $1 AND block_id = $2",
+        )
+        .bind(&channel_id.0)
+        .bind(&block_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?
+        .ok_or(garden_core::error::RepoError::NotFound)?;
+
+        // Order by (position, block_id) so ties have a stable order
+        let previous = sqlx::query_as::<_, BlockWithPositionRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                c.position
+            FROM blocks b
+            INNER JOIN connections c ON b.id = c.block_id
+            WHERE c.channel_id = $1 AND (c.position, c.block_id) < ($2, $3)
+            ORDER BY c.position DESC, c.block_id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(position)
+        .bind(&block_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let next = sqlx::query_as::<_, BlockWithPositionRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                c.position
+            FROM blocks b
+            INNER JOIN connections c ON b.id = c.block_id
+            WHERE c.channel_id = $1 AND (c.position, c.block_id) > ($2, $3)
+            ORDER BY c.position ASC, c.block_id ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(position)
+        .bind(&block_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
 
-        // Use consistent timestamp for all connections in the batch
-        let connected_at = chrono::Utc::now().to_rfc3339();
+        let previous = previous
+            .map(|r| r.into_block_with_position().map(|(block, _)| block))
+            .transpose()?;
+        let next = next
+            .map(|r| r.into_block_with_position().map(|(block, _)| block))
+            .transpose()?;
 
-        for (block_id, channel_id, position) in connections {
-            sqlx::query(
-                r#"
-                INSERT INTO connections (block_id, channel_id, position, connected_at)
-                VALUES ($1, $2, $3, $4)
-                "#,
-            )
-            .bind(&block_id.0)
-            .bind(&channel_id.0)
-            .bind(position)
-            .bind(&connected_at)
-            .execute(&mut *tx)
-            .await
-            .map_err(crate::error::DbError::from)?;
-        }
+        Ok((previous, next))
+    }
 
-        tx.commit().await.map_err(crate::error::DbError::from)?;
-        Ok(())
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0), err))]
+    async fn block_index_in_channel(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+    ) -> RepoResult<Option<(usize, usize)>> {
+        // Count the connections ordered before this one by (position,
+        // block_id), matching `neighbors`
+        let row: Option<(i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM connections o
+                 WHERE o.channel_id = c.channel_id
+                   AND (o.position, o.block_id) < (c.position, c.block_id)),
+                (SELECT COUNT(*) FROM connections o WHERE o.channel_id = c.channel_id)
+            FROM connections c
+            WHERE c.channel_id = $1 AND c.block_id = $2
+            "#,
+        )
+        .bind(&channel_id.0)
+        .bind(&block_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(row.map(|(index, total)| (index as usize, total as usize)))
     }
 
-    #[instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
-    async fn disconnect(&self, block_id: &BlockId, channel_id: &ChannelId) -> RepoResult<()> {
-        let result = sqlx::query("DELETE FROM connections WHERE block_id = $1 AND channel_id = $2")
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0)))]
+    async fn get_channels_for_block(
+        &self,
+        block_id: &BlockId,
+        order: ChannelOrder,
+    ) -> RepoResult<Vec<Channel>> {
+        let order_by = match order {
+            ChannelOrder::ByConnectedAtDesc => "c.connected_at DESC, c.channel_id ASC",
+            ChannelOrder::ByTitleAsc => "ch.title COLLATE NOCASE ASC, ch.id ASC",
+        };
+        let sql = format!(
+            r#"
+            SELECT
+                ch.id, ch.title, ch.description, ch.created_at, ch.updated_at, ch.accessed_at,
+                ch.body
+            FROM channels ch
+            INNER JOIN connections c ON ch.id = c.channel_id
+            WHERE c.block_id = $1
+            ORDER BY {}
+            "#,
+            order_by
+        );
+        let rows = sqlx::query_as::<_, ChannelRow>(&sql)
             .bind(&block_id.0)
-            .bind(&channel_id.0)
-            .execute(&self.pool)
+            .fetch_all(&self.pool)
             .await
             .map_err(crate::error::DbError::from)?;
 
-        if result.rows_affected() == 0 {
-            return Err(garden_core::error::RepoError::NotFound);
-        }
-
-        Ok(())
+        rows.into_iter()
+            .map(|r| r.into_channel())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Into::into)
     }
 
-    #[instrument(skip(self), fields(channel_id = %channel_id.0), err)]
-    async fn get_blocks_in_channel(&self, channel_id: &ChannelId) -> RepoResult<Vec<(Block, i32)>> {
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0), err))]
+    async fn get_channels_for_block_paged(
+        &self,
+        block_id: &BlockId,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>> {
         let start = Instant::now();
 
-        let rows = sqlx::query_as::<_, BlockWithPositionRow>(
+        let (total,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM connections WHERE block_id = $1")
+                .bind(&block_id.0)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(crate::error::DbError::from)?;
+
+        let rows = sqlx::query_as::<_, ChannelRow>(
             r#"
             SELECT
-                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
-                b.source_url, b.source_title, b.creator, b.original_date, b.notes,
-                c.position
-            FROM blocks b
-            INNER JOIN connections c ON b.id = c.block_id
-            WHERE c.channel_id = $1
-            ORDER BY c.position ASC
+                ch.id, ch.title, ch.description, ch.created_at, ch.updated_at, ch.accessed_at,
+                ch.body
+            FROM channels ch
+            INNER JOIN connections c ON ch.id = c.channel_id
+            WHERE c.block_id = $1
+            ORDER BY c.connected_at DESC, c.channel_id ASC
+            LIMIT $2 OFFSET $3
             "#,
         )
-        .bind(&channel_id.0)
+        .bind(&block_id.0)
+        .bind(limit as i64)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(crate::error::DbError::from)?;
 
-        let result: Vec<(Block, i32)> = rows
+        let items: Vec<Channel> = rows
             .into_iter()
-            .map(|r| r.into_block_with_position())
+            .map(|r| r.into_channel())
             .collect::<Result<Vec<_>, _>>()?;
 
         let elapsed = start.elapsed();
-        if elapsed.as_millis() > SLOW_QUERY_THRESHOLD_MS {
+        if self.queries.record(
+            "connections.get_channels_for_block_paged",
+            elapsed,
+            items.len(),
+        ) {
             warn!(
                 elapsed_ms = elapsed.as_millis(),
-                blocks = result.len(),
-                "Slow query: get blocks in channel"
+                channels = items.len(),
+                "Slow query: get channels for block (paged)"
             );
         } else {
             info!(
                 elapsed_ms = elapsed.as_millis(),
-                blocks = result.len(),
-                "Retrieved blocks in channel"
+                channels = items.len(),
+                "Retrieved page of channels for block"
             );
         }
 
-        Ok(result)
+        Ok(Page::new(items, total as usize, offset, limit))
     }
 
-    #[instrument(skip(self), fields(block_id = %block_id.0))]
-    async fn get_channels_for_block(&self, block_id: &BlockId) -> RepoResult<Vec<Channel>> {
+    #[cfg_attr(feature = "tracing", instrument(skip(self), err))]
+    async fn channels_with_content_type(
+        &self,
+        content_type: &str,
+        limit: usize,
+        offset: usize,
+    ) -> RepoResult<Page<Channel>> {
+        let start = Instant::now();
+
+        let (total,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(DISTINCT c.channel_id)
+            FROM connections c
+            INNER JOIN blocks b ON b.id = c.block_id
+            WHERE b.content_type = $1
+            "#,
+        )
+        .bind(content_type)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
         let rows = sqlx::query_as::<_, ChannelRow>(
             r#"
             SELECT
-                ch.id, ch.title, ch.description, ch.created_at, ch.updated_at
+                ch.id, ch.title, ch.description, ch.created_at, ch.updated_at, ch.accessed_at,
+                ch.body
             FROM channels ch
-            INNER JOIN connections c ON ch.id = c.channel_id
-            WHERE c.block_id = $1
-            ORDER BY c.connected_at DESC
+            WHERE EXISTS (
+                SELECT 1 FROM connections c
+                INNER JOIN blocks b ON b.id = c.block_id
+                WHERE c.channel_id = ch.id AND b.content_type = $1
+            )
+            ORDER BY ch.created_at DESC, ch.id ASC
+            LIMIT $2 OFFSET $3
             "#,
         )
-        .bind(&block_id.0)
+        .bind(content_type)
+        .bind(limit as i64)
+        .bind(offset as i64)
         .fetch_all(&self.pool)
         .await
         .map_err(crate::error::DbError::from)?;
 
-        rows.into_iter()
+        let items: Vec<Channel> = rows
+            .into_iter()
             .map(|r| r.into_channel())
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(Into::into)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let elapsed = start.elapsed();
+        if self.queries.record(
+            "connections.channels_with_content_type",
+            elapsed,
+            items.len(),
+        ) {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                channels = items.len(),
+                "Slow query: channels with content type"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                channels = items.len(),
+                "Retrieved page of channels with content type"
+            );
+        }
+
+        Ok(Page::new(items, total as usize, offset, limit))
     }
 
-    #[instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(block_id = %block_id.0, channel_id = %channel_id.0)))]
     async fn get_connection(
         &self,
         block_id: &BlockId,
@@ -192,7 +1114,7 @@ impl ConnectionRepository for SqliteConnectionRepository {
         }
     }
 
-    #[instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0))]
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0)))]
     async fn reorder(
         &self,
         channel_id: &ChannelId,
@@ -220,8 +1142,91 @@ impl ConnectionRepository for SqliteConnectionRepository {
         Ok(())
     }
 
-    #[instrument(skip(self), fields(channel_id = %channel_id.0))]
-    async fn next_position(&self, channel_id: &ChannelId) -> RepoResult<i32> {
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_a = %block_a.0, block_b = %block_b.0)))]
+    async fn swap_positions(
+        &self,
+        channel_id: &ChannelId,
+        block_a: &BlockId,
+        block_b: &BlockId,
+    ) -> RepoResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::DbError::from)?;
+
+        let mut positions = Vec::with_capacity(2);
+        for block_id in [block_a, block_b] {
+            let position: Option<(i32,)> = sqlx::query_as(
+                "SELECT position FROM connections WHERE block_id = $1 AND channel_id = $2",
+            )
+            .bind(&block_id.0)
+            .bind(&channel_id.0)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::from)?;
+            let (position,) = position.ok_or(garden_core::error::RepoError::NotFound)?;
+            positions.push(position);
+        }
+
+        for (block_id, position) in [(block_a, positions[1]), (block_b, positions[0])] {
+            sqlx::query(
+                "UPDATE connections SET position = $3 WHERE block_id = $1 AND channel_id = $2",
+            )
+            .bind(&block_id.0)
+            .bind(&channel_id.0)
+            .bind(position)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::DbError::from)?;
+        }
+
+        tx.commit().await.map_err(crate::error::DbError::from)?;
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0, block_id = %block_id.0)))]
+    async fn move_relative(
+        &self,
+        channel_id: &ChannelId,
+        block_id: &BlockId,
+        anchor: &BlockId,
+        placement: Placement,
+        gap: i32,
+    ) -> RepoResult<()> {
+        self.apply_order(channel_id, gap, |current| {
+            move_relative(current, block_id, anchor, placement)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, order), fields(channel_id = %channel_id.0, blocks = order.len())))]
+    async fn set_order(
+        &self,
+        channel_id: &ChannelId,
+        order: &[BlockId],
+        gap: i32,
+    ) -> RepoResult<()> {
+        self.apply_order(channel_id, gap, |current| complete_order(current, order))
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self, block_ids), fields(channel_id = %channel_id.0, blocks = block_ids.len())))]
+    async fn move_group(
+        &self,
+        channel_id: &ChannelId,
+        block_ids: &[BlockId],
+        target_index: usize,
+        gap: i32,
+    ) -> RepoResult<()> {
+        self.apply_order(channel_id, gap, |current| {
+            move_group(current, block_ids, target_index)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    async fn next_position(&self, channel_id: &ChannelId, gap: i32) -> RepoResult<Option<i32>> {
         let result: Option<(Option<i32>,)> =
             sqlx::query_as("SELECT MAX(position) FROM connections WHERE channel_id = $1")
                 .bind(&channel_id.0)
@@ -229,9 +1234,199 @@ impl ConnectionRepository for SqliteConnectionRepository {
                 .await
                 .map_err(crate::error::DbError::from)?;
 
-        // If no connections exist, or max is NULL, start at 0
-        // Otherwise, return max + 1
-        Ok(result.and_then(|(max,)| max).map(|m| m + 1).unwrap_or(0))
+        // An empty channel (NULL max) starts at 0
+        Ok(match result.and_then(|(max,)| max) {
+            Some(max) => max.checked_add(gap),
+            None => Some(0),
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    async fn sharing_stats(&self, channel_id: &ChannelId) -> RepoResult<SharingStats> {
+        let (total, exclusive): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*),
+                   COALESCE(SUM((SELECT COUNT(*) FROM connections o WHERE o.block_id = c.block_id) = 1), 0)
+            FROM connections c
+            WHERE c.channel_id = $1
+            "#,
+        )
+        .bind(&channel_id.0)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(SharingStats {
+            total: total as usize,
+            exclusive: exclusive as usize,
+            shared: (total - exclusive) as usize,
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), fields(channel_id = %channel_id.0)))]
+    async fn content_type_breakdown_in_channel(
+        &self,
+        channel_id: &ChannelId,
+    ) -> RepoResult<HashMap<String, usize>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT b.content_type, COUNT(*)
+            FROM connections c
+            INNER JOIN blocks b ON b.id = c.block_id
+            WHERE c.channel_id = $1
+            GROUP BY b.content_type
+            "#,
+        )
+        .bind(&channel_id.0)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(content_type, count)| (content_type, count as usize))
+            .collect())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    async fn counts_by_channel(&self) -> RepoResult<HashMap<ChannelId, usize>> {
+        let rows: Vec<(String, i64)> =
+            sqlx::query_as("SELECT channel_id, COUNT(*) FROM connections GROUP BY channel_id")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(crate::error::DbError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(channel_id, count)| (ChannelId(channel_id), count as usize))
+            .collect())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), err))]
+    async fn channels_with_position_collisions(&self) -> RepoResult<Vec<ChannelId>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT DISTINCT channel_id
+            FROM connections
+            GROUP BY channel_id, position
+            HAVING COUNT(*) > 1
+            ORDER BY channel_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(rows.into_iter().map(|(id,)| ChannelId(id)).collect())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), err))]
+    async fn find_position_anomalies(
+        &self,
+        gap: i32,
+    ) -> RepoResult<Vec<(ChannelId, PositionIssue)>> {
+        // A gap is a step between distinct non-negative positions wider
+        // than `gap`, with the first step taken from -1
+        let rows: Vec<(String, bool, bool, bool)> = sqlx::query_as(
+            r#"
+            WITH steps AS (
+                SELECT
+                    channel_id,
+                    position - COALESCE(
+                        LAG(position) OVER (PARTITION BY channel_id ORDER BY position),
+                        -1
+                    ) AS step
+                FROM (SELECT DISTINCT channel_id, position FROM connections WHERE position >= 0)
+            )
+            SELECT
+                channel_id,
+                MIN(position) < 0 AS negative,
+                COUNT(*) > COUNT(DISTINCT position) AS duplicate,
+                channel_id IN (SELECT channel_id FROM steps WHERE step > $1) AS gap
+            FROM connections
+            GROUP BY channel_id
+            HAVING negative OR duplicate OR gap
+            ORDER BY channel_id
+            "#,
+        )
+        .bind(gap)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        Ok(rows
+            .into_iter()
+            .flat_map(|(id, negative, duplicate, gap)| {
+                [
+                    (negative, PositionIssue::Negative),
+                    (duplicate, PositionIssue::Duplicate),
+                    (gap, PositionIssue::Gap),
+                ]
+                .into_iter()
+                .filter(|(found, _)| *found)
+                .map(move |(_, issue)| (ChannelId(id.clone()), issue))
+            })
+            .collect())
+    }
+
+    #[cfg_attr(feature = "tracing", instrument(skip(self), err))]
+    async fn most_connected_blocks(
+        &self,
+        limit: usize,
+        include_archived: bool,
+    ) -> RepoResult<Vec<(Block, usize)>> {
+        let start = Instant::now();
+
+        let rows = sqlx::query_as::<_, ConnectedBlockRow>(
+            r#"
+            SELECT
+                b.id, b.content_type, b.content_json, b.created_at, b.updated_at,
+                b.source_url, b.source_title, b.creator, b.original_date, b.notes, b.archived,
+                m.channels
+            FROM blocks b
+            INNER JOIN (
+                SELECT block_id, COUNT(*) AS channels FROM connections
+                GROUP BY block_id
+            ) m ON b.id = m.block_id
+            WHERE $2 OR b.archived = 0
+            ORDER BY m.channels DESC, b.created_at DESC, b.id ASC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .bind(include_archived)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::DbError::from)?;
+
+        let items = rows
+            .into_iter()
+            .map(|r| {
+                r.block
+                    .into_block()
+                    .map(|block| (block, r.channels as usize))
+            })
+            .collect::<RepoResult<Vec<_>>>()?;
+
+        let elapsed = start.elapsed();
+        if self
+            .queries
+            .record("connections.most_connected_blocks", elapsed, items.len())
+        {
+            warn!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Slow query: most connected blocks"
+            );
+        } else {
+            info!(
+                elapsed_ms = elapsed.as_millis(),
+                blocks = items.len(),
+                "Retrieved most connected blocks"
+            );
+        }
+
+        Ok(items)
     }
 }
 
@@ -260,45 +1455,55 @@ impl ConnectionRow {
 
 #[derive(sqlx::FromRow)]
 struct BlockWithPositionRow {
-    id: String,
-    #[allow(dead_code)]
-    content_type: String,
-    content_json: String,
-    created_at: String,
-    updated_at: String,
-    // Archive metadata fields
-    source_url: Option<String>,
-    source_title: Option<String>,
-    creator: Option<String>,
-    original_date: Option<String>,
-    notes: Option<String>,
+    #[sqlx(flatten)]
+    block: BlockRow,
     position: i32,
 }
 
 impl BlockWithPositionRow {
     fn into_block_with_position(self) -> RepoResult<(Block, i32)> {
+        Ok((self.block.into_block()?, self.position))
+    }
+}
+
+/// A block plus the number of channels it is connected to.
+#[derive(sqlx::FromRow)]
+struct ConnectedBlockRow {
+    #[sqlx(flatten)]
+    block: BlockRow,
+    channels: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct BlockConnectionRow {
+    #[sqlx(flatten)]
+    block: BlockWithPositionRow,
+    connected_at: String,
+}
+
+impl BlockConnectionRow {
+    fn into_block_with_connection(self, channel_id: &ChannelId) -> RepoResult<(Block, Connection)> {
         use super::util::parse_datetime;
 
-        let content: BlockContent =
-            serde_json::from_str(&self.content_json).map_err(crate::error::DbError::from)?;
-
-        Ok((
-            Block {
-                id: BlockId(self.id),
-                content,
-                created_at: parse_datetime(&self.created_at, "created_at")?,
-                updated_at: parse_datetime(&self.updated_at, "updated_at")?,
-                source_url: self.source_url,
-                source_title: self.source_title,
-                creator: self.creator,
-                original_date: self.original_date,
-                notes: self.notes,
-            },
-            self.position,
-        ))
+        let connected_at = parse_datetime(&self.connected_at, "connected_at")?;
+        let (block, position) = self.block.into_block_with_position()?;
+        let connection = Connection {
+            block_id: block.id.clone(),
+            channel_id: channel_id.clone(),
+            position,
+            connected_at,
+        };
+        Ok((block, connection))
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct BlockChannelRow {
+    block_id: String,
+    #[sqlx(flatten)]
+    channel: ChannelRow,
+}
+
 #[derive(sqlx::FromRow)]
 struct ChannelRow {
     id: String,
@@ -306,6 +1511,8 @@ struct ChannelRow {
     description: Option<String>,
     created_at: String,
     updated_at: String,
+    accessed_at: Option<String>,
+    body: Option<String>,
 }
 
 impl ChannelRow {
@@ -316,8 +1523,13 @@ impl ChannelRow {
             id: ChannelId(self.id),
             title: self.title,
             description: self.description,
+            body: self.body,
             created_at: parse_datetime(&self.created_at, "created_at")?,
             updated_at: parse_datetime(&self.updated_at, "updated_at")?,
+            accessed_at: self
+                .accessed_at
+                .map(|t| parse_datetime(&t, "accessed_at"))
+                .transpose()?,
         })
     }
 }
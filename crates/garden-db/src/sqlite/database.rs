@@ -3,31 +3,68 @@
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
 use std::path::Path;
 use std::str::FromStr;
-use tracing::{info, instrument};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::{Handle, RuntimeFlavor};
+#[cfg(feature = "tracing")]
+use tracing::instrument;
+use tracing::{info, warn};
 
-use super::{SqliteBlockRepository, SqliteChannelRepository, SqliteConnectionRepository};
+use garden_core::models::{BlockContent, IntegrityReport};
+
+use super::slow_query::QueryMonitor;
+use super::{
+    block, integrity, reconcile, SlowQuery, SqliteActivityRepository, SqliteBlockRepository,
+    SqliteChannelRepository, SqliteConfig, SqliteConnectionRepository,
+};
 use crate::error::DbResult;
 
+/// Version of the stored block content format, tracked in SQLite's
+/// `user_version`. Bump it whenever [`upgrade_content`] changes.
+const CONTENT_VERSION: i64 = 1;
+
+/// Opt-in index making channel titles unique, ignoring case.
+///
+/// Kept out of `migrations/` because existing gardens may already have
+/// channels with the same title, which would make the migration fail.
+const UNIQUE_CHANNEL_TITLES_INDEX: &str = "idx_channels_title_unique";
+
+/// Bring one block's content up to [`CONTENT_VERSION`].
+///
+/// Currently only normalizes: fields missing from old rows are filled with
+/// their serde defaults when the row is rewritten.
+fn upgrade_content(content: BlockContent) -> BlockContent {
+    content
+}
+
 /// SQLite database connection manager.
 ///
 /// Manages the connection pool and provides access to repositories.
 #[derive(Clone)]
 pub struct SqliteDatabase {
     pool: SqlitePool,
+    queries: QueryMonitor,
+    max_content_bytes: usize,
+    max_batch_params: usize,
 }
 
 impl SqliteDatabase {
     /// Create a new database connection to a file.
     ///
     /// Creates the database file if it doesn't exist.
-    #[instrument(skip_all, fields(path = %path.as_ref().display()))]
     pub async fn new(path: impl AsRef<Path>) -> DbResult<Self> {
+        Self::with_config(path, &SqliteConfig::default()).await
+    }
+
+    /// Like [`Self::new`], with the given connection settings.
+    #[cfg_attr(feature = "tracing", instrument(skip_all, fields(path = %path.as_ref().display())))]
+    pub async fn with_config(path: impl AsRef<Path>, config: &SqliteConfig) -> DbResult<Self> {
         let path = path.as_ref();
 
         let options = SqliteConnectOptions::new()
             .filename(path)
             .create_if_missing(true)
-            .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
+            .journal_mode(config.journal_mode.into())
             .synchronous(sqlx::sqlite::SqliteSynchronous::Normal)
             .busy_timeout(std::time::Duration::from_secs(30))
             .foreign_keys(true); // Enable FK constraint enforcement
@@ -38,14 +75,19 @@ impl SqliteDatabase {
             .await?;
 
         info!("Connected to SQLite database");
-        Ok(Self { pool })
+        Ok(Self::from_pool(pool, config))
     }
 
     /// Create an in-memory database (useful for testing).
-    #[instrument]
     pub async fn in_memory() -> DbResult<Self> {
+        Self::in_memory_with_config(&SqliteConfig::default()).await
+    }
+
+    /// Like [`Self::in_memory`], with the given connection settings.
+    #[cfg_attr(feature = "tracing", instrument)]
+    pub async fn in_memory_with_config(config: &SqliteConfig) -> DbResult<Self> {
         let options = SqliteConnectOptions::from_str(":memory:")?
-            .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
+            .journal_mode(config.journal_mode.into())
             .synchronous(sqlx::sqlite::SqliteSynchronous::Normal)
             .foreign_keys(true); // Enable FK constraint enforcement
 
@@ -55,45 +97,228 @@ impl SqliteDatabase {
             .await?;
 
         info!("Connected to in-memory SQLite database");
-        Ok(Self { pool })
+        Ok(Self::from_pool(pool, config))
+    }
+
+    /// Create a named in-memory database shared by every pooled connection.
+    ///
+    /// Unlike [`Self::in_memory`], the pool may hold several connections, all
+    /// seeing the same data, so tests can exercise concurrent access. Opening
+    /// the same `name` again in this process attaches to the same database.
+    /// In-memory databases can't use WAL, so the journal mode is left at
+    /// SQLite's default. The pool keeps one connection open for its lifetime,
+    /// since the database is dropped once its last connection closes.
+    #[cfg_attr(feature = "tracing", instrument)]
+    pub async fn in_memory_shared(name: &str) -> DbResult<Self> {
+        let options =
+            SqliteConnectOptions::from_str(&format!("file:{}?mode=memory&cache=shared", name))?
+                .busy_timeout(std::time::Duration::from_secs(30))
+                .foreign_keys(true); // Enable FK constraint enforcement
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+
+        info!("Connected to shared in-memory SQLite database");
+        Ok(Self::from_pool(pool, &SqliteConfig::default()))
+    }
+
+    fn from_pool(pool: SqlitePool, config: &SqliteConfig) -> Self {
+        Self {
+            pool,
+            queries: QueryMonitor::default(),
+            max_content_bytes: config.max_content_bytes,
+            max_batch_params: config.max_batch_params,
+        }
+    }
+
+    /// Call `callback` whenever a query takes longer than the slow-query
+    /// threshold.
+    ///
+    /// Applies to repositories obtained after this call. Slow queries are
+    /// still logged as before; the callback lets an app surface them too,
+    /// e.g. in a diagnostics view. It runs on the querying task, so keep it
+    /// cheap.
+    pub fn on_slow_query(mut self, callback: impl Fn(SlowQuery) + Send + Sync + 'static) -> Self {
+        self.queries.set_callback(Arc::new(callback));
+        self
+    }
+
+    /// Set how long a query may take before it counts as slow.
+    ///
+    /// Defaults to [`DEFAULT_SLOW_QUERY_THRESHOLD`](super::DEFAULT_SLOW_QUERY_THRESHOLD).
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.queries.set_threshold(threshold);
+        self
     }
 
     /// Run database migrations.
     ///
     /// Migrations are embedded at compile time from the `migrations/` directory.
-    /// After running migrations, the schema is verified to ensure all required tables exist.
-    #[instrument(skip(self))]
+    /// A migration whose columns are already in place (e.g. after an
+    /// interrupted run) is recorded as applied without running it, with a
+    /// warning naming the columns. After running migrations, the schema is
+    /// verified to ensure all required tables and columns exist.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `DbError::SchemaInvalid` without touching the database if
+    /// it has migrations applied that this build doesn't know about, i.e. it
+    /// was last opened by a newer version of the app.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn migrate(&self) -> DbResult<()> {
         info!("Running database migrations...");
         // Use compile-time embedded migrations for deterministic path resolution
-        sqlx::migrate!().run(&self.pool).await?;
+        let migrator = sqlx::migrate!();
+        self.check_not_newer(&migrator).await?;
+        reconcile::apply_pending(&self.pool, &migrator).await?;
+        // Nothing is left to apply; this checks applied migrations' checksums
+        migrator.run(&self.pool).await?;
         info!("Migrations complete");
 
         // Verify schema after migrations
         self.verify_schema().await?;
+
+        self.upgrade_content().await?;
         Ok(())
     }
 
+    /// Rewrite stored block content written by older versions.
+    ///
+    /// Runs as part of [`Self::migrate`], and is skipped once the database's
+    /// content version is current.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn upgrade_content(&self) -> DbResult<()> {
+        let (version,): (i64,) = sqlx::query_as("PRAGMA user_version")
+            .fetch_one(&self.pool)
+            .await?;
+        if version >= CONTENT_VERSION {
+            return Ok(());
+        }
+
+        info!(
+            from = version,
+            to = CONTENT_VERSION,
+            "Upgrading block content"
+        );
+        block::migrate_content(&self.pool, upgrade_content, self.max_content_bytes).await?;
+        // PRAGMA values can't be bound as parameters
+        sqlx::query(&format!("PRAGMA user_version = {}", CONTENT_VERSION))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Turn case-insensitive unique channel titles on or off.
+    ///
+    /// Off by default. The setting is stored in the database (as a unique
+    /// index on `channels.title`), so it only needs to be applied once.
+    /// While on, creating or renaming a channel to a title that is already
+    /// taken fails with `RepoError::DuplicateTitle`, which `GardenService`
+    /// reports as `DomainError::DuplicateTitle`.
+    ///
+    /// # Errors
+    ///
+    /// Turning it on fails with `DbError::Duplicate` if existing channels
+    /// already share a title; rename them first.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn set_unique_channel_titles(&self, enabled: bool) -> DbResult<()> {
+        let sql = if enabled {
+            format!(
+                "CREATE UNIQUE INDEX IF NOT EXISTS {} ON channels(title COLLATE NOCASE)",
+                UNIQUE_CHANNEL_TITLES_INDEX
+            )
+        } else {
+            format!("DROP INDEX IF EXISTS {}", UNIQUE_CHANNEL_TITLES_INDEX)
+        };
+        sqlx::query(&sql).execute(&self.pool).await.map_err(|e| {
+            if e.to_string().contains("UNIQUE constraint failed") {
+                crate::error::DbError::Duplicate
+            } else {
+                crate::error::DbError::from(e)
+            }
+        })?;
+        info!(enabled, "Unique channel titles set");
+        Ok(())
+    }
+
+    /// Whether channel titles are currently required to be unique.
+    pub async fn unique_channel_titles(&self) -> DbResult<bool> {
+        let (count,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = $1")
+                .bind(UNIQUE_CHANNEL_TITLES_INDEX)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(count > 0)
+    }
+
     /// Verify that the database schema is valid.
     ///
-    /// Checks that all required tables exist and are accessible.
-    #[instrument(skip(self))]
+    /// Checks that all required tables exist and are accessible, and that
+    /// columns added by later migrations are present. Like [`Self::migrate`],
+    /// it refuses a database written by a newer version of the app.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
     pub async fn verify_schema(&self) -> DbResult<()> {
-        const REQUIRED_TABLES: &[&str] = &["channels", "blocks", "connections"];
+        self.check_not_newer(&sqlx::migrate!()).await?;
+
+        const REQUIRED_TABLES: &[&str] = &[
+            "channels",
+            "blocks",
+            "connections",
+            "blocks_fts",
+            "idempotency",
+            "block_tags",
+            "activity_log",
+        ];
+        const REQUIRED_COLUMNS: &[(&str, &str)] = &[
+            ("channels", "accessed_at"),
+            ("channels", "body"),
+            ("blocks", "source_url"),
+            ("blocks", "source_title"),
+            ("blocks", "creator"),
+            ("blocks", "original_date"),
+            ("blocks", "notes"),
+            ("blocks", "archived"),
+            ("blocks", "duration"),
+            ("idempotency", "kind"),
+        ];
+        const RECOVERY: &str = "migrations did not complete; restore the database from a \
+                                backup, or move it aside so Garden creates a new one";
 
         for table in REQUIRED_TABLES {
-            let exists: (i32,) = sqlx::query_as(
-                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?",
-            )
-            .bind(*table)
-            .fetch_one(&self.pool)
-            .await
-            .map_err(crate::error::DbError::from)?;
+            let exists: (i32,) =
+                sqlx::query_as("SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?")
+                    .bind(*table)
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(crate::error::DbError::from)?;
 
             if exists.0 == 0 {
                 return Err(crate::error::DbError::SchemaInvalid(format!(
-                    "required table '{}' does not exist",
-                    table
+                    "required table '{}' does not exist; {}",
+                    table, RECOVERY
+                )));
+            }
+        }
+
+        for (table, column) in REQUIRED_COLUMNS {
+            // table_xinfo, unlike table_info, also lists generated columns
+            let exists: (i32,) =
+                sqlx::query_as("SELECT COUNT(*) FROM pragma_table_xinfo(?) WHERE name=?")
+                    .bind(*table)
+                    .bind(*column)
+                    .fetch_one(&self.pool)
+                    .await
+                    .map_err(crate::error::DbError::from)?;
+
+            if exists.0 == 0 {
+                return Err(crate::error::DbError::SchemaInvalid(format!(
+                    "required column '{}.{}' does not exist; {}",
+                    table, column, RECOVERY
                 )));
             }
         }
@@ -102,19 +327,72 @@ impl SqliteDatabase {
         Ok(())
     }
 
+    /// Fail if the database has a migration applied that is newer than any
+    /// embedded in this build.
+    ///
+    /// An older build would otherwise run against a schema it doesn't
+    /// understand, and could write rows the newer one can't read.
+    async fn check_not_newer(&self, migrator: &sqlx::migrate::Migrator) -> DbResult<()> {
+        let (tracked,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        if tracked == 0 {
+            return Ok(());
+        }
+
+        let (applied,): (Option<i64>,) =
+            sqlx::query_as("SELECT MAX(version) FROM _sqlx_migrations")
+                .fetch_one(&self.pool)
+                .await?;
+        let known = migrator.iter().map(|m| m.version).max().unwrap_or(0);
+        match applied {
+            Some(applied) if applied > known => {
+                warn!(applied, known, "Database schema is newer than this build");
+                Err(crate::error::DbError::SchemaInvalid(format!(
+                    "database is newer than this app version (schema {}, this version supports up \
+                     to {}); update Garden to open it",
+                    applied, known
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the database for corruption and inconsistent data.
+    ///
+    /// Runs SQLite's own `integrity_check` and `foreign_key_check`, then looks
+    /// for duplicate connections and position collisions within channels.
+    /// Problems are returned in the report rather than as errors; an `Err`
+    /// means the checks themselves could not run.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn integrity_report(&self) -> DbResult<IntegrityReport> {
+        integrity::integrity_report(&self.pool).await
+    }
+
     /// Get a channel repository.
     pub fn channel_repository(&self) -> SqliteChannelRepository {
-        SqliteChannelRepository::new(self.pool.clone())
+        SqliteChannelRepository::with_monitor(self.pool.clone(), self.queries.clone())
     }
 
     /// Get a block repository.
     pub fn block_repository(&self) -> SqliteBlockRepository {
-        SqliteBlockRepository::new(self.pool.clone())
+        SqliteBlockRepository::with_monitor(self.pool.clone(), self.queries.clone())
+            .with_max_content_bytes(self.max_content_bytes)
+            .with_max_batch_params(self.max_batch_params)
     }
 
     /// Get a connection repository.
     pub fn connection_repository(&self) -> SqliteConnectionRepository {
-        SqliteConnectionRepository::new(self.pool.clone())
+        SqliteConnectionRepository::with_monitor(self.pool.clone(), self.queries.clone())
+            .with_max_batch_params(self.max_batch_params)
+            .with_max_content_bytes(self.max_content_bytes)
+    }
+
+    /// Get an activity log repository.
+    pub fn activity_repository(&self) -> SqliteActivityRepository {
+        SqliteActivityRepository::with_monitor(self.pool.clone(), self.queries.clone())
     }
 
     /// Get the underlying pool (for advanced usage).
@@ -127,6 +405,87 @@ impl SqliteDatabase {
         self.pool.close().await;
         info!("Database connection closed");
     }
+
+    /// Copy all WAL contents into the main database file.
+    #[cfg_attr(feature = "tracing", instrument(skip(self)))]
+    pub async fn checkpoint(&self) -> DbResult<()> {
+        checkpoint(&self.pool).await
+    }
+
+    /// Create a guard that checkpoints the WAL when dropped.
+    ///
+    /// Hold the guard for as long as the application runs. See
+    /// [`CheckpointGuard`] for details.
+    pub fn checkpoint_guard(&self) -> CheckpointGuard {
+        CheckpointGuard {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+/// Checkpoints the WAL on a best-effort basis when dropped.
+///
+/// [`SqliteDatabase::close`] is async and never runs if the app panics or
+/// exits abruptly, which can leave committed data only in the `-wal` file.
+/// Dropping this guard, including while unwinding from a panic, copies the
+/// WAL into the main database file first.
+///
+/// Async drop isn't available, so the checkpoint blocks: via
+/// `block_in_place` on a multi-threaded runtime, or on a temporary runtime
+/// when dropped outside one. On a current-thread runtime the checkpoint is
+/// skipped, since blocking there would deadlock. Failures are logged and
+/// never panic. Nothing happens if the pool was already closed.
+pub struct CheckpointGuard {
+    pool: SqlitePool,
+}
+
+impl Drop for CheckpointGuard {
+    fn drop(&mut self) {
+        if self.pool.is_closed() {
+            return;
+        }
+
+        let result = match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| handle.block_on(checkpoint(&self.pool)))
+            }
+            Ok(_) => {
+                warn!("Skipping WAL checkpoint on drop inside a current-thread runtime");
+                return;
+            }
+            Err(_) => match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime.block_on(checkpoint(&self.pool)),
+                Err(e) => {
+                    warn!(error = %e, "Failed to start runtime for WAL checkpoint");
+                    return;
+                }
+            },
+        };
+
+        match result {
+            Ok(()) => info!("WAL checkpointed on drop"),
+            Err(e) => warn!(error = %e, "WAL checkpoint on drop failed"),
+        }
+    }
+}
+
+impl std::fmt::Debug for CheckpointGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CheckpointGuard")
+            .field("closed", &self.pool.is_closed())
+            .finish()
+    }
+}
+
+/// Run a truncating WAL checkpoint on the pool.
+async fn checkpoint(pool: &SqlitePool) -> DbResult<()> {
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+        .execute(pool)
+        .await?;
+    Ok(())
 }
 
 impl std::fmt::Debug for SqliteDatabase {
@@ -0,0 +1,97 @@
+//! Database integrity checks.
+//!
+//! Used by the maintenance screen after manual edits or a bad import. The
+//! checks only read; repairing anything is left to the caller.
+
+use garden_core::models::{
+    BlockId, ChannelId, DuplicateConnection, ForeignKeyViolation, IntegrityReport,
+    PositionCollision,
+};
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+
+use crate::error::DbResult;
+
+/// Run every integrity check against the pool.
+pub(super) async fn integrity_report(pool: &SqlitePool) -> DbResult<IntegrityReport> {
+    let integrity_errors: Vec<(String,)> = sqlx::query_as("PRAGMA integrity_check")
+        .fetch_all(pool)
+        .await?;
+    let integrity_errors = integrity_errors
+        .into_iter()
+        .map(|(msg,)| msg)
+        .filter(|msg| msg != "ok")
+        .collect();
+
+    let fk_rows: Vec<(String, Option<i64>, String, i64)> =
+        sqlx::query_as("PRAGMA foreign_key_check")
+            .fetch_all(pool)
+            .await?;
+    let foreign_key_violations = fk_rows
+        .into_iter()
+        .map(|(table, rowid, parent, _fkid)| ForeignKeyViolation {
+            table,
+            rowid,
+            parent,
+        })
+        .collect();
+
+    let duplicate_rows: Vec<(String, String, i64)> = sqlx::query_as(
+        r#"
+        SELECT block_id, channel_id, COUNT(*)
+        FROM connections
+        GROUP BY block_id, channel_id
+        HAVING COUNT(*) > 1
+        ORDER BY channel_id, block_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    let duplicate_connections = duplicate_rows
+        .into_iter()
+        .map(|(block_id, channel_id, count)| DuplicateConnection {
+            block_id: BlockId(block_id),
+            channel_id: ChannelId(channel_id),
+            count: count as usize,
+        })
+        .collect();
+
+    let collision_rows: Vec<(String, i32, i64)> = sqlx::query_as(
+        r#"
+        SELECT channel_id, position, COUNT(*)
+        FROM connections
+        GROUP BY channel_id, position
+        HAVING COUNT(*) > 1
+        ORDER BY channel_id, position
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+    let position_collisions = collision_rows
+        .into_iter()
+        .map(|(channel_id, position, count)| PositionCollision {
+            channel_id: ChannelId(channel_id),
+            position,
+            count: count as usize,
+        })
+        .collect();
+
+    let report = IntegrityReport {
+        integrity_errors,
+        foreign_key_violations,
+        duplicate_connections,
+        position_collisions,
+    };
+    if report.is_healthy() {
+        info!("Integrity check passed");
+    } else {
+        warn!(
+            integrity_errors = report.integrity_errors.len(),
+            foreign_key_violations = report.foreign_key_violations.len(),
+            duplicate_connections = report.duplicate_connections.len(),
+            position_collisions = report.position_collisions.len(),
+            "Integrity check found problems"
+        );
+    }
+    Ok(report)
+}
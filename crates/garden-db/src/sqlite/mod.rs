@@ -3,13 +3,21 @@
 //! This module provides SQLite implementations of the repository traits
 //! for desktop and embedded use cases.
 
+mod activity;
 mod block;
 mod channel;
+mod config;
 mod connection;
 mod database;
+mod integrity;
+mod reconcile;
+mod slow_query;
 mod util;
 
+pub use activity::SqliteActivityRepository;
 pub use block::SqliteBlockRepository;
 pub use channel::SqliteChannelRepository;
+pub use config::{JournalMode, SqliteConfig, DEFAULT_MAX_BATCH_PARAMS, DEFAULT_MAX_CONTENT_BYTES};
 pub use connection::SqliteConnectionRepository;
-pub use database::SqliteDatabase;
+pub use database::{CheckpointGuard, SqliteDatabase};
+pub use slow_query::{SlowQuery, SlowQueryCallback, DEFAULT_SLOW_QUERY_THRESHOLD};
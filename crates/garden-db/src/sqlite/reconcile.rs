@@ -0,0 +1,150 @@
+//! Recovery from partially applied migrations.
+//!
+//! sqlx runs each migration in a transaction, but a schema can still get
+//! ahead of `_sqlx_migrations`, e.g. when a migration was interrupted in a
+//! copy of the database or its tables were created by hand. Every `CREATE`
+//! in the migrations uses `IF NOT EXISTS`, so re-running them is harmless;
+//! only `ALTER TABLE ... ADD COLUMN` fails when its column is already there.
+//! This module records such migrations as applied instead of running them.
+
+use std::collections::HashSet;
+
+use sqlx::migrate::{Migrate, Migration, Migrator};
+use sqlx::{SqliteConnection, SqlitePool};
+use tracing::{info, warn};
+
+use crate::error::{DbError, DbResult};
+
+/// The columns each migration adds with `ALTER TABLE`, by version.
+///
+/// A migration that adds a column must be listed here.
+const ADDED_COLUMNS: &[(i64, &str, &str)] = &[
+    (20260117000000, "blocks", "source_url"),
+    (20260117000000, "blocks", "source_title"),
+    (20260117000000, "blocks", "creator"),
+    (20260117000000, "blocks", "original_date"),
+    (20260117000000, "blocks", "notes"),
+    (20260202000000, "channels", "accessed_at"),
+    (20260205000000, "channels", "body"),
+    (20260207000000, "blocks", "archived"),
+    (20260208000000, "blocks", "duration"),
+];
+
+/// Apply pending migrations in order, recording those whose columns are
+/// already in the schema as applied without running them.
+///
+/// An index such a migration would also create is not built; queries that
+/// use it only get slower.
+///
+/// Returns the versions that had to be reconciled.
+///
+/// # Errors
+///
+/// Fails with `DbError::SchemaInvalid` if a migration's columns are only
+/// partly in place, since neither running nor skipping it would complete it.
+pub(crate) async fn apply_pending(pool: &SqlitePool, migrator: &Migrator) -> DbResult<Vec<i64>> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let applied: HashSet<i64> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+
+    let mut reconciled = Vec::new();
+    for migration in migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration() && !applied.contains(&m.version))
+    {
+        let mut present = Vec::new();
+        let mut missing = Vec::new();
+        for (_, table, column) in ADDED_COLUMNS
+            .iter()
+            .filter(|(version, _, _)| *version == migration.version)
+        {
+            let name = format!("{}.{}", table, column);
+            if column_exists(&mut conn, table, column).await? {
+                present.push(name);
+            } else {
+                missing.push(name);
+            }
+        }
+
+        if present.is_empty() {
+            conn.apply(migration).await?;
+            continue;
+        }
+        if !missing.is_empty() {
+            return Err(DbError::SchemaInvalid(format!(
+                "migration {} was partially applied: {} exist but {} do not; restore the \
+                 database from a backup, or move it aside so Garden creates a new one",
+                migration.version,
+                present.join(", "),
+                missing.join(", ")
+            )));
+        }
+
+        warn!(
+            version = migration.version,
+            description = %migration.description,
+            columns = ?present,
+            "Migration's columns already exist; recording it as applied"
+        );
+        record_applied(&mut conn, migration).await?;
+        reconciled.push(migration.version);
+    }
+
+    if !reconciled.is_empty() {
+        info!(versions = ?reconciled, "Reconciled partially applied migrations");
+    }
+    Ok(reconciled)
+}
+
+/// Record a migration as applied without running it, as sqlx would.
+async fn record_applied(conn: &mut SqliteConnection, migration: &Migration) -> DbResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+        VALUES ($1, $2, TRUE, $3, 0)
+        "#,
+    )
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+async fn column_exists(conn: &mut SqliteConnection, table: &str, column: &str) -> DbResult<bool> {
+    // table_xinfo, unlike table_info, also lists generated columns
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM pragma_table_xinfo($1) WHERE name = $2 COLLATE NOCASE",
+    )
+    .bind(table)
+    .bind(column)
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(count > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_add_column_migration_is_listed() {
+        for migration in sqlx::migrate!().iter() {
+            let adds_column = migration.sql.to_ascii_uppercase().contains("ADD COLUMN");
+            let listed = ADDED_COLUMNS
+                .iter()
+                .any(|(version, _, _)| *version == migration.version);
+            assert_eq!(
+                adds_column, listed,
+                "migration {} is missing from ADDED_COLUMNS, or listed without adding a column",
+                migration.version
+            );
+        }
+    }
+}
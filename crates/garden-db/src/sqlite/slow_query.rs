@@ -0,0 +1,71 @@
+//! Slow query detection and reporting.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a query may take before it is reported as slow, by default.
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(50);
+
+/// A query that took longer than the slow-query threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowQuery {
+    /// Which query was slow, as `{repository}.{method}`, e.g. `blocks.search`.
+    pub name: &'static str,
+    /// How long the query took.
+    pub elapsed: Duration,
+    /// How many rows it returned.
+    pub rows: usize,
+}
+
+/// Callback invoked with every slow query. See
+/// [`SqliteDatabase::on_slow_query`](super::SqliteDatabase::on_slow_query).
+pub type SlowQueryCallback = Arc<dyn Fn(SlowQuery) + Send + Sync>;
+
+/// Decides which queries are slow and reports them to the callback.
+///
+/// Shared by the repositories a [`SqliteDatabase`](super::SqliteDatabase)
+/// hands out, so they all report to the same place.
+#[derive(Clone)]
+pub(crate) struct QueryMonitor {
+    threshold: Duration,
+    on_slow_query: Option<SlowQueryCallback>,
+}
+
+impl Default for QueryMonitor {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+            on_slow_query: None,
+        }
+    }
+}
+
+impl QueryMonitor {
+    /// Set the slow-query threshold.
+    pub(crate) fn set_threshold(&mut self, threshold: Duration) {
+        self.threshold = threshold;
+    }
+
+    /// Set the callback invoked for each slow query.
+    pub(crate) fn set_callback(&mut self, callback: SlowQueryCallback) {
+        self.on_slow_query = Some(callback);
+    }
+
+    /// Record a finished query, returning whether it was slow.
+    ///
+    /// Slow queries are passed to the callback, if any; logging is left to
+    /// the caller.
+    pub(crate) fn record(&self, name: &'static str, elapsed: Duration, rows: usize) -> bool {
+        if elapsed <= self.threshold {
+            return false;
+        }
+        if let Some(callback) = &self.on_slow_query {
+            callback(SlowQuery {
+                name,
+                elapsed,
+                rows,
+            });
+        }
+        true
+    }
+}
@@ -1,6 +1,7 @@
 //! Utility functions for SQLite repository implementations.
 
 use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
 
 use crate::error::DbError;
 
@@ -32,6 +33,66 @@ pub fn parse_datetime(value: &str, field: &'static str) -> Result<DateTime<Utc>,
         .map(|dt| dt.with_timezone(&Utc))
 }
 
+/// How many rows of `params_per_row` bound parameters fit in one statement
+/// under `max_params`. Never less than one.
+pub fn rows_per_statement(max_params: usize, params_per_row: usize) -> usize {
+    (max_params / params_per_row).max(1)
+}
+
+/// Look up the entity recorded under an idempotency key.
+///
+/// Keys are scoped by `kind`, the entity type (e.g. `"channel"`), so the
+/// same key can be used for different kinds of entity. Keys recorded before
+/// `not_before` are deleted first, so an expired key is never returned. Call
+/// this inside the same transaction that creates the entity and records the
+/// key.
+pub async fn find_idempotency_key(
+    conn: &mut SqliteConnection,
+    kind: &str,
+    key: &str,
+    not_before: DateTime<Utc>,
+) -> Result<Option<String>, DbError> {
+    sqlx::query("DELETE FROM idempotency WHERE created_at < $1")
+        .bind(not_before.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT entity_id FROM idempotency WHERE kind = $1 AND key = $2")
+            .bind(kind)
+            .bind(key)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+    Ok(row.map(|(entity_id,)| entity_id))
+}
+
+/// Record that `key` created the entity of type `kind` with the given ID.
+///
+/// Replaces any previous entry for the key and kind, e.g. one whose entity
+/// has since been deleted.
+pub async fn record_idempotency_key(
+    conn: &mut SqliteConnection,
+    kind: &str,
+    key: &str,
+    entity_id: &str,
+) -> Result<(), DbError> {
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO idempotency (kind, key, entity_id, created_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(kind)
+    .bind(key)
+    .bind(entity_id)
+    .bind(Utc::now().to_rfc3339())
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,9 +3,18 @@
 //! These tests use an in-memory SQLite database to verify that all
 //! repository implementations work correctly together.
 
-use garden_core::models::{Block, BlockContent, BlockId, Channel, ChannelId};
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use garden_core::error::{DomainError, RepoError};
+use garden_core::models::{
+    ActivityKind, Block, BlockContent, BlockId, Channel, ChannelId, ChannelOrder, ChannelSetMode,
+    ChannelUpdate, Connection, DuplicateConnection, FieldUpdate, GalleryItem, GardenExport,
+    ImportStrategy, IntegrityReport, NewBlock, NewChannel, Placement, PositionCollision,
+    PositionIssue, SearchMode, SharingStats,
+};
 use garden_core::ports::{BlockRepository, ChannelRepository, ConnectionRepository};
-use garden_db::sqlite::SqliteDatabase;
+use garden_core::services::GardenService;
+use garden_db::error::DbError;
+use garden_db::sqlite::{JournalMode, SlowQuery, SqliteConfig, SqliteDatabase};
 
 /// Helper to set up a clean test database.
 async fn setup_db() -> SqliteDatabase {
@@ -67,6 +76,27 @@ async fn channel_update() {
     );
 }
 
+#[tokio::test]
+async fn channel_body_round_trips() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+
+    let mut channel = Channel::with_description("Notes", "Short summary");
+    channel.body = Some("# About\n\nA longer *Markdown* body.".to_string());
+    repo.create(&channel).await.expect("Failed to create");
+
+    let retrieved = repo.get(&channel.id).await.unwrap().expect("Not found");
+    assert_eq!(retrieved.body, channel.body);
+    assert_eq!(retrieved.description, Some("Short summary".to_string()));
+
+    channel.body = None;
+    repo.update(&channel).await.expect("Failed to update");
+
+    let retrieved = repo.get(&channel.id).await.unwrap().expect("Not found");
+    assert!(retrieved.body.is_none());
+    assert_eq!(retrieved.description, Some("Short summary".to_string()));
+}
+
 #[tokio::test]
 async fn channel_delete() {
     let db = setup_db().await;
@@ -109,6 +139,32 @@ async fn channel_list_pagination() {
     assert_eq!(page3.items.len(), 1);
 }
 
+#[tokio::test]
+async fn channel_list_breaks_timestamp_ties_by_id() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+
+    let created_at = Utc::now();
+    let mut ids = Vec::new();
+    for i in 0..7 {
+        let mut channel = Channel::new(format!("Channel {}", i));
+        channel.created_at = created_at;
+        repo.create(&channel).await.expect("Failed to create");
+        ids.push(channel.id);
+    }
+    ids.sort_by(|a, b| b.0.cmp(&a.0));
+
+    // Same order on every pass, with no channel on two pages
+    for _ in 0..3 {
+        let mut paged = Vec::new();
+        for offset in (0..7).step_by(3) {
+            let page = repo.list(3, offset).await.expect("Failed to list");
+            paged.extend(page.items.into_iter().map(|c| c.id));
+        }
+        assert_eq!(paged, ids);
+    }
+}
+
 #[tokio::test]
 async fn channel_count() {
     let db = setup_db().await;
@@ -124,6 +180,230 @@ async fn channel_count() {
     assert_eq!(repo.count().await.expect("Failed to count"), 3);
 }
 
+#[tokio::test]
+async fn channel_touch_accessed_orders_recent_list() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+
+    let first = Channel::new("First");
+    let second = Channel::new("Second");
+    let unopened = Channel::new("Unopened");
+    for channel in [&first, &second, &unopened] {
+        repo.create(channel).await.expect("Failed to create");
+    }
+
+    // Access in the opposite order to creation
+    repo.touch_accessed(&second.id)
+        .await
+        .expect("Failed to touch");
+    repo.touch_accessed(&first.id)
+        .await
+        .expect("Failed to touch");
+
+    let recent = repo
+        .list_recently_accessed(10)
+        .await
+        .expect("Failed to list recent");
+    let ids: Vec<_> = recent.iter().map(|c| c.id.clone()).collect();
+    assert_eq!(ids, vec![first.id.clone(), second.id.clone()]);
+
+    // Touching does not count as an update
+    let retrieved = repo
+        .get(&first.id)
+        .await
+        .expect("Failed to get")
+        .expect("Not found");
+    assert!(retrieved.accessed_at.is_some());
+    assert_eq!(retrieved.updated_at, first.updated_at);
+}
+
+#[tokio::test]
+async fn channel_list_recently_updated_puts_edited_first() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+
+    let mut older = Channel::new("Older");
+    let newer = Channel::new("Newer");
+    for channel in [&older, &newer] {
+        repo.create(channel).await.expect("Failed to create");
+    }
+
+    older.title = "Older, edited".to_string();
+    older.updated_at = chrono::Utc::now();
+    repo.update(&older).await.expect("Failed to update");
+
+    let recent = repo
+        .list_recently_updated(10)
+        .await
+        .expect("Failed to list recently updated");
+    let ids: Vec<_> = recent.iter().map(|c| c.id.clone()).collect();
+    assert_eq!(ids, vec![older.id.clone(), newer.id.clone()]);
+    assert_eq!(repo.list_recently_updated(1).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn channel_touch_accessed_nonexistent() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+
+    let result = repo.touch_accessed(&ChannelId::new()).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn channel_create_idempotent_returns_original() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+    let not_before = chrono::Utc::now() - chrono::Duration::hours(1);
+
+    let first = repo
+        .create_idempotent(&Channel::new("Retried"), "retry-1", not_before)
+        .await
+        .expect("Failed to create");
+    let second = repo
+        .create_idempotent(&Channel::new("Retried"), "retry-1", not_before)
+        .await
+        .expect("Failed to create");
+
+    assert_eq!(first.id, second.id);
+    assert_eq!(repo.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn channel_create_idempotent_expired_key_creates_new() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+
+    let first = repo
+        .create_idempotent(&Channel::new("Retried"), "retry-1", chrono::Utc::now())
+        .await
+        .expect("Failed to create");
+    let later = chrono::Utc::now() + chrono::Duration::seconds(1);
+    let second = repo
+        .create_idempotent(&Channel::new("Retried"), "retry-1", later)
+        .await
+        .expect("Failed to create");
+
+    assert_ne!(first.id, second.id);
+    assert_eq!(repo.count().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn channel_unique_titles_reject_duplicates() {
+    let db = setup_db().await;
+    db.set_unique_channel_titles(true).await.unwrap();
+    assert!(db.unique_channel_titles().await.unwrap());
+    let service = GardenService::new(
+        db.channel_repository(),
+        db.block_repository(),
+        db.connection_repository(),
+    );
+    let new_channel = |title: &str| NewChannel {
+        title: title.to_string(),
+        description: None,
+        body: None,
+    };
+
+    service
+        .create_channel(new_channel("Reading"))
+        .await
+        .unwrap();
+    let result = service.create_channel(new_channel("reading")).await;
+    assert!(
+        matches!(&result, Err(DomainError::DuplicateTitle(title)) if title == "reading"),
+        "{:?}",
+        result
+    );
+
+    let other = service
+        .create_channel(new_channel("Writing"))
+        .await
+        .unwrap();
+    let result = service
+        .update_channel(
+            &other.id,
+            ChannelUpdate {
+                title: Some("READING".to_string()),
+                description: FieldUpdate::Keep,
+                body: FieldUpdate::Keep,
+            },
+        )
+        .await;
+    assert!(matches!(result, Err(DomainError::DuplicateTitle(_))));
+
+    // A block that already exists is not mistaken for a taken title
+    let conns = db.connection_repository();
+    let block = Block::new(BlockContent::text("Kept"));
+    db.block_repository().create(&block).await.unwrap();
+    let result = conns
+        .create_connected(&[Channel::new("Fresh")], std::slice::from_ref(&block), &[])
+        .await;
+    assert!(matches!(result, Err(RepoError::Duplicate)), "{result:?}");
+    let result = conns
+        .create_connected(&[Channel::new("READING")], &[], &[])
+        .await;
+    assert!(
+        matches!(result, Err(RepoError::DuplicateTitle)),
+        "{result:?}"
+    );
+}
+
+#[tokio::test]
+async fn channel_unique_titles_off_by_default() {
+    let db = setup_db().await;
+    let repo = db.channel_repository();
+    assert!(!db.unique_channel_titles().await.unwrap());
+
+    repo.create(&Channel::new("Reading")).await.unwrap();
+    repo.create(&Channel::new("Reading")).await.unwrap();
+
+    // Existing duplicates block turning it on
+    let result = db.set_unique_channel_titles(true).await;
+    assert!(matches!(result, Err(DbError::Duplicate)));
+    assert!(!db.unique_channel_titles().await.unwrap());
+}
+
+#[tokio::test]
+async fn import_channel_with_taken_title_writes_nothing() {
+    let db = setup_db().await;
+    db.set_unique_channel_titles(true).await.unwrap();
+    let service = GardenService::new(
+        db.channel_repository(),
+        db.block_repository(),
+        db.connection_repository(),
+    );
+    let channel = service
+        .create_channel(NewChannel {
+            title: "Reading".to_string(),
+            description: None,
+            body: None,
+        })
+        .await
+        .unwrap();
+    let block = service.create_block(NewBlock::text("Book")).await.unwrap();
+    service
+        .connect_block(&block.id, &channel.id, None)
+        .await
+        .unwrap();
+    let export = service.export_channel(&channel.id).await.unwrap();
+
+    let result = service
+        .import_channel(export, ImportStrategy::RemapIds)
+        .await;
+
+    assert!(
+        matches!(&result, Err(DomainError::DuplicateTitle(title)) if title == "Reading"),
+        "{:?}",
+        result
+    );
+    let stored = db
+        .block_repository()
+        .for_each(100, &mut |_| {})
+        .await
+        .unwrap();
+    assert_eq!(stored, 1, "no blocks left behind by the failed import");
+}
+
 // =============================================================================
 // Block Repository Tests
 // =============================================================================
@@ -215,6 +495,42 @@ async fn block_update() {
     }
 }
 
+#[tokio::test]
+async fn block_update_batch_is_all_or_nothing() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+
+    let mut blocks: Vec<Block> = (0..3)
+        .map(|i| {
+            Block::new(BlockContent::Text {
+                body: format!("Block {}", i),
+            })
+        })
+        .collect();
+    repo.create_batch(&blocks).await.expect("Failed to create");
+
+    for block in &mut blocks {
+        block.creator = Some("Jane Doe".to_string());
+    }
+    let missing = Block::new(BlockContent::Text {
+        body: "Never stored".to_string(),
+    });
+    let mut with_missing = blocks.clone();
+    with_missing.push(missing);
+    let result = repo.update_batch(&with_missing).await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+    for block in &blocks {
+        let stored = repo.get(&block.id).await.unwrap().unwrap();
+        assert_eq!(stored.creator, None);
+    }
+
+    repo.update_batch(&blocks).await.expect("Failed to update");
+    for block in &blocks {
+        let stored = repo.get(&block.id).await.unwrap().unwrap();
+        assert_eq!(stored.creator.as_deref(), Some("Jane Doe"));
+    }
+}
+
 #[tokio::test]
 async fn block_delete() {
     let db = setup_db().await;
@@ -259,196 +575,1761 @@ async fn block_create_batch() {
     }
 }
 
-// =============================================================================
-// Connection Repository Tests
-// =============================================================================
-
 #[tokio::test]
-async fn connection_connect_and_get() {
+async fn block_create_idempotent_returns_original() {
     let db = setup_db().await;
-    let channels = db.channel_repository();
-    let blocks = db.block_repository();
-    let conns = db.connection_repository();
-
-    // Create a channel and block
-    let channel = Channel::new("Test Channel");
-    let block = Block::new(BlockContent::Text {
-        body: "Test block".to_string(),
-    });
-
-    channels
-        .create(&channel)
-        .await
-        .expect("Failed to create channel");
-    blocks.create(&block).await.expect("Failed to create block");
+    let repo = db.block_repository();
+    let not_before = chrono::Utc::now() - chrono::Duration::hours(1);
 
-    // Connect them
-    conns
-        .connect(&block.id, &channel.id, 0)
+    let first = repo
+        .create_idempotent(&Block::new(BlockContent::text("Hi")), "retry-1", not_before)
         .await
-        .expect("Failed to connect");
-
-    // Verify connection exists
-    let connection = conns
-        .get_connection(&block.id, &channel.id)
+        .expect("Failed to create");
+    let second = repo
+        .create_idempotent(&Block::new(BlockContent::text("Hi")), "retry-1", not_before)
         .await
-        .expect("Failed to get connection")
-        .expect("Connection not found");
+        .expect("Failed to create");
 
-    assert_eq!(connection.block_id, block.id);
-    assert_eq!(connection.channel_id, channel.id);
-    assert_eq!(connection.position, 0);
+    assert_eq!(first.id, second.id);
+    assert!(repo.get(&first.id).await.unwrap().is_some());
 }
 
 #[tokio::test]
-async fn connection_disconnect() {
+async fn idempotency_keys_are_scoped_by_entity_type() {
     let db = setup_db().await;
     let channels = db.channel_repository();
     let blocks = db.block_repository();
-    let conns = db.connection_repository();
-
-    let channel = Channel::new("Test");
-    let block = Block::new(BlockContent::Text {
-        body: "Test".to_string(),
-    });
+    let not_before = chrono::Utc::now() - chrono::Duration::hours(1);
 
-    channels
-        .create(&channel)
+    let channel = channels
+        .create_idempotent(&Channel::new("Retried"), "retry-1", not_before)
         .await
-        .expect("Failed to create channel");
-    blocks.create(&block).await.expect("Failed to create block");
-    conns
-        .connect(&block.id, &channel.id, 0)
+        .unwrap();
+    let block = blocks
+        .create_idempotent(&Block::new(BlockContent::text("Hi")), "retry-1", not_before)
         .await
-        .expect("Failed to connect");
+        .unwrap();
+    assert!(blocks.get(&block.id).await.unwrap().is_some());
 
-    // Disconnect
-    conns
-        .disconnect(&block.id, &channel.id)
+    let retried = channels
+        .create_idempotent(&Channel::new("Retried"), "retry-1", not_before)
         .await
-        .expect("Failed to disconnect");
+        .unwrap();
+    assert_eq!(retried.id, channel.id);
+}
 
-    // Verify disconnected
-    let result = conns
-        .get_connection(&block.id, &channel.id)
+#[tokio::test]
+async fn block_create_batch_empty() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+
+    repo.create_batch(&[])
         .await
-        .expect("Failed to get");
-    assert!(result.is_none());
+        .expect("Empty batch should be a no-op");
 }
 
 #[tokio::test]
-async fn connection_get_blocks_in_channel() {
+async fn block_search_with_snippet() {
     let db = setup_db().await;
-    let channels = db.channel_repository();
-    let blocks = db.block_repository();
-    let conns = db.connection_repository();
+    let repo = db.block_repository();
 
-    let channel = Channel::new("Test");
-    channels
-        .create(&channel)
+    let matching = Block::new(BlockContent::text("A walled garden of links"));
+    let other = Block::new(BlockContent::text("Something else entirely"));
+    repo.create(&matching).await.unwrap();
+    repo.create(&other).await.unwrap();
+
+    let page = repo
+        .search("garden", &SearchMode::snippet(), 10, 0, false)
         .await
-        .expect("Failed to create channel");
+        .expect("Failed to search blocks");
 
-    // Create and connect 3 blocks with specific positions
-    let block1 = Block::new(BlockContent::Text {
-        body: "First".to_string(),
-    });
-    let block2 = Block::new(BlockContent::Text {
-        body: "Second".to_string(),
-    });
-    let block3 = Block::new(BlockContent::Text {
-        body: "Third".to_string(),
-    });
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].block.id, matching.id);
+    let snippet = page.items[0].snippet.as_deref().unwrap();
+    assert!(snippet.contains("<mark>garden</mark>"));
+}
 
-    blocks.create(&block1).await.unwrap();
-    blocks.create(&block2).await.unwrap();
-    blocks.create(&block3).await.unwrap();
+#[tokio::test]
+async fn block_search_tracks_updates_and_deletes() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
 
-    // Connect in non-sequential order to test ordering
-    conns.connect(&block2.id, &channel.id, 1).await.unwrap();
-    conns.connect(&block1.id, &channel.id, 0).await.unwrap();
-    conns.connect(&block3.id, &channel.id, 2).await.unwrap();
+    let mut block = Block::new(BlockContent::text("original wording"));
+    repo.create(&block).await.unwrap();
 
-    // Get blocks - should be ordered by position
-    let blocks_in_channel = conns
-        .get_blocks_in_channel(&channel.id)
+    block.notes = Some("annotated".to_string());
+    repo.update(&block).await.unwrap();
+    let page = repo
+        .search("annotated", &SearchMode::Plain, 10, 0, false)
         .await
-        .expect("Failed to get blocks");
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert!(page.items[0].snippet.is_none());
 
-    assert_eq!(blocks_in_channel.len(), 3);
-    assert_eq!(blocks_in_channel[0].0.id, block1.id);
-    assert_eq!(blocks_in_channel[0].1, 0);
-    assert_eq!(blocks_in_channel[1].0.id, block2.id);
-    assert_eq!(blocks_in_channel[1].1, 1);
-    assert_eq!(blocks_in_channel[2].0.id, block3.id);
-    assert_eq!(blocks_in_channel[2].1, 2);
+    repo.delete(&block.id).await.unwrap();
+    let page = repo
+        .search("original", &SearchMode::Plain, 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 0);
 }
 
 #[tokio::test]
-async fn connection_get_channels_for_block() {
+async fn block_search_treats_syntax_literally() {
     let db = setup_db().await;
-    let channels = db.channel_repository();
-    let blocks = db.block_repository();
-    let conns = db.connection_repository();
+    let repo = db.block_repository();
 
-    // Create one block and multiple channels
-    let block = Block::new(BlockContent::Text {
-        body: "Shared block".to_string(),
-    });
-    blocks.create(&block).await.unwrap();
+    repo.create(&Block::new(BlockContent::text("quoted \"text\" here")))
+        .await
+        .unwrap();
 
-    let channel1 = Channel::new("Channel 1");
-    let channel2 = Channel::new("Channel 2");
-    let channel3 = Channel::new("Channel 3");
+    let page = repo
+        .search("\"text", &SearchMode::Plain, 10, 0, false)
+        .await
+        .expect("FTS syntax in query should not error");
+    assert_eq!(page.total, 1);
+}
 
-    channels.create(&channel1).await.unwrap();
-    channels.create(&channel2).await.unwrap();
-    channels.create(&channel3).await.unwrap();
+#[tokio::test]
+async fn block_metadata_completeness_counts_missing_fields() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
 
-    // Connect block to all channels
-    conns.connect(&block.id, &channel1.id, 0).await.unwrap();
-    conns.connect(&block.id, &channel2.id, 0).await.unwrap();
-    conns.connect(&block.id, &channel3.id, 0).await.unwrap();
+    let bare = Block::new(BlockContent::text("Bare"));
+    let mut sourced = Block::new(BlockContent::text("Sourced"));
+    sourced.source_url = Some("https://example.com/a".to_string());
+    sourced.creator = Some("Ada".to_string());
+    let mut dated = Block::new(BlockContent::text("Dated"));
+    dated.original_date = Some("1843".to_string());
+    dated.notes = Some(String::new());
+    repo.create_batch(&[bare, sourced, dated]).await.unwrap();
+
+    let stats = repo.metadata_completeness().await.unwrap();
+
+    assert_eq!(stats.total, 3);
+    assert_eq!(stats.missing_source_url, 2);
+    assert_eq!(stats.missing_creator, 2);
+    assert_eq!(stats.missing_original_date, 2);
+    assert_eq!(stats.missing_notes, 3);
+}
 
-    // Get channels for block
-    let channels_for_block = conns
-        .get_channels_for_block(&block.id)
+#[tokio::test]
+async fn block_source_domains_grouped() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+
+    for url in [
+        "https://www.nytimes.com/one",
+        "https://nytimes.com/two",
+        "https://youtube.com/watch?v=1",
+    ] {
+        let mut block = Block::new(BlockContent::text("Archived"));
+        block.source_url = Some(url.to_string());
+        repo.create(&block).await.unwrap();
+    }
+    repo.create(&Block::new(BlockContent::text("No source")))
         .await
-        .expect("Failed to get channels");
+        .unwrap();
 
-    assert_eq!(channels_for_block.len(), 3);
+    let facets = repo.source_domains().await.unwrap();
+    assert_eq!(
+        facets,
+        vec![
+            ("nytimes.com".to_string(), 2),
+            ("youtube.com".to_string(), 1)
+        ]
+    );
 }
 
 #[tokio::test]
-async fn connection_reorder() {
+async fn block_count_file_references_includes_galleries() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+
+    let shared = "images/shared.jpg";
+    let image = Block::new(BlockContent::image(shared, "image/jpeg"));
+    let gallery = Block::new(BlockContent::gallery(vec![
+        GalleryItem::new("images/other.jpg", "image/jpeg"),
+        GalleryItem::new(shared, "image/jpeg"),
+    ]));
+    let unrelated = Block::new(BlockContent::image("images/other.jpg", "image/jpeg"));
+    repo.create_batch(&[image.clone(), gallery.clone(), unrelated])
+        .await
+        .unwrap();
+
+    assert_eq!(repo.count_file_references(shared).await.unwrap(), 2);
+
+    repo.delete(&image.id).await.unwrap();
+    assert_eq!(repo.count_file_references(shared).await.unwrap(), 1);
+
+    repo.delete(&gallery.id).await.unwrap();
+    assert_eq!(repo.count_file_references(shared).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn block_total_word_count() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+    assert_eq!(repo.total_word_count().await.unwrap(), 0);
+
+    let blocks = vec![
+        Block::new(BlockContent::text("The quick brown fox")),
+        Block::new(BlockContent::link_with_meta(
+            "https://example.com",
+            Some("Jumps over".to_string()),
+            Some("the lazy dog".to_string()),
+            None,
+        )),
+        Block::new(BlockContent::image("images/fox.jpg", "image/jpeg")),
+    ];
+    repo.create_batch(&blocks).await.unwrap();
+
+    assert_eq!(repo.total_word_count().await.unwrap(), 9);
+
+    repo.delete(&blocks[0].id).await.unwrap();
+    assert_eq!(repo.total_word_count().await.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn block_tags_add_and_remove_in_bulk() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+
+    let blocks: Vec<Block> = (0..3)
+        .map(|i| Block::new(BlockContent::text(format!("Block {}", i))))
+        .collect();
+    repo.create_batch(&blocks).await.unwrap();
+    let ids: Vec<BlockId> = blocks.iter().map(|b| b.id.clone()).collect();
+
+    assert_eq!(repo.add_tag(&ids[..1], "reading").await.unwrap(), 1);
+    assert_eq!(repo.add_tag(&ids, "reading").await.unwrap(), 2);
+    assert_eq!(repo.add_tag(&ids[..1], "art").await.unwrap(), 1);
+    assert_eq!(
+        repo.get_tags(&ids[0]).await.unwrap(),
+        vec!["art", "reading"]
+    );
+
+    assert_eq!(repo.remove_tag(&ids, "art").await.unwrap(), 1);
+    assert_eq!(repo.get_tags(&ids[0]).await.unwrap(), vec!["reading"]);
+
+    // Tags are removed along with their block
+    repo.delete(&ids[2]).await.unwrap();
+    assert_eq!(repo.remove_tag(&ids, "reading").await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn block_tags_missing_block_rolls_back() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+
+    let block = Block::new(BlockContent::text("Tagged"));
+    repo.create(&block).await.unwrap();
+
+    let result = repo
+        .add_tag(
+            &[block.id.clone(), BlockId("missing".to_string())],
+            "reading",
+        )
+        .await;
+
+    assert!(matches!(result, Err(RepoError::NotFound)));
+    assert!(repo.get_tags(&block.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn block_find_created_between() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+    let base = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+
+    // One block just before March, three in March, one on April 1st
+    let offsets = [
+        Duration::milliseconds(-1),
+        Duration::zero(),
+        Duration::days(15) + Duration::microseconds(250),
+        Duration::days(31) - Duration::milliseconds(1),
+        Duration::days(31),
+    ];
+    let blocks: Vec<Block> = offsets
+        .iter()
+        .enumerate()
+        .map(|(i, offset)| {
+            let mut block = Block::new(BlockContent::text(format!("Block {}", i)));
+            block.created_at = base + *offset;
+            block
+        })
+        .collect();
+    repo.create_batch(&blocks).await.unwrap();
+
+    let end = base + Duration::days(31) - Duration::milliseconds(1);
+    let page = repo
+        .find_created_between(base, end, 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 3);
+    let ids: Vec<_> = page.items.iter().map(|b| b.id.clone()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            blocks[3].id.clone(),
+            blocks[2].id.clone(),
+            blocks[1].id.clone()
+        ]
+    );
+
+    let page = repo
+        .find_created_between(base, end, 1, 1, false)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.items[0].id, blocks[2].id);
+}
+
+#[tokio::test]
+async fn block_counts_by_day() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+    let day = |d: u32| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+
+    // Three blocks on the 1st, one on the 3rd, one on the 4th, none on the
+    // 2nd, and one just before the range
+    let times = [
+        Utc.with_ymd_and_hms(2026, 2, 28, 23, 59, 59).unwrap(),
+        Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 30, 0).unwrap(),
+        Utc.with_ymd_and_hms(2026, 3, 1, 23, 59, 59).unwrap(),
+        Utc.with_ymd_and_hms(2026, 3, 3, 8, 0, 0).unwrap(),
+        Utc.with_ymd_and_hms(2026, 3, 4, 0, 0, 0).unwrap(),
+    ];
+    let blocks: Vec<Block> = times
+        .iter()
+        .map(|created_at| {
+            let mut block = Block::new(BlockContent::text("Saved"));
+            block.created_at = *created_at;
+            block
+        })
+        .collect();
+    repo.create_batch(&blocks).await.unwrap();
+
+    let counts = repo.counts_by_day(day(1), day(3)).await.unwrap();
+
+    // The empty 2nd is absent rather than zero
+    assert_eq!(counts, vec![(day(1), 3), (day(3), 1)]);
+}
+
+#[tokio::test]
+async fn block_find_by_duration() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+    let audio = |path: &str, seconds: f32| {
+        Block::new(BlockContent::audio_with_meta(
+            path,
+            "audio/mpeg",
+            None,
+            Some(seconds),
+            None,
+            None,
+        ))
+    };
+    let short = audio("audio/short.mp3", 60.0);
+    let long = audio("audio/long.mp3", 2400.0);
+    let unknown = Block::new(BlockContent::audio("audio/unknown.mp3", "audio/mpeg"));
+    let text = Block::new(BlockContent::text("No duration"));
+    repo.create_batch(&[short.clone(), long.clone(), unknown, text])
+        .await
+        .unwrap();
+
+    let page = repo
+        .find_by_duration(Some(1800.0), None, 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].id, long.id);
+
+    let page = repo
+        .find_by_duration(None, Some(60.0), 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].id, short.id);
+
+    let page = repo.find_by_duration(None, None, 10, 0).await.unwrap();
+    assert_eq!(page.total, 2);
+}
+
+#[tokio::test]
+async fn block_for_each_visits_every_block_once() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+    let blocks: Vec<Block> = (0..250)
+        .map(|i| Block::new(BlockContent::text(format!("Block {}", i))))
+        .collect();
+    repo.create_batch(&blocks).await.unwrap();
+
+    let mut seen = std::collections::HashSet::new();
+    let visited = repo
+        .for_each(100, &mut |block| assert!(seen.insert(block.id)))
+        .await
+        .unwrap();
+
+    assert_eq!(visited, 250);
+    assert_eq!(seen.len(), 250);
+}
+
+#[tokio::test]
+async fn block_migrate_content_rewrites_old_rows() {
+    let db = setup_db().await;
+    let repo = db.block_repository();
+
+    // A row written before `title` and friends existed
+    let id = BlockId::new();
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        "INSERT INTO blocks (id, content_type, content_json, created_at, updated_at)
+         VALUES ($1, 'link', $2, $3, $3)",
+    )
+    .bind(&id.0)
+    .bind(r#"{"type":"link","url":"https://example.com"}"#)
+    .bind(&now)
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let fill_title = |content| match content {
+        BlockContent::Link {
+            url,
+            title: None,
+            description,
+            alt_text,
+        } => BlockContent::Link {
+            title: Some(url.clone()),
+            url,
+            description,
+            alt_text,
+        },
+        other => other,
+    };
+    let rewritten = repo.migrate_content(fill_title).await.unwrap();
+    assert_eq!(rewritten, 1);
+
+    let (json, updated_at): (String, String) =
+        sqlx::query_as("SELECT content_json, updated_at FROM blocks WHERE id = $1")
+            .bind(&id.0)
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+    let stored: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(stored["title"], "https://example.com");
+    assert_eq!(updated_at, now);
+
+    // Already upgraded rows are left alone
+    assert_eq!(repo.migrate_content(fill_title).await.unwrap(), 0);
+
+    // `migrate` recorded the content version, so it won't rescan on startup
+    let (version,): (i64,) = sqlx::query_as("PRAGMA user_version")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert!(version > 0);
+}
+
+// =============================================================================
+// Connection Repository Tests
+// =============================================================================
+
+#[tokio::test]
+async fn connection_connect_and_get() {
     let db = setup_db().await;
     let channels = db.channel_repository();
     let blocks = db.block_repository();
     let conns = db.connection_repository();
 
-    let channel = Channel::new("Test");
-    channels.create(&channel).await.unwrap();
+    // Create a channel and block
+    let channel = Channel::new("Test Channel");
+    let block = Block::new(BlockContent::Text {
+        body: "Test block".to_string(),
+    });
+
+    channels
+        .create(&channel)
+        .await
+        .expect("Failed to create channel");
+    blocks.create(&block).await.expect("Failed to create block");
+
+    // Connect them
+    conns
+        .connect(&block.id, &channel.id, 0)
+        .await
+        .expect("Failed to connect");
 
+    // Verify connection exists
+    let connection = conns
+        .get_connection(&block.id, &channel.id)
+        .await
+        .expect("Failed to get connection")
+        .expect("Connection not found");
+
+    assert_eq!(connection.block_id, block.id);
+    assert_eq!(connection.channel_id, channel.id);
+    assert_eq!(connection.position, 0);
+}
+
+#[tokio::test]
+async fn connection_disconnect() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Test");
     let block = Block::new(BlockContent::Text {
         body: "Test".to_string(),
     });
-    blocks.create(&block).await.unwrap();
 
-    conns.connect(&block.id, &channel.id, 0).await.unwrap();
+    channels
+        .create(&channel)
+        .await
+        .expect("Failed to create channel");
+    blocks.create(&block).await.expect("Failed to create block");
+    conns
+        .connect(&block.id, &channel.id, 0)
+        .await
+        .expect("Failed to connect");
 
-    // Reorder to position 5
+    // Disconnect
     conns
-        .reorder(&channel.id, &block.id, 5)
+        .disconnect(&block.id, &channel.id)
         .await
-        .expect("Failed to reorder");
+        .expect("Failed to disconnect");
 
-    let connection = conns
+    // Verify disconnected
+    let result = conns
         .get_connection(&block.id, &channel.id)
         .await
-        .unwrap()
-        .unwrap();
+        .expect("Failed to get");
+    assert!(result.is_none());
+}
 
-    assert_eq!(connection.position, 5);
+#[tokio::test]
+async fn connection_get_blocks_in_channel() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Test");
+    channels
+        .create(&channel)
+        .await
+        .expect("Failed to create channel");
+
+    // Create and connect 3 blocks with specific positions
+    let block1 = Block::new(BlockContent::Text {
+        body: "First".to_string(),
+    });
+    let block2 = Block::new(BlockContent::Text {
+        body: "Second".to_string(),
+    });
+    let block3 = Block::new(BlockContent::Text {
+        body: "Third".to_string(),
+    });
+
+    blocks.create(&block1).await.unwrap();
+    blocks.create(&block2).await.unwrap();
+    blocks.create(&block3).await.unwrap();
+
+    // Connect in non-sequential order to test ordering
+    conns.connect(&block2.id, &channel.id, 1).await.unwrap();
+    conns.connect(&block1.id, &channel.id, 0).await.unwrap();
+    conns.connect(&block3.id, &channel.id, 2).await.unwrap();
+
+    // Get blocks - should be ordered by position
+    let blocks_in_channel = conns
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .expect("Failed to get blocks");
+
+    assert_eq!(blocks_in_channel.len(), 3);
+    assert_eq!(blocks_in_channel[0].0.id, block1.id);
+    assert_eq!(blocks_in_channel[0].1, 0);
+    assert_eq!(blocks_in_channel[1].0.id, block2.id);
+    assert_eq!(blocks_in_channel[1].1, 1);
+    assert_eq!(blocks_in_channel[2].0.id, block3.id);
+    assert_eq!(blocks_in_channel[2].1, 2);
+}
+
+#[tokio::test]
+async fn connection_get_blocks_in_channel_paged() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Paged");
+    channels.create(&channel).await.unwrap();
+
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let block = Block::new(BlockContent::text(format!("Block {}", i)));
+        blocks.create(&block).await.unwrap();
+        conns.connect(&block.id, &channel.id, i).await.unwrap();
+        ids.push(block.id);
+    }
+
+    let page = conns
+        .get_blocks_in_channel_paged(&channel.id, 2, 1, false)
+        .await
+        .expect("Failed to get page");
+    assert_eq!(page.total, 5);
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.items[0].0.id, ids[1]);
+    assert_eq!(page.items[1].0.id, ids[2]);
+    assert_eq!(page.items[1].1, 2);
+}
+
+#[tokio::test]
+async fn archived_blocks_hidden_from_search_and_channel_pages() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Inbox");
+    channels.create(&channel).await.unwrap();
+    let kept = Block::new(BlockContent::text("garden kept"));
+    let mut archived = Block::new(BlockContent::text("garden archived"));
+    archived.archived = true;
+    for (i, block) in [&kept, &archived].into_iter().enumerate() {
+        blocks.create(block).await.unwrap();
+        conns
+            .connect(&block.id, &channel.id, i as i32)
+            .await
+            .unwrap();
+    }
+
+    let hits = blocks
+        .search("garden", &SearchMode::Plain, 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(hits.total, 1);
+    assert_eq!(hits.items[0].block.id, kept.id);
+    let page = conns
+        .get_blocks_in_channel_paged(&channel.id, 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].0.id, kept.id);
+
+    let hits = blocks
+        .search("garden", &SearchMode::Plain, 10, 0, true)
+        .await
+        .unwrap();
+    assert_eq!(hits.total, 2);
+    let page = conns
+        .get_blocks_in_channel_paged(&channel.id, 10, 0, true)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 2);
+
+    // Every channel query leaves the archived block out unless asked
+    for include_archived in [false, true] {
+        let expected = if include_archived { 2 } else { 1 };
+        let all = conns
+            .get_blocks_in_channel(&channel.id, include_archived)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), expected);
+        let recent = conns
+            .recent_connections_in_channel(&channel.id, 10, include_archived)
+            .await
+            .unwrap();
+        assert_eq!(recent.len(), expected);
+        let union = conns
+            .blocks_in_channels(
+                std::slice::from_ref(&channel.id),
+                ChannelSetMode::Union,
+                10,
+                0,
+                include_archived,
+            )
+            .await
+            .unwrap();
+        assert_eq!((union.total, union.items.len()), (expected, expected));
+        let listed = conns
+            .blocks_with_channels(None, 10, 0, include_archived)
+            .await
+            .unwrap();
+        assert_eq!((listed.total, listed.items.len()), (expected, expected));
+        let popular = conns
+            .most_connected_blocks(10, include_archived)
+            .await
+            .unwrap();
+        assert_eq!(popular.len(), expected);
+    }
+
+    let fetched = blocks.get(&archived.id).await.unwrap().unwrap();
+    assert!(fetched.archived);
+}
+
+#[tokio::test]
+async fn connection_neighbors() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Neighbors");
+    channels.create(&channel).await.unwrap();
+
+    let mut ids = Vec::new();
+    for i in 0..3 {
+        let block = Block::new(BlockContent::text(format!("Block {}", i)));
+        blocks.create(&block).await.unwrap();
+        conns.connect(&block.id, &channel.id, i * 10).await.unwrap();
+        ids.push(block.id);
+    }
+
+    let (previous, next) = conns.neighbors(&channel.id, &ids[1]).await.unwrap();
+    assert_eq!(previous.map(|b| b.id), Some(ids[0].clone()));
+    assert_eq!(next.map(|b| b.id), Some(ids[2].clone()));
+
+    let (previous, next) = conns.neighbors(&channel.id, &ids[0]).await.unwrap();
+    assert!(previous.is_none());
+    assert_eq!(next.map(|b| b.id), Some(ids[1].clone()));
+
+    let (previous, next) = conns.neighbors(&channel.id, &ids[2]).await.unwrap();
+    assert_eq!(previous.map(|b| b.id), Some(ids[1].clone()));
+    assert!(next.is_none());
+
+    let result = conns.neighbors(&channel.id, &BlockId::new()).await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+}
+
+#[tokio::test]
+async fn connection_block_index_in_channel() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Viewer");
+    let other = Channel::new("Other");
+    channels.create(&channel).await.unwrap();
+    channels.create(&other).await.unwrap();
+
+    // Two blocks share a position, so the tie is broken by block ID
+    let mut ids = Vec::new();
+    for position in [0, 10, 10] {
+        let block = Block::new(BlockContent::text(format!("At {}", position)));
+        blocks.create(&block).await.unwrap();
+        conns
+            .connect(&block.id, &channel.id, position)
+            .await
+            .unwrap();
+        ids.push(block.id);
+    }
+    conns.connect(&ids[0], &other.id, 0).await.unwrap();
+    let (low, high) = if ids[1].0 < ids[2].0 {
+        (&ids[1], &ids[2])
+    } else {
+        (&ids[2], &ids[1])
+    };
+
+    for (id, expected) in [
+        (&ids[0], Some((0, 3))),
+        (low, Some((1, 3))),
+        (high, Some((2, 3))),
+        (&BlockId::new(), None),
+    ] {
+        let index = conns.block_index_in_channel(&channel.id, id).await.unwrap();
+        assert_eq!(index, expected);
+    }
+}
+
+#[tokio::test]
+async fn connection_get_channels_for_block() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    // Create one block and multiple channels
+    let block = Block::new(BlockContent::Text {
+        body: "Shared block".to_string(),
+    });
+    blocks.create(&block).await.unwrap();
+
+    let channel1 = Channel::new("Channel 1");
+    let channel2 = Channel::new("Channel 2");
+    let channel3 = Channel::new("Channel 3");
+
+    channels.create(&channel1).await.unwrap();
+    channels.create(&channel2).await.unwrap();
+    channels.create(&channel3).await.unwrap();
+
+    // Connect block to all channels
+    conns.connect(&block.id, &channel1.id, 0).await.unwrap();
+    conns.connect(&block.id, &channel2.id, 0).await.unwrap();
+    conns.connect(&block.id, &channel3.id, 0).await.unwrap();
+
+    // Get channels for block
+    let channels_for_block = conns
+        .get_channels_for_block(&block.id, ChannelOrder::default())
+        .await
+        .expect("Failed to get channels");
+
+    assert_eq!(channels_for_block.len(), 3);
+}
+
+#[tokio::test]
+async fn connection_get_channels_for_block_by_title() {
+    let db = setup_db().await;
+    let block = Block::new(BlockContent::text("Shared"));
+    db.block_repository().create(&block).await.unwrap();
+    let conns = db.connection_repository();
+    for title in ["Zebra", "apple", "Mango"] {
+        let channel = Channel::new(title);
+        db.channel_repository().create(&channel).await.unwrap();
+        conns.connect(&block.id, &channel.id, 0).await.unwrap();
+    }
+
+    let channels = conns
+        .get_channels_for_block(&block.id, ChannelOrder::ByTitleAsc)
+        .await
+        .unwrap();
+
+    let titles: Vec<_> = channels.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, vec!["apple", "Mango", "Zebra"]);
+}
+
+#[tokio::test]
+async fn connection_get_channels_for_block_paged() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let block = Block::new(BlockContent::text("Popular"));
+    blocks.create(&block).await.unwrap();
+    for i in 0..7 {
+        let channel = Channel::new(format!("Channel {}", i));
+        channels.create(&channel).await.unwrap();
+        conns.connect(&block.id, &channel.id, 0).await.unwrap();
+    }
+
+    let all = conns
+        .get_channels_for_block_paged(&block.id, 100, 0)
+        .await
+        .expect("Failed to get channels");
+    assert_eq!(all.total, 7);
+    assert_eq!(all.items.len(), 7);
+
+    let page = conns
+        .get_channels_for_block_paged(&block.id, 3, 3)
+        .await
+        .expect("Failed to get page");
+    assert_eq!(page.total, 7);
+    let page_ids: Vec<_> = page.items.iter().map(|c| c.id.clone()).collect();
+    let expected: Vec<_> = all.items[3..6].iter().map(|c| c.id.clone()).collect();
+    assert_eq!(page_ids, expected);
+}
+
+#[tokio::test]
+async fn connection_upsert_inserts_then_updates_position() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Test");
+    channels.create(&channel).await.unwrap();
+    let block = Block::new(BlockContent::text("Test"));
+    blocks.create(&block).await.unwrap();
+
+    conns.upsert(&block.id, &channel.id, 1).await.unwrap();
+    let first = conns
+        .get_connection(&block.id, &channel.id)
+        .await
+        .unwrap()
+        .unwrap();
+    conns.upsert(&block.id, &channel.id, 4).await.unwrap();
+    let second = conns
+        .get_connection(&block.id, &channel.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(first.position, 1);
+    assert_eq!(second.position, 4);
+    assert_eq!(second.connected_at, first.connected_at);
+    assert_eq!(
+        conns
+            .get_blocks_in_channel(&channel.id, false)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn connection_reorder() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Test");
+    channels.create(&channel).await.unwrap();
+
+    let block = Block::new(BlockContent::Text {
+        body: "Test".to_string(),
+    });
+    blocks.create(&block).await.unwrap();
+
+    conns.connect(&block.id, &channel.id, 0).await.unwrap();
+
+    // Reorder to position 5
+    conns
+        .reorder(&channel.id, &block.id, 5)
+        .await
+        .expect("Failed to reorder");
+
+    let connection = conns
+        .get_connection(&block.id, &channel.id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(connection.position, 5);
+}
+
+#[tokio::test]
+async fn connection_recent_in_channel() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Recent");
+    channels.create(&channel).await.unwrap();
+    let mut ids = Vec::new();
+    // The last block connected goes to the top of the channel
+    for (body, position) in [("First", 1), ("Second", 2), ("Third", 0)] {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        connections
+            .connect(&block.id, &channel.id, position)
+            .await
+            .unwrap();
+        ids.push(block.id);
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+
+    let recent = connections
+        .recent_connections_in_channel(&channel.id, 2, false)
+        .await
+        .unwrap();
+
+    let recent: Vec<(BlockId, i32)> = recent
+        .into_iter()
+        .map(|(block, conn)| (block.id, conn.position))
+        .collect();
+    assert_eq!(recent, vec![(ids[2].clone(), 0), (ids[1].clone(), 2)]);
+}
+
+#[tokio::test]
+async fn connection_all_in_channel() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("All");
+    channels.create(&channel).await.unwrap();
+    let mut ids = Vec::new();
+    for position in [3, 1, 1] {
+        let block = Block::new(BlockContent::text("Block"));
+        blocks.create(&block).await.unwrap();
+        connections
+            .connect(&block.id, &channel.id, position)
+            .await
+            .unwrap();
+        ids.push(block.id);
+    }
+
+    let all: Vec<(BlockId, i32)> = connections
+        .connections_in_channel(&channel.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(block, conn)| (block.id, conn.position))
+        .collect();
+
+    // Ties on position fall back to block ID
+    let (low, high) = if ids[1].0 < ids[2].0 {
+        (ids[1].clone(), ids[2].clone())
+    } else {
+        (ids[2].clone(), ids[1].clone())
+    };
+    assert_eq!(all, vec![(low, 1), (high, 1), (ids[0].clone(), 3)]);
+}
+
+#[tokio::test]
+async fn connection_blocks_in_channels() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let a = Channel::new("A");
+    let b = Channel::new("B");
+    channels.create(&a).await.unwrap();
+    channels.create(&b).await.unwrap();
+    let mut ids = Vec::new();
+    for body in ["Only A", "Both", "Only B"] {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        ids.push(block.id);
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+    connections
+        .connect_batch(&[
+            (ids[0].clone(), a.id.clone(), 0),
+            (ids[1].clone(), a.id.clone(), 1),
+            (ids[1].clone(), b.id.clone(), 0),
+            (ids[2].clone(), b.id.clone(), 1),
+        ])
+        .await
+        .unwrap();
+    let both = [a.id.clone(), b.id.clone()];
+
+    let union = connections
+        .blocks_in_channels(&both, ChannelSetMode::Union, 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(union.total, 3);
+    let union: Vec<BlockId> = union.items.into_iter().map(|b| b.id).collect();
+    assert_eq!(union, vec![ids[2].clone(), ids[1].clone(), ids[0].clone()]);
+
+    let intersection = connections
+        .blocks_in_channels(&both, ChannelSetMode::Intersection, 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(intersection.total, 1);
+    assert_eq!(intersection.items[0].id, ids[1]);
+
+    // A repeated channel doesn't raise the bar for intersection
+    let repeated = connections
+        .blocks_in_channels(
+            &[a.id.clone(), a.id.clone()],
+            ChannelSetMode::Intersection,
+            10,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(repeated.total, 2);
+}
+
+#[tokio::test]
+async fn connection_blocks_not_in_channel() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Picks");
+    channels.create(&channel).await.unwrap();
+    let mut ids = Vec::new();
+    for body in ["In channel", "Free", "Also free"] {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        ids.push(block.id);
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+    let mut archived = Block::new(BlockContent::text("Archived"));
+    archived.archived = true;
+    blocks.create(&archived).await.unwrap();
+    connections.connect(&ids[0], &channel.id, 0).await.unwrap();
+
+    let first = connections
+        .blocks_not_in_channel(&channel.id, 1, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(first.total, 2);
+    assert!(first.has_next());
+    assert_eq!(first.items[0].id, ids[2]);
+
+    let second = connections
+        .blocks_not_in_channel(&channel.id, 1, 1, false)
+        .await
+        .unwrap();
+    assert_eq!(second.total, 2);
+    assert!(!second.has_next());
+    assert_eq!(second.items[0].id, ids[1]);
+
+    let with_archived = connections
+        .blocks_not_in_channel(&channel.id, 10, 0, true)
+        .await
+        .unwrap();
+    assert_eq!(with_archived.total, 3);
+    assert_eq!(with_archived.items[0].id, archived.id);
+}
+
+#[tokio::test]
+async fn connection_blocks_with_channels() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let a = Channel::new("a");
+    let b = Channel::new("B");
+    channels.create(&a).await.unwrap();
+    channels.create(&b).await.unwrap();
+    let mut ids = Vec::new();
+    for body in ["Only A", "Both", "Neither"] {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        ids.push(block.id);
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+    connections
+        .connect_batch(&[
+            (ids[0].clone(), a.id.clone(), 0),
+            (ids[1].clone(), b.id.clone(), 0),
+            (ids[1].clone(), a.id.clone(), 1),
+        ])
+        .await
+        .unwrap();
+
+    let page = connections
+        .blocks_with_channels(None, 10, 0, false)
+        .await
+        .unwrap();
+
+    assert_eq!(page.total, 3);
+    let listed: Vec<(BlockId, Vec<ChannelId>)> = page
+        .items
+        .into_iter()
+        .map(|(block, chs)| (block.id, chs.into_iter().map(|c| c.id).collect()))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            (ids[2].clone(), vec![]),
+            (ids[1].clone(), vec![a.id.clone(), b.id.clone()]),
+            (ids[0].clone(), vec![a.id.clone()]),
+        ]
+    );
+
+    let page = connections
+        .blocks_with_channels(None, 1, 1, false)
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].0.id, ids[1]);
+    assert_eq!(page.items[0].1.len(), 2);
+}
+
+#[tokio::test]
+async fn connection_blocks_with_channels_by_id() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let a = Channel::new("a");
+    let b = Channel::new("B");
+    channels.create(&a).await.unwrap();
+    channels.create(&b).await.unwrap();
+    let mut ids = Vec::new();
+    for body in ["Only A", "Both", "Neither"] {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        ids.push(block.id);
+        tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+    }
+    connections
+        .connect_batch(&[
+            (ids[0].clone(), a.id.clone(), 0),
+            (ids[1].clone(), b.id.clone(), 0),
+            (ids[1].clone(), a.id.clone(), 1),
+        ])
+        .await
+        .unwrap();
+
+    let requested = [
+        ids[1].clone(),
+        BlockId::from_string("missing"),
+        ids[2].clone(),
+        ids[0].clone(),
+        ids[1].clone(),
+    ];
+    let page = connections
+        .blocks_with_channels(Some(&requested), 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 3);
+
+    // Newest first, like the unfiltered page
+    let listed: Vec<(BlockId, Vec<ChannelId>)> = page
+        .items
+        .into_iter()
+        .map(|(block, chs)| (block.id, chs.into_iter().map(|c| c.id).collect()))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            (ids[2].clone(), vec![]),
+            (ids[1].clone(), vec![a.id.clone(), b.id.clone()]),
+            (ids[0].clone(), vec![a.id.clone()]),
+        ]
+    );
+    let empty = connections
+        .blocks_with_channels(Some(&[]), 10, 0, false)
+        .await
+        .unwrap();
+    assert_eq!(empty.total, 0);
+    assert!(empty.items.is_empty());
+}
+
+#[tokio::test]
+async fn connection_sharing_stats() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Mine");
+    let other = Channel::new("Other");
+    channels.create(&channel).await.unwrap();
+    channels.create(&other).await.unwrap();
+    let exclusive = Block::new(BlockContent::text("Only here"));
+    let shared = Block::new(BlockContent::text("Everywhere"));
+    blocks.create(&exclusive).await.unwrap();
+    blocks.create(&shared).await.unwrap();
+    connections
+        .connect_batch(&[
+            (exclusive.id.clone(), channel.id.clone(), 0),
+            (shared.id.clone(), channel.id.clone(), 1),
+            (shared.id.clone(), other.id.clone(), 0),
+        ])
+        .await
+        .unwrap();
+
+    let stats = connections.sharing_stats(&channel.id).await.unwrap();
+    assert_eq!(
+        stats,
+        SharingStats {
+            total: 2,
+            exclusive: 1,
+            shared: 1,
+        }
+    );
+
+    let empty = connections.sharing_stats(&ChannelId::new()).await.unwrap();
+    assert_eq!(empty, SharingStats::default());
+}
+
+#[tokio::test]
+async fn connection_content_type_breakdown_in_channel() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Mixed");
+    let other = Channel::new("Other");
+    channels.create(&channel).await.unwrap();
+    channels.create(&other).await.unwrap();
+    let mixed = vec![
+        Block::new(BlockContent::image("images/a.png", "image/png")),
+        Block::new(BlockContent::image("images/b.jpg", "image/jpeg")),
+        Block::new(BlockContent::video("videos/c.mp4", "video/mp4")),
+        Block::new(BlockContent::link("https://example.com/1")),
+        Block::new(BlockContent::link("https://example.com/2")),
+        Block::new(BlockContent::link("https://example.com/3")),
+    ];
+    let elsewhere = Block::new(BlockContent::text("Elsewhere"));
+    blocks.create_batch(&mixed).await.unwrap();
+    blocks.create(&elsewhere).await.unwrap();
+
+    let mut batch: Vec<_> = mixed
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (b.id.clone(), channel.id.clone(), i as i32))
+        .collect();
+    batch.push((elsewhere.id.clone(), other.id.clone(), 0));
+    connections.connect_batch(&batch).await.unwrap();
+
+    let breakdown = connections
+        .content_type_breakdown_in_channel(&channel.id)
+        .await
+        .unwrap();
+
+    assert_eq!(breakdown.len(), 3);
+    assert_eq!(breakdown["image"], 2);
+    assert_eq!(breakdown["video"], 1);
+    assert_eq!(breakdown["link"], 3);
+    assert!(!breakdown.contains_key("text"));
+
+    let empty = connections
+        .content_type_breakdown_in_channel(&ChannelId::new())
+        .await
+        .unwrap();
+    assert!(empty.is_empty());
+}
+
+#[tokio::test]
+async fn connection_most_connected_blocks() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let mut channel_ids = Vec::new();
+    for title in ["One", "Two", "Three"] {
+        let channel = Channel::new(title);
+        channels.create(&channel).await.unwrap();
+        channel_ids.push(channel.id);
+    }
+    let popular = Block::new(BlockContent::text("Everywhere"));
+    let single = Block::new(BlockContent::text("Only once"));
+    let unconnected = Block::new(BlockContent::text("Nowhere"));
+    for block in [&popular, &single, &unconnected] {
+        blocks.create(block).await.unwrap();
+    }
+    let mut conns: Vec<_> = channel_ids
+        .iter()
+        .map(|id| (popular.id.clone(), id.clone(), 0))
+        .collect();
+    conns.push((single.id.clone(), channel_ids[0].clone(), 1));
+    connections.connect_batch(&conns).await.unwrap();
+
+    let ranked = connections.most_connected_blocks(10, false).await.unwrap();
+    let ranked: Vec<_> = ranked.iter().map(|(b, n)| (b.id.clone(), *n)).collect();
+    assert_eq!(ranked, vec![(popular.id.clone(), 3), (single.id, 1)]);
+
+    let top = connections.most_connected_blocks(1, false).await.unwrap();
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].0.id, popular.id);
+}
+
+#[tokio::test]
+async fn connection_move_relative() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Ordered");
+    channels.create(&channel).await.unwrap();
+    let mut ids = Vec::new();
+    for (i, body) in ["A", "B", "C"].into_iter().enumerate() {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        connections
+            .connect(&block.id, &channel.id, i as i32)
+            .await
+            .unwrap();
+        ids.push(block.id);
+    }
+
+    connections
+        .move_relative(&channel.id, &ids[2], &ids[0], Placement::Before, 1)
+        .await
+        .unwrap();
+
+    let order: Vec<(BlockId, i32)> = connections
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(b, pos)| (b.id, pos))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            (ids[2].clone(), 0),
+            (ids[0].clone(), 1),
+            (ids[1].clone(), 2)
+        ]
+    );
+
+    let result = connections
+        .move_relative(&channel.id, &ids[0], &BlockId::new(), Placement::After, 1)
+        .await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+}
+
+#[tokio::test]
+async fn connection_swap_positions() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Swapped");
+    channels.create(&channel).await.unwrap();
+    let mut ids = Vec::new();
+    for (i, body) in ["A", "B"].into_iter().enumerate() {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        connections
+            .connect(&block.id, &channel.id, i as i32)
+            .await
+            .unwrap();
+        ids.push(block.id);
+    }
+
+    connections
+        .swap_positions(&channel.id, &ids[0], &ids[1])
+        .await
+        .unwrap();
+
+    let order: Vec<(BlockId, i32)> = connections
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(b, pos)| (b.id, pos))
+        .collect();
+    assert_eq!(order, vec![(ids[1].clone(), 0), (ids[0].clone(), 1)]);
+
+    let result = connections
+        .swap_positions(&channel.id, &ids[0], &BlockId::new())
+        .await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+    let kept = connections
+        .get_connection(&ids[0], &channel.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(kept.position, 1);
+}
+
+#[tokio::test]
+async fn connection_merge_blocks_repoints_and_deletes() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let first = Channel::new("First");
+    let second = Channel::new("Second");
+    channels.create(&first).await.unwrap();
+    channels.create(&second).await.unwrap();
+    let keep = Block::new(BlockContent::text("Same words"));
+    let dupe = Block::new(BlockContent::text("Same words"));
+    blocks
+        .create_batch(&[keep.clone(), dupe.clone()])
+        .await
+        .unwrap();
+    connections.connect(&keep.id, &first.id, 0).await.unwrap();
+    connections.connect(&dupe.id, &first.id, 1).await.unwrap();
+    connections.connect(&dupe.id, &second.id, 4).await.unwrap();
+
+    let joined = connections
+        .merge_blocks(&keep.id, std::slice::from_ref(&dupe.id))
+        .await
+        .unwrap();
+    assert_eq!(joined, vec![second.id.clone()]);
+
+    assert!(blocks.get(&dupe.id).await.unwrap().is_none());
+    let in_first = connections
+        .get_blocks_in_channel(&first.id, false)
+        .await
+        .unwrap();
+    assert_eq!(in_first.len(), 1);
+    assert_eq!(
+        (in_first[0].0.id.clone(), in_first[0].1),
+        (keep.id.clone(), 0)
+    );
+    let moved = connections
+        .get_connection(&keep.id, &second.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(moved.position, 4);
+
+    // A missing block rolls the whole merge back
+    let other = Block::new(BlockContent::text("Same words"));
+    blocks.create(&other).await.unwrap();
+    connections.connect(&other.id, &first.id, 2).await.unwrap();
+    let result = connections
+        .merge_blocks(&keep.id, &[other.id.clone(), BlockId::new()])
+        .await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+    assert!(blocks.get(&other.id).await.unwrap().is_some());
+    assert!(connections
+        .get_connection(&other.id, &first.id)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn connection_prepend_shifts_existing_blocks() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Stacked");
+    let other = Channel::new("Elsewhere");
+    channels.create(&channel).await.unwrap();
+    channels.create(&other).await.unwrap();
+    let mut ids = Vec::new();
+    for body in ["A", "B", "Top"] {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        ids.push(block.id);
+    }
+    connections.connect(&ids[0], &channel.id, 0).await.unwrap();
+    connections.connect(&ids[1], &channel.id, 1).await.unwrap();
+    connections.connect(&ids[0], &other.id, 0).await.unwrap();
+
+    connections.prepend(&ids[2], &channel.id).await.unwrap();
+
+    let order: Vec<(BlockId, i32)> = connections
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(b, pos)| (b.id, pos))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            (ids[2].clone(), 0),
+            (ids[0].clone(), 1),
+            (ids[1].clone(), 2)
+        ]
+    );
+    let untouched = connections
+        .get_connection(&ids[0], &other.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(untouched.position, 0);
+
+    let result = connections.prepend(&ids[2], &channel.id).await;
+    assert!(matches!(result, Err(RepoError::Duplicate)));
+    let kept = connections
+        .get_connection(&ids[0], &channel.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(kept.position, 1);
+}
+
+#[tokio::test]
+async fn connection_set_order() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Ordered");
+    channels.create(&channel).await.unwrap();
+    let mut ids = Vec::new();
+    for (i, body) in ["A", "B", "C"].into_iter().enumerate() {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        connections
+            .connect(&block.id, &channel.id, (i as i32) * 10)
+            .await
+            .unwrap();
+        ids.push(block.id);
+    }
+
+    // B is left out, so it follows the listed blocks.
+    connections
+        .set_order(&channel.id, &[ids[2].clone(), ids[0].clone()], 10)
+        .await
+        .unwrap();
+
+    let order: Vec<(BlockId, i32)> = connections
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(b, pos)| (b.id, pos))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            (ids[2].clone(), 0),
+            (ids[0].clone(), 10),
+            (ids[1].clone(), 20)
+        ]
+    );
+
+    let result = connections
+        .set_order(&channel.id, &[BlockId::new()], 10)
+        .await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+}
+
+#[tokio::test]
+async fn connection_move_group() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let channel = Channel::new("Grouped");
+    channels.create(&channel).await.unwrap();
+    let mut ids = Vec::new();
+    for (i, body) in ["A", "B", "C", "D", "E"].into_iter().enumerate() {
+        let block = Block::new(BlockContent::text(body));
+        blocks.create(&block).await.unwrap();
+        connections
+            .connect(&block.id, &channel.id, i as i32)
+            .await
+            .unwrap();
+        ids.push(block.id);
+    }
+
+    // D then B in the request, but the group keeps its channel order.
+    connections
+        .move_group(&channel.id, &[ids[3].clone(), ids[1].clone()], 0, 1)
+        .await
+        .unwrap();
+
+    let order: Vec<(BlockId, i32)> = connections
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(b, pos)| (b.id, pos))
+        .collect();
+    let expected: Vec<(BlockId, i32)> = [1, 3, 0, 2, 4]
+        .iter()
+        .enumerate()
+        .map(|(pos, &i)| (ids[i].clone(), pos as i32))
+        .collect();
+    assert_eq!(order, expected);
+
+    let result = connections
+        .move_group(&channel.id, &[ids[0].clone(), BlockId::new()], 1, 1)
+        .await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+}
+
+#[tokio::test]
+async fn connection_channels_with_position_collisions() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let clean = Channel::new("Clean");
+    let broken = Channel::new("Broken");
+    channels.create(&clean).await.unwrap();
+    channels.create(&broken).await.unwrap();
+    for (i, channel) in [&clean, &clean, &broken, &broken].into_iter().enumerate() {
+        let block = Block::new(BlockContent::text("Block"));
+        blocks.create(&block).await.unwrap();
+        let position = if channel.id == clean.id { i as i32 } else { 0 };
+        connections
+            .connect(&block.id, &channel.id, position)
+            .await
+            .unwrap();
+    }
+
+    let colliding = connections
+        .channels_with_position_collisions()
+        .await
+        .unwrap();
+    assert_eq!(colliding, vec![broken.id]);
+}
+
+#[tokio::test]
+async fn connection_find_position_anomalies() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let clean = Channel::new("Clean");
+    let negative = Channel::new("Negative");
+    let messy = Channel::new("Messy");
+    for channel in [&clean, &negative, &messy] {
+        channels.create(channel).await.unwrap();
+    }
+    for (channel, position) in [
+        (&clean, 0),
+        (&clean, 1),
+        (&negative, -1),
+        (&negative, 0),
+        (&messy, 0),
+        (&messy, 0),
+        (&messy, 3),
+    ] {
+        let block = Block::new(BlockContent::text("Block"));
+        blocks.create(&block).await.unwrap();
+        connections
+            .connect(&block.id, &channel.id, position)
+            .await
+            .unwrap();
+    }
+
+    let mut expected = vec![
+        (negative.id.clone(), PositionIssue::Negative),
+        (messy.id.clone(), PositionIssue::Duplicate),
+        (messy.id.clone(), PositionIssue::Gap),
+    ];
+    expected.sort_by(|a, b| a.0 .0.cmp(&b.0 .0));
+    let anomalies = connections.find_position_anomalies(1).await.unwrap();
+    assert_eq!(anomalies, expected);
+
+    // With a gap of 3, the messy channel's jump from 0 to 3 is allowed
+    let anomalies = connections.find_position_anomalies(3).await.unwrap();
+    assert_eq!(anomalies.len(), 2);
+    assert!(!anomalies.contains(&(messy.id.clone(), PositionIssue::Gap)));
+}
+
+#[tokio::test]
+async fn connection_channels_with_content_type() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let connections = db.connection_repository();
+
+    let videos = Channel::new("Videos");
+    let notes = Channel::new("Notes");
+    channels.create(&videos).await.unwrap();
+    channels.create(&notes).await.unwrap();
+    let video = Block::new(BlockContent::video("videos/clip.mp4", "video/mp4"));
+    let text = Block::new(BlockContent::text("Note"));
+    let other_video = Block::new(BlockContent::video("videos/other.mp4", "video/mp4"));
+    for block in [&video, &text, &other_video] {
+        blocks.create(block).await.unwrap();
+    }
+    connections.connect(&video.id, &videos.id, 0).await.unwrap();
+    connections
+        .connect(&other_video.id, &videos.id, 1)
+        .await
+        .unwrap();
+    connections.connect(&text.id, &notes.id, 0).await.unwrap();
+
+    let page = connections
+        .channels_with_content_type("video", 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].id, videos.id);
+
+    let page = connections
+        .channels_with_content_type("audio", 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 0);
 }
 
 #[tokio::test]
@@ -463,10 +2344,10 @@ async fn connection_next_position() {
 
     // Empty channel should have next position 0
     let pos = conns
-        .next_position(&channel.id)
+        .next_position(&channel.id, 1)
         .await
         .expect("Failed to get next position");
-    assert_eq!(pos, 0);
+    assert_eq!(pos, Some(0));
 
     // Add some blocks
     let block1 = Block::new(BlockContent::Text {
@@ -482,12 +2363,114 @@ async fn connection_next_position() {
     conns.connect(&block1.id, &channel.id, 0).await.unwrap();
     conns.connect(&block2.id, &channel.id, 5).await.unwrap();
 
-    // Next position should be max + 1 = 6
+    // Next position should be max + 1 = 6, or max + gap
     let pos = conns
-        .next_position(&channel.id)
+        .next_position(&channel.id, 1)
         .await
         .expect("Failed to get next position");
-    assert_eq!(pos, 6);
+    assert_eq!(pos, Some(6));
+    assert_eq!(
+        conns.next_position(&channel.id, 100).await.unwrap(),
+        Some(105)
+    );
+
+    // No room past i32::MAX
+    conns
+        .upsert(&block2.id, &channel.id, i32::MAX - 10)
+        .await
+        .unwrap();
+    assert_eq!(conns.next_position(&channel.id, 100).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn connection_batch_connect_empty() {
+    let db = setup_db().await;
+    let conns = db.connection_repository();
+
+    conns
+        .connect_batch(&[])
+        .await
+        .expect("Empty batch should be a no-op");
+}
+
+#[tokio::test]
+async fn connection_create_connected_is_all_or_nothing() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Template");
+    let first = Block::new(BlockContent::text("First"));
+    let second = Block::new(BlockContent::text("Second"));
+    let batch = vec![first.clone(), second.clone()];
+
+    // The last connection points at a channel that doesn't exist
+    let result = conns
+        .create_connected(
+            std::slice::from_ref(&channel),
+            &batch,
+            &[
+                (first.id.clone(), channel.id.clone(), 0),
+                (second.id.clone(), ChannelId::new(), 0),
+            ],
+        )
+        .await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+    assert!(channels.get(&channel.id).await.unwrap().is_none());
+    assert!(blocks.get(&first.id).await.unwrap().is_none());
+
+    conns
+        .create_connected(
+            std::slice::from_ref(&channel),
+            &batch,
+            &[
+                (first.id.clone(), channel.id.clone(), 0),
+                (second.id.clone(), channel.id.clone(), 1),
+            ],
+        )
+        .await
+        .unwrap();
+    let in_channel = conns
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap();
+    let ids: Vec<_> = in_channel.iter().map(|(b, _)| b.id.clone()).collect();
+    assert_eq!(ids, vec![first.id.clone(), second.id.clone()]);
+}
+
+#[tokio::test]
+async fn connection_counts_by_channel() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let one = Channel::new("One");
+    let two = Channel::new("Two");
+    let empty = Channel::new("Empty");
+    for channel in [&one, &two, &empty] {
+        channels.create(channel).await.unwrap();
+    }
+
+    let block1 = Block::new(BlockContent::text("1"));
+    let block2 = Block::new(BlockContent::text("2"));
+    blocks.create(&block1).await.unwrap();
+    blocks.create(&block2).await.unwrap();
+
+    conns.connect(&block1.id, &one.id, 0).await.unwrap();
+    conns.connect(&block2.id, &one.id, 1).await.unwrap();
+    conns.connect(&block1.id, &two.id, 0).await.unwrap();
+
+    let counts = conns
+        .counts_by_channel()
+        .await
+        .expect("Failed to count blocks by channel");
+
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[&one.id], 2);
+    assert_eq!(counts[&two.id], 1);
+    assert!(!counts.contains_key(&empty.id));
 }
 
 #[tokio::test]
@@ -524,10 +2507,143 @@ async fn connection_batch_connect() {
         .expect("Failed to batch connect");
 
     // Verify all connections
-    let blocks_in_channel = conns.get_blocks_in_channel(&channel.id).await.unwrap();
+    let blocks_in_channel = conns
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap();
     assert_eq!(blocks_in_channel.len(), 5);
 }
 
+#[tokio::test]
+async fn connection_batch_connect_past_parameter_limit() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Large");
+    channels.create(&channel).await.unwrap();
+
+    // 2,000 rows bind 22,000 block and 8,000 connection parameters
+    let block_list: Vec<Block> = (0..2000)
+        .map(|i| Block::new(BlockContent::text(format!("Block {}", i))))
+        .collect();
+    blocks.create_batch(&block_list).await.unwrap();
+
+    let connections: Vec<(BlockId, ChannelId, i32)> = block_list
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (b.id.clone(), channel.id.clone(), i as i32))
+        .collect();
+    conns.connect_batch(&connections).await.unwrap();
+
+    let blocks_in_channel = conns
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap();
+    assert_eq!(blocks_in_channel.len(), 2000);
+    assert!(blocks_in_channel
+        .iter()
+        .zip(&block_list)
+        .enumerate()
+        .all(|(i, ((b, pos), expected))| b.id == expected.id && *pos == i as i32));
+}
+
+#[tokio::test]
+async fn connection_batch_connect_rolls_back_every_chunk() {
+    // Three connections per statement
+    let config = SqliteConfig::new().max_batch_params(12);
+    let db = SqliteDatabase::in_memory_with_config(&config)
+        .await
+        .unwrap();
+    db.migrate().await.unwrap();
+    let channels = db.channel_repository();
+    let blocks = db.block_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Chunked");
+    channels.create(&channel).await.unwrap();
+    let block_list: Vec<Block> = (0..7)
+        .map(|i| Block::new(BlockContent::text(format!("Block {}", i))))
+        .collect();
+    blocks.create_batch(&block_list).await.unwrap();
+
+    // The last chunk repeats a connection from the first
+    let mut connections: Vec<(BlockId, ChannelId, i32)> = block_list
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (b.id.clone(), channel.id.clone(), i as i32))
+        .collect();
+    connections.push(connections[0].clone());
+
+    let result = conns.connect_batch(&connections).await;
+    assert!(matches!(result, Err(RepoError::Duplicate)), "{result:?}");
+    assert!(conns
+        .get_blocks_in_channel(&channel.id, false)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+// =============================================================================
+// Activity Log Tests
+// =============================================================================
+
+#[tokio::test]
+async fn activity_feed_lists_mutations_newest_first() {
+    let db = setup_db().await;
+    let service = GardenService::new(
+        db.channel_repository(),
+        db.block_repository(),
+        db.connection_repository(),
+    )
+    .with_activity_log(std::sync::Arc::new(db.activity_repository()));
+
+    let channel = service
+        .create_channel(NewChannel {
+            title: "Reading".to_string(),
+            description: None,
+            body: None,
+        })
+        .await
+        .unwrap();
+    let block = service.create_block(NewBlock::text("Note")).await.unwrap();
+    service
+        .connect_block(&block.id, &channel.id, None)
+        .await
+        .unwrap();
+    service
+        .reorder_block(&channel.id, &block.id, 3)
+        .await
+        .unwrap();
+    service.delete_channel(&channel.id).await.unwrap();
+
+    let feed = service.activity_feed(10, 0).await.unwrap();
+    let entries: Vec<_> = feed
+        .items
+        .iter()
+        .map(|e| (e.kind, e.entity_id.clone()))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (ActivityKind::ChannelDeleted, channel.id.0.clone()),
+            (ActivityKind::BlockReordered, block.id.0.clone()),
+            (ActivityKind::BlockConnected, block.id.0.clone()),
+            (ActivityKind::BlockCreated, block.id.0.clone()),
+            (ActivityKind::ChannelCreated, channel.id.0.clone()),
+        ]
+    );
+    assert_eq!(feed.total, 5);
+    // Entries outlive the channel they describe
+    assert_eq!(feed.items[1].channel_id.as_ref(), Some(&channel.id));
+    assert_eq!(feed.items[1].block_id.as_ref(), Some(&block.id));
+
+    let page = service.activity_feed(2, 1).await.unwrap();
+    assert_eq!(page.items, feed.items[1..3].to_vec());
+    assert!(page.has_next());
+}
+
 // =============================================================================
 // Cascade Delete Tests
 // =============================================================================
@@ -588,6 +2704,406 @@ async fn cascade_delete_block_removes_connections() {
     assert!(channel_result.is_some());
 }
 
+// =============================================================================
+// Integrity Tests
+// =============================================================================
+
+#[tokio::test]
+async fn integrity_report_healthy_db_is_empty() {
+    let db = setup_db().await;
+    let channel = Channel::new("Healthy");
+    let block = Block::new(BlockContent::text("Fine"));
+    db.channel_repository().create(&channel).await.unwrap();
+    db.block_repository().create(&block).await.unwrap();
+    db.connection_repository()
+        .connect(&block.id, &channel.id, 0)
+        .await
+        .unwrap();
+
+    let report = db.integrity_report().await.unwrap();
+
+    assert!(report.is_healthy());
+    assert_eq!(report, IntegrityReport::default());
+}
+
+#[tokio::test]
+async fn integrity_report_flags_collisions_and_dangling_rows() {
+    let db = setup_db().await;
+    let channel = Channel::new("Messy");
+    let a = Block::new(BlockContent::text("A"));
+    let b = Block::new(BlockContent::text("B"));
+    db.channel_repository().create(&channel).await.unwrap();
+    db.block_repository()
+        .create_batch(&[a.clone(), b.clone()])
+        .await
+        .unwrap();
+    let conns = db.connection_repository();
+    conns.connect(&a.id, &channel.id, 3).await.unwrap();
+    conns.connect(&b.id, &channel.id, 3).await.unwrap();
+
+    // Simulate a manual edit that bypassed foreign keys
+    sqlx::query("PRAGMA foreign_keys = OFF")
+        .execute(db.pool())
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO connections (block_id, channel_id, position, connected_at) \
+         VALUES ('missing', $1, 4, '2026-01-01T00:00:00Z')",
+    )
+    .bind(&channel.id.0)
+    .execute(db.pool())
+    .await
+    .unwrap();
+    sqlx::query("PRAGMA foreign_keys = ON")
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+    let report = db.integrity_report().await.unwrap();
+
+    assert!(!report.is_healthy());
+    assert_eq!(
+        report.position_collisions,
+        vec![PositionCollision {
+            channel_id: channel.id.clone(),
+            position: 3,
+            count: 2,
+        }]
+    );
+    assert_eq!(report.foreign_key_violations.len(), 1);
+    assert_eq!(report.foreign_key_violations[0].table, "connections");
+    assert_eq!(report.foreign_key_violations[0].parent, "blocks");
+}
+
+#[tokio::test]
+async fn integrity_report_flags_duplicate_connections() {
+    let db = setup_db().await;
+    let channel = Channel::new("Duplicated");
+    let block = Block::new(BlockContent::text("Twice"));
+    db.channel_repository().create(&channel).await.unwrap();
+    db.block_repository().create(&block).await.unwrap();
+
+    // Rebuild the table without its primary key, as a careless manual
+    // migration might, so the same pair can be inserted twice
+    for statement in [
+        "CREATE TABLE connections_copy AS SELECT * FROM connections",
+        "DROP TABLE connections",
+        "ALTER TABLE connections_copy RENAME TO connections",
+    ] {
+        sqlx::query(statement).execute(db.pool()).await.unwrap();
+    }
+    for position in [0, 1] {
+        sqlx::query(
+            "INSERT INTO connections (block_id, channel_id, position, connected_at) \
+             VALUES ($1, $2, $3, '2026-01-01T00:00:00Z')",
+        )
+        .bind(&block.id.0)
+        .bind(&channel.id.0)
+        .bind(position)
+        .execute(db.pool())
+        .await
+        .unwrap();
+    }
+
+    let report = db.integrity_report().await.unwrap();
+
+    assert_eq!(
+        report.duplicate_connections,
+        vec![DuplicateConnection {
+            block_id: block.id.clone(),
+            channel_id: channel.id.clone(),
+            count: 2,
+        }]
+    );
+    assert!(report.position_collisions.is_empty());
+}
+
+// =============================================================================
+// Lifecycle Tests
+// =============================================================================
+
+/// Create a unique database path under the system temp dir.
+fn temp_db_path() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("garden-db-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("garden.db")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn checkpoint_guard_flushes_wal_on_drop() {
+    let path = temp_db_path();
+    let db = SqliteDatabase::new(&path).await.unwrap();
+    db.migrate().await.unwrap();
+    let guard = db.checkpoint_guard();
+
+    let channel = Channel::new("Survives");
+    db.channel_repository().create(&channel).await.unwrap();
+
+    // Dropping the guard without closing checkpoints the WAL
+    drop(guard);
+    let wal = std::fs::metadata(path.with_extension("db-wal")).unwrap();
+    assert_eq!(wal.len(), 0);
+    drop(db);
+
+    let reopened = SqliteDatabase::new(&path).await.unwrap();
+    let retrieved = reopened
+        .channel_repository()
+        .get(&channel.id)
+        .await
+        .unwrap()
+        .expect("Channel not found after reopen");
+    assert_eq!(retrieved.title, "Survives");
+
+    reopened.close().await;
+    std::fs::remove_dir_all(path.parent().unwrap()).ok();
+}
+
+#[tokio::test]
+async fn delete_journal_mode_leaves_no_wal_file() {
+    let path = temp_db_path();
+    let config = SqliteConfig::new().journal_mode(JournalMode::Delete);
+    let db = SqliteDatabase::with_config(&path, &config).await.unwrap();
+    db.migrate().await.unwrap();
+    db.channel_repository()
+        .create(&Channel::new("No WAL"))
+        .await
+        .unwrap();
+
+    db.close().await;
+
+    assert!(path.exists());
+    assert!(!path.with_extension("db-wal").exists());
+    assert!(!path.with_extension("db-shm").exists());
+    std::fs::remove_dir_all(path.parent().unwrap()).ok();
+}
+
+#[tokio::test]
+async fn in_memory_runs_without_wal() {
+    let config = SqliteConfig::new().journal_mode(JournalMode::Memory);
+    let db = SqliteDatabase::in_memory_with_config(&config)
+        .await
+        .unwrap();
+    db.migrate().await.unwrap();
+
+    let (mode,): (String,) = sqlx::query_as("PRAGMA journal_mode")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(mode, "memory");
+}
+
+#[tokio::test]
+async fn block_repository_rejects_oversized_content() {
+    let config = SqliteConfig::new().max_content_bytes(1024);
+    let db = SqliteDatabase::in_memory_with_config(&config)
+        .await
+        .unwrap();
+    db.migrate().await.unwrap();
+    let blocks = db.block_repository();
+
+    let oversized = Block::new(BlockContent::text("x".repeat(2048)));
+    let result = blocks.create(&oversized).await;
+    assert!(
+        matches!(result, Err(RepoError::ContentTooLarge { max: 1024, .. })),
+        "{result:?}"
+    );
+    assert!(blocks.get(&oversized.id).await.unwrap().is_none());
+
+    let mut block = Block::new(BlockContent::text("Small"));
+    blocks.create(&block).await.unwrap();
+    block.content = BlockContent::text("x".repeat(2048));
+    assert!(blocks.update(&block).await.is_err());
+    let stored = blocks.get(&block.id).await.unwrap().unwrap();
+    assert!(matches!(stored.content, BlockContent::Text { body } if body == "Small"));
+}
+
+#[tokio::test]
+async fn import_garden_is_all_or_nothing() {
+    let config = SqliteConfig::new().max_content_bytes(1024);
+    let db = SqliteDatabase::in_memory_with_config(&config)
+        .await
+        .unwrap();
+    db.migrate().await.unwrap();
+    let service = GardenService::new(
+        db.channel_repository(),
+        db.block_repository(),
+        db.connection_repository(),
+    );
+
+    // The second block is valid but too large to store
+    let channel = Channel::new("Imported");
+    let small = Block::new(BlockContent::text("Small"));
+    let oversized = Block::new(BlockContent::text("x".repeat(2048)));
+    let export = GardenExport::new(
+        vec![channel.clone()],
+        vec![small.clone(), oversized.clone()],
+        vec![
+            Connection::new(small.id.clone(), channel.id.clone(), 0),
+            Connection::new(oversized.id.clone(), channel.id.clone(), 1),
+        ],
+    );
+
+    let result = service.import_garden(export, false).await;
+
+    assert!(result.is_err());
+    assert_eq!(service.count_channels().await.unwrap(), 0);
+    assert!(db
+        .block_repository()
+        .get(&small.id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn checkpoint_guard_drops_outside_runtime() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (db, guard) = runtime.block_on(async {
+        let db = SqliteDatabase::in_memory().await.unwrap();
+        db.migrate().await.unwrap();
+        db.channel_repository()
+            .create(&Channel::new("Test"))
+            .await
+            .unwrap();
+        let guard = db.checkpoint_guard();
+        (db, guard)
+    });
+
+    // Must not panic without an ambient runtime
+    drop(guard);
+    drop(db);
+}
+
+#[tokio::test]
+async fn slow_query_callback_reports_query() {
+    let seen: std::sync::Arc<std::sync::Mutex<Vec<SlowQuery>>> = Default::default();
+    let sink = seen.clone();
+    let db = setup_db()
+        .await
+        .with_slow_query_threshold(std::time::Duration::ZERO)
+        .on_slow_query(move |query| sink.lock().unwrap().push(query));
+    let channels = db.channel_repository();
+    channels.create(&Channel::new("One")).await.unwrap();
+    channels.create(&Channel::new("Two")).await.unwrap();
+
+    channels.list(10, 0).await.unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].name, "channels.list");
+    assert_eq!(seen[0].rows, 2);
+}
+
+#[tokio::test]
+async fn in_memory_shared_is_visible_across_connections() {
+    let name = format!("garden-shared-{}", uuid::Uuid::new_v4());
+    let db = SqliteDatabase::in_memory_shared(&name).await.unwrap();
+    db.migrate().await.unwrap();
+
+    // Hold both at once so they must be distinct pooled connections
+    let mut writer = db.pool().acquire().await.unwrap();
+    let mut reader = db.pool().acquire().await.unwrap();
+
+    let channel = Channel::new("Shared");
+    sqlx::query("INSERT INTO channels (id, title, created_at, updated_at) VALUES ($1, $2, $3, $3)")
+        .bind(&channel.id.0)
+        .bind(&channel.title)
+        .bind(channel.created_at.to_rfc3339())
+        .execute(&mut *writer)
+        .await
+        .unwrap();
+
+    let (title,): (String,) = sqlx::query_as("SELECT title FROM channels WHERE id = $1")
+        .bind(&channel.id.0)
+        .fetch_one(&mut *reader)
+        .await
+        .unwrap();
+    assert_eq!(title, "Shared");
+
+    drop((writer, reader));
+    db.close().await;
+}
+
+#[tokio::test]
+async fn migrate_recovers_from_half_applied_schema() {
+    let db = SqliteDatabase::in_memory().await.unwrap();
+    // The channels table as the full set of migrations leaves it, with
+    // nothing recorded in _sqlx_migrations
+    sqlx::query(
+        "CREATE TABLE channels (
+            id TEXT PRIMARY KEY NOT NULL,
+            title TEXT NOT NULL,
+            description TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            accessed_at TEXT,
+            body TEXT
+        )",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    db.migrate().await.expect("migrate should reconcile");
+    db.verify_schema().await.unwrap();
+    // Running again finds nothing to do
+    db.migrate().await.unwrap();
+
+    let (applied,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_migrations")
+        .fetch_one(db.pool())
+        .await
+        .unwrap();
+    assert_eq!(applied as usize, sqlx::migrate!().iter().count());
+
+    let mut channel = Channel::new("Recovered");
+    channel.body = Some("About".to_string());
+    db.channel_repository().create(&channel).await.unwrap();
+    let retrieved = db
+        .channel_repository()
+        .get(&channel.id)
+        .await
+        .unwrap()
+        .expect("Channel not found");
+    assert_eq!(retrieved.body.as_deref(), Some("About"));
+}
+
+#[tokio::test]
+async fn verify_schema_names_missing_column() {
+    let db = setup_db().await;
+    sqlx::query("ALTER TABLE channels DROP COLUMN body")
+        .execute(db.pool())
+        .await
+        .unwrap();
+
+    let result = db.verify_schema().await;
+
+    assert!(matches!(
+        result,
+        Err(DbError::SchemaInvalid(ref msg)) if msg.contains("channels.body")
+    ));
+}
+
+#[tokio::test]
+async fn migrate_refuses_database_from_newer_version() {
+    let db = setup_db().await;
+    // A migration from a future build
+    sqlx::query(
+        "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+         VALUES (99990101000000, 'from the future', TRUE, X'00', 0)",
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    for result in [db.migrate().await, db.verify_schema().await] {
+        assert!(matches!(
+            result,
+            Err(DbError::SchemaInvalid(ref msg))
+                if msg.contains("database is newer than this app version")
+        ));
+    }
+}
+
 // =============================================================================
 // Error Handling Tests
 // =============================================================================
@@ -620,3 +3136,16 @@ async fn error_disconnect_nonexistent() {
     let result = conns.disconnect(&BlockId::new(), &ChannelId::new()).await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn error_connect_nonexistent_block() {
+    let db = setup_db().await;
+    let channels = db.channel_repository();
+    let conns = db.connection_repository();
+
+    let channel = Channel::new("Test");
+    channels.create(&channel).await.unwrap();
+
+    let result = conns.connect(&BlockId::new(), &channel.id, 0).await;
+    assert!(matches!(result, Err(RepoError::NotFound)));
+}
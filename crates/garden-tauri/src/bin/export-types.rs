@@ -1,6 +1,9 @@
 //! Binary to export TypeScript types from Rust models.
 //!
-//! Run with: cargo run --package garden-core --bin export-types
+//! Lives in garden-tauri so it can reach both the garden-core models and the
+//! IPC-only types (errors, events, command results) in one pass.
+//!
+//! Run with: cargo run --package garden-tauri --bin export-types
 //!
 //! Or use the justfile command: just gen-types
 //!
@@ -28,28 +31,80 @@ fn main() {
     // Ensure output directory exists
     ensure_output_dir();
 
-    println!("Exporting TypeScript types from garden-core...\n");
+    println!("Exporting TypeScript types from garden-core and garden-tauri...\n");
 
     // Channel types
     export::<garden_core::models::ChannelId>("ChannelId");
     export::<garden_core::models::Channel>("Channel");
     export::<garden_core::models::NewChannel>("NewChannel");
     export::<garden_core::models::ChannelUpdate>("ChannelUpdate");
+    export::<garden_core::models::ChannelPreview>("ChannelPreview");
 
     // Block types
     export::<garden_core::models::BlockId>("BlockId");
     export::<garden_core::models::BlockContent>("BlockContent");
+    export::<garden_core::models::GalleryItem>("GalleryItem");
     export::<garden_core::models::Block>("Block");
     export::<garden_core::models::NewBlock>("NewBlock");
     export::<garden_core::models::BlockUpdate>("BlockUpdate");
+    export::<garden_core::models::ContentKind>("ContentKind");
+    export::<garden_core::models::MetadataStats>("MetadataStats");
 
     // Connection types
     export::<garden_core::models::Connection>("Connection");
     export::<garden_core::models::NewConnection>("NewConnection");
+    export::<garden_core::models::BlockNeighbors>("BlockNeighbors");
+    export::<garden_core::models::BlockPositionInfo>("BlockPositionInfo");
+    export::<garden_core::models::BlockContext>("BlockContext");
+    export::<garden_core::models::ChannelOrder>("ChannelOrder");
+    export::<garden_core::models::BlockSortKey>("BlockSortKey");
+    export::<garden_core::models::OrderSnapshot>("OrderSnapshot");
+    export::<garden_core::models::PositionIssue>("PositionIssue");
+    export::<garden_core::models::ChannelSetMode>("ChannelSetMode");
+    export::<garden_core::models::Placement>("Placement");
+    export::<garden_core::models::SharingStats>("SharingStats");
+
+    // Activity types
+    export::<garden_core::models::ActivityKind>("ActivityKind");
+    export::<garden_core::models::ActivityEntry>("ActivityEntry");
+
+    // Search types
+    export::<garden_core::models::SearchMode>("SearchMode");
+    export::<garden_core::models::SearchHit>("SearchHit");
+
+    // Import/export types
+    export::<garden_core::models::GardenExport>("GardenExport");
+    export::<garden_core::models::ChannelExport>("ChannelExport");
+    export::<garden_core::models::ImportStrategy>("ImportStrategy");
+    export::<garden_core::models::InvalidBlock>("InvalidBlock");
+    export::<garden_core::models::ImportPlan>("ImportPlan");
+    export::<garden_core::models::UrlImportLine>("UrlImportLine");
+    export::<garden_core::models::UrlImportReport>("UrlImportReport");
+
+    // Maintenance types
+    export::<garden_core::models::IntegrityReport>("IntegrityReport");
+    export::<garden_core::models::ForeignKeyViolation>("ForeignKeyViolation");
+    export::<garden_core::models::DuplicateConnection>("DuplicateConnection");
+    export::<garden_core::models::PositionCollision>("PositionCollision");
+
+    // Media types
+    export::<garden_core::models::StorageUsage>("StorageUsage");
+    export::<garden_core::models::MediaUsage>("MediaUsage");
+    export::<garden_core::models::LinkStatus>("LinkStatus");
+    export::<garden_core::models::MediaType>("MediaType");
+    export::<garden_core::models::SupportedType>("SupportedType");
 
     // Utility types
     export::<garden_core::models::FieldUpdate<String>>("FieldUpdate");
     export::<garden_core::models::Page<()>>("Page");
+    export::<garden_core::models::ApiVersion>("ApiVersion");
+
+    // IPC types
+    export::<garden_tauri::ErrorCode>("ErrorCode");
+    export::<garden_tauri::TauriError>("TauriError");
+    export::<garden_tauri::ConnectionChangeKind>("ConnectionChangeKind");
+    export::<garden_tauri::ConnectionChanged>("ConnectionChanged");
+    export::<garden_tauri::commands::media::MediaImportResult>("MediaImportResult");
 
     println!("\n✅ TypeScript types exported to {}/", OUTPUT_DIR);
 }
@@ -1,17 +1,39 @@
 //! Block-related Tauri commands.
 //!
-//! This module provides 5 commands for block CRUD operations:
+//! This module provides 14 commands for block operations:
 //! - `block_create` - Create a new block
 //! - `block_create_batch` - Create multiple blocks at once
+//! - `block_create_and_connect` - Create blocks and add them to a channel
 //! - `block_get` - Get a block by ID
 //! - `block_update` - Update a block
+//! - `block_convert` - Change a block's content type (e.g. text to link)
+//! - `block_set_note` - Set or clear a block's notes
+//! - `block_set_source_url` - Set or clear a block's source URL
 //! - `block_delete` - Delete a block
+//! - `block_find_duplicates` - Find groups of blocks with the same content
+//! - `block_merge_duplicates` - Fold duplicate blocks into one
+//! - `block_search` - Full-text search with optional highlighted snippets
+//! - `block_list_with_channels` - Page through all blocks with the channels each is in
+//! - `block_find_by_duration` - Page through video and audio blocks by length
+//! - `block_activity_heatmap` - Count blocks created per day
+//! - `block_get_tags` - Get a block's tags
+//! - `blocks_add_tag` - Add a tag to several blocks at once
+//! - `blocks_remove_tag` - Remove a tag from several blocks at once
+//!
+//! `block_create_and_connect` also emits a `connection_changed` event per
+//! block, and `block_merge_duplicates` one per channel the kept block joins
+//! (see [`ConnectionChanged`]).
 
-use garden_core::models::{Block, BlockId, BlockUpdate, NewBlock};
-use tauri::State;
+use chrono::NaiveDate;
+use garden_core::models::{
+    Block, BlockContext, BlockId, BlockUpdate, Channel, ChannelId, Connection, ContentKind,
+    MetadataStats, NewBlock, Page, SearchHit, SearchMode,
+};
+use tauri::{AppHandle, State};
 use tracing::instrument;
 
 use crate::error::{CommandResult, TauriError};
+use crate::events::{ConnectionChangeKind, ConnectionChanged};
 use crate::state::AppState;
 
 /// Create a new block.
@@ -19,6 +41,8 @@ use crate::state::AppState;
 /// # Arguments
 ///
 /// * `new_block` - The block content (text or link)
+/// * `idempotency_key` - Optional client-chosen key; retrying with the same
+///   key returns the block from the first call instead of a duplicate
 ///
 /// # Returns
 ///
@@ -27,13 +51,18 @@ use crate::state::AppState;
 /// # Errors
 ///
 /// - `VALIDATION_ERROR` if the content is invalid (empty text, invalid URL, etc.)
+///   or the idempotency key is empty or too long
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
-#[instrument(skip(state, new_block))]
-pub async fn block_create(state: State<'_, AppState>, new_block: NewBlock) -> CommandResult<Block> {
+#[instrument(skip(state, new_block, idempotency_key))]
+pub async fn block_create(
+    state: State<'_, AppState>,
+    new_block: NewBlock,
+    idempotency_key: Option<String>,
+) -> CommandResult<Block> {
     state
         .service()
-        .create_block(new_block)
+        .create_block_idempotent(new_block, idempotency_key)
         .await
         .map_err(TauriError::from)
 }
@@ -68,6 +97,49 @@ pub async fn block_create_batch(
         .map_err(TauriError::from)
 }
 
+/// Create blocks and connect them to a channel in one step.
+///
+/// The common import flow: saves creating blocks and then shuttling their
+/// IDs into `connection_connect_batch`.
+///
+/// # Arguments
+///
+/// * `new_blocks` - The blocks to create, in channel order
+/// * `channel_id` - The channel to connect them to
+/// * `start` - Optional position of the first block; defaults to the end of
+///   the channel
+///
+/// # Returns
+///
+/// Each created block paired with its connection, in order.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
+/// - `VALIDATION_ERROR` if any block is invalid or `start` is negative
+///   (nothing is created)
+/// - `DATABASE_ERROR` for storage failures (created blocks are removed)
+#[tauri::command]
+#[instrument(skip(app, state, new_blocks), fields(count = new_blocks.len(), channel_id = %channel_id.0))]
+pub async fn block_create_and_connect(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    new_blocks: Vec<NewBlock>,
+    channel_id: ChannelId,
+    start: Option<i32>,
+) -> CommandResult<Vec<(Block, Connection)>> {
+    let created = state
+        .service()
+        .create_and_connect_blocks(new_blocks, &channel_id, start)
+        .await
+        .map_err(TauriError::from)?;
+
+    for (block, _) in &created {
+        ConnectionChanged::new(&channel_id, &block.id, ConnectionChangeKind::Connected).emit(&app);
+    }
+    Ok(created)
+}
+
 /// Get a block by ID.
 ///
 /// # Arguments
@@ -106,7 +178,7 @@ pub async fn block_get(state: State<'_, AppState>, id: BlockId) -> CommandResult
 /// # Errors
 ///
 /// - `BLOCK_NOT_FOUND` if no block exists with this ID
-/// - `VALIDATION_ERROR` if the new content is invalid
+/// - `VALIDATION_ERROR` if the new content or source URL is invalid
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
 #[instrument(skip(state, update), fields(block_id = %id.0))]
@@ -122,6 +194,171 @@ pub async fn block_update(
         .map_err(TauriError::from)
 }
 
+/// Apply the same metadata changes to several blocks at once.
+///
+/// Only the archive metadata fields are applied; setting `content` is
+/// rejected, so blocks with different content can't be overwritten with
+/// the same body.
+///
+/// # Arguments
+///
+/// * `block_ids` - The blocks to update
+/// * `update` - The metadata changes to apply to each block
+///
+/// # Returns
+///
+/// The number of blocks updated.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if any block does not exist (nothing is updated)
+/// - `VALIDATION_ERROR` if the update sets content or an invalid source URL
+/// - `DATABASE_ERROR` for storage failures (all changes are rolled back)
+#[tauri::command]
+#[instrument(skip(state, block_ids, update), fields(count = block_ids.len()))]
+pub async fn blocks_update_metadata(
+    state: State<'_, AppState>,
+    block_ids: Vec<BlockId>,
+    update: BlockUpdate,
+) -> CommandResult<usize> {
+    state
+        .service()
+        .update_blocks_metadata(&block_ids, update)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Archive or unarchive a block.
+///
+/// Archived blocks stay connected to their channels but are hidden from
+/// search and channel views unless `includeArchived` is passed. Unlike
+/// deletion, this is fully reversible.
+///
+/// # Arguments
+///
+/// * `id` - The block ID
+/// * `archived` - Whether the block should be archived
+///
+/// # Returns
+///
+/// The updated block.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if no block exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(block_id = %id.0))]
+pub async fn block_set_archived(
+    state: State<'_, AppState>,
+    id: BlockId,
+    archived: bool,
+) -> CommandResult<Block> {
+    state
+        .service()
+        .set_block_archived(&id, archived)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Change a block's content type.
+///
+/// Text converts to a link when it is a single http(s) URL; a link converts
+/// to text made of its title and URL. Archive metadata is kept.
+///
+/// # Arguments
+///
+/// * `id` - The block ID to convert
+/// * `target` - The content type to convert to
+///
+/// # Returns
+///
+/// The converted block.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if no block exists with this ID
+/// - `VALIDATION_ERROR` if the block cannot be converted to `target`
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(block_id = %id.0))]
+pub async fn block_convert(
+    state: State<'_, AppState>,
+    id: BlockId,
+    target: ContentKind,
+) -> CommandResult<Block> {
+    state
+        .service()
+        .convert_block(&id, target)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Set or clear a block's notes.
+///
+/// A shorthand for `block_update` that saves the frontend from building a
+/// `FieldUpdate` for a single field.
+///
+/// # Arguments
+///
+/// * `id` - The block ID to update
+/// * `note` - The new notes, or `None` to clear them
+///
+/// # Returns
+///
+/// The updated block.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if no block exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, note), fields(block_id = %id.0))]
+pub async fn block_set_note(
+    state: State<'_, AppState>,
+    id: BlockId,
+    note: Option<String>,
+) -> CommandResult<Block> {
+    state
+        .service()
+        .update_block(&id, BlockUpdate::set_notes(note))
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Set or clear a block's source URL.
+///
+/// A shorthand for `block_update` that saves the frontend from building a
+/// `FieldUpdate` for a single field.
+///
+/// # Arguments
+///
+/// * `id` - The block ID to update
+/// * `source_url` - The new source URL, or `None` to clear it
+///
+/// # Returns
+///
+/// The updated block.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if no block exists with this ID
+/// - `VALIDATION_ERROR` if the URL is not a valid http or https URL
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(block_id = %id.0))]
+pub async fn block_set_source_url(
+    state: State<'_, AppState>,
+    id: BlockId,
+    source_url: Option<String>,
+) -> CommandResult<Block> {
+    state
+        .service()
+        .update_block(&id, BlockUpdate::set_source_url(source_url))
+        .await
+        .map_err(TauriError::from)
+}
+
 /// Delete a block.
 ///
 /// This also removes all connections between this block and any channels.
@@ -144,6 +381,374 @@ pub async fn block_delete(state: State<'_, AppState>, id: BlockId) -> CommandRes
         .map_err(TauriError::from)
 }
 
+/// Find groups of blocks with the same content, for a "Find duplicates"
+/// tool.
+///
+/// # Returns
+///
+/// Groups of two or more block IDs, each in ID order. Media blocks match
+/// when their stored files have the same hash, even under different paths.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn block_find_duplicates(state: State<'_, AppState>) -> CommandResult<Vec<Vec<BlockId>>> {
+    state
+        .service()
+        .find_duplicate_blocks()
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Merge duplicate blocks into one.
+///
+/// The kept block takes over the merged blocks' place in every channel it
+/// isn't already in, then the merged blocks are deleted. Nothing changes
+/// if any step fails.
+///
+/// # Arguments
+///
+/// * `keep` - The block to keep
+/// * `merge` - The blocks to fold into it and delete
+///
+/// # Returns
+///
+/// The channels the kept block was added to. A `connection_changed` event
+/// is emitted for each.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if any block doesn't exist
+/// - `INVALID_INPUT` if `keep` is also listed in `merge`
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(app, state, merge), fields(keep = %keep.0, merged = merge.len()))]
+pub async fn block_merge_duplicates(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    keep: BlockId,
+    merge: Vec<BlockId>,
+) -> CommandResult<Vec<ChannelId>> {
+    let joined = state
+        .service()
+        .merge_duplicate_blocks(&keep, &merge)
+        .await
+        .map_err(TauriError::from)?;
+
+    for channel_id in &joined {
+        ConnectionChanged::new(channel_id, &keep, ConnectionChangeKind::Connected).emit(&app);
+    }
+    Ok(joined)
+}
+
+/// Search blocks by text.
+///
+/// # Arguments
+///
+/// * `query` - Search terms; every term must match
+/// * `mode` - `plain` (default) or `snippet` with `open`/`close` delimiters
+///   wrapped around each matched term
+/// * `limit` - Maximum number of hits to return (default: 20, max: 100)
+/// * `offset` - Number of hits to skip (default: 0)
+/// * `include_archived` - Whether to include archived blocks (default: false)
+///
+/// # Returns
+///
+/// A page of search hits ordered by relevance.
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if the query is empty
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, mode))]
+pub async fn block_search(
+    state: State<'_, AppState>,
+    query: String,
+    mode: Option<SearchMode>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_archived: Option<bool>,
+) -> CommandResult<Page<SearchHit>> {
+    let mode = mode.unwrap_or_default();
+    let limit = limit.unwrap_or(20).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .search_blocks(
+            &query,
+            &mode,
+            limit,
+            offset,
+            include_archived.unwrap_or(false),
+        )
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Count blocks missing each archive metadata field.
+///
+/// Drives the "finish archiving" nudge. A field counts as missing when it
+/// is unset or empty.
+///
+/// # Returns
+///
+/// The number of blocks, and how many lack a source URL, creator, original
+/// date, or notes.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+///
+/// # Example
+///
+/// ```typescript
+/// const stats = await invoke<MetadataStats>('block_metadata_stats');
+/// if (stats.missing_creator > 0) nudge(`${stats.missing_creator} blocks have no creator`);
+/// ```
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn block_metadata_stats(state: State<'_, AppState>) -> CommandResult<MetadataStats> {
+    state
+        .service()
+        .metadata_stats()
+        .await
+        .map_err(TauriError::from)
+}
+
+/// List all blocks with the channels each one is in.
+///
+/// For a library view across the whole garden; channels are fetched for
+/// the whole page at once rather than per block.
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of blocks to return (default: 20, max: 100)
+/// * `offset` - Number of blocks to skip (default: 0)
+/// * `include_archived` - Whether to include archived blocks (default: false)
+///
+/// # Returns
+///
+/// A page of `[block, channels]` pairs, newest block first, with each
+/// block's channels ordered by title.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn block_list_with_channels(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_archived: Option<bool>,
+) -> CommandResult<Page<(Block, Vec<Channel>)>> {
+    let limit = limit.unwrap_or(20).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .list_blocks_with_channels(limit, offset, include_archived.unwrap_or(false))
+        .await
+        .map_err(TauriError::from)
+}
+
+/// List video and audio blocks by length.
+///
+/// # Arguments
+///
+/// * `min` - Shortest duration to include, in seconds (default: no minimum)
+/// * `max` - Longest duration to include, in seconds (default: no maximum)
+/// * `limit` - Maximum number of blocks to return (default: 20, max: 100)
+/// * `offset` - Number of blocks to skip (default: 0)
+///
+/// # Returns
+///
+/// A page of blocks, newest first. Media of unknown length and archived
+/// blocks are left out.
+///
+/// # Errors
+///
+/// - `INVALID_INPUT` if a bound is negative or `min` exceeds `max`
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn block_find_by_duration(
+    state: State<'_, AppState>,
+    min: Option<f32>,
+    max: Option<f32>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> CommandResult<Page<Block>> {
+    let limit = limit.unwrap_or(20).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .blocks_by_duration(min, max, limit, offset)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Count blocks created per day, for an activity heatmap.
+///
+/// # Arguments
+///
+/// * `start` - First day to count, as `YYYY-MM-DD`
+/// * `end` - Last day to count, as `YYYY-MM-DD`
+///
+/// # Returns
+///
+/// `[day, count]` pairs in date order. Days are UTC, and days with no new
+/// blocks are left out, so fill the gaps with zero.
+///
+/// # Errors
+///
+/// - `INVALID_INPUT` if `start` is after `end`
+/// - `DATABASE_ERROR` for storage failures
+///
+/// # Example
+///
+/// ```typescript
+/// const days = await invoke<[string, number][]>('block_activity_heatmap', {
+///   start: '2026-01-01',
+///   end: '2026-12-31',
+/// });
+/// ```
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn block_activity_heatmap(
+    state: State<'_, AppState>,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> CommandResult<Vec<(NaiveDate, usize)>> {
+    state
+        .service()
+        .activity_heatmap(start, end)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get several blocks, each with the channels it is in.
+///
+/// # Arguments
+///
+/// * `ids` - The block IDs
+///
+/// # Returns
+///
+/// One context per block, in the order requested with repeats dropped.
+/// Each block's channels are ordered by title.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if any ID does not match a block
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, ids), fields(count = ids.len()))]
+pub async fn block_get_contexts(
+    state: State<'_, AppState>,
+    ids: Vec<BlockId>,
+) -> CommandResult<Vec<BlockContext>> {
+    state
+        .service()
+        .get_block_contexts(&ids)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get a block's tags.
+///
+/// # Arguments
+///
+/// * `id` - The block ID
+///
+/// # Returns
+///
+/// The block's tags, sorted alphabetically.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if no block exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(block_id = %id.0))]
+pub async fn block_get_tags(state: State<'_, AppState>, id: BlockId) -> CommandResult<Vec<String>> {
+    state
+        .service()
+        .get_block_tags(&id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Add a tag to several blocks at once.
+///
+/// The tag is trimmed and lowercased. Blocks that already have the tag are
+/// skipped.
+///
+/// # Arguments
+///
+/// * `block_ids` - The blocks to tag
+/// * `tag` - The tag to add
+///
+/// # Returns
+///
+/// The number of blocks that gained the tag.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if any block does not exist (nothing is tagged)
+/// - `VALIDATION_ERROR` if the tag is empty or too long
+/// - `DATABASE_ERROR` for storage failures (all changes are rolled back)
+#[tauri::command]
+#[instrument(skip(state, block_ids), fields(count = block_ids.len()))]
+pub async fn blocks_add_tag(
+    state: State<'_, AppState>,
+    block_ids: Vec<BlockId>,
+    tag: String,
+) -> CommandResult<usize> {
+    state
+        .service()
+        .add_tag_to_blocks(&block_ids, &tag)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Remove a tag from several blocks at once.
+///
+/// Blocks without the tag are skipped.
+///
+/// # Arguments
+///
+/// * `block_ids` - The blocks to untag
+/// * `tag` - The tag to remove
+///
+/// # Returns
+///
+/// The number of blocks that lost the tag.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if any block does not exist (nothing is untagged)
+/// - `VALIDATION_ERROR` if the tag is empty or too long
+/// - `DATABASE_ERROR` for storage failures (all changes are rolled back)
+#[tauri::command]
+#[instrument(skip(state, block_ids), fields(count = block_ids.len()))]
+pub async fn blocks_remove_tag(
+    state: State<'_, AppState>,
+    block_ids: Vec<BlockId>,
+    tag: String,
+) -> CommandResult<usize> {
+    state
+        .service()
+        .remove_tag_from_blocks(&block_ids, &tag)
+        .await
+        .map_err(TauriError::from)
+}
+
 #[cfg(test)]
 mod tests {
     // Integration tests require Tauri test harness
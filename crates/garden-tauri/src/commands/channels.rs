@@ -1,14 +1,35 @@
 //! Channel-related Tauri commands.
 //!
-//! This module provides 6 commands for channel CRUD operations:
+//! This module provides 18 commands for channel operations:
 //! - `channel_create` - Create a new channel
 //! - `channel_get` - Get a channel by ID
 //! - `channel_list` - List channels with pagination
 //! - `channel_update` - Update a channel
 //! - `channel_delete` - Delete a channel
 //! - `channel_count` - Get total channel count
+//! - `channel_touch` - Record that a channel was opened
+//! - `channel_list_recent` - List recently opened channels
+//! - `channel_list_recently_updated` - List recently edited channels
+//! - `channel_get_preview` - Get a channel with its first few blocks
+//! - `channel_block_sharing` - Count blocks exclusive to or shared by a channel
+//! - `channel_composition` - Count a channel's blocks by content type
+//! - `channel_export` - Export a channel with its blocks and connections
+//! - `channel_export_markdown` - Render a channel as Markdown
+//! - `channel_import` - Import a channel export
+//! - `channel_import_urls` - Import a file of URLs as link blocks
+//! - `channel_sort` - Sort a channel's blocks by date or title
+//! - `channel_snapshot_order` - Record a channel's block order
+//! - `channel_restore_order` - Put a channel back in a recorded order
+//! - `channel_repair_positions` - Renumber blocks with bad positions
+//! - `channel_position_anomalies` - Find channels with bad block positions
 
-use garden_core::models::{Channel, ChannelId, ChannelUpdate, NewChannel, Page};
+use std::collections::HashMap;
+
+use garden_core::models::{
+    Block, BlockSortKey, Channel, ChannelExport, ChannelId, ChannelPreview, ChannelUpdate,
+    ImportStrategy, NewBlock, NewChannel, OrderSnapshot, Page, PositionIssue, SharingStats,
+    UrlImportReport,
+};
 use tauri::State;
 use tracing::instrument;
 
@@ -20,6 +41,8 @@ use crate::state::AppState;
 /// # Arguments
 ///
 /// * `new_channel` - The channel data (title, optional description)
+/// * `idempotency_key` - Optional client-chosen key; retrying with the same
+///   key returns the channel from the first call instead of a duplicate
 ///
 /// # Returns
 ///
@@ -27,18 +50,55 @@ use crate::state::AppState;
 ///
 /// # Errors
 ///
-/// - `VALIDATION_ERROR` if the title is empty or too long
-/// - `DUPLICATE_ERROR` if a channel with the same ID exists (unlikely with UUIDs)
+/// - `VALIDATION_ERROR` if the title is empty or too long, or the idempotency
+///   key is empty or too long
+/// - `DUPLICATE_TITLE` if unique titles are enforced and the title is taken
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
-#[instrument(skip(state), fields(title = %new_channel.title))]
+#[instrument(skip(state, idempotency_key), fields(title = %new_channel.title))]
 pub async fn channel_create(
     state: State<'_, AppState>,
     new_channel: NewChannel,
+    idempotency_key: Option<String>,
 ) -> CommandResult<Channel> {
     state
         .service()
-        .create_channel(new_channel)
+        .create_channel_idempotent(new_channel, idempotency_key)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Create a channel with an initial set of blocks, for instantiating a
+/// template.
+///
+/// Everything is validated before anything is written, and a failure
+/// part-way removes what was already written, so the channel either comes
+/// with all its blocks or isn't created at all.
+///
+/// # Arguments
+///
+/// * `new_channel` - The channel data (title, optional description)
+/// * `new_blocks` - The blocks to create, in channel order
+///
+/// # Returns
+///
+/// The created channel and its blocks, in order.
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if the channel or any block is invalid
+/// - `DUPLICATE_TITLE` if unique titles are enforced and the title is taken
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, new_blocks), fields(title = %new_channel.title, count = new_blocks.len()))]
+pub async fn channel_create_with_blocks(
+    state: State<'_, AppState>,
+    new_channel: NewChannel,
+    new_blocks: Vec<NewBlock>,
+) -> CommandResult<(Channel, Vec<Block>)> {
+    state
+        .service()
+        .create_channel_with_blocks(new_channel, new_blocks)
         .await
         .map_err(TauriError::from)
 }
@@ -114,6 +174,7 @@ pub async fn channel_list(
 ///
 /// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
 /// - `VALIDATION_ERROR` if the new title is empty or too long
+/// - `DUPLICATE_TITLE` if unique titles are enforced and the title is taken
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
 #[instrument(skip(state, update), fields(channel_id = %id.0))]
@@ -171,6 +232,432 @@ pub async fn channel_count(state: State<'_, AppState>) -> CommandResult<usize> {
         .map_err(TauriError::from)
 }
 
+/// Record that a channel was opened.
+///
+/// Call this when the user opens a channel. Only the access time changes;
+/// the channel's `updated_at` is left alone.
+///
+/// # Arguments
+///
+/// * `id` - The channel ID
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %id.0))]
+pub async fn channel_touch(state: State<'_, AppState>, id: ChannelId) -> CommandResult<()> {
+    state
+        .service()
+        .mark_channel_accessed(&id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// List recently opened channels.
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of channels to return (default: 10, max: 100)
+///
+/// # Returns
+///
+/// Channels that have been opened, most recently accessed first.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn channel_list_recent(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<Channel>> {
+    let limit = limit.unwrap_or(10).min(100);
+
+    state
+        .service()
+        .list_recently_accessed(limit)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// List recently edited channels.
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of channels to return (default: 10, max: 100)
+///
+/// # Returns
+///
+/// Channels ordered by `updated_at`, most recently edited first.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn channel_list_recently_updated(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<Channel>> {
+    let limit = limit.unwrap_or(10).min(100);
+
+    state
+        .service()
+        .recently_updated_channels(limit)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get a channel with its first few blocks, for rendering a channel card.
+///
+/// # Arguments
+///
+/// * `id` - The channel ID
+/// * `block_limit` - Maximum number of preview blocks (default: 4, max: 50)
+///
+/// # Returns
+///
+/// The channel, its total block count, and its first blocks in position
+/// order.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %id.0))]
+pub async fn channel_get_preview(
+    state: State<'_, AppState>,
+    id: ChannelId,
+    block_limit: Option<usize>,
+) -> CommandResult<ChannelPreview> {
+    let block_limit = block_limit.unwrap_or(4).min(50);
+
+    state
+        .service()
+        .get_channel_preview(&id, block_limit)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Count how many of a channel's blocks appear only in it and how many are
+/// shared with other channels.
+///
+/// # Arguments
+///
+/// * `id` - The channel ID
+///
+/// # Returns
+///
+/// The total, exclusive, and shared block counts.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %id.0))]
+pub async fn channel_block_sharing(
+    state: State<'_, AppState>,
+    id: ChannelId,
+) -> CommandResult<SharingStats> {
+    state
+        .service()
+        .channel_block_sharing(&id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Count a channel's blocks by content type.
+///
+/// # Arguments
+///
+/// * `id` - The channel ID
+///
+/// # Returns
+///
+/// A map from content type (`image`, `video`, `link`, ...) to the number of
+/// blocks of that type. Types the channel has none of are absent.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %id.0))]
+pub async fn channel_composition(
+    state: State<'_, AppState>,
+    id: ChannelId,
+) -> CommandResult<HashMap<String, usize>> {
+    state
+        .service()
+        .channel_composition(&id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Export a single channel for sharing or backup.
+///
+/// # Arguments
+///
+/// * `id` - The channel ID
+///
+/// # Returns
+///
+/// The channel with its blocks in position order and the matching
+/// connections.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %id.0))]
+pub async fn channel_export(
+    state: State<'_, AppState>,
+    id: ChannelId,
+) -> CommandResult<ChannelExport> {
+    state
+        .service()
+        .export_channel(&id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Render a channel as Markdown for publishing.
+///
+/// # Arguments
+///
+/// * `id` - The channel ID
+///
+/// # Returns
+///
+/// A Markdown document with the channel title as a heading and one list
+/// item per block, in position order. Media is referenced under `media/`.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %id.0))]
+pub async fn channel_export_markdown(
+    state: State<'_, AppState>,
+    id: ChannelId,
+) -> CommandResult<String> {
+    state
+        .service()
+        .export_channel_markdown(&id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Import a channel export, recreating its blocks and their order.
+///
+/// # Arguments
+///
+/// * `export` - The channel export to import
+/// * `strategy` - `remap_ids` (default) to give everything fresh IDs, or
+///   `preserve_ids` to keep the IDs from the export
+///
+/// # Returns
+///
+/// The imported channel.
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if the export was written by a newer version, a block
+///   is invalid, or the channel already exists with `preserve_ids`
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, export), fields(blocks = export.blocks.len()))]
+pub async fn channel_import(
+    state: State<'_, AppState>,
+    export: ChannelExport,
+    strategy: Option<ImportStrategy>,
+) -> CommandResult<Channel> {
+    state
+        .service()
+        .import_channel(export, strategy.unwrap_or_default())
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Import a text file of URLs, one per line, as link blocks in a channel.
+///
+/// Blank lines and `#` comments are ignored. Invalid URLs are skipped and
+/// reported rather than failing the import.
+///
+/// # Arguments
+///
+/// * `path` - Path to the URL list
+/// * `channel_id` - The channel to append the links to
+/// * `enrich` - Fetch each page's title and description (default true)
+///
+/// # Returns
+///
+/// A per-line report of what was imported and what was skipped.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `MEDIA_ERROR` if the file cannot be read
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0))]
+pub async fn channel_import_urls(
+    state: State<'_, AppState>,
+    path: String,
+    channel_id: ChannelId,
+    enrich: Option<bool>,
+) -> CommandResult<UrlImportReport> {
+    let media = state.media_service();
+    let media = enrich.unwrap_or(true).then_some(media.as_ref());
+    state
+        .service()
+        .import_urls_from_file(&path, &channel_id, media)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Sort a channel's blocks, rewriting their positions.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel to sort
+/// * `key` - `created_at`, `title`, or `connected_at`
+/// * `ascending` - Sort oldest or A-Z first (default true)
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0))]
+pub async fn channel_sort(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    key: BlockSortKey,
+    ascending: Option<bool>,
+) -> CommandResult<()> {
+    state
+        .service()
+        .sort_channel(&channel_id, key, ascending.unwrap_or(true))
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Record a channel's current block order, e.g. before sorting it.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel to snapshot
+///
+/// # Returns
+///
+/// The snapshot, to pass back to `channel_restore_order` for "Undo sort".
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0))]
+pub async fn channel_snapshot_order(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+) -> CommandResult<OrderSnapshot> {
+    state
+        .service()
+        .snapshot_channel_order(&channel_id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Put a channel's blocks back in a snapshot's order, renumbering their
+/// positions.
+///
+/// Blocks disconnected since the snapshot are skipped; blocks connected
+/// since follow the rest.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel to restore
+/// * `snapshot` - A snapshot from `channel_snapshot_order`
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `INVALID_INPUT` if the snapshot is of a different channel
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, snapshot), fields(channel_id = %channel_id.0))]
+pub async fn channel_restore_order(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    snapshot: OrderSnapshot,
+) -> CommandResult<()> {
+    state
+        .service()
+        .restore_channel_order(&channel_id, &snapshot)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Renumber a channel's blocks from zero if any position is negative,
+/// shared, or leaves a gap.
+///
+/// Ties are broken by when each block was connected. Runs automatically at
+/// startup for every channel with shared positions.
+///
+/// # Returns
+///
+/// `true` if positions were renumbered, `false` if they were already fine.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0))]
+pub async fn channel_repair_positions(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+) -> CommandResult<bool> {
+    state
+        .service()
+        .repair_channel_positions(&channel_id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Find channels whose block positions are negative, shared, or have gaps.
+///
+/// Each reported channel can be fixed with `channel_repair_positions`.
+///
+/// # Returns
+///
+/// `[channelId, issue]` pairs, one per channel and kind of issue, ordered
+/// by channel ID.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn channel_position_anomalies(
+    state: State<'_, AppState>,
+) -> CommandResult<Vec<(ChannelId, PositionIssue)>> {
+    state
+        .service()
+        .position_anomalies()
+        .await
+        .map_err(TauriError::from)
+}
+
 #[cfg(test)]
 mod tests {
     // Integration tests require Tauri test harness
@@ -1,20 +1,38 @@
 //! Connection-related Tauri commands.
 //!
-//! This module provides 8 commands for managing block-channel connections:
+//! This module provides 16 commands for managing block-channel connections:
 //! - `connection_connect` - Connect a block to a channel
 //! - `connection_connect_batch` - Connect multiple blocks to a channel
 //! - `connection_disconnect` - Disconnect a block from a channel
 //! - `connection_get` - Get a specific connection
 //! - `connection_get_blocks_in_channel` - Get all blocks in a channel
 //! - `connection_get_blocks_with_positions` - Get blocks with their positions
+//! - `connection_get_recent` - Get the blocks most recently added to a channel
+//! - `connection_get_blocks_in_channels` - Page through blocks in any or all of several channels
+//! - `connection_get_available_blocks` - Page through blocks not yet in a channel
+//! - `connection_get_popular_blocks` - Get the blocks in the most channels
 //! - `connection_get_channels_for_block` - Get all channels containing a block
+//! - `connection_get_channels_for_block_paged` - Page through channels containing a block
+//! - `connection_get_channels_with_content_type` - Page through channels holding a content type
+//! - `connection_get_neighbors` - Get the blocks before and after a block
+//! - `connection_get_position_info` - Get a block's index and the channel's block count
 //! - `connection_reorder` - Change a block's position within a channel
+//! - `connection_move_relative` - Move a block before or after another block
+//! - `connection_move_group` - Move several blocks together as a contiguous run
+//!
+//! Successful connect, disconnect, reorder, and move commands also emit a
+//! `connection_changed` event (see [`ConnectionChanged`]) so other windows
+//! can refresh.
 
-use garden_core::models::{Block, BlockId, Channel, ChannelId, Connection};
-use tauri::State;
+use garden_core::models::{
+    Block, BlockId, BlockNeighbors, BlockPositionInfo, Channel, ChannelId, ChannelOrder,
+    ChannelSetMode, Connection, ContentKind, Page, Placement,
+};
+use tauri::{AppHandle, State};
 use tracing::instrument;
 
 use crate::error::{CommandResult, TauriError};
+use crate::events::{ConnectionChangeKind, ConnectionChanged};
 use crate::state::AppState;
 
 /// Connect a block to a channel.
@@ -34,20 +52,62 @@ use crate::state::AppState;
 /// - `BLOCK_NOT_FOUND` if the block doesn't exist
 /// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
 /// - `VALIDATION_ERROR` if the block is already connected to this channel
+///   or the position is negative
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
-#[instrument(skip(state), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
+#[instrument(skip(app, state), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
 pub async fn connection_connect(
+    app: AppHandle,
     state: State<'_, AppState>,
     block_id: BlockId,
     channel_id: ChannelId,
     position: Option<i32>,
 ) -> CommandResult<Connection> {
-    state
+    let connection = state
         .service()
         .connect_block(&block_id, &channel_id, position)
         .await
-        .map_err(TauriError::from)
+        .map_err(TauriError::from)?;
+
+    ConnectionChanged::new(&channel_id, &block_id, ConnectionChangeKind::Connected).emit(&app);
+    Ok(connection)
+}
+
+/// Connect a block to the top of a channel.
+///
+/// Every block already in the channel moves down one position.
+///
+/// # Arguments
+///
+/// * `block_id` - The block to connect
+/// * `channel_id` - The channel to connect to
+///
+/// # Returns
+///
+/// The created connection, at position 0.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if the block doesn't exist
+/// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
+/// - `VALIDATION_ERROR` if the block is already connected to this channel
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(app, state), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
+pub async fn connection_prepend(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    block_id: BlockId,
+    channel_id: ChannelId,
+) -> CommandResult<Connection> {
+    let connection = state
+        .service()
+        .prepend_block(&block_id, &channel_id)
+        .await
+        .map_err(TauriError::from)?;
+
+    ConnectionChanged::new(&channel_id, &block_id, ConnectionChangeKind::Connected).emit(&app);
+    Ok(connection)
 }
 
 /// Connect multiple blocks to a channel at once.
@@ -69,21 +129,28 @@ pub async fn connection_connect(
 ///
 /// - `BLOCK_NOT_FOUND` if any block doesn't exist
 /// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
-/// - `VALIDATION_ERROR` if any block is already connected
+/// - `VALIDATION_ERROR` if any block is already connected or the starting
+///   position is negative
 /// - `DATABASE_ERROR` for storage failures (entire batch is rolled back)
 #[tauri::command]
-#[instrument(skip(state), fields(count = block_ids.len(), channel_id = %channel_id.0))]
+#[instrument(skip(app, state), fields(count = block_ids.len(), channel_id = %channel_id.0))]
 pub async fn connection_connect_batch(
+    app: AppHandle,
     state: State<'_, AppState>,
     block_ids: Vec<BlockId>,
     channel_id: ChannelId,
     starting_position: Option<i32>,
 ) -> CommandResult<Vec<Connection>> {
-    state
+    let connections = state
         .service()
         .connect_blocks(&block_ids, &channel_id, starting_position)
         .await
-        .map_err(TauriError::from)
+        .map_err(TauriError::from)?;
+
+    for block_id in &block_ids {
+        ConnectionChanged::new(&channel_id, block_id, ConnectionChangeKind::Connected).emit(&app);
+    }
+    Ok(connections)
 }
 
 /// Disconnect a block from a channel.
@@ -95,11 +162,14 @@ pub async fn connection_connect_batch(
 ///
 /// # Errors
 ///
-/// - `CONNECTION_NOT_FOUND` if the connection doesn't exist
+/// - `BLOCK_NOT_FOUND` if the block doesn't exist
+/// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
+/// - `CONNECTION_NOT_FOUND` if the block isn't in the channel
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
-#[instrument(skip(state), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
+#[instrument(skip(app, state), fields(block_id = %block_id.0, channel_id = %channel_id.0))]
 pub async fn connection_disconnect(
+    app: AppHandle,
     state: State<'_, AppState>,
     block_id: BlockId,
     channel_id: ChannelId,
@@ -108,7 +178,10 @@ pub async fn connection_disconnect(
         .service()
         .disconnect_block(&block_id, &channel_id)
         .await
-        .map_err(TauriError::from)
+        .map_err(TauriError::from)?;
+
+    ConnectionChanged::new(&channel_id, &block_id, ConnectionChangeKind::Disconnected).emit(&app);
+    Ok(())
 }
 
 /// Get a specific connection.
@@ -147,6 +220,7 @@ pub async fn connection_get(
 /// # Arguments
 ///
 /// * `channel_id` - The channel ID
+/// * `include_archived` - Whether to include archived blocks (default: false)
 ///
 /// # Returns
 ///
@@ -154,16 +228,18 @@ pub async fn connection_get(
 ///
 /// # Errors
 ///
+/// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
 #[instrument(skip(state), fields(channel_id = %channel_id.0))]
 pub async fn connection_get_blocks_in_channel(
     state: State<'_, AppState>,
     channel_id: ChannelId,
+    include_archived: Option<bool>,
 ) -> CommandResult<Vec<Block>> {
     state
         .service()
-        .get_blocks_in_channel(&channel_id)
+        .get_blocks_in_channel(&channel_id, include_archived.unwrap_or(false))
         .await
         .map_err(TauriError::from)
 }
@@ -175,6 +251,7 @@ pub async fn connection_get_blocks_in_channel(
 /// # Arguments
 ///
 /// * `channel_id` - The channel ID
+/// * `include_archived` - Whether to include archived blocks (default: false)
 ///
 /// # Returns
 ///
@@ -182,31 +259,190 @@ pub async fn connection_get_blocks_in_channel(
 ///
 /// # Errors
 ///
+/// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
 #[instrument(skip(state), fields(channel_id = %channel_id.0))]
 pub async fn connection_get_blocks_with_positions(
     state: State<'_, AppState>,
     channel_id: ChannelId,
+    include_archived: Option<bool>,
 ) -> CommandResult<Vec<(Block, i32)>> {
     state
         .service()
-        .get_blocks_in_channel_with_positions(&channel_id)
+        .get_blocks_in_channel_with_positions(&channel_id, include_archived.unwrap_or(false))
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get the blocks most recently connected to a channel.
+///
+/// Ordered by when each block was connected, not by position, for
+/// highlighting what was just added.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel ID
+/// * `limit` - Maximum number of blocks to return (default: 10, max: 100)
+/// * `include_archived` - Whether to include archived blocks (default: false)
+///
+/// # Returns
+///
+/// Tuples of (block, connection), most recently connected first.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0))]
+pub async fn connection_get_recent(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    limit: Option<usize>,
+    include_archived: Option<bool>,
+) -> CommandResult<Vec<(Block, Connection)>> {
+    let limit = limit.unwrap_or(10).min(100);
+
+    state
+        .service()
+        .recent_in_channel(&channel_id, limit, include_archived.unwrap_or(false))
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get a page of the blocks in any or all of several channels.
+///
+/// # Arguments
+///
+/// * `channel_ids` - The channels to combine
+/// * `mode` - `union` for blocks in any channel, `intersection` for blocks
+///   in every channel
+/// * `limit` - Maximum number of blocks to return (default: 20, max: 100)
+/// * `offset` - Number of blocks to skip (default: 0)
+/// * `include_archived` - Whether to include archived blocks (default: false)
+///
+/// # Returns
+///
+/// A page of matching blocks, newest first, with the total count.
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if no channels are given
+/// - `CHANNEL_NOT_FOUND` if any channel doesn't exist
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, channel_ids), fields(channels = channel_ids.len()))]
+pub async fn connection_get_blocks_in_channels(
+    state: State<'_, AppState>,
+    channel_ids: Vec<ChannelId>,
+    mode: ChannelSetMode,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_archived: Option<bool>,
+) -> CommandResult<Page<Block>> {
+    let limit = limit.unwrap_or(20).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .blocks_in_channels(
+            &channel_ids,
+            mode,
+            limit,
+            offset,
+            include_archived.unwrap_or(false),
+        )
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get a page of the blocks not yet in a channel, for "add from library"
+/// pickers. Archived blocks are left out unless `include_archived` is set.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel blocks would be added to
+/// * `limit` - Maximum number of blocks to return (default: 20, max: 100)
+/// * `offset` - Number of blocks to skip (default: 0)
+/// * `include_archived` - Whether to include archived blocks (default: false)
+///
+/// # Returns
+///
+/// A page of blocks, newest first, with the total count.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0))]
+pub async fn connection_get_available_blocks(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    include_archived: Option<bool>,
+) -> CommandResult<Page<Block>> {
+    let limit = limit.unwrap_or(20).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .available_blocks_for_channel(
+            &channel_id,
+            limit,
+            offset,
+            include_archived.unwrap_or(false),
+        )
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get the blocks that appear in the most channels.
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of blocks to return (default: 20, max: 100)
+/// * `include_archived` - Whether to include archived blocks (default: false)
+///
+/// # Returns
+///
+/// Tuples of (block, channel count), most connected first.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn connection_get_popular_blocks(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+    include_archived: Option<bool>,
+) -> CommandResult<Vec<(Block, usize)>> {
+    let limit = limit.unwrap_or(20).min(100);
+
+    state
+        .service()
+        .popular_blocks(limit, include_archived.unwrap_or(false))
         .await
         .map_err(TauriError::from)
 }
 
 /// Get all channels that contain a block.
 ///
-/// Useful for showing where a block appears across the system.
+/// Useful for showing where a block appears across the system. The result
+/// is unbounded; use `connection_get_channels_for_block_paged` for blocks
+/// that may be in many channels.
 ///
 /// # Arguments
 ///
 /// * `block_id` - The block ID
+/// * `order` - `by_connected_at_desc` (default) or `by_title_asc`
 ///
 /// # Returns
 ///
-/// The channels containing this block.
+/// The channels containing this block, in the requested order.
 ///
 /// # Errors
 ///
@@ -216,10 +452,141 @@ pub async fn connection_get_blocks_with_positions(
 pub async fn connection_get_channels_for_block(
     state: State<'_, AppState>,
     block_id: BlockId,
+    order: Option<ChannelOrder>,
 ) -> CommandResult<Vec<Channel>> {
     state
         .service()
-        .get_channels_for_block(&block_id)
+        .get_channels_for_block(&block_id, order.unwrap_or_default())
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get a page of the channels that contain a block.
+///
+/// # Arguments
+///
+/// * `block_id` - The block ID
+/// * `limit` - Maximum number of channels to return (default: 20, max: 100)
+/// * `offset` - Number of channels to skip (default: 0)
+///
+/// # Returns
+///
+/// A page of channels, most recently connected first, with the total count.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(block_id = %block_id.0))]
+pub async fn connection_get_channels_for_block_paged(
+    state: State<'_, AppState>,
+    block_id: BlockId,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> CommandResult<Page<Channel>> {
+    let limit = limit.unwrap_or(20).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .get_channels_for_block_paged(&block_id, limit, offset)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get a page of the channels holding at least one block of a content type.
+///
+/// # Arguments
+///
+/// * `kind` - The content type, e.g. `video`
+/// * `limit` - Maximum number of channels to return (default: 20, max: 100)
+/// * `offset` - Number of channels to skip (default: 0)
+///
+/// # Returns
+///
+/// A page of channels, newest first, with the total count.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn connection_get_channels_with_content_type(
+    state: State<'_, AppState>,
+    kind: ContentKind,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> CommandResult<Page<Channel>> {
+    let limit = limit.unwrap_or(20).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .channels_with_content_type(kind, limit, offset)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get the blocks before and after a block within a channel.
+///
+/// Used for previous/next navigation in the block detail view.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel being browsed
+/// * `block_id` - The block currently shown
+///
+/// # Returns
+///
+/// The previous and next blocks by position; either is `null` at the ends
+/// of the channel.
+///
+/// # Errors
+///
+/// - `CONNECTION_NOT_FOUND` if the block is not in the channel
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0, block_id = %block_id.0))]
+pub async fn connection_get_neighbors(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    block_id: BlockId,
+) -> CommandResult<BlockNeighbors> {
+    state
+        .service()
+        .block_neighbors(&channel_id, &block_id)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Get a block's index within a channel and the channel's block count.
+///
+/// Used for the "3 of 42" indicator in the block detail view.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel being browsed
+/// * `block_id` - The block currently shown
+///
+/// # Returns
+///
+/// The block's zero-based `index` in position order and the channel's
+/// `total` block count.
+///
+/// # Errors
+///
+/// - `CONNECTION_NOT_FOUND` if the block is not in the channel
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0, block_id = %block_id.0))]
+pub async fn connection_get_position_info(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    block_id: BlockId,
+) -> CommandResult<BlockPositionInfo> {
+    state
+        .service()
+        .block_position_info(&channel_id, &block_id)
         .await
         .map_err(TauriError::from)
 }
@@ -230,15 +597,19 @@ pub async fn connection_get_channels_for_block(
 ///
 /// * `channel_id` - The channel ID
 /// * `block_id` - The block ID
-/// * `new_position` - The new position
+/// * `new_position` - The new position (must be non-negative)
 ///
 /// # Errors
 ///
-/// - `CONNECTION_NOT_FOUND` if the connection doesn't exist
+/// - `VALIDATION_ERROR` if the new position is negative
+/// - `BLOCK_NOT_FOUND` if the block doesn't exist
+/// - `CHANNEL_NOT_FOUND` if the channel doesn't exist
+/// - `CONNECTION_NOT_FOUND` if the block isn't in the channel
 /// - `DATABASE_ERROR` for storage failures
 #[tauri::command]
-#[instrument(skip(state), fields(channel_id = %channel_id.0, block_id = %block_id.0))]
+#[instrument(skip(app, state), fields(channel_id = %channel_id.0, block_id = %block_id.0))]
 pub async fn connection_reorder(
+    app: AppHandle,
     state: State<'_, AppState>,
     channel_id: ChannelId,
     block_id: BlockId,
@@ -248,7 +619,122 @@ pub async fn connection_reorder(
         .service()
         .reorder_block(&channel_id, &block_id, new_position)
         .await
-        .map_err(TauriError::from)
+        .map_err(TauriError::from)?;
+
+    ConnectionChanged::new(&channel_id, &block_id, ConnectionChangeKind::Reordered).emit(&app);
+    Ok(())
+}
+
+/// Swap the positions of two blocks in a channel.
+///
+/// Both positions change in one transaction, so the blocks never share a
+/// position mid-swap as they could with two `connection_reorder` calls.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel ID
+/// * `block_a` - One block to swap
+/// * `block_b` - The other block to swap
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if `block_a` and `block_b` are the same block
+/// - `CONNECTION_NOT_FOUND` if either block is not in the channel
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(app, state), fields(channel_id = %channel_id.0, block_a = %block_a.0, block_b = %block_b.0))]
+pub async fn connection_swap(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    block_a: BlockId,
+    block_b: BlockId,
+) -> CommandResult<()> {
+    state
+        .service()
+        .swap_blocks(&channel_id, &block_a, &block_b)
+        .await
+        .map_err(TauriError::from)?;
+
+    for block_id in [&block_a, &block_b] {
+        ConnectionChanged::new(&channel_id, block_id, ConnectionChangeKind::Reordered).emit(&app);
+    }
+    Ok(())
+}
+
+/// Move a block directly before or after another block in a channel.
+///
+/// The channel's positions are renumbered so the block lands next to the
+/// anchor without colliding with it.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel ID
+/// * `block_id` - The block to move
+/// * `anchor` - The block to move next to
+/// * `placement` - `before` or `after` the anchor
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if `block_id` and `anchor` are the same block
+/// - `CONNECTION_NOT_FOUND` if either block is not in the channel
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(app, state), fields(channel_id = %channel_id.0, block_id = %block_id.0))]
+pub async fn connection_move_relative(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    block_id: BlockId,
+    anchor: BlockId,
+    placement: Placement,
+) -> CommandResult<()> {
+    state
+        .service()
+        .move_block_relative(&channel_id, &block_id, &anchor, placement)
+        .await
+        .map_err(TauriError::from)?;
+
+    ConnectionChanged::new(&channel_id, &block_id, ConnectionChangeKind::Reordered).emit(&app);
+    Ok(())
+}
+
+/// Move several blocks in a channel together as one contiguous run.
+///
+/// The blocks keep their current relative order and land at
+/// `target_index` among the channel's other blocks; the channel's
+/// positions are renumbered in one transaction.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel ID
+/// * `block_ids` - The blocks to move
+/// * `target_index` - Where the run starts, counted among the other blocks
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if `block_ids` is empty
+/// - `CONNECTION_NOT_FOUND` if any block is not in the channel
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(app, state), fields(channel_id = %channel_id.0, count = block_ids.len()))]
+pub async fn connection_move_group(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+    block_ids: Vec<BlockId>,
+    target_index: usize,
+) -> CommandResult<()> {
+    state
+        .service()
+        .move_block_group(&channel_id, &block_ids, target_index)
+        .await
+        .map_err(TauriError::from)?;
+
+    for block_id in &block_ids {
+        ConnectionChanged::new(&channel_id, block_id, ConnectionChangeKind::Reordered).emit(&app);
+    }
+    Ok(())
 }
 
 #[cfg(test)]
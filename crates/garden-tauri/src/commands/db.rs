@@ -0,0 +1,34 @@
+//! Database maintenance Tauri commands.
+//!
+//! This module provides 1 command for the maintenance screen:
+//! - `db_integrity_check` - Check the database for corruption and bad data
+
+use garden_core::models::IntegrityReport;
+use tauri::State;
+use tracing::instrument;
+
+use crate::error::{CommandResult, TauriError};
+use crate::state::AppState;
+
+/// Check the database for corruption and inconsistent data.
+///
+/// Read-only: problems are reported, not repaired.
+///
+/// # Returns
+///
+/// A report listing SQLite integrity errors, dangling foreign keys, duplicate
+/// connections, and blocks sharing a position within a channel. Every list
+/// is empty for a healthy database.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` if the checks could not run
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn db_integrity_check(state: State<'_, AppState>) -> CommandResult<IntegrityReport> {
+    state
+        .database()
+        .integrity_report()
+        .await
+        .map_err(TauriError::from)
+}
@@ -0,0 +1,102 @@
+//! Whole-garden Tauri commands.
+//!
+//! This module provides 3 commands for operations spanning the whole garden:
+//! - `garden_import_plan` - Preview what importing an export would do
+//! - `api_version` - Report the backend's schema and crate versions
+//! - `activity_feed` - List recent changes, newest first
+
+use garden_core::models::{ActivityEntry, ApiVersion, GardenExport, ImportPlan, Page};
+use tauri::State;
+use tracing::instrument;
+
+use crate::error::{CommandResult, TauriError};
+use crate::state::AppState;
+
+/// Preview importing a garden export without writing anything.
+///
+/// Runs the same validation and collision detection as a real import and
+/// reports the outcome, so the user can review it before committing.
+///
+/// # Arguments
+///
+/// * `archive` - The garden export to check
+///
+/// # Returns
+///
+/// An import plan with counts of what would be created, ID collisions with
+/// existing data, and any blocks that fail validation.
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if the export was written by a newer version
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state, archive), fields(blocks = archive.blocks.len()))]
+pub async fn garden_import_plan(
+    state: State<'_, AppState>,
+    archive: GardenExport,
+) -> CommandResult<ImportPlan> {
+    state
+        .service()
+        .plan_import(&archive)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Report the backend's schema and crate versions.
+///
+/// The frontend compares `schema_version` against the version its generated
+/// types were built from to detect a mismatched backend.
+///
+/// # Returns
+///
+/// The current [`GARDEN_SCHEMA_VERSION`](garden_core::GARDEN_SCHEMA_VERSION)
+/// and `garden-core` crate version.
+#[tauri::command]
+#[instrument]
+pub fn api_version() -> ApiVersion {
+    ApiVersion::current()
+}
+
+/// List recent changes to channels, blocks, and connections.
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of entries to return (default: 50, max: 100)
+/// * `offset` - Number of entries to skip (default: 0)
+///
+/// # Returns
+///
+/// A page of activity entries, newest first.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn activity_feed(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> CommandResult<Page<ActivityEntry>> {
+    let limit = limit.unwrap_or(50).min(100);
+    let offset = offset.unwrap_or(0);
+
+    state
+        .service()
+        .activity_feed(limit, offset)
+        .await
+        .map_err(TauriError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_version_reports_current_schema_version() {
+        let version = api_version();
+        assert_eq!(version.schema_version, garden_core::GARDEN_SCHEMA_VERSION);
+        assert_eq!(version.crate_version, env!("CARGO_PKG_VERSION"));
+    }
+}
@@ -2,17 +2,21 @@
 //!
 //! This module provides Tauri commands for importing and managing media files
 //! (images, videos, audio). Media files are stored in the app's data directory
-//! and served via Tauri's asset protocol.
+//! (or a user-chosen root, see `media_set_root`) and served via Tauri's asset
+//! protocol.
 
 use std::path::PathBuf;
 
-use garden_core::services::{MediaError, MediaInfo};
+use garden_core::models::{Block, BlockId, ChannelId, LinkStatus, StorageUsage, SupportedType};
+use garden_core::services::{MediaError, MediaInfo, MediaService};
 use serde::{Deserialize, Serialize};
-use tauri::State;
-use tracing::{info, instrument};
+use tauri::{AppHandle, State};
+use tracing::{error, info, instrument};
 use ts_rs::TS;
 
-use crate::error::{CommandResult, TauriError};
+use crate::error::{CommandResult, ErrorCode, TauriError};
+use crate::init::{allow_media_scope, resolve_app_data_dir};
+use crate::settings::Settings;
 use crate::state::AppState;
 
 /// Response from media import operations.
@@ -20,7 +24,7 @@ use crate::state::AppState;
 /// Contains the stored file path and metadata that can be used to create
 /// a block with the appropriate content type.
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
-#[ts(export, export_to = "../../../packages/types/src/generated/")]
+#[ts(export)]
 pub struct MediaImportResult {
     /// Relative path within media directory (e.g., "images/{uuid}.jpg").
     pub file_path: String,
@@ -30,21 +34,31 @@ pub struct MediaImportResult {
     pub width: Option<u32>,
     /// Image/video height in pixels (None for audio).
     pub height: Option<u32>,
+    /// Width divided by height, when both are known.
+    pub aspect_ratio: Option<f32>,
     /// Duration in seconds (for video/audio, None for images).
     pub duration: Option<f32>,
     /// Original URL if imported from web.
     pub original_url: Option<String>,
+    /// SHA-256 of the stored file, hex-encoded.
+    pub content_hash: Option<String>,
+    /// Why metadata extraction failed, if it did. When set, `width` and
+    /// `height` are unknown rather than not applicable.
+    pub metadata_error: Option<String>,
 }
 
 impl From<MediaInfo> for MediaImportResult {
     fn from(info: MediaInfo) -> Self {
         Self {
             file_path: info.file_path,
-            mime_type: info.mime_type,
+            mime_type: info.mime_type.into(),
             width: info.width,
             height: info.height,
+            aspect_ratio: info.aspect_ratio,
             duration: info.duration,
             original_url: info.original_url,
+            content_hash: info.content_hash,
+            metadata_error: info.metadata_error,
         }
     }
 }
@@ -77,6 +91,8 @@ impl From<MediaError> for TauriError {
 /// # Arguments
 ///
 /// * `url` - The URL to download from (must be a valid HTTP/HTTPS URL)
+/// * `import_id` - Optional ID chosen by the caller, for cancelling the
+///   import with `media_cancel_import`
 ///
 /// # Returns
 ///
@@ -85,6 +101,8 @@ impl From<MediaError> for TauriError {
 /// # Errors
 ///
 /// Returns an error if:
+/// - An import with the same `import_id` is already running
+/// - The import is cancelled (`MEDIA_ERROR`, "Download failed: cancelled")
 /// - The URL cannot be reached or returns an error status
 /// - The content type is not a supported media type (image/video/audio)
 /// - The file cannot be written to disk
@@ -103,10 +121,28 @@ impl From<MediaError> for TauriError {
 pub async fn media_import_from_url(
     state: State<'_, AppState>,
     url: String,
+    import_id: Option<String>,
 ) -> CommandResult<MediaImportResult> {
     info!("Importing media from URL");
 
-    let media_info = state.media_service().import_from_url(&url).await?;
+    // Tracked until the guard drops at the end of the command
+    let import = import_id
+        .map(|id| {
+            state.track_import(id.clone()).ok_or_else(|| {
+                TauriError::with_entity(
+                    ErrorCode::ValidationError,
+                    format!("An import with ID {} is already running", id),
+                    id,
+                )
+            })
+        })
+        .transpose()?;
+    let cancel = import
+        .as_ref()
+        .map(|guard| guard.token().clone())
+        .unwrap_or_default();
+
+    let media_info = state.media_service().import_from_url(&url, &cancel).await?;
 
     info!(
         file_path = %media_info.file_path,
@@ -117,6 +153,32 @@ pub async fn media_import_from_url(
     Ok(media_info.into())
 }
 
+/// Cancel a running URL import.
+///
+/// The download stops, its partial file is removed, and the import's
+/// `media_import_from_url` call fails with a "cancelled" media error.
+///
+/// # Arguments
+///
+/// * `import_id` - The ID passed to `media_import_from_url`
+///
+/// # Returns
+///
+/// `true` if the import was running and is now cancelled, `false` if no
+/// import with this ID is running (e.g. it already finished).
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn media_cancel_import(
+    state: State<'_, AppState>,
+    import_id: String,
+) -> CommandResult<bool> {
+    let cancelled = state.cancel_import(&import_id);
+    if cancelled {
+        info!("Media import cancelled");
+    }
+    Ok(cancelled)
+}
+
 /// Import media from a local file.
 ///
 /// Copies the file to the media directory, detects its type, and extracts metadata.
@@ -165,10 +227,11 @@ pub async fn media_import_from_file(
     Ok(media_info.into())
 }
 
-/// Delete a media file.
+/// Delete a media file if no block references it.
 ///
-/// Removes the file from the media directory. This should be called when
-/// deleting a block that contains media content.
+/// Removes the file from the media directory. This should be called after
+/// deleting a block that contains media content. Files still used by another
+/// block (e.g. shared with a gallery) are kept.
 ///
 /// # Arguments
 ///
@@ -176,7 +239,8 @@ pub async fn media_import_from_file(
 ///
 /// # Returns
 ///
-/// `()` on success. Does not error if the file doesn't exist.
+/// `true` if the file was deleted, `false` if it is still referenced.
+/// Does not error if the file doesn't exist.
 ///
 /// # Example
 ///
@@ -185,13 +249,207 @@ pub async fn media_import_from_file(
 /// ```
 #[tauri::command]
 #[instrument(skip(state), fields(file_path = %file_path))]
-pub async fn media_delete(state: State<'_, AppState>, file_path: String) -> CommandResult<()> {
-    info!("Deleting media file");
+pub async fn media_delete(state: State<'_, AppState>, file_path: String) -> CommandResult<bool> {
+    let media = state.media_service();
+    state
+        .service()
+        .delete_unreferenced_media(&file_path, &media)
+        .await
+        .map_err(TauriError::from)
+}
 
-    state.media_service().delete(&file_path).await?;
+/// Count the blocks that reference a media file.
+///
+/// Includes gallery blocks that contain the file as one of their items.
+///
+/// # Arguments
+///
+/// * `file_path` - Relative path within media directory (e.g., "images/a1b2c3d4.jpg")
+///
+/// # Returns
+///
+/// The number of blocks using the file; `0` means it is safe to delete.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(file_path = %file_path))]
+pub async fn media_reference_count(
+    state: State<'_, AppState>,
+    file_path: String,
+) -> CommandResult<usize> {
+    state
+        .service()
+        .media_reference_count(&file_path)
+        .await
+        .map_err(TauriError::from)
+}
 
-    info!("Media file deleted");
-    Ok(())
+/// Check a block's media files against the hashes stored at import.
+///
+/// # Arguments
+///
+/// * `block_id` - The block whose media to check
+///
+/// # Returns
+///
+/// `true` if every hashed file is unchanged, `false` if any file was
+/// modified or is missing.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if no block exists with this ID
+/// - `VALIDATION_ERROR` if the block has no media or no stored hashes
+/// - `MEDIA_ERROR` if a file cannot be read
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(block_id = %block_id.0))]
+pub async fn media_verify(state: State<'_, AppState>, block_id: BlockId) -> CommandResult<bool> {
+    let media = state.media_service();
+    state
+        .service()
+        .verify_media(&block_id, &media)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Convert an image block's file to another format, e.g. PNG to WebP.
+///
+/// The block is pointed at the new file, and the old file is removed once
+/// no other block uses it.
+///
+/// # Arguments
+///
+/// * `block_id` - The image block to convert
+/// * `target_mime` - The format to convert to, e.g. `image/webp`
+///
+/// # Returns
+///
+/// The updated block, with its new path, MIME type, dimensions, and hash.
+///
+/// # Errors
+///
+/// - `BLOCK_NOT_FOUND` if no block exists with this ID
+/// - `VALIDATION_ERROR` if the block is not an image block
+/// - `MEDIA_ERROR` if the image can't be converted to the format, or a file
+///   cannot be read or written
+/// - `DATABASE_ERROR` for storage failures
+#[tauri::command]
+#[instrument(skip(state), fields(block_id = %block_id.0))]
+pub async fn media_reencode_block(
+    state: State<'_, AppState>,
+    block_id: BlockId,
+    target_mime: String,
+) -> CommandResult<Block> {
+    let media = state.media_service();
+    state
+        .service()
+        .reencode_block_media(&block_id, &media, &target_mime)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Find media files referenced by a channel's blocks that are missing on disk.
+///
+/// Useful before exporting or sharing a channel, to warn that some images
+/// won't come along.
+///
+/// # Arguments
+///
+/// * `channel_id` - The channel to check
+///
+/// # Returns
+///
+/// Each missing file as `[blockId, filePath]`, in channel order. Empty if
+/// everything is present.
+///
+/// # Errors
+///
+/// - `CHANNEL_NOT_FOUND` if no channel exists with this ID
+/// - `DATABASE_ERROR` for storage failures
+///
+/// # Example
+///
+/// ```typescript
+/// const missing = await invoke<[string, string][]>('media_validate_channel', {
+///   channelId: 'abc123'
+/// });
+/// if (missing.length > 0) warn(`${missing.length} media files are missing`);
+/// ```
+#[tauri::command]
+#[instrument(skip(state), fields(channel_id = %channel_id.0))]
+pub async fn media_validate_channel(
+    state: State<'_, AppState>,
+    channel_id: ChannelId,
+) -> CommandResult<Vec<(BlockId, String)>> {
+    let media = state.media_service();
+    state
+        .service()
+        .validate_channel_media(&channel_id, &media)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// Check whether link blocks' URLs still resolve, for a link rot report.
+///
+/// Requests go out one at a time under the media rate limit, so a large
+/// report takes a while.
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of link blocks to check (default: 100, max: 1000)
+///
+/// # Returns
+///
+/// Each checked block as `[blockId, status]`, in block ID order.
+///
+/// # Errors
+///
+/// - `DATABASE_ERROR` for storage failures
+///
+/// # Example
+///
+/// ```typescript
+/// const report = await invoke<[string, LinkStatus][]>('media_check_links', {
+///   limit: 50
+/// });
+/// const rotten = report.filter(([, s]) => s.status === 'not_found');
+/// ```
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn media_check_links(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<(BlockId, LinkStatus)>> {
+    let limit = limit.unwrap_or(100).min(1000);
+    let media = state.media_service();
+    state
+        .service()
+        .check_links(&media, limit)
+        .await
+        .map_err(TauriError::from)
+}
+
+/// List the media formats Garden recognizes.
+///
+/// Use this to build the file picker's accept filter instead of keeping a
+/// separate list in the frontend.
+///
+/// # Returns
+///
+/// Each format's MIME type, stored file extension, and media type.
+///
+/// # Example
+///
+/// ```typescript
+/// const types = await invoke<SupportedType[]>('media_supported_types');
+/// const accept = types.map((t) => t.mime).join(',');
+/// ```
+#[tauri::command]
+#[instrument]
+pub fn media_supported_types() -> Vec<SupportedType> {
+    MediaService::supported_types()
 }
 
 /// Check if a media file exists.
@@ -249,6 +507,111 @@ pub async fn media_get_full_path(
     Ok(full_path.to_string_lossy().to_string())
 }
 
+/// Measure the disk space used by stored media.
+///
+/// Only files under the image, video, and audio directories are counted.
+///
+/// # Returns
+///
+/// Total bytes and file count, with a breakdown per media type.
+///
+/// # Example
+///
+/// ```typescript
+/// const usage = await invoke<StorageUsage>('media_storage_usage');
+/// // usage.total_bytes = 1288490188, usage.file_count = 430
+/// ```
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn media_storage_usage(state: State<'_, AppState>) -> CommandResult<StorageUsage> {
+    Ok(state.media_service().storage_usage().await?)
+}
+
+/// Move media storage to a different directory.
+///
+/// The new root is prepared (subdirectories created, writability checked),
+/// saved to `settings.json` so it survives restarts, and used for all
+/// subsequent media operations.
+///
+/// With `migrate` (the default), existing files are copied to the new root
+/// and removed from the old one once every copy succeeds. Stored blocks keep
+/// their relative paths, so they resolve against the new root unchanged.
+/// The setting is saved before any file moves and restored if the move
+/// fails, so the saved root always holds the files.
+///
+/// With `migrate: false`, the root is only repointed. This is instant and
+/// suits a root that already holds the files (e.g. a drive synced from
+/// another machine), but media not present in the new root will appear
+/// missing until it is copied there.
+///
+/// # Arguments
+///
+/// * `path` - Absolute path to the new media root
+/// * `migrate` - Whether to move existing files (default: true)
+///
+/// # Returns
+///
+/// The number of files migrated (0 when only repointing).
+///
+/// # Errors
+///
+/// - `VALIDATION_ERROR` if the path is not absolute
+/// - `MEDIA_ERROR` if the new root is not writable, overlaps the current
+///   root, or a file cannot be copied
+/// - `INTERNAL_ERROR` if the settings file cannot be written
+///
+/// # Example
+///
+/// ```typescript
+/// const moved = await invoke<number>('media_set_root', {
+///   path: '/Volumes/External/garden-media'
+/// });
+/// ```
+#[tauri::command]
+#[instrument(skip(app, state), fields(path = %path))]
+pub async fn media_set_root(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    migrate: Option<bool>,
+) -> CommandResult<usize> {
+    let new_root = PathBuf::from(&path);
+    if !new_root.is_absolute() {
+        return Err(TauriError::new(
+            ErrorCode::ValidationError,
+            "Media root must be an absolute path",
+        ));
+    }
+
+    MediaService::prepare_root(&new_root)?;
+
+    let app_data_dir = resolve_app_data_dir(&app)?;
+    let mut settings = Settings::load(&app_data_dir)?;
+    let previous_root = settings.media_root.replace(new_root.clone());
+    settings.save(&app_data_dir)?;
+
+    let moved = if migrate.unwrap_or(true) {
+        match state.media_service().migrate_to(&new_root).await {
+            Ok(moved) => moved,
+            Err(e) => {
+                settings.media_root = previous_root;
+                if let Err(save_error) = settings.save(&app_data_dir) {
+                    error!(error = %save_error, "Failed to restore the media root setting");
+                }
+                return Err(e.into());
+            }
+        }
+    } else {
+        0
+    };
+
+    allow_media_scope(&app, &new_root);
+    state.set_media_root(new_root);
+
+    info!(moved, "Media root changed");
+    Ok(moved)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,11 +620,14 @@ mod tests {
     fn media_import_result_from_media_info() {
         let info = MediaInfo {
             file_path: "images/test.jpg".to_string(),
-            mime_type: "image/jpeg".to_string(),
+            mime_type: "image/jpeg".into(),
             width: Some(800),
             height: Some(600),
+            aspect_ratio: Some(800.0 / 600.0),
             duration: None,
             original_url: Some("https://example.com/test.jpg".to_string()),
+            content_hash: None,
+            metadata_error: None,
         };
 
         let result: MediaImportResult = info.into();
@@ -1,11 +1,14 @@
 //! Tauri command handlers.
 //!
-//! This module organizes all IPC commands into four categories:
+//! This module organizes all IPC commands into six categories:
 //!
 //! - **Channels**: CRUD operations for channels (collections)
 //! - **Blocks**: CRUD operations for blocks (content)
 //! - **Connections**: Managing block-channel relationships
 //! - **Media**: Importing and managing media files
+//! - **Garden**: Operations spanning the whole garden (import, versioning,
+//!   activity feed)
+//! - **Database**: Maintenance checks on the underlying database
 //!
 //! All commands follow the naming convention `{domain}_{action}` and are
 //! instrumented with tracing spans for observability.
@@ -13,12 +16,16 @@
 pub mod blocks;
 pub mod channels;
 pub mod connections;
+pub mod db;
+pub mod garden;
 pub mod media;
 
 // Re-export all commands for easy registration
 pub use blocks::*;
 pub use channels::*;
 pub use connections::*;
+pub use db::*;
+pub use garden::*;
 pub use media::*;
 
 /// Generate the Tauri invoke handler with all commands.
@@ -39,34 +46,94 @@ pub use media::*;
 macro_rules! generate_handler {
     () => {
         tauri::generate_handler![
-            // Channel commands (6)
+            // Channel commands (22)
             $crate::commands::channel_create,
+            $crate::commands::channel_create_with_blocks,
             $crate::commands::channel_get,
             $crate::commands::channel_list,
             $crate::commands::channel_update,
             $crate::commands::channel_delete,
             $crate::commands::channel_count,
-            // Block commands (5)
+            $crate::commands::channel_touch,
+            $crate::commands::channel_list_recent,
+            $crate::commands::channel_list_recently_updated,
+            $crate::commands::channel_get_preview,
+            $crate::commands::channel_block_sharing,
+            $crate::commands::channel_composition,
+            $crate::commands::channel_export,
+            $crate::commands::channel_export_markdown,
+            $crate::commands::channel_import,
+            $crate::commands::channel_import_urls,
+            $crate::commands::channel_sort,
+            $crate::commands::channel_snapshot_order,
+            $crate::commands::channel_restore_order,
+            $crate::commands::channel_repair_positions,
+            $crate::commands::channel_position_anomalies,
+            // Block commands (22)
             $crate::commands::block_create,
             $crate::commands::block_create_batch,
+            $crate::commands::block_create_and_connect,
             $crate::commands::block_get,
             $crate::commands::block_update,
+            $crate::commands::blocks_update_metadata,
+            $crate::commands::block_set_archived,
+            $crate::commands::block_convert,
+            $crate::commands::block_set_note,
+            $crate::commands::block_set_source_url,
             $crate::commands::block_delete,
-            // Connection commands (8)
+            $crate::commands::block_find_duplicates,
+            $crate::commands::block_merge_duplicates,
+            $crate::commands::block_search,
+            $crate::commands::block_metadata_stats,
+            $crate::commands::block_list_with_channels,
+            $crate::commands::block_find_by_duration,
+            $crate::commands::block_activity_heatmap,
+            $crate::commands::block_get_contexts,
+            $crate::commands::block_get_tags,
+            $crate::commands::blocks_add_tag,
+            $crate::commands::blocks_remove_tag,
+            // Connection commands (20)
             $crate::commands::connection_connect,
+            $crate::commands::connection_prepend,
             $crate::commands::connection_connect_batch,
             $crate::commands::connection_disconnect,
             $crate::commands::connection_get,
             $crate::commands::connection_get_blocks_in_channel,
             $crate::commands::connection_get_blocks_with_positions,
+            $crate::commands::connection_get_recent,
+            $crate::commands::connection_get_blocks_in_channels,
+            $crate::commands::connection_get_available_blocks,
+            $crate::commands::connection_get_popular_blocks,
             $crate::commands::connection_get_channels_for_block,
+            $crate::commands::connection_get_channels_for_block_paged,
+            $crate::commands::connection_get_channels_with_content_type,
+            $crate::commands::connection_get_neighbors,
+            $crate::commands::connection_get_position_info,
             $crate::commands::connection_reorder,
-            // Media commands (5)
+            $crate::commands::connection_swap,
+            $crate::commands::connection_move_relative,
+            $crate::commands::connection_move_group,
+            // Media commands (14)
             $crate::commands::media_import_from_url,
+            $crate::commands::media_cancel_import,
             $crate::commands::media_import_from_file,
             $crate::commands::media_delete,
+            $crate::commands::media_reference_count,
+            $crate::commands::media_verify,
+            $crate::commands::media_reencode_block,
+            $crate::commands::media_validate_channel,
+            $crate::commands::media_check_links,
+            $crate::commands::media_supported_types,
             $crate::commands::media_exists,
             $crate::commands::media_get_full_path,
+            $crate::commands::media_storage_usage,
+            $crate::commands::media_set_root,
+            // Garden commands (3)
+            $crate::commands::garden_import_plan,
+            $crate::commands::api_version,
+            $crate::commands::activity_feed,
+            // Database commands (1)
+            $crate::commands::db_integrity_check,
         ]
     };
 }
@@ -26,6 +26,8 @@ pub enum ErrorCode {
     ValidationError,
     /// A duplicate record was detected.
     DuplicateError,
+    /// Another channel already has this title (when titles must be unique).
+    DuplicateTitle,
     /// A database operation failed.
     DatabaseError,
     /// Application initialization failed.
@@ -117,7 +119,12 @@ impl From<DomainError> for TauriError {
                 ),
             ),
             DomainError::InvalidInput(msg) => Self::new(ErrorCode::ValidationError, msg),
+            DomainError::DuplicateTitle(title) => Self::new(
+                ErrorCode::DuplicateTitle,
+                format!("A channel titled '{}' already exists", title),
+            ),
             DomainError::Repository(repo_err) => repo_err.into(),
+            DomainError::Media(media_err) => media_err.into(),
         }
     }
 }
@@ -127,6 +134,13 @@ impl From<RepoError> for TauriError {
         match err {
             RepoError::NotFound => Self::new(ErrorCode::DatabaseError, "Record not found"),
             RepoError::Duplicate => Self::new(ErrorCode::DuplicateError, "Record already exists"),
+            RepoError::DuplicateTitle => Self::new(
+                ErrorCode::DuplicateTitle,
+                "A channel with this title already exists",
+            ),
+            err @ RepoError::ContentTooLarge { .. } => {
+                Self::new(ErrorCode::ValidationError, err.to_string())
+            }
             RepoError::Database(msg) => Self::new(ErrorCode::DatabaseError, msg),
             RepoError::Serialization(msg) => Self::new(
                 ErrorCode::InternalError,
@@ -198,6 +212,15 @@ mod tests {
         assert_eq!(tauri_err.code, ErrorCode::DuplicateError);
     }
 
+    #[test]
+    fn duplicate_title_has_own_code() {
+        let domain_err = DomainError::DuplicateTitle("Reading".to_string());
+        let tauri_err: TauriError = domain_err.into();
+
+        assert_eq!(tauri_err.code, ErrorCode::DuplicateTitle);
+        assert!(tauri_err.message.contains("Reading"));
+    }
+
     #[test]
     fn serializes_to_json() {
         let err =
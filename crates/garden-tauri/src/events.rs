@@ -0,0 +1,85 @@
+//! Events emitted to the frontend.
+//!
+//! Commands that change shared state emit events so that other windows can
+//! invalidate their caches. Events are fire-and-forget: a failure to emit is
+//! logged and never fails the command that triggered it.
+
+use garden_core::models::{BlockId, ChannelId};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+use tracing::warn;
+use ts_rs::TS;
+
+/// Event name for connection changes.
+pub const CONNECTION_CHANGED: &str = "connection_changed";
+
+/// What happened to a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionChangeKind {
+    /// A block was connected to a channel.
+    Connected,
+    /// A block was disconnected from a channel.
+    Disconnected,
+    /// A block moved within a channel.
+    Reordered,
+}
+
+/// Payload of the `connection_changed` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[ts(export)]
+pub struct ConnectionChanged {
+    /// The channel whose contents changed.
+    pub channel_id: ChannelId,
+    /// The block that was connected, disconnected, or moved.
+    pub block_id: BlockId,
+    /// What happened.
+    pub kind: ConnectionChangeKind,
+}
+
+impl ConnectionChanged {
+    /// Create a new connection change payload.
+    pub fn new(channel_id: &ChannelId, block_id: &BlockId, kind: ConnectionChangeKind) -> Self {
+        Self {
+            channel_id: channel_id.clone(),
+            block_id: block_id.clone(),
+            kind,
+        }
+    }
+
+    /// Emit this change to all windows.
+    pub fn emit<R: Runtime>(&self, app: &AppHandle<R>) {
+        if let Err(e) = app.emit(CONNECTION_CHANGED, self) {
+            warn!(error = %e, "Failed to emit {} event", CONNECTION_CHANGED);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connection_changed_payload_carries_ids() {
+        let channel_id = ChannelId("channel-1".to_string());
+        let block_id = BlockId("block-1".to_string());
+        let event = ConnectionChanged::new(&channel_id, &block_id, ConnectionChangeKind::Connected);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["channel_id"], "channel-1");
+        assert_eq!(json["block_id"], "block-1");
+        assert_eq!(json["kind"], "connected");
+    }
+
+    #[test]
+    fn connection_change_kinds_serialize_snake_case() {
+        let kinds = [
+            (ConnectionChangeKind::Disconnected, "disconnected"),
+            (ConnectionChangeKind::Reordered, "reordered"),
+        ];
+        for (kind, expected) in kinds {
+            assert_eq!(serde_json::to_value(kind).unwrap(), expected);
+        }
+    }
+}
@@ -3,16 +3,19 @@
 //! This module handles application setup including:
 //! - Resolving platform-specific data directories
 //! - Creating the database file and media directories
+//! - Applying user settings (e.g. a media root override)
 //! - Running migrations
 //! - Constructing the AppState
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use garden_core::services::MediaService;
 use garden_db::sqlite::SqliteDatabase;
 use tauri::{AppHandle, Manager};
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
 
 use crate::error::{CommandResult, TauriError};
+use crate::settings::Settings;
 use crate::state::AppState;
 
 /// Database filename.
@@ -21,9 +24,6 @@ const DATABASE_FILENAME: &str = "garden.db";
 /// Media directory name.
 const MEDIA_DIRNAME: &str = "media";
 
-/// Media subdirectories for different content types.
-const MEDIA_SUBDIRS: &[&str] = &["images", "videos", "audio"];
-
 /// Initialize the database and create the application state.
 ///
 /// This function:
@@ -31,7 +31,9 @@ const MEDIA_SUBDIRS: &[&str] = &["images", "videos", "audio"];
 /// 2. Creates the directory if it doesn't exist
 /// 3. Connects to (or creates) the SQLite database
 /// 4. Runs any pending migrations
-/// 5. Returns the initialized AppState
+/// 5. Prepares the media root (`{app_data}/media`, or the `media_root`
+///    override from `settings.json`)
+/// 6. Returns the initialized AppState
 ///
 /// # Platform-specific paths
 ///
@@ -46,6 +48,7 @@ const MEDIA_SUBDIRS: &[&str] = &["images", "videos", "audio"];
 /// - The directory cannot be created
 /// - The database connection fails
 /// - Migrations fail
+/// - The settings file is invalid or the media root is not writable
 ///
 /// # Example
 ///
@@ -77,9 +80,6 @@ pub async fn initialize_database(app: &AppHandle) -> CommandResult<AppState> {
             error!(error = %e, path = %parent.display(), "Failed to create app data directory");
             TauriError::initialization(format!("Failed to create app data directory: {}", e))
         })?;
-
-        // Create media directories
-        initialize_media_directories(parent)?;
     }
 
     // Connect to database
@@ -94,26 +94,38 @@ pub async fn initialize_database(app: &AppHandle) -> CommandResult<AppState> {
         TauriError::initialization(format!("Failed to run migrations: {}", e))
     })?;
 
-    // Get media directory path
-    let media_path = app.path().app_data_dir().map_err(|e| {
-        error!(error = %e, "Failed to resolve app data directory for media");
-        TauriError::initialization(format!("Failed to resolve app data directory: {}", e))
-    })?.join(MEDIA_DIRNAME);
+    // Resolve and prepare the media root
+    let media_path = resolve_media_path(app)?;
+    MediaService::prepare_root(&media_path).map_err(|e| {
+        error!(error = %e, path = %media_path.display(), "Media directory is not usable");
+        TauriError::initialization(format!("Media directory is not usable: {}", e))
+    })?;
+    allow_media_scope(app, &media_path);
+    info!(path = %media_path.display(), "Media directories initialized");
+
+    let state = AppState::new(database, media_path);
+
+    // Older data can have blocks sharing a position; fix it while we're here
+    match state.service().repair_all_positions().await {
+        Ok(repaired) if !repaired.is_empty() => {
+            warn!(
+                channels = repaired.len(),
+                "Repaired colliding block positions"
+            )
+        }
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "Failed to repair block positions"),
+    }
 
     info!("Database initialized successfully");
-    Ok(AppState::new(database, media_path))
+    Ok(state)
 }
 
 /// Resolve the full path to the database file.
 ///
 /// Uses Tauri's path resolver to get the platform-appropriate app data directory.
 fn resolve_database_path(app: &AppHandle) -> CommandResult<PathBuf> {
-    let app_data_dir = app.path().app_data_dir().map_err(|e| {
-        error!(error = %e, "Failed to resolve app data directory");
-        TauriError::initialization(format!("Failed to resolve app data directory: {}", e))
-    })?;
-
-    Ok(app_data_dir.join(DATABASE_FILENAME))
+    Ok(resolve_app_data_dir(app)?.join(DATABASE_FILENAME))
 }
 
 /// Get the database path for the current app (useful for debugging).
@@ -125,30 +137,37 @@ pub fn get_database_path(app: &AppHandle) -> Option<PathBuf> {
 
 /// Get the media directory path for the current app.
 ///
+/// Honours the `media_root` override in the settings file.
 /// Returns `None` if the path cannot be resolved.
 pub fn get_media_path(app: &AppHandle) -> Option<PathBuf> {
-    app.path()
-        .app_data_dir()
-        .ok()
-        .map(|dir| dir.join(MEDIA_DIRNAME))
+    resolve_media_path(app).ok()
 }
 
-/// Initialize the media directory structure.
+/// Resolve the app data directory.
+pub(crate) fn resolve_app_data_dir(app: &AppHandle) -> CommandResult<PathBuf> {
+    app.path().app_data_dir().map_err(|e| {
+        error!(error = %e, "Failed to resolve app data directory");
+        TauriError::initialization(format!("Failed to resolve app data directory: {}", e))
+    })
+}
+
+/// Resolve the media root: the settings override if set, else `{app_data}/media`.
+fn resolve_media_path(app: &AppHandle) -> CommandResult<PathBuf> {
+    let app_data_dir = resolve_app_data_dir(app)?;
+    let settings = Settings::load(&app_data_dir)?;
+    Ok(settings
+        .media_root
+        .unwrap_or_else(|| app_data_dir.join(MEDIA_DIRNAME)))
+}
+
+/// Allow the asset protocol to serve files from the media root.
 ///
-/// Creates the media directory and subdirectories for images, videos, and audio.
-fn initialize_media_directories(app_data_dir: &std::path::Path) -> CommandResult<()> {
-    let media_dir = app_data_dir.join(MEDIA_DIRNAME);
-
-    for subdir in MEDIA_SUBDIRS {
-        let path = media_dir.join(subdir);
-        std::fs::create_dir_all(&path).map_err(|e| {
-            error!(error = %e, path = %path.display(), "Failed to create media directory");
-            TauriError::initialization(format!("Failed to create media directory: {}", e))
-        })?;
+/// The static scope in `tauri.conf.json` only covers the default location,
+/// so an override root must be added at runtime.
+pub(crate) fn allow_media_scope(app: &AppHandle, media_root: &Path) {
+    if let Err(e) = app.asset_protocol_scope().allow_directory(media_root, true) {
+        warn!(error = %e, path = %media_root.display(), "Failed to allow media root in asset scope");
     }
-
-    info!(path = %media_dir.display(), "Media directories initialized");
-    Ok(())
 }
 
 #[cfg(test)]
@@ -54,39 +54,106 @@
 //!
 //! # Commands
 //!
-//! All 24 commands follow the `{domain}_{action}` naming convention:
+//! All 82 commands follow the `{domain}_{action}` naming convention:
 //!
-//! ## Channels (6)
+//! ## Channels (22)
 //! - `channel_create` - Create a new channel
+//! - `channel_create_with_blocks` - Create a channel with its initial blocks
 //! - `channel_get` - Get a channel by ID
 //! - `channel_list` - List channels with pagination
 //! - `channel_update` - Update a channel
 //! - `channel_delete` - Delete a channel
 //! - `channel_count` - Get total channel count
+//! - `channel_touch` - Record that a channel was opened
+//! - `channel_list_recent` - List recently opened channels
+//! - `channel_list_recently_updated` - List recently edited channels
+//! - `channel_get_preview` - Get a channel with its first few blocks
+//! - `channel_block_sharing` - Count blocks exclusive to or shared by a channel
+//! - `channel_composition` - Count a channel's blocks by content type
+//! - `channel_export` - Export a channel with its blocks and connections
+//! - `channel_export_markdown` - Render a channel as Markdown
+//! - `channel_import` - Import a channel export
+//! - `channel_import_urls` - Import a file of URLs as link blocks
+//! - `channel_sort` - Sort a channel's blocks by date or title
+//! - `channel_snapshot_order` - Record a channel's block order
+//! - `channel_restore_order` - Restore a recorded block order
+//! - `channel_repair_positions` - Renumber blocks with bad positions
+//! - `channel_position_anomalies` - Find channels with bad block positions
 //!
-//! ## Blocks (5)
+//! ## Blocks (22)
 //! - `block_create` - Create a new block
 //! - `block_create_batch` - Create multiple blocks
+//! - `block_create_and_connect` - Create blocks and add them to a channel
 //! - `block_get` - Get a block by ID
 //! - `block_update` - Update a block
+//! - `blocks_update_metadata` - Set the same metadata on several blocks
+//! - `block_set_archived` - Archive or unarchive a block
+//! - `block_convert` - Change a block's content type
+//! - `block_set_note` - Set or clear a block's notes
+//! - `block_set_source_url` - Set or clear a block's source URL
 //! - `block_delete` - Delete a block
+//! - `block_find_duplicates` - Find blocks with the same content
+//! - `block_merge_duplicates` - Merge duplicate blocks into one
+//! - `block_search` - Search blocks by text
+//! - `block_metadata_stats` - Count blocks missing each archive metadata field
+//! - `block_list_with_channels` - List blocks with their channels
+//! - `block_find_by_duration` - List video and audio blocks by length
+//! - `block_activity_heatmap` - Count blocks created per day
+//! - `block_get_contexts` - Get several blocks with their channels
+//! - `block_get_tags` - Get a block's tags
+//! - `blocks_add_tag` - Tag several blocks at once
+//! - `blocks_remove_tag` - Untag several blocks at once
 //!
-//! ## Connections (8)
+//! ## Connections (20)
 //! - `connection_connect` - Connect a block to a channel
+//! - `connection_prepend` - Connect a block to the top of a channel
 //! - `connection_connect_batch` - Connect multiple blocks
 //! - `connection_disconnect` - Disconnect a block
 //! - `connection_get` - Get a specific connection
 //! - `connection_get_blocks_in_channel` - Get blocks in a channel
 //! - `connection_get_blocks_with_positions` - Get blocks with positions
+//! - `connection_get_recent` - Get the blocks most recently added to a channel
+//! - `connection_get_blocks_in_channels` - Page through blocks in any or all of several channels
+//! - `connection_get_available_blocks` - Page through blocks not yet in a channel
+//! - `connection_get_popular_blocks` - Get the blocks in the most channels
 //! - `connection_get_channels_for_block` - Get channels for a block
+//! - `connection_get_channels_for_block_paged` - Page through channels for a block
+//! - `connection_get_channels_with_content_type` - Page through channels holding a content type
+//! - `connection_get_neighbors` - Get the blocks before and after a block
+//! - `connection_get_position_info` - Get a block's index and the channel's block count
 //! - `connection_reorder` - Reorder a block
+//! - `connection_swap` - Swap the positions of two blocks
+//! - `connection_move_relative` - Move a block before or after another block
+//! - `connection_move_group` - Move several blocks together as a contiguous run
 //!
-//! ## Media (5)
+//! ## Media (14)
 //! - `media_import_from_url` - Import media from a URL
+//! - `media_cancel_import` - Cancel a running URL import
 //! - `media_import_from_file` - Import media from a local file
-//! - `media_delete` - Delete a media file
+//! - `media_delete` - Delete a media file unless a block still uses it
+//! - `media_reference_count` - Count blocks that reference a media file
+//! - `media_verify` - Check a block's media against its stored hashes
+//! - `media_reencode_block` - Convert an image block's file to another format
+//! - `media_validate_channel` - List a channel's media files missing on disk
+//! - `media_check_links` - Report which link blocks' URLs no longer resolve
+//! - `media_supported_types` - List the media formats Garden recognizes
 //! - `media_exists` - Check if a media file exists
 //! - `media_get_full_path` - Get the full filesystem path for a media file
+//! - `media_storage_usage` - Measure the disk space used by media
+//! - `media_set_root` - Move media storage to a different directory
+//!
+//! ## Garden (3)
+//! - `garden_import_plan` - Preview importing a garden export
+//! - `api_version` - Report the backend's schema and crate versions
+//! - `activity_feed` - List recent changes, newest first
+//!
+//! ## Database (1)
+//! - `db_integrity_check` - Check the database for corruption and bad data
+//!
+//! # Events
+//!
+//! Connection mutations emit a `connection_changed` event with a
+//! [`ConnectionChanged`] payload so other windows can refresh.
 //!
 //! # Error Handling
 //!
@@ -96,12 +163,16 @@
 
 pub mod commands;
 pub mod error;
+pub mod events;
 pub mod init;
+pub mod settings;
 pub mod state;
 
 // Re-export primary types for ergonomic usage
 pub use error::{CommandResult, ErrorCode, TauriError};
+pub use events::{ConnectionChangeKind, ConnectionChanged};
 pub use init::initialize_database;
+pub use settings::Settings;
 pub use state::AppState;
 
 // The generate_handler! macro is automatically exported via #[macro_export]
@@ -116,4 +187,19 @@ mod tests {
         // Verify key types are exported
         let _: fn() -> CommandResult<()> = || Ok(());
     }
+
+    #[test]
+    #[ignore] // Run with: cargo test --package garden-tauri export_typescript_types -- --ignored
+    fn export_typescript_types() {
+        use ts_rs::TS;
+
+        ErrorCode::export_all().expect("Failed to export ErrorCode");
+        TauriError::export_all().expect("Failed to export TauriError");
+        ConnectionChangeKind::export_all().expect("Failed to export ConnectionChangeKind");
+        ConnectionChanged::export_all().expect("Failed to export ConnectionChanged");
+        commands::media::MediaImportResult::export_all()
+            .expect("Failed to export MediaImportResult");
+
+        println!("TypeScript types exported successfully!");
+    }
 }
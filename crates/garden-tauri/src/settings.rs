@@ -0,0 +1,94 @@
+//! Persistent user settings.
+//!
+//! Settings live in a small JSON file in the app data directory, next to
+//! the database. A missing file means all defaults.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::error::{CommandResult, TauriError};
+
+/// Settings filename.
+pub const SETTINGS_FILENAME: &str = "settings.json";
+
+/// User settings read at startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Override for the media root directory.
+    ///
+    /// When `None`, media is stored in `{app_data}/media`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_root: Option<PathBuf>,
+}
+
+impl Settings {
+    /// Load settings from the given app data directory.
+    ///
+    /// Returns defaults if the settings file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `INITIALIZATION_ERROR` if the file exists but cannot be
+    /// read or parsed.
+    pub fn load(app_data_dir: &Path) -> CommandResult<Self> {
+        let path = app_data_dir.join(SETTINGS_FILENAME);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                error!(error = %e, path = %path.display(), "Failed to read settings");
+                return Err(TauriError::initialization(format!(
+                    "Failed to read settings: {}",
+                    e
+                )));
+            }
+        };
+
+        serde_json::from_str(&contents).map_err(|e| {
+            error!(error = %e, path = %path.display(), "Failed to parse settings");
+            TauriError::initialization(format!("Failed to parse settings: {}", e))
+        })
+    }
+
+    /// Save settings to the given app data directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `INTERNAL_ERROR` if the file cannot be written.
+    pub fn save(&self, app_data_dir: &Path) -> CommandResult<()> {
+        let path = app_data_dir.join(SETTINGS_FILENAME);
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| TauriError::internal(format!("Failed to serialize settings: {}", e)))?;
+
+        std::fs::write(&path, contents).map_err(|e| {
+            error!(error = %e, path = %path.display(), "Failed to write settings");
+            TauriError::internal(format!("Failed to write settings: {}", e))
+        })?;
+
+        info!(path = %path.display(), "Settings saved");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_default_has_no_media_root() {
+        let settings: Settings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn settings_round_trip_media_root() {
+        let settings = Settings {
+            media_root: Some(PathBuf::from("/Volumes/External/garden-media")),
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        let parsed: Settings = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, settings);
+    }
+}
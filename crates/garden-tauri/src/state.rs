@@ -4,12 +4,14 @@
 //! state in a thread-safe manner. It wraps the `GardenService` with concrete
 //! SQLite repository implementations, plus the MediaService for file operations.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock};
 
-use garden_core::services::{GardenService, MediaService};
+use garden_core::services::{CancellationToken, GardenService, MediaService};
 use garden_db::sqlite::{
-    SqliteBlockRepository, SqliteChannelRepository, SqliteConnectionRepository, SqliteDatabase,
+    CheckpointGuard, SqliteBlockRepository, SqliteChannelRepository, SqliteConnectionRepository,
+    SqliteDatabase,
 };
 
 /// Type alias for the concrete GardenService with SQLite repositories.
@@ -19,6 +21,9 @@ use garden_db::sqlite::{
 pub type SqliteGardenService =
     GardenService<SqliteChannelRepository, SqliteBlockRepository, SqliteConnectionRepository>;
 
+/// In-flight URL imports, keyed by the ID the frontend gave each one.
+type ActiveImports = Arc<Mutex<HashMap<String, CancellationToken>>>;
+
 /// Application state managed by Tauri.
 ///
 /// This struct is designed to be:
@@ -51,7 +56,15 @@ pub struct AppState {
     /// The database connection manager (for lifecycle management).
     database: Arc<SqliteDatabase>,
     /// The MediaService for importing and managing media files.
-    media_service: Arc<MediaService>,
+    ///
+    /// Behind a lock so the media root can be changed at runtime.
+    media_service: Arc<RwLock<Arc<MediaService>>>,
+    /// Cancellation tokens for running imports, so they can be stopped by
+    /// ID from another command.
+    imports: ActiveImports,
+    /// Checkpoints the WAL when the last clone is dropped, so data is
+    /// flushed even if `close` never runs (e.g. after a panic).
+    _checkpoint: Arc<CheckpointGuard>,
 }
 
 impl AppState {
@@ -69,13 +82,16 @@ impl AppState {
         let block_repo = database.block_repository();
         let connection_repo = database.connection_repository();
 
-        let service = GardenService::new(channel_repo, block_repo, connection_repo);
+        let service = GardenService::new(channel_repo, block_repo, connection_repo)
+            .with_activity_log(Arc::new(database.activity_repository()));
         let media_service = MediaService::new(media_root);
 
         Self {
             service: Arc::new(service),
+            _checkpoint: Arc::new(database.checkpoint_guard()),
             database: Arc::new(database),
-            media_service: Arc::new(media_service),
+            media_service: Arc::new(RwLock::new(Arc::new(media_service))),
+            imports: ActiveImports::default(),
         }
     }
 
@@ -95,23 +111,108 @@ impl AppState {
         &self.database
     }
 
-    /// Get a reference to the MediaService.
+    /// Get the current MediaService.
     ///
     /// Used for importing and managing media files (images, videos, audio).
-    #[inline]
-    pub fn media_service(&self) -> &MediaService {
-        &self.media_service
+    /// Returns a shared handle so an in-flight operation keeps using the
+    /// root it started with even if the root is changed meanwhile.
+    pub fn media_service(&self) -> Arc<MediaService> {
+        // A poisoned lock still holds a valid service; keep using it
+        let guard = self
+            .media_service
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        Arc::clone(&guard)
+    }
+
+    /// Replace the MediaService with one rooted at `media_root`.
+    ///
+    /// The caller is responsible for preparing the directory (see
+    /// [`MediaService::prepare_root`]) and migrating any existing files.
+    pub fn set_media_root(&self, media_root: PathBuf) {
+        let mut guard = self
+            .media_service
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *guard = Arc::new(MediaService::new(media_root));
+    }
+
+    /// Track an import under `id` so [`Self::cancel_import`] can stop it.
+    ///
+    /// The import stays tracked until the returned guard is dropped.
+    /// Returns `None` if an import with this ID is already running.
+    pub fn track_import(&self, id: String) -> Option<ImportGuard> {
+        let mut imports = self
+            .imports
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if imports.contains_key(&id) {
+            return None;
+        }
+        let token = CancellationToken::new();
+        imports.insert(id.clone(), token.clone());
+        Some(ImportGuard {
+            imports: Arc::clone(&self.imports),
+            id,
+            token,
+        })
+    }
+
+    /// Cancel the running import tracked under `id`.
+    ///
+    /// Returns `false` if no such import is running, e.g. because it
+    /// already finished.
+    pub fn cancel_import(&self, id: &str) -> bool {
+        let imports = self
+            .imports
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match imports.get(id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
     }
 
     /// Gracefully close the database connection.
     ///
     /// This should be called during application shutdown to ensure
     /// all pending operations complete and connections are released.
+    /// If it is skipped, dropping the last `AppState` still checkpoints
+    /// the WAL on a best-effort basis (see [`CheckpointGuard`]).
     pub async fn close(&self) {
         self.database.close().await;
     }
 }
 
+/// Keeps an import tracked in [`AppState`] while it runs.
+///
+/// Dropping the guard stops tracking the import, whether it finished,
+/// failed, or was cancelled.
+pub struct ImportGuard {
+    imports: ActiveImports,
+    id: String,
+    token: CancellationToken,
+}
+
+impl ImportGuard {
+    /// The token that is cancelled when the import is cancelled.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for ImportGuard {
+    fn drop(&mut self) {
+        self.imports
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&self.id);
+    }
+}
+
 impl std::fmt::Debug for AppState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AppState")
@@ -137,4 +238,18 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<AppState>();
     }
+
+    #[tokio::test]
+    async fn cancel_import_reaches_tracked_import() {
+        let database = SqliteDatabase::in_memory().await.unwrap();
+        let state = AppState::new(database, std::env::temp_dir());
+
+        let guard = state.track_import("video".to_string()).unwrap();
+        assert!(state.track_import("video".to_string()).is_none());
+        assert!(state.cancel_import("video"));
+        assert!(guard.token().is_cancelled());
+
+        drop(guard);
+        assert!(!state.cancel_import("video"));
+    }
 }
@@ -30,20 +30,46 @@ import { invoke } from "@tauri-apps/api/core";
 
 import type {
   Block,
+  BlockContext,
   BlockId,
+  BlockNeighbors,
+  BlockPositionInfo,
+  BlockSortKey,
   BlockUpdate,
+  ContentKind,
+  MetadataStats,
   NewBlock,
 } from "./generated/index.js";
 import type {
   Channel,
+  ChannelExport,
   ChannelId,
+  ChannelOrder,
+  ChannelPreview,
+  ChannelSetMode,
   ChannelUpdate,
+  ImportStrategy,
   NewChannel,
+  UrlImportReport,
+} from "./generated/index.js";
+import type {
+  Connection,
+  OrderSnapshot,
+  Placement,
+  PositionIssue,
+  SharingStats,
 } from "./generated/index.js";
-import type { Connection } from "./generated/index.js";
 import type { Page } from "./generated/index.js";
 import type { ErrorCode, TauriError } from "./generated/index.js";
-import type { MediaImportResult } from "./generated/index.js";
+import type {
+  LinkStatus,
+  MediaImportResult,
+  StorageUsage,
+  SupportedType,
+} from "./generated/index.js";
+import type { GardenExport, ImportPlan } from "./generated/index.js";
+import type { IntegrityReport } from "./generated/index.js";
+import type { ActivityEntry, ApiVersion } from "./generated/index.js";
 
 // Re-export types for convenience
 export type { ErrorCode, TauriError };
@@ -86,10 +112,10 @@ export class GardenError extends Error {
   }
 
   /**
-   * Check if this is a duplicate error.
+   * Check if this is a duplicate error, including a taken channel title.
    */
   isDuplicate(): boolean {
-    return this.code === "DUPLICATE_ERROR";
+    return this.code === "DUPLICATE_ERROR" || this.code === "DUPLICATE_TITLE";
   }
 }
 
@@ -123,9 +149,29 @@ async function safeInvoke<T>(
 export const channels = {
   /**
    * Create a new channel.
+   *
+   * Retrying with the same `idempotencyKey` returns the original channel.
+   */
+  create(newChannel: NewChannel, idempotencyKey?: string): Promise<Channel> {
+    return safeInvoke<Channel>("channel_create", {
+      newChannel,
+      idempotencyKey,
+    });
+  },
+
+  /**
+   * Create a channel with its initial blocks, for instantiating a template.
+   *
+   * Either everything is created or nothing is.
    */
-  create(newChannel: NewChannel): Promise<Channel> {
-    return safeInvoke<Channel>("channel_create", { newChannel });
+  createWithBlocks(
+    newChannel: NewChannel,
+    newBlocks: NewBlock[],
+  ): Promise<[Channel, Block[]]> {
+    return safeInvoke<[Channel, Block[]]>("channel_create_with_blocks", {
+      newChannel,
+      newBlocks,
+    });
   },
 
   /**
@@ -165,6 +211,140 @@ export const channels = {
   count(): Promise<number> {
     return safeInvoke<number>("channel_count", {});
   },
+
+  /**
+   * Record that a channel was opened (does not change `updated_at`).
+   */
+  touch(id: ChannelId): Promise<void> {
+    return safeInvoke<void>("channel_touch", { id });
+  },
+
+  /**
+   * List recently opened channels, most recent first.
+   */
+  listRecent(limit?: number): Promise<Channel[]> {
+    return safeInvoke<Channel[]>("channel_list_recent", { limit });
+  },
+
+  /**
+   * List recently edited channels, most recent first.
+   */
+  listRecentlyUpdated(limit?: number): Promise<Channel[]> {
+    return safeInvoke<Channel[]>("channel_list_recently_updated", { limit });
+  },
+
+  /**
+   * Get a channel with its first few blocks and total block count.
+   */
+  getPreview(id: ChannelId, blockLimit?: number): Promise<ChannelPreview> {
+    return safeInvoke<ChannelPreview>("channel_get_preview", {
+      id,
+      blockLimit,
+    });
+  },
+
+  /**
+   * Count how many of a channel's blocks are exclusive to it versus shared.
+   */
+  blockSharing(id: ChannelId): Promise<SharingStats> {
+    return safeInvoke<SharingStats>("channel_block_sharing", { id });
+  },
+
+  /**
+   * Count a channel's blocks by content type, e.g. `{ image: 12, link: 20 }`.
+   * Types the channel has no blocks of are absent.
+   */
+  composition(id: ChannelId): Promise<Record<string, number>> {
+    return safeInvoke<Record<string, number>>("channel_composition", { id });
+  },
+
+  /**
+   * Export a channel with its ordered blocks and connections.
+   */
+  export(id: ChannelId): Promise<ChannelExport> {
+    return safeInvoke<ChannelExport>("channel_export", { id });
+  },
+
+  /**
+   * Render a channel as Markdown, with media referenced under `media/`.
+   */
+  exportMarkdown(id: ChannelId): Promise<string> {
+    return safeInvoke<string>("channel_export_markdown", { id });
+  },
+
+  /**
+   * Import a channel export. IDs are remapped unless `strategy` says otherwise.
+   */
+  import(exported: ChannelExport, strategy?: ImportStrategy): Promise<Channel> {
+    return safeInvoke<Channel>("channel_import", {
+      export: exported,
+      strategy,
+    });
+  },
+
+  /**
+   * Import a text file of URLs, one per line, as link blocks in a channel.
+   * Pages are fetched for titles and descriptions unless `enrich` is false.
+   */
+  importUrls(
+    path: string,
+    channelId: ChannelId,
+    enrich?: boolean,
+  ): Promise<UrlImportReport> {
+    return safeInvoke<UrlImportReport>("channel_import_urls", {
+      path,
+      channelId,
+      enrich,
+    });
+  },
+
+  /**
+   * Sort a channel's blocks, rewriting their positions.
+   * Ascending (oldest or A-Z first) unless `ascending` is false.
+   */
+  sort(
+    channelId: ChannelId,
+    key: BlockSortKey,
+    ascending?: boolean,
+  ): Promise<void> {
+    return safeInvoke<void>("channel_sort", { channelId, key, ascending });
+  },
+
+  /**
+   * Record a channel's current block order, e.g. before sorting, so it can
+   * be put back with `restoreOrder`.
+   */
+  snapshotOrder(channelId: ChannelId): Promise<OrderSnapshot> {
+    return safeInvoke<OrderSnapshot>("channel_snapshot_order", { channelId });
+  },
+
+  /**
+   * Put a channel's blocks back in a snapshot's order ("Undo sort").
+   * Blocks disconnected since are skipped; newer blocks go last.
+   */
+  restoreOrder(channelId: ChannelId, snapshot: OrderSnapshot): Promise<void> {
+    return safeInvoke<void>("channel_restore_order", { channelId, snapshot });
+  },
+
+  /**
+   * Renumber a channel's blocks from zero if any position is negative,
+   * shared, or leaves a gap.
+   *
+   * @returns true if positions were renumbered
+   */
+  repairPositions(channelId: ChannelId): Promise<boolean> {
+    return safeInvoke<boolean>("channel_repair_positions", { channelId });
+  },
+
+  /**
+   * Find channels whose block positions are negative, shared, or have
+   * gaps, as `[channelId, issue]` pairs. Fix each with `repairPositions`.
+   */
+  positionAnomalies(): Promise<[ChannelId, PositionIssue][]> {
+    return safeInvoke<[ChannelId, PositionIssue][]>(
+      "channel_position_anomalies",
+    );
+  },
 };
 
 /**
@@ -173,9 +353,11 @@ export const channels = {
 export const blocks = {
   /**
    * Create a new block.
+   *
+   * Retrying with the same `idempotencyKey` returns the original block.
    */
-  create(newBlock: NewBlock): Promise<Block> {
-    return safeInvoke<Block>("block_create", { newBlock });
+  create(newBlock: NewBlock, idempotencyKey?: string): Promise<Block> {
+    return safeInvoke<Block>("block_create", { newBlock, idempotencyKey });
   },
 
   /**
@@ -185,6 +367,23 @@ export const blocks = {
     return safeInvoke<Block[]>("block_create_batch", { newBlocks });
   },
 
+  /**
+   * Create blocks and connect them to a channel, in order.
+   *
+   * Appends to the end of the channel unless `start` is given.
+   */
+  createAndConnect(
+    newBlocks: NewBlock[],
+    channelId: ChannelId,
+    start?: number,
+  ): Promise<[Block, Connection][]> {
+    return safeInvoke<[Block, Connection][]>("block_create_and_connect", {
+      newBlocks,
+      channelId,
+      start,
+    });
+  },
+
   /**
    * Get a block by ID.
    */
@@ -199,12 +398,152 @@ export const blocks = {
     return safeInvoke<Block>("block_update", { id, update });
   },
 
+  /**
+   * Apply the same metadata changes to several blocks. The update must not
+   * set content. Returns how many blocks were updated.
+   */
+  updateMetadata(blockIds: BlockId[], update: BlockUpdate): Promise<number> {
+    return safeInvoke<number>("blocks_update_metadata", { blockIds, update });
+  },
+
+  /**
+   * Archive or unarchive a block.
+   *
+   * Archived blocks stay connected but are hidden from search and channel
+   * views by default. Unlike deletion, this is reversible.
+   */
+  setArchived(id: BlockId, archived: boolean): Promise<Block> {
+    return safeInvoke<Block>("block_set_archived", { id, archived });
+  },
+
+  /**
+   * Count blocks missing each archive metadata field.
+   *
+   * A field counts as missing when it is unset or empty.
+   */
+  metadataStats(): Promise<MetadataStats> {
+    return safeInvoke<MetadataStats>("block_metadata_stats");
+  },
+
+  /**
+   * Change a block's content type, e.g. promote a pasted URL to a link.
+   */
+  convert(id: BlockId, target: ContentKind): Promise<Block> {
+    return safeInvoke<Block>("block_convert", { id, target });
+  },
+
+  /**
+   * Set a block's notes, or clear them with `null`.
+   */
+  setNote(id: BlockId, note: string | null): Promise<Block> {
+    return safeInvoke<Block>("block_set_note", { id, note });
+  },
+
+  /**
+   * Set a block's source URL, or clear it with `null`.
+   */
+  setSourceUrl(id: BlockId, sourceUrl: string | null): Promise<Block> {
+    return safeInvoke<Block>("block_set_source_url", { id, sourceUrl });
+  },
+
   /**
    * Delete a block.
    */
   delete(id: BlockId): Promise<void> {
     return safeInvoke<void>("block_delete", { id });
   },
+
+  /**
+   * Find groups of blocks with the same content. Each group has at least
+   * two block IDs, oldest first when IDs are time-ordered.
+   */
+  findDuplicates(): Promise<BlockId[][]> {
+    return safeInvoke<BlockId[][]>("block_find_duplicates");
+  },
+
+  /**
+   * Fold duplicate blocks into `keep`, which takes over their channels,
+   * then delete them. Resolves to the channels `keep` was added to.
+   */
+  mergeDuplicates(keep: BlockId, merge: BlockId[]): Promise<ChannelId[]> {
+    return safeInvoke<ChannelId[]>("block_merge_duplicates", { keep, merge });
+  },
+
+  /**
+   * Get a page of all blocks, newest first, as `[block, channels]` pairs
+   * with each block's channels ordered by title. Archived blocks are left
+   * out unless `includeArchived` is set.
+   */
+  listWithChannels(options?: {
+    limit?: number;
+    offset?: number;
+    includeArchived?: boolean;
+  }): Promise<Page<[Block, Channel[]]>> {
+    return safeInvoke<Page<[Block, Channel[]]>>("block_list_with_channels", {
+      limit: options?.limit,
+      offset: options?.offset,
+      includeArchived: options?.includeArchived,
+    });
+  },
+
+  /**
+   * Get a page of video and audio blocks whose duration in seconds falls
+   * within the given bounds, newest first. Omitted bounds are open.
+   */
+  findByDuration(options?: {
+    min?: number;
+    max?: number;
+    limit?: number;
+    offset?: number;
+  }): Promise<Page<Block>> {
+    return safeInvoke<Page<Block>>("block_find_by_duration", {
+      min: options?.min,
+      max: options?.max,
+      limit: options?.limit,
+      offset: options?.offset,
+    });
+  },
+
+  /**
+   * Count blocks created on each day from `start` to `end` (inclusive,
+   * `YYYY-MM-DD`, UTC), as `[day, count]` pairs in date order. Days with no
+   * new blocks are absent, so fill the gaps with zero.
+   */
+  activityHeatmap(start: string, end: string): Promise<[string, number][]> {
+    return safeInvoke<[string, number][]>("block_activity_heatmap", {
+      start,
+      end,
+    });
+  },
+
+  /**
+   * Get several blocks, each with the channels it is in, in one call.
+   * Fails with BLOCK_NOT_FOUND if any ID is unknown.
+   */
+  getContexts(ids: BlockId[]): Promise<BlockContext[]> {
+    return safeInvoke<BlockContext[]>("block_get_contexts", { ids });
+  },
+
+  /**
+   * Get a block's tags, sorted alphabetically.
+   */
+  getTags(id: BlockId): Promise<string[]> {
+    return safeInvoke<string[]>("block_get_tags", { id });
+  },
+
+  /**
+   * Add a tag to several blocks. Returns how many blocks gained the tag.
+   */
+  addTag(blockIds: BlockId[], tag: string): Promise<number> {
+    return safeInvoke<number>("blocks_add_tag", { blockIds, tag });
+  },
+
+  /**
+   * Remove a tag from several blocks. Returns how many blocks lost the tag.
+   */
+  removeTag(blockIds: BlockId[], tag: string): Promise<number> {
+    return safeInvoke<number>("blocks_remove_tag", { blockIds, tag });
+  },
 };
 
 /**
@@ -226,6 +565,14 @@ export const connections = {
     });
   },
 
+  /**
+   * Connect a block to the top of a channel, moving every other block
+   * down one position.
+   */
+  prepend(blockId: BlockId, channelId: ChannelId): Promise<Connection> {
+    return safeInvoke<Connection>("connection_prepend", { blockId, channelId });
+  },
+
   /**
    * Connect multiple blocks to a channel.
    */
@@ -257,31 +604,177 @@ export const connections = {
 
   /**
    * Get all blocks in a channel.
+   *
+   * @param includeArchived - Whether to include archived blocks (default: false)
    */
-  getBlocksInChannel(channelId: ChannelId): Promise<Block[]> {
+  getBlocksInChannel(
+    channelId: ChannelId,
+    includeArchived?: boolean,
+  ): Promise<Block[]> {
     return safeInvoke<Block[]>("connection_get_blocks_in_channel", {
       channelId,
+      includeArchived,
     });
   },
 
   /**
    * Get all blocks in a channel with their positions.
+   *
+   * @param includeArchived - Whether to include archived blocks (default: false)
    */
-  getBlocksWithPositions(channelId: ChannelId): Promise<[Block, number][]> {
+  getBlocksWithPositions(
+    channelId: ChannelId,
+    includeArchived?: boolean,
+  ): Promise<[Block, number][]> {
     return safeInvoke<[Block, number][]>(
       "connection_get_blocks_with_positions",
       {
         channelId,
+        includeArchived,
       },
     );
   },
 
+  /**
+   * Get the blocks most recently connected to a channel, newest first.
+   *
+   * @param includeArchived - Whether to include archived blocks (default: false)
+   */
+  getRecent(
+    channelId: ChannelId,
+    limit?: number,
+    includeArchived?: boolean,
+  ): Promise<[Block, Connection][]> {
+    return safeInvoke<[Block, Connection][]>("connection_get_recent", {
+      channelId,
+      limit,
+      includeArchived,
+    });
+  },
+
+  /**
+   * Get a page of the blocks in any (`union`) or every (`intersection`)
+   * one of several channels, newest first. Archived blocks are left out
+   * unless `includeArchived` is set.
+   */
+  getBlocksInChannels(
+    channelIds: ChannelId[],
+    mode: ChannelSetMode,
+    options?: { limit?: number; offset?: number; includeArchived?: boolean },
+  ): Promise<Page<Block>> {
+    return safeInvoke<Page<Block>>("connection_get_blocks_in_channels", {
+      channelIds,
+      mode,
+      limit: options?.limit,
+      offset: options?.offset,
+      includeArchived: options?.includeArchived,
+    });
+  },
+
+  /**
+   * Get a page of the blocks not yet in a channel, newest first, for
+   * picking blocks to add. Archived blocks are left out unless
+   * `includeArchived` is set.
+   */
+  getAvailableBlocks(
+    channelId: ChannelId,
+    options?: { limit?: number; offset?: number; includeArchived?: boolean },
+  ): Promise<Page<Block>> {
+    return safeInvoke<Page<Block>>("connection_get_available_blocks", {
+      channelId,
+      limit: options?.limit,
+      offset: options?.offset,
+      includeArchived: options?.includeArchived,
+    });
+  },
+
+  /**
+   * Get the blocks in the most channels, as `[block, channelCount]` pairs,
+   * most connected first.
+   *
+   * @param includeArchived - Whether to include archived blocks (default: false)
+   */
+  getPopularBlocks(
+    limit?: number,
+    includeArchived?: boolean,
+  ): Promise<[Block, number][]> {
+    return safeInvoke<[Block, number][]>("connection_get_popular_blocks", {
+      limit,
+      includeArchived,
+    });
+  },
+
   /**
    * Get all channels containing a block.
    */
-  getChannelsForBlock(blockId: BlockId): Promise<Channel[]> {
+  getChannelsForBlock(
+    blockId: BlockId,
+    order?: ChannelOrder,
+  ): Promise<Channel[]> {
     return safeInvoke<Channel[]>("connection_get_channels_for_block", {
       blockId,
+      order,
+    });
+  },
+
+  /**
+   * Get a page of the channels containing a block.
+   */
+  getChannelsForBlockPaged(
+    blockId: BlockId,
+    options?: { limit?: number; offset?: number },
+  ): Promise<Page<Channel>> {
+    return safeInvoke<Page<Channel>>(
+      "connection_get_channels_for_block_paged",
+      {
+        blockId,
+        limit: options?.limit,
+        offset: options?.offset,
+      },
+    );
+  },
+
+  /**
+   * Get a page of the channels holding at least one block of a content type.
+   */
+  getChannelsWithContentType(
+    kind: ContentKind,
+    options?: { limit?: number; offset?: number },
+  ): Promise<Page<Channel>> {
+    return safeInvoke<Page<Channel>>(
+      "connection_get_channels_with_content_type",
+      {
+        kind,
+        limit: options?.limit,
+        offset: options?.offset,
+      },
+    );
+  },
+
+  /**
+   * Get the blocks before and after a block in a channel.
+   */
+  getNeighbors(
+    channelId: ChannelId,
+    blockId: BlockId,
+  ): Promise<BlockNeighbors> {
+    return safeInvoke<BlockNeighbors>("connection_get_neighbors", {
+      channelId,
+      blockId,
+    });
+  },
+
+  /**
+   * Get a block's zero-based index in a channel and the channel's block
+   * count, e.g. for a "3 of 42" indicator.
+   */
+  getPositionInfo(
+    channelId: ChannelId,
+    blockId: BlockId,
+  ): Promise<BlockPositionInfo> {
+    return safeInvoke<BlockPositionInfo>("connection_get_position_info", {
+      channelId,
+      blockId,
     });
   },
 
@@ -299,6 +792,48 @@ export const connections = {
       newPosition,
     });
   },
+
+  /**
+   * Swap the positions of two blocks in a channel in one step.
+   */
+  swap(channelId: ChannelId, blockA: BlockId, blockB: BlockId): Promise<void> {
+    return safeInvoke<void>("connection_swap", { channelId, blockA, blockB });
+  },
+
+  /**
+   * Move a block directly before or after another block in a channel.
+   */
+  moveRelative(
+    channelId: ChannelId,
+    blockId: BlockId,
+    anchor: BlockId,
+    placement: Placement,
+  ): Promise<void> {
+    return safeInvoke<void>("connection_move_relative", {
+      channelId,
+      blockId,
+      anchor,
+      placement,
+    });
+  },
+
+  /**
+   * Move several blocks together as a contiguous run, keeping their
+   * current relative order.
+   *
+   * @param targetIndex - Where the run starts, counted among the other blocks
+   */
+  moveGroup(
+    channelId: ChannelId,
+    blockIds: BlockId[],
+    targetIndex: number,
+  ): Promise<void> {
+    return safeInvoke<void>("connection_move_group", {
+      channelId,
+      blockIds,
+      targetIndex,
+    });
+  },
 };
 
 /**
@@ -312,6 +847,7 @@ export const media = {
    * in the appropriate media subdirectory.
    *
    * @param url - The URL to download from (must be a valid HTTP/HTTPS URL)
+   * @param importId - Optional ID of your choosing, for cancelImport
    * @returns MediaImportResult containing the stored file path and metadata
    *
    * @example
@@ -321,8 +857,23 @@ export const media = {
    * // result.mime_type = "image/jpeg"
    * ```
    */
-  importFromUrl(url: string): Promise<MediaImportResult> {
-    return safeInvoke<MediaImportResult>("media_import_from_url", { url });
+  importFromUrl(url: string, importId?: string): Promise<MediaImportResult> {
+    return safeInvoke<MediaImportResult>("media_import_from_url", {
+      url,
+      importId,
+    });
+  },
+
+  /**
+   * Cancel a running URL import started with an importId.
+   *
+   * The pending importFromUrl call rejects with a "cancelled" media error.
+   *
+   * @param importId - The ID passed to importFromUrl
+   * @returns true if the import was cancelled, false if it wasn't running
+   */
+  cancelImport(importId: string): Promise<boolean> {
+    return safeInvoke<boolean>("media_cancel_import", { importId });
   },
 
   /**
@@ -344,15 +895,76 @@ export const media = {
   },
 
   /**
-   * Delete a media file.
+   * Delete a media file unless a block still references it.
    *
-   * Removes the file from the media directory. This should be called when
+   * Removes the file from the media directory. This should be called after
    * deleting a block that contains media content.
    *
    * @param filePath - Relative path within media directory (e.g., "images/a1b2c3d4.jpg")
+   * @returns true if the file was deleted, false if it is still referenced
+   */
+  delete(filePath: string): Promise<boolean> {
+    return safeInvoke<boolean>("media_delete", { filePath });
+  },
+
+  /**
+   * Count the blocks that reference a media file (including gallery items).
+   *
+   * @param filePath - Relative path within media directory
+   * @returns The number of referencing blocks; 0 means it is safe to delete
    */
-  delete(filePath: string): Promise<void> {
-    return safeInvoke<void>("media_delete", { filePath });
+  referenceCount(filePath: string): Promise<number> {
+    return safeInvoke<number>("media_reference_count", { filePath });
+  },
+
+  /**
+   * Check a block's media files against the hashes stored at import.
+   *
+   * @param blockId - The block whose media to check
+   * @returns true if every hashed file is unchanged, false otherwise
+   */
+  verify(blockId: BlockId): Promise<boolean> {
+    return safeInvoke<boolean>("media_verify", { blockId });
+  },
+
+  /**
+   * Convert an image block's file to another format, e.g. PNG to WebP.
+   *
+   * @param blockId - The image block to convert
+   * @param targetMime - The format to convert to, e.g. "image/webp"
+   * @returns The updated block, pointing at the new file
+   */
+  reencodeBlock(blockId: BlockId, targetMime: string): Promise<Block> {
+    return safeInvoke<Block>("media_reencode_block", { blockId, targetMime });
+  },
+
+  /**
+   * Find media files referenced by a channel's blocks that are missing on disk.
+   *
+   * @param channelId - The channel to check
+   * @returns Each missing file as [blockId, filePath], in channel order
+   */
+  validateChannel(channelId: ChannelId): Promise<[BlockId, string][]> {
+    return safeInvoke<[BlockId, string][]>("media_validate_channel", {
+      channelId,
+    });
+  },
+
+  /**
+   * Check whether link blocks' URLs still resolve, for a link rot report.
+   *
+   * @param limit - Maximum number of link blocks to check (default: 100)
+   * @returns Each checked block as [blockId, status], in block ID order
+   */
+  checkLinks(limit?: number): Promise<[BlockId, LinkStatus][]> {
+    return safeInvoke<[BlockId, LinkStatus][]>("media_check_links", { limit });
+  },
+
+  /**
+   * List the media formats Garden recognizes, for file picker filters.
+   */
+  supportedTypes(): Promise<SupportedType[]> {
+    return safeInvoke<SupportedType[]>("media_supported_types");
   },
 
   /**
@@ -377,6 +989,92 @@ export const media = {
   getFullPath(filePath: string): Promise<string> {
     return safeInvoke<string>("media_get_full_path", { filePath });
   },
+
+  /**
+   * Measure the disk space used by stored media.
+   *
+   * @returns Total bytes and file count, with a breakdown per media type
+   */
+  storageUsage(): Promise<StorageUsage> {
+    return safeInvoke<StorageUsage>("media_storage_usage");
+  },
+
+  /**
+   * Move media storage to a new location.
+   *
+   * Stored blocks keep their relative paths, so nothing else changes. If a
+   * file fails to move, the copies are removed and the current root stays
+   * in use. An existing file in the new root is never overwritten.
+   *
+   * @param path - Absolute path to the new media root
+   * @param migrate - Whether to move existing files (default: true)
+   * @returns The number of files moved
+   */
+  setRoot(path: string, migrate?: boolean): Promise<number> {
+    return safeInvoke<number>("media_set_root", { path, migrate });
+  },
+};
+
+/**
+ * Import API methods.
+ */
+export const imports = {
+  /**
+   * Preview importing a garden export without writing anything.
+   *
+   * @returns What would be created, ID collisions, and invalid blocks
+   */
+  plan(archive: GardenExport): Promise<ImportPlan> {
+    return safeInvoke<ImportPlan>("garden_import_plan", { archive });
+  },
+};
+
+/**
+ * Backend version API methods.
+ */
+export const system = {
+  /**
+   * Get the backend's schema and crate versions.
+   *
+   * @returns The model schema version and `garden-core` crate version
+   */
+  apiVersion(): Promise<ApiVersion> {
+    return safeInvoke<ApiVersion>("api_version");
+  },
+};
+
+/**
+ * Activity log API methods.
+ */
+export const activity = {
+  /**
+   * List recent changes to channels, blocks, and connections.
+   *
+   * @returns A page of entries, newest first
+   */
+  feed(options?: {
+    limit?: number;
+    offset?: number;
+  }): Promise<Page<ActivityEntry>> {
+    return safeInvoke<Page<ActivityEntry>>("activity_feed", {
+      limit: options?.limit,
+      offset: options?.offset,
+    });
+  },
+};
+
+/**
+ * Database maintenance API methods.
+ */
+export const database = {
+  /**
+   * Check the database for corruption and inconsistent data.
+   *
+   * @returns Problems found; every list is empty for a healthy database
+   */
+  integrityCheck(): Promise<IntegrityReport> {
+    return safeInvoke<IntegrityReport>("db_integrity_check");
+  },
 };
 
 /**
@@ -389,6 +1087,10 @@ export const garden = {
   blocks,
   connections,
   media,
+  imports,
+  system,
+  activity,
+  database,
 };
 
 export default garden;
@@ -0,0 +1,37 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActivityKind } from "./ActivityKind";
+import type { BlockId } from "./BlockId";
+import type { ChannelId } from "./ChannelId";
+
+/**
+ * One change in the activity log.
+ *
+ * Entries keep plain IDs rather than references, so they outlive the
+ * channels and blocks they describe.
+ */
+export type ActivityEntry = { 
+/**
+ * Unique identifier for the entry.
+ */
+id: string, 
+/**
+ * What happened.
+ */
+kind: ActivityKind, 
+/**
+ * The channel or block the change is about. For connection changes,
+ * this is the block.
+ */
+entity_id: string, 
+/**
+ * The channel involved, if any.
+ */
+channel_id: ChannelId | null, 
+/**
+ * The block involved, if any.
+ */
+block_id: BlockId | null, 
+/**
+ * When the change happened.
+ */
+at: string, };
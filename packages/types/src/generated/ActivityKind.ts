@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What kind of change an activity entry records.
+ */
+export type ActivityKind = "channel_created" | "channel_updated" | "channel_deleted" | "block_created" | "block_updated" | "block_deleted" | "block_connected" | "block_disconnected" | "block_reordered";
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The backend's model and crate versions, for client compatibility checks.
+ */
+export type ApiVersion = {
+  /**
+   * [`GARDEN_SCHEMA_VERSION`](crate::GARDEN_SCHEMA_VERSION) of this build.
+   */
+  schema_version: number;
+  /**
+   * Version of the `garden-core` crate, e.g. `"0.1.0"`.
+   */
+  crate_version: string;
+};
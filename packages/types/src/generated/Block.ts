@@ -42,4 +42,9 @@ export type Block = {
    * User's personal notes about this block.
    */
   notes: string | null;
+  /**
+   * Hidden from default listings, search, and channel views, but kept
+   * along with its connections. Not a deletion.
+   */
+  archived: boolean;
 };
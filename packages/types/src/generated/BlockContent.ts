@@ -1,9 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GalleryItem } from "./GalleryItem";
 
 /**
  * The content of a block.
  *
- * Supports Text, Link, Image, Video, and Audio types.
+ * Supports Text, Link, Image, Video, Audio, Gallery, and RemoteImage types.
  * Future types: Code.
  */
 export type BlockContent =
@@ -51,6 +52,10 @@ export type BlockContent =
        * Image height in pixels.
        */
       height: number | null;
+      /**
+       * Width divided by height, computed once at import.
+       */
+      aspect_ratio?: number;
       /**
        * MIME type: "image/jpeg", "image/png", etc.
        */
@@ -59,6 +64,10 @@ export type BlockContent =
        * Alt text for accessibility.
        */
       alt_text: string | null;
+      /**
+       * SHA-256 of the stored file, hex-encoded, if known.
+       */
+      content_hash?: string;
     }
   | {
       type: "video";
@@ -78,6 +87,10 @@ export type BlockContent =
        * Video height in pixels.
        */
       height: number | null;
+      /**
+       * Width divided by height, computed once at import.
+       */
+      aspect_ratio?: number;
       /**
        * Duration in seconds.
        */
@@ -90,6 +103,10 @@ export type BlockContent =
        * Alt text for accessibility.
        */
       alt_text: string | null;
+      /**
+       * SHA-256 of the stored file, hex-encoded, if known.
+       */
+      content_hash?: string;
     }
   | {
       type: "audio";
@@ -117,4 +134,34 @@ export type BlockContent =
        * Artist from ID3 tags.
        */
       artist: string | null;
+      /**
+       * SHA-256 of the stored file, hex-encoded, if known.
+       */
+      content_hash?: string;
+    }
+  | {
+      type: "gallery";
+      /**
+       * The gallery's media files, in display order.
+       */
+      items: Array<GalleryItem>;
+    }
+  | {
+      type: "remote_image";
+      /**
+       * The image URL.
+       */
+      url: string;
+      /**
+       * Image width in pixels, if known.
+       */
+      width: number | null;
+      /**
+       * Image height in pixels, if known.
+       */
+      height: number | null;
+      /**
+       * Alt text for accessibility.
+       */
+      alt_text: string | null;
     };
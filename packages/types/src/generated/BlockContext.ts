@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "./Block";
+import type { Channel } from "./Channel";
+
+/**
+ * A block together with the channels it is in.
+ */
+export type BlockContext = {
+  /**
+   * The block itself.
+   */
+  block: Block;
+  /**
+   * The channels containing the block, ordered by title.
+   */
+  channels: Array<Channel>;
+};
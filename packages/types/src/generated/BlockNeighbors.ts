@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "./Block";
+
+/**
+ * The blocks on either side of a block within a channel.
+ */
+export type BlockNeighbors = {
+  /**
+   * The block before, or `None` if this is the first block.
+   */
+  previous: Block | null;
+  /**
+   * The block after, or `None` if this is the last block.
+   */
+  next: Block | null;
+};
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where a block sits within a channel, e.g. for a "3 of 42" indicator.
+ */
+export type BlockPositionInfo = {
+  /**
+   * The block's zero-based index in position order.
+   */
+  index: number;
+  /**
+   * How many blocks the channel holds.
+   */
+  total: number;
+};
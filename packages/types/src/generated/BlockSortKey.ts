@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What to sort a channel's blocks by.
+ */
+export type BlockSortKey = "created_at" | "title" | "connected_at";
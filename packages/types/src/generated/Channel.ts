@@ -17,6 +17,11 @@ export type Channel = {
    * Optional description.
    */
   description: string | null;
+  /**
+   * Optional longer Markdown "about" text; `description` stays the short
+   * summary.
+   */
+  body?: string;
   /**
    * When the channel was created.
    */
@@ -25,4 +30,11 @@ export type Channel = {
    * When the channel was last updated.
    */
   updated_at: string;
+  /**
+   * When the channel was last opened, if ever.
+   *
+   * Tracked separately from `updated_at` so viewing a channel does not
+   * count as editing it.
+   */
+  accessed_at: string | null;
 };
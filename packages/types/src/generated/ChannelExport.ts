@@ -0,0 +1,33 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "./Block";
+import type { Channel } from "./Channel";
+import type { Connection } from "./Connection";
+
+/**
+ * A snapshot of a single channel and its contents.
+ *
+ * The unit for sharing or backing up one collection. Blocks are in channel
+ * order, and `connections` lists the matching positions in the same order.
+ */
+export type ChannelExport = {
+  /**
+   * Format version of this export.
+   */
+  version: number;
+  /**
+   * When the export was taken.
+   */
+  exported_at: string;
+  /**
+   * The exported channel.
+   */
+  channel: Channel;
+  /**
+   * The channel's blocks, in order.
+   */
+  blocks: Array<Block>;
+  /**
+   * The channel's connections, in the same order as `blocks`.
+   */
+  connections: Array<Connection>;
+};
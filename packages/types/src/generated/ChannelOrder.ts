@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How to order the channels a block belongs to.
+ */
+export type ChannelOrder = "by_connected_at_desc" | "by_title_asc";
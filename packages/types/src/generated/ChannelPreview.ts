@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "./Block";
+import type { Channel } from "./Channel";
+
+/**
+ * A channel with its first few blocks, for rendering a channel card.
+ */
+export type ChannelPreview = {
+  /**
+   * The channel.
+   */
+  channel: Channel;
+  /**
+   * Total number of blocks in the channel.
+   */
+  block_count: number;
+  /**
+   * The first blocks in the channel, in position order.
+   */
+  preview_blocks: Array<Block>;
+};
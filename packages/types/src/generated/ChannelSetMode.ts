@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How to combine channels when listing the blocks they contain.
+ */
+export type ChannelSetMode = "union" | "intersection";
@@ -13,4 +13,8 @@ export type ChannelUpdate = {
    * Description update (Keep/Clear/Set).
    */
   description: FieldUpdate<string>;
+  /**
+   * Body update (Keep/Clear/Set).
+   */
+  body: FieldUpdate<string>;
 };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockId } from "./BlockId";
+import type { ChannelId } from "./ChannelId";
+import type { ConnectionChangeKind } from "./ConnectionChangeKind";
+
+/**
+ * Payload of the `connection_changed` event.
+ */
+export type ConnectionChanged = {
+  /**
+   * The channel whose contents changed.
+   */
+  channel_id: ChannelId;
+  /**
+   * The block that was connected, disconnected, or moved.
+   */
+  block_id: BlockId;
+  /**
+   * What happened.
+   */
+  kind: ConnectionChangeKind;
+};
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The kind of a block's content, without its data.
+ *
+ * Matches the `type` tag of [`BlockContent`].
+ */
+export type ContentKind =
+  | "text"
+  | "link"
+  | "image"
+  | "video"
+  | "audio"
+  | "gallery"
+  | "remote_image";
@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockId } from "./BlockId";
+import type { ChannelId } from "./ChannelId";
+
+/**
+ * A block connected to the same channel more than once.
+ */
+export type DuplicateConnection = {
+  /**
+   * The connected block.
+   */
+  block_id: BlockId;
+  /**
+   * The channel it is connected to.
+   */
+  channel_id: ChannelId;
+  /**
+   * Number of connection rows for the pair.
+   */
+  count: number;
+};
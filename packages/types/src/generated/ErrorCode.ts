@@ -9,6 +9,7 @@ export type ErrorCode =
   | "CONNECTION_NOT_FOUND"
   | "VALIDATION_ERROR"
   | "DUPLICATE_ERROR"
+  | "DUPLICATE_TITLE"
   | "DATABASE_ERROR"
   | "INITIALIZATION_ERROR"
   | "INTERNAL_ERROR";
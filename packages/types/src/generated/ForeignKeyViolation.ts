@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A row whose foreign key points at a missing parent.
+ */
+export type ForeignKeyViolation = {
+  /**
+   * The table containing the dangling reference.
+   */
+  table: string;
+  /**
+   * Row ID of the offending row, if the table has one.
+   */
+  rowid: number | null;
+  /**
+   * The table the reference should point into.
+   */
+  parent: string;
+};
@@ -0,0 +1,31 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A single media file within a gallery block.
+ */
+export type GalleryItem = {
+  /**
+   * Relative path within media directory: "images/{uuid}.{ext}"
+   */
+  file_path: string;
+  /**
+   * MIME type: "image/jpeg", "video/mp4", etc.
+   */
+  mime_type: string;
+  /**
+   * Width in pixels.
+   */
+  width: number | null;
+  /**
+   * Height in pixels.
+   */
+  height: number | null;
+  /**
+   * Alt text for accessibility.
+   */
+  alt_text: string | null;
+  /**
+   * SHA-256 of the stored file, hex-encoded, if known.
+   */
+  content_hash?: string;
+};
@@ -0,0 +1,38 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "./Block";
+import type { Channel } from "./Channel";
+import type { Connection } from "./Connection";
+
+/**
+ * A snapshot of an entire garden.
+ *
+ * Used for backups and for moving a garden between instances. IDs and
+ * timestamps are kept as-is so that re-importing an export is idempotent.
+ */
+export type GardenExport = {
+  /**
+   * Format version of this export.
+   */
+  version: number;
+  /**
+   * [`GARDEN_SCHEMA_VERSION`](crate::GARDEN_SCHEMA_VERSION) of the build
+   * that wrote the export; 0 if it predates the field.
+   */
+  schema_version: number;
+  /**
+   * When the export was taken.
+   */
+  exported_at: string;
+  /**
+   * All channels.
+   */
+  channels: Array<Channel>;
+  /**
+   * All blocks.
+   */
+  blocks: Array<Block>;
+  /**
+   * All connections between blocks and channels.
+   */
+  connections: Array<Connection>;
+};
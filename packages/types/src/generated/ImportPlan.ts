@@ -0,0 +1,40 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InvalidBlock } from "./InvalidBlock";
+
+/**
+ * Summary of what importing a [`GardenExport`] does.
+ *
+ * Returned by both a dry run and a real import, so the frontend can show
+ * the same report before and after.
+ */
+export type ImportPlan = {
+  /**
+   * Channels that will be created.
+   */
+  new_channels: number;
+  /**
+   * Blocks that will be created.
+   */
+  new_blocks: number;
+  /**
+   * Connections that will be created.
+   */
+  new_connections: number;
+  /**
+   * Channels whose ID already exists; the existing channel is kept.
+   */
+  channel_collisions: number;
+  /**
+   * Blocks whose ID already exists; the existing block is kept.
+   */
+  block_collisions: number;
+  /**
+   * Connections skipped because they already exist, are duplicated in the
+   * export, or reference a missing or invalid block or channel.
+   */
+  skipped_connections: number;
+  /**
+   * Blocks that failed validation and will not be imported.
+   */
+  invalid_blocks: Array<InvalidBlock>;
+};
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How to treat IDs when importing a [`ChannelExport`].
+ */
+export type ImportStrategy = "remap_ids" | "preserve_ids";
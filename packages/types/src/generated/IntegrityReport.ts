@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DuplicateConnection } from "./DuplicateConnection";
+import type { ForeignKeyViolation } from "./ForeignKeyViolation";
+import type { PositionCollision } from "./PositionCollision";
+
+/**
+ * Problems found by a database integrity check.
+ */
+export type IntegrityReport = {
+  /**
+   * Messages from `PRAGMA integrity_check`, empty when it reports `ok`.
+   */
+  integrity_errors: Array<string>;
+  /**
+   * Rows reported by `PRAGMA foreign_key_check`.
+   */
+  foreign_key_violations: Array<ForeignKeyViolation>;
+  /**
+   * Block/channel pairs with more than one connection row.
+   *
+   * The primary key prevents this, so any hit means the table was
+   * rebuilt or edited without it.
+   */
+  duplicate_connections: Array<DuplicateConnection>;
+  /**
+   * Positions used by more than one block in a channel.
+   */
+  position_collisions: Array<PositionCollision>;
+};
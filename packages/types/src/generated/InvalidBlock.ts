@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockId } from "./BlockId";
+
+/**
+ * A block in an export that failed validation.
+ */
+export type InvalidBlock = {
+  /**
+   * The offending block.
+   */
+  block_id: BlockId;
+  /**
+   * Why the block was rejected.
+   */
+  reason: string;
+};
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The outcome of checking a link block's URL.
+ */
+export type LinkStatus =
+  | { status: "ok" }
+  | { status: "redirected"; detail: string }
+  | { status: "not_found" }
+  | { status: "error"; detail: string };
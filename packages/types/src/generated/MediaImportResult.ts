@@ -23,6 +23,10 @@ width: number | null,
  * Image/video height in pixels (None for audio).
  */
 height: number | null, 
+/**
+ * Width divided by height, when both are known.
+ */
+aspect_ratio: number | null, 
 /**
  * Duration in seconds (for video/audio, None for images).
  */
@@ -30,4 +34,13 @@ duration: number | null,
 /**
  * Original URL if imported from web.
  */
-original_url: string | null, };
+original_url: string | null, 
+/**
+ * SHA-256 of the stored file, hex-encoded.
+ */
+content_hash: string | null, 
+/**
+ * Why metadata extraction failed, if it did. When set, `width` and
+ * `height` are unknown rather than not applicable.
+ */
+metadata_error: string | null, };
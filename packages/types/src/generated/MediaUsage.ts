@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Bytes and files used by one kind of media.
+ */
+export type MediaUsage = {
+  /**
+   * Total size of the files, in bytes.
+   */
+  bytes: number;
+  /**
+   * Number of files.
+   */
+  file_count: number;
+};
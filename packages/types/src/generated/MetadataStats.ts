@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How many blocks are missing each archive metadata field.
+ *
+ * A field counts as missing when it is unset or empty.
+ */
+export type MetadataStats = {
+  /**
+   * Number of blocks counted.
+   */
+  total: number;
+  /**
+   * Blocks without a source URL.
+   */
+  missing_source_url: number;
+  /**
+   * Blocks without a creator.
+   */
+  missing_creator: number;
+  /**
+   * Blocks without an original date.
+   */
+  missing_original_date: number;
+  /**
+   * Blocks without notes.
+   */
+  missing_notes: number;
+};
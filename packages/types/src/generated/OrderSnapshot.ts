@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockId } from "./BlockId";
+import type { ChannelId } from "./ChannelId";
+
+/**
+ * A channel's block order at one moment, kept so a sort can be undone.
+ */
+export type OrderSnapshot = {
+  /**
+   * The channel the order belongs to.
+   */
+  channel_id: ChannelId;
+  /**
+   * Each block with its position, in channel order.
+   */
+  positions: Array<[BlockId, number]>;
+  /**
+   * When the snapshot was taken.
+   */
+  taken_at: string;
+};
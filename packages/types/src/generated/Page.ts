@@ -2,6 +2,10 @@
 
 /**
  * A paginated response.
+ *
+ * The navigation fields (`has_next`, `has_prev`, `page_number`,
+ * `total_pages`) are computed by the backend and serialized alongside the
+ * items, so the frontend does not have to derive them itself.
  */
 export type Page<T> = {
   /**
@@ -20,4 +24,20 @@ export type Page<T> = {
    * Maximum number of items per page.
    */
   limit: number;
+  /**
+   * Whether there are items after this page.
+   */
+  has_next: boolean;
+  /**
+   * Whether there are items before this page.
+   */
+  has_prev: boolean;
+  /**
+   * The current page number (0-indexed).
+   */
+  page_number: number;
+  /**
+   * The total number of pages.
+   */
+  total_pages: number;
 };
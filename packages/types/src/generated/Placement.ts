@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where to put a moved block relative to its anchor block.
+ */
+export type Placement = "before" | "after";
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChannelId } from "./ChannelId";
+
+/**
+ * Several blocks sharing one position within a channel.
+ *
+ * Reads tolerate this by breaking ties on block ID, but it usually means a
+ * reorder or import went wrong.
+ */
+export type PositionCollision = {
+  /**
+   * The channel with the collision.
+   */
+  channel_id: ChannelId;
+  /**
+   * The shared position.
+   */
+  position: number;
+  /**
+   * Number of blocks at the position.
+   */
+  count: number;
+};
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A problem with the positions of a channel's blocks.
+ */
+export type PositionIssue = "negative" | "duplicate" | "gap";
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Block } from "./Block";
+
+/**
+ * A single search result.
+ */
+export type SearchHit = {
+  /**
+   * The matching block.
+   */
+  block: Block;
+  /**
+   * Snippet with highlighted terms (only in `SearchMode::Snippet`).
+   */
+  snippet: string | null;
+};
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How search results should be returned.
+ */
+export type SearchMode =
+  | { mode: "plain" }
+  | {
+      mode: "snippet";
+      /**
+       * Delimiter inserted before each matched term.
+       */
+      open: string;
+      /**
+       * Delimiter inserted after each matched term.
+       */
+      close: string;
+    };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How many of a channel's blocks are unique to it versus shared.
+ */
+export type SharingStats = {
+  /**
+   * Number of blocks in the channel.
+   */
+  total: number;
+  /**
+   * Blocks connected to this channel and no other.
+   */
+  exclusive: number;
+  /**
+   * Blocks also connected to at least one other channel.
+   */
+  shared: number;
+};
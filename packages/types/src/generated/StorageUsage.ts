@@ -0,0 +1,28 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MediaUsage } from "./MediaUsage";
+
+/**
+ * Disk space used by the media directory, in total and per media type.
+ */
+export type StorageUsage = {
+  /**
+   * Total size of all media files, in bytes.
+   */
+  total_bytes: number;
+  /**
+   * Number of media files.
+   */
+  file_count: number;
+  /**
+   * Files under `images/`.
+   */
+  images: MediaUsage;
+  /**
+   * Files under `videos/`.
+   */
+  videos: MediaUsage;
+  /**
+   * Files under `audio/`.
+   */
+  audio: MediaUsage;
+};
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MediaType } from "./MediaType";
+
+/**
+ * A media format Garden recognizes, for building file picker filters.
+ */
+export type SupportedType = {
+  /**
+   * The MIME type, e.g. `image/jpeg`.
+   */
+  mime: string;
+  /**
+   * The extension stored files of this type get, without the dot.
+   */
+  extension: string;
+  /**
+   * Which kind of media the type is.
+   */
+  media_type: MediaType;
+};
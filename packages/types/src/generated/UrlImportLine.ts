@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockId } from "./BlockId";
+
+/**
+ * The outcome of one line of a URL list import.
+ */
+export type UrlImportLine = {
+  /**
+   * Line number in the file, starting at 1.
+   */
+  line: number;
+  /**
+   * The URL as written, trimmed.
+   */
+  url: string;
+  /**
+   * The link block created for this line, if it was imported.
+   */
+  block_id: BlockId | null;
+  /**
+   * Why the line was skipped, if it was.
+   */
+  error: string | null;
+};
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UrlImportLine } from "./UrlImportLine";
+
+/**
+ * Summary of importing a newline-delimited list of URLs into a channel.
+ *
+ * Blank lines and `#` comments are not reported.
+ */
+export type UrlImportReport = {
+  /**
+   * Number of link blocks created and connected.
+   */
+  imported: number;
+  /**
+   * Number of lines skipped.
+   */
+  skipped: number;
+  /**
+   * Every URL line, in file order.
+   */
+  lines: Array<UrlImportLine>;
+};
@@ -6,25 +6,74 @@ export type { Channel } from "./Channel";
 export type { ChannelId } from "./ChannelId";
 export type { NewChannel } from "./NewChannel";
 export type { ChannelUpdate } from "./ChannelUpdate";
+export type { ChannelPreview } from "./ChannelPreview";
 
 // Block types
 export type { Block } from "./Block";
 export type { BlockId } from "./BlockId";
 export type { BlockContent } from "./BlockContent";
+export type { GalleryItem } from "./GalleryItem";
 export type { NewBlock } from "./NewBlock";
 export type { BlockUpdate } from "./BlockUpdate";
+export type { ContentKind } from "./ContentKind";
+export type { MetadataStats } from "./MetadataStats";
 
 // Connection types
 export type { Connection } from "./Connection";
 export type { NewConnection } from "./NewConnection";
+export type { BlockNeighbors } from "./BlockNeighbors";
+export type { BlockPositionInfo } from "./BlockPositionInfo";
+export type { BlockContext } from "./BlockContext";
+export type { ChannelOrder } from "./ChannelOrder";
+export type { BlockSortKey } from "./BlockSortKey";
+export type { OrderSnapshot } from "./OrderSnapshot";
+export type { PositionIssue } from "./PositionIssue";
+export type { ChannelSetMode } from "./ChannelSetMode";
+export type { Placement } from "./Placement";
+export type { SharingStats } from "./SharingStats";
 
 // Utility types
 export type { FieldUpdate } from "./FieldUpdate";
 export type { Page } from "./Page";
+export type { ApiVersion } from "./ApiVersion";
+
+// Activity types
+export type { ActivityKind } from "./ActivityKind";
+export type { ActivityEntry } from "./ActivityEntry";
+
+// Search types
+export type { SearchMode } from "./SearchMode";
+export type { SearchHit } from "./SearchHit";
+
+// Import/export types
+export type { GardenExport } from "./GardenExport";
+export type { ChannelExport } from "./ChannelExport";
+export type { ImportStrategy } from "./ImportStrategy";
+export type { ImportPlan } from "./ImportPlan";
+export type { UrlImportLine } from "./UrlImportLine";
+export type { UrlImportReport } from "./UrlImportReport";
+export type { InvalidBlock } from "./InvalidBlock";
+
+// Maintenance types
+export type { IntegrityReport } from "./IntegrityReport";
+export type { ForeignKeyViolation } from "./ForeignKeyViolation";
+export type { DuplicateConnection } from "./DuplicateConnection";
+export type { PositionCollision } from "./PositionCollision";
+
+// Media types
+export type { StorageUsage } from "./StorageUsage";
+export type { MediaUsage } from "./MediaUsage";
+export type { LinkStatus } from "./LinkStatus";
+export type { MediaType } from "./MediaType";
+export type { SupportedType } from "./SupportedType";
 
 // Error types (from garden-tauri)
 export type { ErrorCode } from "./ErrorCode";
 export type { TauriError } from "./TauriError";
 
+// Event types (from garden-tauri)
+export type { ConnectionChangeKind } from "./ConnectionChangeKind";
+export type { ConnectionChanged } from "./ConnectionChanged";
+
 // Media types (from garden-tauri)
 export type { MediaImportResult } from "./MediaImportResult";
@@ -11,6 +11,8 @@ export {
   blocks,
   connections,
   media,
+  imports,
+  database,
   GardenError,
 } from "./api.js";
 export type { ErrorCode, TauriError } from "./api.js";
@@ -44,18 +46,24 @@ export {
   ImageContentSchema,
   VideoContentSchema,
   AudioContentSchema,
+  GalleryItemSchema,
+  GalleryContentSchema,
+  RemoteImageContentSchema,
   BlockSchema,
   ChannelSchema,
   DateTimeSchema,
   ChannelPageSchema,
   BlockPageSchema,
   NewChannelInputSchema,
+  httpUrl,
   // Type guards
   isTextContent,
   isLinkContent,
   isImageContent,
   isVideoContent,
   isAudioContent,
+  isGalleryContent,
+  isRemoteImageContent,
   isMediaContent,
   // Parse functions
   parseBlockContent,
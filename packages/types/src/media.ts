@@ -5,14 +5,15 @@
  * application's data directory and served via Tauri's asset protocol.
  */
 
-import { convertFileSrc } from "@tauri-apps/api/core";
-import { appDataDir, join } from "@tauri-apps/api/path";
+import { convertFileSrc, invoke } from "@tauri-apps/api/core";
 
 /**
  * Convert a relative media file path to an asset:// URL for use in HTML elements.
  *
  * This function takes the relative path stored in BlockContent (e.g., "images/abc123.jpg")
  * and converts it to a full asset:// URL that can be used in <img>, <video>, or <audio> src.
+ * The path is resolved by the backend, so a custom media root (see `media_set_root`)
+ * is honoured.
  *
  * @param filePath - Relative path within the media directory (e.g., "images/abc123.jpg")
  * @returns Promise resolving to an asset:// URL
@@ -30,8 +31,7 @@ import { appDataDir, join } from "@tauri-apps/api/path";
  * ```
  */
 export async function getMediaAssetUrl(filePath: string): Promise<string> {
-  const appData = await appDataDir();
-  const fullPath = await join(appData, "media", filePath);
+  const fullPath = await invoke<string>("media_get_full_path", { filePath });
   return convertFileSrc(fullPath);
 }
 
@@ -40,7 +40,8 @@ export async function getMediaAssetUrl(filePath: string): Promise<string> {
  *
  * This is a synchronous version that requires the app data directory to be
  * passed in. Useful when you've already resolved the appDataDir and want to
- * avoid async operations in render functions.
+ * avoid async operations in render functions. It assumes the default media
+ * root (`{appData}/media`); use `getMediaAssetUrl` if a custom root is set.
  *
  * @param filePath - Relative path within the media directory
  * @param appDataPath - The resolved app data directory path
@@ -33,6 +33,27 @@
 import { z } from "zod";
 import type { BlockContent, Block, Channel, Page } from "./generated/index.js";
 
+// ─────────────────────────────────────────────────────────────────────────────
+// URL Schemas
+// ─────────────────────────────────────────────────────────────────────────────
+
+/**
+ * Schema for a URL the UI may render as a clickable link.
+ *
+ * @remarks
+ * Mirrors the backend's `validate_url`: only http and https are allowed, so
+ * `javascript:`, `data:`, `blob:`, and `file:` URLs are rejected.
+ */
+export function httpUrl(message = "Invalid URL format") {
+  return z
+    .string()
+    .url(message)
+    .refine(
+      (url) => /^https?:\/\//i.test(url),
+      "Only http and https URLs are allowed",
+    );
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // BlockContent Variant Schemas
 // ─────────────────────────────────────────────────────────────────────────────
@@ -56,7 +77,7 @@ export const TextContentSchema = z.object({
  */
 export const LinkContentSchema = z.object({
   type: z.literal("link"),
-  url: z.string().url("Invalid URL format"),
+  url: httpUrl(),
   title: z.string().nullable(),
   description: z.string().nullable(),
   alt_text: z.string().nullable(),
@@ -72,11 +93,12 @@ export const LinkContentSchema = z.object({
 export const ImageContentSchema = z.object({
   type: z.literal("image"),
   file_path: z.string().min(1, "File path cannot be empty"),
-  original_url: z.string().url().nullable(),
+  original_url: httpUrl().nullable(),
   width: z.number().int().positive().nullable(),
   height: z.number().int().positive().nullable(),
   mime_type: z.string().regex(/^image\//, "Must be an image MIME type"),
   alt_text: z.string().nullable(),
+  content_hash: z.string().optional(),
 });
 
 /**
@@ -88,12 +110,13 @@ export const ImageContentSchema = z.object({
 export const VideoContentSchema = z.object({
   type: z.literal("video"),
   file_path: z.string().min(1, "File path cannot be empty"),
-  original_url: z.string().url().nullable(),
+  original_url: httpUrl().nullable(),
   width: z.number().int().positive().nullable(),
   height: z.number().int().positive().nullable(),
   duration: z.number().positive().nullable(),
   mime_type: z.string().regex(/^video\//, "Must be a video MIME type"),
   alt_text: z.string().nullable(),
+  content_hash: z.string().optional(),
 });
 
 /**
@@ -106,18 +129,63 @@ export const VideoContentSchema = z.object({
 export const AudioContentSchema = z.object({
   type: z.literal("audio"),
   file_path: z.string().min(1, "File path cannot be empty"),
-  original_url: z.string().url().nullable(),
+  original_url: httpUrl().nullable(),
   duration: z.number().positive().nullable(),
   mime_type: z.string().regex(/^audio\//, "Must be an audio MIME type"),
   title: z.string().nullable(),
   artist: z.string().nullable(),
+  content_hash: z.string().optional(),
+});
+
+/**
+ * Schema for a single gallery item.
+ *
+ * @remarks
+ * Gallery items are images or videos; audio is rejected.
+ */
+export const GalleryItemSchema = z.object({
+  file_path: z.string().min(1, "File path cannot be empty"),
+  mime_type: z
+    .string()
+    .regex(/^(image|video)\//, "Must be an image or video MIME type"),
+  width: z.number().int().positive().nullable(),
+  height: z.number().int().positive().nullable(),
+  alt_text: z.string().nullable(),
+  content_hash: z.string().optional(),
+});
+
+/**
+ * Schema for gallery block content.
+ *
+ * @remarks
+ * Validates that the gallery has at least one item.
+ */
+export const GalleryContentSchema = z.object({
+  type: z.literal("gallery"),
+  items: z.array(GalleryItemSchema).min(1, "Gallery cannot be empty"),
+});
+
+/**
+ * Schema for remote image block content.
+ *
+ * @remarks
+ * The image is hot-linked by URL and never downloaded, so there is no
+ * file path or MIME type.
+ */
+export const RemoteImageContentSchema = z.object({
+  type: z.literal("remote_image"),
+  url: httpUrl(),
+  width: z.number().int().positive().nullable(),
+  height: z.number().int().positive().nullable(),
+  alt_text: z.string().nullable(),
 });
 
 /**
  * Schema for BlockContent discriminated union.
  *
  * @remarks
- * Validates all five content types: text, link, image, video, audio.
+ * Validates all seven content types: text, link, image, video, audio,
+ * gallery, remote_image.
  * Uses Zod's discriminatedUnion for efficient parsing based on the `type` field.
  */
 export const BlockContentSchema = z.discriminatedUnion("type", [
@@ -126,6 +194,8 @@ export const BlockContentSchema = z.discriminatedUnion("type", [
   ImageContentSchema,
   VideoContentSchema,
   AudioContentSchema,
+  GalleryContentSchema,
+  RemoteImageContentSchema,
 ]);
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -207,7 +277,32 @@ export function isAudioContent(
 }
 
 /**
- * Checks if the content is any media type (image, video, or audio).
+ * Checks if the content is a gallery block.
+ *
+ * @param content - The block content to check
+ * @returns `true` if content is gallery type, with narrowed type
+ */
+export function isGalleryContent(
+  content: BlockContent,
+): content is BlockContent & { type: "gallery" } {
+  return content.type === "gallery";
+}
+
+/**
+ * Checks if the content is a hot-linked remote image.
+ *
+ * @param content - The block content to check
+ * @returns `true` if content is remote_image type, with narrowed type
+ */
+export function isRemoteImageContent(
+  content: BlockContent,
+): content is BlockContent & { type: "remote_image" } {
+  return content.type === "remote_image";
+}
+
+/**
+ * Checks if the content is any media type (image, video, audio, gallery,
+ * or remote image), matching `BlockContent::is_media` in garden-core.
  *
  * @param content - The block content to check
  * @returns `true` if content is a media type, with narrowed type
@@ -218,17 +313,21 @@ export function isAudioContent(
  * @example
  * ```typescript
  * if (isMediaContent(block.content)) {
- *   const url = await getMediaAssetUrl(block.content.file_path);
+ *   openMediaViewer(block);
  * }
  * ```
  */
 export function isMediaContent(
   content: BlockContent,
-): content is BlockContent & { type: "image" | "video" | "audio" } {
+): content is BlockContent & {
+  type: "image" | "video" | "audio" | "gallery" | "remote_image";
+} {
   return (
     content.type === "image" ||
     content.type === "video" ||
-    content.type === "audio"
+    content.type === "audio" ||
+    content.type === "gallery" ||
+    content.type === "remote_image"
   );
 }
 
@@ -262,11 +361,12 @@ export const BlockSchema = z.object({
   content: BlockContentSchema,
   created_at: DateTimeSchema,
   updated_at: DateTimeSchema,
-  source_url: z.string().url().nullable(),
+  source_url: httpUrl().nullable(),
   source_title: z.string().nullable(),
   creator: z.string().nullable(),
   original_date: z.string().nullable(),
   notes: z.string().nullable(),
+  archived: z.boolean(),
 });
 
 /**
@@ -276,8 +376,10 @@ export const ChannelSchema = z.object({
   id: z.string(),
   title: z.string().min(1, "Channel title cannot be empty"),
   description: z.string().nullable(),
+  body: z.string().optional(),
   created_at: DateTimeSchema,
   updated_at: DateTimeSchema,
+  accessed_at: DateTimeSchema.nullable(),
 });
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -303,6 +405,10 @@ export function createPageSchema<T extends z.ZodTypeAny>(itemSchema: T) {
     total: z.number().int().nonnegative(),
     offset: z.number().int().nonnegative(),
     limit: z.number().int().positive(),
+    has_next: z.boolean(),
+    has_prev: z.boolean(),
+    page_number: z.number().int().nonnegative(),
+    total_pages: z.number().int().nonnegative(),
   });
 }
 
@@ -446,7 +552,7 @@ export const NewTextBlockSchema = z.object({
 export const NewLinkBlockSchema = z.object({
   content: z.object({
     type: z.literal("link"),
-    url: z.string().url("Please enter a valid URL"),
+    url: httpUrl("Please enter a valid URL"),
     title: z.string().nullable().optional(),
     description: z.string().nullable().optional(),
     alt_text: z.string().nullable().optional(),
@@ -465,6 +571,7 @@ export const NewChannelInputSchema = z.object({
     .min(1, "Channel title is required")
     .max(200, "Channel title is too long"),
   description: z.string().max(2000, "Description is too long").nullable(),
+  body: z.string().max(100000, "Body is too long").optional(),
 });
 
 /**
@@ -491,6 +598,7 @@ export const NewChannelInputSchema = z.object({
 export function validateNewChannelInput(input: {
   title: string;
   description: string | null;
+  body?: string;
 }) {
   return NewChannelInputSchema.safeParse(input);
 }
@@ -510,7 +618,7 @@ export function validateNewChannelInput(input: {
  * ```
  */
 export function validateUrl(url: string) {
-  return z.string().url().safeParse(url);
+  return httpUrl().safeParse(url);
 }
 
 // Re-export zod for consumers who need custom schemas